@@ -15,6 +15,11 @@ impl ApiError {
     pub fn new(status: StatusCode, message: impl Into<String>) -> Self {
         Self { status, message: message.into() }
     }
+
+    /// 403 for a namespace outside the caller's access scope.
+    pub fn forbidden_namespace(namespace: &str) -> Self {
+        Self::new(StatusCode::FORBIDDEN, format!("namespace {} is not permitted", namespace))
+    }
 }
 
 impl From<mnemo_core::error::MnemoError> for ApiError {
@@ -1,7 +1,8 @@
 use axum::{
-    Json,
+    Extension, Json,
     extract::{Path, Query},
 };
+use mnemo_core::access::AccessScope;
 use serde::Serialize;
 
 #[derive(Serialize)]
@@ -16,6 +17,7 @@ pub struct GraphExpandParams {
 }
 
 pub async fn graph_expand(
+    Extension(scope): Extension<AccessScope>,
     Path(id): Path<String>,
     Query(params): Query<GraphExpandParams>,
 ) -> Json<GraphExpandResponse> {
@@ -27,9 +29,10 @@ pub async fn graph_expand(
     let engine = mnemo_storage::graph::graph_engine::GraphEngine::new(store);
     let query_engine = mnemo_storage::graph::query_engine::GraphQueryEngine::new(engine);
 
-    let expansion = query_engine.expand_with_edges(&surreal_url, &id, depth).await.unwrap_or(
-        mnemo_storage::graph::query_engine::GraphExpansion { nodes: vec![], edges: vec![] },
-    );
+    let expansion =
+        query_engine.expand_with_edges_scoped(&surreal_url, &id, depth, &scope).await.unwrap_or(
+            mnemo_storage::graph::query_engine::GraphExpansion { nodes: vec![], edges: vec![] },
+        );
 
     let edges = expansion
         .edges
@@ -1,4 +1,5 @@
-use axum::{Json, extract::Path};
+use axum::{Extension, Json, extract::Path};
+use mnemo_core::access::AccessScope;
 use mnemo_storage::surreal_store::SurrealStore;
 use serde::Deserialize;
 use serde::Serialize;
@@ -79,7 +80,12 @@ fn format_label(row: &NodeQueryRow) -> String {
     row.path.clone()
 }
 
-pub async fn graph_node(Path(id): Path<String>) -> Json<GraphNodeDetail> {
+/// A node and its `contains` neighbors. Records outside the caller's scope are treated as
+/// missing, whether asked for directly or reached as a neighbor.
+pub async fn graph_node(
+    Extension(scope): Extension<AccessScope>,
+    Path(id): Path<String>,
+) -> Json<GraphNodeDetail> {
     let node_thing = parse_thing(&id);
 
     let store = match SurrealStore::get().await {
@@ -95,15 +101,28 @@ pub async fn graph_node(Path(id): Path<String>) -> Json<GraphNodeDetail> {
         }
     };
 
-    let node_sql = r#"
-        SELECT id, path, namespace, 'file' AS kind, null AS chunk_index FROM file WHERE id = $id
+    let visible =
+        scope.surreal_record_condition("").map(|c| format!(" AND {c}")).unwrap_or_default();
+    let node_sql = format!(
+        r#"
+        SELECT id, path, namespace, 'file' AS kind, null AS chunk_index FROM file WHERE id = $id{visible}
         UNION ALL
-        SELECT id, path, namespace, 'chunk' AS kind, chunk_index FROM chunk WHERE id = $id
-    "#;
+        SELECT id, path, namespace, 'chunk' AS kind, chunk_index FROM chunk WHERE id = $id{visible}
+    "#
+    );
+    let node_sql = node_sql.as_str();
 
     let node_rows: Vec<NodeQueryRow> =
         store.query_typed_bind(node_sql, ("id", node_thing.clone())).await.unwrap_or_default();
     tracing::info!("Graph node lookup rows={}", node_rows.len());
+    if node_rows.is_empty() && !scope.is_unrestricted() {
+        return Json(GraphNodeDetail {
+            id: id.clone(),
+            label: id,
+            node_type: "unknown".into(),
+            neighbors: Vec::new(),
+        });
+    }
 
     let mut node_type = "unknown".to_string();
     let mut label = id.clone();
@@ -154,7 +173,7 @@ pub async fn graph_node(Path(id): Path<String>) -> Json<GraphNodeDetail> {
             n_type = nr.kind.clone();
             n_label = format_label(nr);
             neighbors.push(Neighbor { id: normalized_id, label: n_label, node_type: n_type });
-        } else {
+        } else if scope.is_unrestricted() {
             neighbors.push(Neighbor { id: other_id_str, label: n_label, node_type: n_type });
         }
     }
@@ -1,4 +1,5 @@
-use axum::{Json, extract::Query};
+use axum::{Extension, Json, extract::Query};
+use mnemo_core::access::AccessScope;
 use mnemo_storage::surreal_store::SurrealStore;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
@@ -80,7 +81,20 @@ fn thing_to_string(thing: &Thing) -> String {
     thing.to_string()
 }
 
-pub async fn graph_snapshot(Query(params): Query<SnapshotParams>) -> Json<GraphSnapshot> {
+/// ` WHERE ...` keeping only file and chunk records the scope may see; empty when unrestricted.
+pub(crate) fn record_filter(scope: &AccessScope) -> String {
+    scope.surreal_record_condition("").map(|c| format!(" WHERE {c}")).unwrap_or_default()
+}
+
+/// ` WHERE ...` keeping only edges between records the scope may see.
+fn edge_filter(scope: &AccessScope) -> String {
+    scope.surreal_edge_condition().map(|c| format!(" WHERE {c}")).unwrap_or_default()
+}
+
+pub async fn graph_snapshot(
+    Extension(scope): Extension<AccessScope>,
+    Query(params): Query<SnapshotParams>,
+) -> Json<GraphSnapshot> {
     let limit = params.limit.unwrap_or(500);
     let offset = params.offset.unwrap_or(0);
 
@@ -102,8 +116,9 @@ pub async fn graph_snapshot(Query(params): Query<SnapshotParams>) -> Json<GraphS
     let mut nodes: Vec<GraphNode> = Vec::new();
     let mut edges: Vec<GraphEdge> = Vec::new();
 
+    let visible = record_filter(&scope);
     let file_rows: Vec<FileRow> =
-        match store.query_typed("SELECT id, path, namespace FROM file;").await {
+        match store.query_typed(&format!("SELECT id, path, namespace FROM file{visible};")).await {
             Ok(rows) => rows,
             Err(err) => {
                 tracing::error!("Surreal file query failed: {err}");
@@ -129,14 +144,16 @@ pub async fn graph_snapshot(Query(params): Query<SnapshotParams>) -> Json<GraphS
         });
     }
 
-    let chunk_rows: Vec<ChunkRow> =
-        match store.query_typed("SELECT id, path, namespace, chunk_index FROM chunk;").await {
-            Ok(rows) => rows,
-            Err(err) => {
-                tracing::error!("Surreal chunk query failed: {err}");
-                Vec::new()
-            }
-        };
+    let chunk_rows: Vec<ChunkRow> = match store
+        .query_typed(&format!("SELECT id, path, namespace, chunk_index FROM chunk{visible};"))
+        .await
+    {
+        Ok(rows) => rows,
+        Err(err) => {
+            tracing::error!("Surreal chunk query failed: {err}");
+            Vec::new()
+        }
+    };
     tracing::info!("Graph snapshot: fetched {} chunk rows", chunk_rows.len());
     for mut row in chunk_rows {
         row.id = normalize_thing(row.id, "chunk");
@@ -152,6 +169,7 @@ pub async fn graph_snapshot(Query(params): Query<SnapshotParams>) -> Json<GraphS
         });
     }
 
+    // Edges are kept only between returned nodes below, which drops edges to hidden records.
     let edge_rows: Vec<EdgeRow> = match store.query_typed("SELECT in, out FROM contains;").await {
         Ok(rows) => rows,
         Err(err) => {
@@ -203,7 +221,7 @@ pub async fn graph_snapshot(Query(params): Query<SnapshotParams>) -> Json<GraphS
     })
 }
 
-pub async fn graph_debug() -> Json<serde_json::Value> {
+pub async fn graph_debug(Extension(scope): Extension<AccessScope>) -> Json<serde_json::Value> {
     let store = match SurrealStore::get().await {
         Ok(c) => c,
         Err(err) => {
@@ -217,14 +235,19 @@ pub async fn graph_debug() -> Json<serde_json::Value> {
         }
     };
 
-    let files: Vec<FileRow> =
-        store.query_typed("SELECT id, path, namespace FROM file;").await.unwrap_or_default();
+    let visible = record_filter(&scope);
+    let files: Vec<FileRow> = store
+        .query_typed(&format!("SELECT id, path, namespace FROM file{visible};"))
+        .await
+        .unwrap_or_default();
     let chunks: Vec<ChunkRow> = store
-        .query_typed("SELECT id, path, namespace, chunk_index FROM chunk;")
+        .query_typed(&format!("SELECT id, path, namespace, chunk_index FROM chunk{visible};"))
+        .await
+        .unwrap_or_default();
+    let edges: Vec<EdgeRow> = store
+        .query_typed(&format!("SELECT in, out FROM contains{};", edge_filter(&scope)))
         .await
         .unwrap_or_default();
-    let edges: Vec<EdgeRow> =
-        store.query_typed("SELECT in, out FROM contains;").await.unwrap_or_default();
 
     tracing::info!(
         "Graph debug: files={}, chunks={}, edges={}",
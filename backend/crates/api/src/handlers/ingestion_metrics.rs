@@ -1,13 +1,30 @@
+use std::collections::BTreeMap;
+
 use axum::Json;
 use serde_json::json;
 
 pub async fn ingestion_metrics() -> Json<serde_json::Value> {
     let metrics = mnemo_ingest::metrics::snapshot_last_metrics();
+    let skipped: BTreeMap<&str, usize> =
+        metrics.skip_counts.iter().map(|(reason, n)| (reason.as_str(), *n)).collect();
+    let skipped_files: Vec<serde_json::Value> = metrics
+        .skipped_files
+        .iter()
+        .map(|f| json!({"path": f.path, "reason": f.reason.as_str()}))
+        .collect();
     Json(json!({
         "documents": metrics.documents_processed,
         "chunks": metrics.chunks_produced,
         "embeddings": metrics.embedding_calls,
+        "embedding_cache_hits": metrics.embedding_cache_hits,
+        "embedding_cache_misses": metrics.embedding_cache_misses,
+        "embedding_cache_hit_rate": metrics.embedding_cache_hit_rate(),
+        "embedding_failures": metrics.embedding_failures,
         "qdrant_writes": metrics.qdrant_writes,
-        "duration_ms": metrics.duration_ms
+        "duration_ms": metrics.duration_ms,
+        "documents_removed": metrics.documents_removed,
+        "chunks_removed": metrics.chunks_removed,
+        "skipped": skipped,
+        "skipped_files": skipped_files
     }))
 }
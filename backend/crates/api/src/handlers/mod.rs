@@ -13,7 +13,9 @@ pub mod jobs_run;
 pub mod metrics;
 pub mod metrics_basic;
 pub mod providers_list;
+pub mod rag_agentic;
 pub mod rag_debug;
+pub mod rag_feedback;
 pub mod rag_handler;
 pub mod rag_metadata;
 pub mod rag_query;
@@ -0,0 +1,52 @@
+use crate::handlers::error::ApiError;
+use axum::{Extension, Json};
+use mnemo_core::access::AccessScope;
+use mnemo_core::models::rag_context::RetrievalStatus;
+use mnemo_core::rag::agentic::{AgenticConfig, AgenticResult, AgenticRetriever};
+use mnemo_core::rag::orchestrator::RAGOrchestrator;
+use mnemo_inference::engines::tensorzero::{TensorZeroConfig, TensorZeroEngine};
+use mnemo_inference::traits::InferenceEngine;
+use serde::Deserialize;
+use std::sync::Arc;
+
+#[derive(Deserialize)]
+pub struct RagAgenticRequest {
+    pub query: String,
+    #[serde(default)]
+    pub namespace: Option<String>,
+    /// Overrides `MNEMO_AGENT_MAX_STEPS` for this request (capped at 12).
+    #[serde(default)]
+    pub max_steps: Option<usize>,
+}
+
+fn planner() -> Option<Arc<dyn InferenceEngine + Send + Sync>> {
+    match TensorZeroConfig::from_env().and_then(TensorZeroEngine::new) {
+        Ok(engine) => Some(Arc::new(engine)),
+        Err(err) => {
+            tracing::warn!("Agentic planner unavailable, running a single pass: {}", err);
+            None
+        }
+    }
+}
+
+/// Multi-hop retrieval; returns the full step trace and accumulated evidence.
+pub async fn rag_agentic(
+    Extension(scope): Extension<AccessScope>,
+    Json(req): Json<RagAgenticRequest>,
+) -> Result<Json<AgenticResult>, ApiError> {
+    let namespace = req.namespace.unwrap_or_else(|| "default".into());
+    if !scope.allows_namespace(&namespace) {
+        return Err(ApiError::forbidden_namespace(&namespace));
+    }
+    let orchestrator = RAGOrchestrator::new().with_namespace(namespace).with_scope(scope);
+    let mut config = AgenticConfig::default();
+    if let Some(steps) = req.max_steps {
+        config.max_steps = steps.clamp(1, 12);
+    }
+    let result =
+        AgenticRetriever::new(&orchestrator, planner()).with_config(config).run(&req.query).await;
+    if result.status == RetrievalStatus::Forbidden {
+        return Err(ApiError::forbidden_namespace(orchestrator.namespace()));
+    }
+    Ok(Json(result))
+}
@@ -1,14 +1,22 @@
-use axum::Json;
-use mnemo_core::rag::orchestrator::RAGOrchestrator;
+use crate::handlers::error::ApiError;
+use axum::{Extension, Json};
+use mnemo_core::access::AccessScope;
+use mnemo_core::models::rag_context::RetrievalStatus;
+use mnemo_core::rag::orchestrator::{RAGOrchestrator, confidence_score};
 use serde::{Deserialize, Serialize};
+use uuid::Uuid;
 
 #[derive(Deserialize)]
 pub struct RagDebugRequest {
     pub query: String,
+    #[serde(default)]
+    pub namespace: Option<String>,
 }
 
 #[derive(Serialize)]
 pub struct CandidateDebug {
+    pub point_id: String,
+    pub document: String,
     pub chunk: String,
     pub vector_score: f32,
     pub keyword_score: f32,
@@ -17,19 +25,39 @@ pub struct CandidateDebug {
     pub final_score: f32,
     pub tags: Vec<String>,
     pub neighbors_count: usize,
+    pub screening_flags: Vec<String>,
+    pub quarantined: bool,
 }
 
 #[derive(Serialize)]
 pub struct RagDebugResponse {
+    pub query_id: Uuid,
+    /// Backend status only: `ok` or `degraded_backend`; `forbidden` is returned as a 403.
+    pub status: RetrievalStatus,
+    pub confidence: f32,
     pub candidates: Vec<CandidateDebug>,
 }
 
-pub async fn rag_debug(Json(req): Json<RagDebugRequest>) -> Json<RagDebugResponse> {
-    let orchestrator = RAGOrchestrator::new();
-    let raw = orchestrator.gather_candidates(&req.query, None).await;
+pub async fn rag_debug(
+    Extension(scope): Extension<AccessScope>,
+    Json(req): Json<RagDebugRequest>,
+) -> Result<Json<RagDebugResponse>, ApiError> {
+    let namespace = req.namespace.unwrap_or_else(|| "default".into());
+    if !scope.allows_namespace(&namespace) {
+        return Err(ApiError::forbidden_namespace(&namespace));
+    }
+    let orchestrator = RAGOrchestrator::new().with_namespace(namespace).with_scope(scope);
+    let (raw, status) = orchestrator.retrieve(&req.query, None).await;
+    if status == RetrievalStatus::Forbidden {
+        return Err(ApiError::forbidden_namespace(orchestrator.namespace()));
+    }
+    let query_id = orchestrator.log_query(&req.query, &raw);
+    let confidence = confidence_score(&raw.iter().map(|c| c.final_score).collect::<Vec<_>>());
     let candidates = raw
         .into_iter()
         .map(|c| CandidateDebug {
+            point_id: c.point_id,
+            document: c.document,
             chunk: c.chunk,
             vector_score: c.vector_score,
             keyword_score: c.keyword_score,
@@ -38,8 +66,10 @@ pub async fn rag_debug(Json(req): Json<RagDebugRequest>) -> Json<RagDebugRespons
             final_score: c.final_score,
             tags: c.tags,
             neighbors_count: c.neighbors_count,
+            screening_flags: c.screening_flags,
+            quarantined: c.quarantined,
         })
         .collect();
 
-    Json(RagDebugResponse { candidates })
+    Ok(Json(RagDebugResponse { query_id, status, confidence, candidates }))
 }
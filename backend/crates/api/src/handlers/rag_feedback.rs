@@ -0,0 +1,31 @@
+use crate::handlers::error::ApiError;
+use axum::{Extension, Json, http::StatusCode};
+use mnemo_core::access::AccessScope;
+use mnemo_core::ranking::feedback::{self, AnswerFeedback};
+use serde::Serialize;
+use uuid::Uuid;
+
+#[derive(Serialize)]
+pub struct RagFeedbackResponse {
+    pub query_id: Uuid,
+    pub recorded: bool,
+}
+
+/// Record thumbs up/down and the selected citations for a previously answered query.
+/// Callers may only rate queries answered in a namespace their scope allows.
+pub async fn rag_feedback(
+    Extension(scope): Extension<AccessScope>,
+    Json(req): Json<AnswerFeedback>,
+) -> Result<Json<RagFeedbackResponse>, ApiError> {
+    let Some(namespace) = feedback::query_namespace(req.query_id).await? else {
+        return Err(ApiError::new(
+            StatusCode::NOT_FOUND,
+            format!("unknown query id {}", req.query_id),
+        ));
+    };
+    if !scope.allows_namespace(&namespace) {
+        return Err(ApiError::forbidden_namespace(&namespace));
+    }
+    feedback::record_feedback(&req, &namespace).await?;
+    Ok(Json(RagFeedbackResponse { query_id: req.query_id, recorded: true }))
+}
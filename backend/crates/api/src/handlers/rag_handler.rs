@@ -16,6 +16,8 @@ pub async fn rag_query(Json(req): Json<ContextQueryRequest>) -> Json<ContextQuer
             "graph_neighbors": ctx.graph_neighbors,
             "ontology_tags": ctx.ontology_tags,
             "debug_candidates": ctx.debug_candidates,
+            "status": ctx.status,
+            "confidence": ctx.confidence,
         }),
     })
 }
@@ -1,4 +1,8 @@
-use axum::Json;
+use crate::handlers::error::ApiError;
+use axum::{Extension, Json};
+use mnemo_core::access::AccessScope;
+use mnemo_core::models::rag_context::RetrievalStatus;
+use mnemo_core::rag::orchestrator::RAGOrchestrator;
 use serde::Deserialize;
 use uuid::Uuid;
 
@@ -6,15 +10,37 @@ use uuid::Uuid;
 pub struct RagQueryRequest {
     pub session: Option<Uuid>,
     pub query: String,
+    #[serde(default)]
+    pub namespace: Option<String>,
 }
 
 #[derive(serde::Serialize)]
 pub struct SessionResponse {
     pub session_id: Uuid,
     pub response: String,
+    /// Reference for `/v1/rag/feedback`.
+    pub query_id: Option<Uuid>,
+    /// Point ids of the retrieved chunks, best first.
+    pub citations: Vec<String>,
+    pub status: RetrievalStatus,
+    pub confidence: f32,
+    /// `false` when the retrieved context was insufficient and no answer was generated.
+    pub answered: bool,
 }
 
-pub async fn rag_query(Json(req): Json<RagQueryRequest>) -> Json<SessionResponse> {
+const INSUFFICIENT_CONTEXT_RESPONSE: &str =
+    "I don't have enough indexed context to answer that question.";
+const DEGRADED_BACKEND_RESPONSE: &str =
+    "The knowledge base is temporarily unavailable, so I can't answer that question right now.";
+
+pub async fn rag_query(
+    Extension(scope): Extension<AccessScope>,
+    Json(req): Json<RagQueryRequest>,
+) -> Result<Json<SessionResponse>, ApiError> {
+    let namespace = req.namespace.unwrap_or_else(|| "default".into());
+    if !scope.allows_namespace(&namespace) {
+        return Err(ApiError::forbidden_namespace(&namespace));
+    }
     let session_id = req.session.unwrap_or_else(Uuid::new_v4);
     if req.session.is_none() {
         // create session record
@@ -26,7 +52,27 @@ pub async fn rag_query(Json(req): Json<RagQueryRequest>) -> Json<SessionResponse
     }
 
     let query_text = req.query;
-    let response = format!("rag_response_for: {}", query_text);
+
+    let orchestrator = RAGOrchestrator::new().with_namespace(namespace).with_scope(scope);
+    let ctx = orchestrator.run(&query_text).await;
+    let answered = ctx.is_sufficient();
+    let response = match ctx.status {
+        RetrievalStatus::Ok => format!("rag_response_for: {}", query_text),
+        RetrievalStatus::NoResults => INSUFFICIENT_CONTEXT_RESPONSE.to_string(),
+        RetrievalStatus::DegradedBackend => DEGRADED_BACKEND_RESPONSE.to_string(),
+        RetrievalStatus::Forbidden => {
+            return Err(ApiError::forbidden_namespace(orchestrator.namespace()));
+        }
+    };
+    let citations = if answered {
+        ctx.debug_candidates
+            .iter()
+            .filter(|c| !c.point_id.is_empty() && !c.quarantined)
+            .map(|c| c.point_id.clone())
+            .collect()
+    } else {
+        Vec::new()
+    };
 
     // append history
     let pg_url = std::env::var("MNEMO_METADATA_PG")
@@ -45,5 +91,13 @@ pub async fn rag_query(Json(req): Json<RagQueryRequest>) -> Json<SessionResponse
         }
     }
 
-    Json(SessionResponse { session_id, response })
+    Ok(Json(SessionResponse {
+        session_id,
+        response,
+        query_id: ctx.query_id,
+        citations,
+        status: ctx.status,
+        confidence: ctx.confidence,
+        answered,
+    }))
 }
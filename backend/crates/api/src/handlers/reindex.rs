@@ -6,6 +6,7 @@ pub struct ReindexResponse {
     pub scanned: usize,
     pub reindexed: usize,
     pub skipped: usize,
+    pub removed: usize,
     pub chunks: usize,
     pub success: bool,
 }
@@ -17,11 +18,16 @@ pub async fn reindex() -> Json<ReindexResponse> {
     // Load all sources, but pipeline will drop unchanged docs via fingerprint step.
     let docs = controller.load_sources();
     let scanned = docs.len();
+    let pruned = controller.prune_removed(&docs).await;
+    let removed = controller.metrics.documents_removed;
 
-    let result = controller.run_pipeline(docs).await;
+    let result = match pruned {
+        Ok(()) => controller.run_pipeline(docs).await,
+        Err(err) => Err(err),
+    };
     let reindexed = controller.metrics.documents_processed;
     let chunks = controller.metrics.chunks_produced;
     let skipped = scanned.saturating_sub(reindexed);
 
-    Json(ReindexResponse { scanned, reindexed, skipped, chunks, success: result.is_ok() })
+    Json(ReindexResponse { scanned, reindexed, skipped, removed, chunks, success: result.is_ok() })
 }
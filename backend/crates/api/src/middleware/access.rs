@@ -0,0 +1,47 @@
+use axum::http::{Request, header};
+use axum::{body::Body, http::StatusCode, middleware::Next, response::Response};
+use jsonwebtoken::{DecodingKey, Validation, decode};
+use mnemo_core::access::{AccessPolicy, AccessScope};
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+struct Claims {
+    sub: String,
+}
+
+/// Resolve the caller from `x-api-key` or a `Bearer` JWT (HS256, `MNEMO_JWT_SECRET`).
+fn resolve_principal<B>(req: &Request<B>, policy: &AccessPolicy) -> Option<String> {
+    if let Some(key) = req.headers().get("x-api-key").and_then(|v| v.to_str().ok()) {
+        return policy.principal_for_api_key(key).map(|p| p.to_string());
+    }
+    let token = req
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))?;
+    let secret = std::env::var("MNEMO_JWT_SECRET").ok().filter(|s| !s.is_empty())?;
+    match decode::<Claims>(
+        token,
+        &DecodingKey::from_secret(secret.as_bytes()),
+        &Validation::default(),
+    ) {
+        Ok(data) => Some(data.claims.sub),
+        Err(err) => {
+            tracing::debug!("JWT rejected: {}", err);
+            None
+        }
+    }
+}
+
+/// Attach the caller's `AccessScope` to the request. Without an access policy every request
+/// is unrestricted; with one, unknown callers are rejected.
+pub async fn authorize(mut req: Request<Body>, next: Next) -> Result<Response, StatusCode> {
+    let scope = match AccessPolicy::global() {
+        None => AccessScope::unrestricted(),
+        Some(policy) => resolve_principal(&req, policy)
+            .and_then(|principal| policy.scope_for(&principal))
+            .ok_or(StatusCode::UNAUTHORIZED)?,
+    };
+    req.extensions_mut().insert(scope);
+    Ok(next.run(req).await)
+}
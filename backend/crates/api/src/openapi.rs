@@ -36,6 +36,22 @@ pub fn build_openapi() -> serde_json::Value {
                     "responses": { "200": { "description": "RAG debug" }}
                 }
             },
+            "/v1/rag/agentic": {
+                "post": {
+                    "requestBody": { "content": { "application/json": { "schema": { "type": "object" }}}},
+                    "responses": { "200": { "description": "Multi-hop retrieval trace and evidence" }}
+                }
+            },
+            "/v1/rag/feedback": {
+                "post": {
+                    "requestBody": { "content": { "application/json": { "schema": { "type": "object" }}}},
+                    "responses": {
+                        "200": { "description": "Feedback recorded" },
+                        "403": { "description": "Query namespace not permitted" },
+                        "404": { "description": "Unknown query id" }
+                    }
+                }
+            },
             "/v1/rag/metadata": { "get": { "responses": { "200": { "description": "RAG metadata" }}}},
             "/v1/context/query": {
                 "post": {
@@ -1,6 +1,7 @@
 use crate::config::AppConfig;
 use crate::handlers;
 use crate::handlers::ingestion_metrics::ingestion_metrics;
+use crate::middleware::access::authorize;
 use crate::middleware::rate_limiter::rate_limit;
 use crate::openapi::build_openapi;
 use crate::ws::all_ws::all_ws;
@@ -25,6 +26,9 @@ pub fn build_router() -> Router {
     validate_env_vars();
     let cfg = AppConfig::from_env();
     spawn_llm_healthcheck(cfg.clone());
+    if mnemo_ingest::watcher::watch_enabled() {
+        spawn_filesystem_watcher();
+    }
 
     // Permissive CORS for local development; adjust for production.
     let cors = CorsLayer::new().allow_origin(Any).allow_methods(Any).allow_headers(Any);
@@ -32,17 +36,26 @@ pub fn build_router() -> Router {
     let rag_routes = Router::new()
         .route("/v1/rag/query", post(handlers::rag_query::rag_query))
         .route("/v1/rag/debug", post(handlers::rag_debug::rag_debug))
-        .layer(middleware::from_fn(rate_limit));
+        .route("/v1/rag/agentic", post(handlers::rag_agentic::rag_agentic))
+        .route("/v1/rag/feedback", post(handlers::rag_feedback::rag_feedback))
+        .layer(middleware::from_fn(rate_limit))
+        .layer(middleware::from_fn(authorize));
+
+    // Graph reads and graph/RAG event streams expose indexed content, so they need a caller scope.
+    let scoped_graph_routes = Router::new()
+        .route("/v1/graph/snapshot", get(handlers::graph_snapshot::graph_snapshot))
+        .route("/v1/graph/debug", get(handlers::graph_debug::graph_debug))
+        .route("/v1/graph/node/:id", get(handlers::graph_node::graph_node))
+        .route("/v1/graph/expand/:id", get(handlers::graph_expand::graph_expand))
+        .route("/ws/graph", get(graph_ws))
+        .route("/ws/rag", get(rag_ws))
+        .layer(middleware::from_fn(authorize));
 
     let router = Router::new()
         .route("/health", get(handlers::health::health))
         .route("/v1/health", get(handlers::health::health))
         .route("/v1/context/query", post(handlers::context_query::context_query))
         .route("/v1/rag/test", get(handlers::rag_test::rag_test))
-        .route("/v1/graph/snapshot", get(handlers::graph_snapshot::graph_snapshot))
-        .route("/v1/graph/debug", get(handlers::graph_debug::graph_debug))
-        .route("/v1/graph/node/:id", get(handlers::graph_node::graph_node))
-        .route("/v1/graph/expand/:id", get(handlers::graph_expand::graph_expand))
         .route(
             "/v1/jobs",
             get(handlers::jobs_list::list_jobs).post(handlers::jobs_create::create_job),
@@ -70,11 +83,10 @@ pub fn build_router() -> Router {
         .route("/ws/status", get(status_ws))
         .route("/ws/jobs", get(jobs_ws))
         .route("/ws/logs", get(logs_ws))
-        .route("/ws/graph", get(graph_ws))
-        .route("/ws/rag", get(rag_ws))
         .route("/ws/all", get(all_ws))
         .route("/swagger.json", get(|| async { Json(build_openapi()) }))
-        .merge(rag_routes);
+        .merge(rag_routes)
+        .merge(scoped_graph_routes);
 
     router.layer(TraceLayer::new_for_http()).layer(cors)
 }
@@ -96,6 +108,15 @@ fn validate_env_vars() {
     check("MNEMO_LLM_MODEL");
 }
 
+fn spawn_filesystem_watcher() {
+    let cfg = mnemo_ingest::watcher::WatchConfig::from_env();
+    tokio::spawn(async move {
+        if let Err(err) = mnemo_ingest::watcher::watch_filesystem(cfg).await {
+            tracing::warn!("Filesystem watcher stopped: {}", err);
+        }
+    });
+}
+
 fn spawn_llm_healthcheck(cfg: AppConfig) {
     let llm_url = cfg.llm.url.clone();
     let api_key = cfg.llm.api_key.clone();
@@ -15,6 +15,12 @@ async fn handle_socket(socket: WebSocket) {
     let _ = sender.send(Message::Text(r#"{"event":"connected"}"#.to_string())).await;
 
     while let Ok(msg) = rx.recv().await {
+        // Agent steps reveal queries and documents, so they only go out on the scoped `/ws/rag`.
+        if serde_json::from_str::<serde_json::Value>(&msg).is_ok_and(|v| {
+            v.get("event").and_then(|e| e.as_str()).is_some_and(|e| e.starts_with("rag_agent_"))
+        }) {
+            continue;
+        }
         let _ = sender.send(Message::Text(msg)).await;
     }
 }
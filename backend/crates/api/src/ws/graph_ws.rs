@@ -1,21 +1,30 @@
+use axum::Extension;
 use axum::extract::ws::{Message, WebSocket};
 use axum::{extract::WebSocketUpgrade, response::IntoResponse};
+use mnemo_core::access::AccessScope;
 
 use crate::ws::hub::GLOBAL_WS_HUB;
 
-pub async fn graph_ws(ws: WebSocketUpgrade) -> impl IntoResponse {
-    ws.on_upgrade(handle_socket)
+pub async fn graph_ws(
+    Extension(scope): Extension<AccessScope>,
+    ws: WebSocketUpgrade,
+) -> impl IntoResponse {
+    ws.on_upgrade(move |socket| handle_socket(socket, scope))
 }
 
-async fn handle_socket(mut socket: WebSocket) {
+/// Forward graph updates for namespaces the subscriber may read.
+async fn handle_socket(mut socket: WebSocket, scope: AccessScope) {
     let mut rx = GLOBAL_WS_HUB.subscribe();
     let _ = socket.send(Message::Text(r#"{"event":"connected"}"#.to_string())).await;
 
     while let Ok(msg) = rx.recv().await {
-        if let Ok(value) = serde_json::from_str::<serde_json::Value>(&msg) {
-            if value.get("event").and_then(|e| e.as_str()) == Some("graph_update") {
-                let _ = socket.send(Message::Text(msg.clone())).await;
-            }
+        if let Ok(value) = serde_json::from_str::<serde_json::Value>(&msg)
+            && value.get("event").and_then(|e| e.as_str()) == Some("graph_update")
+            && scope.allows_namespace(
+                value.get("namespace").and_then(|v| v.as_str()).unwrap_or_default(),
+            )
+        {
+            let _ = socket.send(Message::Text(msg.clone())).await;
         }
     }
 }
@@ -1,23 +1,32 @@
+use axum::Extension;
 use axum::extract::ws::{Message, WebSocket};
 use axum::{extract::WebSocketUpgrade, response::IntoResponse};
+use mnemo_core::access::AccessScope;
 
 use crate::ws::hub::GLOBAL_WS_HUB;
 
-pub async fn rag_ws(ws: WebSocketUpgrade) -> impl IntoResponse {
-    ws.on_upgrade(handle_socket)
+pub async fn rag_ws(
+    Extension(scope): Extension<AccessScope>,
+    ws: WebSocketUpgrade,
+) -> impl IntoResponse {
+    ws.on_upgrade(move |socket| handle_socket(socket, scope))
 }
 
-async fn handle_socket(mut socket: WebSocket) {
+/// Forward RAG events about requests the subscriber may see (see `AccessScope::may_receive`).
+async fn handle_socket(mut socket: WebSocket, scope: AccessScope) {
     let mut rx = GLOBAL_WS_HUB.subscribe();
     let _ = socket.send(Message::Text(r#"{"event":"connected"}"#.to_string())).await;
 
     while let Ok(msg) = rx.recv().await {
-        if let Ok(value) = serde_json::from_str::<serde_json::Value>(&msg) {
-            if let Some(event) = value.get("event").and_then(|e| e.as_str()) {
-                if event == "rag_processing" || event == "rag_done" {
-                    let _ = socket.send(Message::Text(msg.clone())).await;
-                }
-            }
+        if let Ok(value) = serde_json::from_str::<serde_json::Value>(&msg)
+            && let Some(event) = value.get("event").and_then(|e| e.as_str())
+            && matches!(event, "rag_processing" | "rag_done" | "rag_agent_step" | "rag_agent_done")
+            && scope.may_receive(
+                value.get("namespace").and_then(|v| v.as_str()).unwrap_or_default(),
+                value.get("principal").and_then(|v| v.as_str()),
+            )
+        {
+            let _ = socket.send(Message::Text(msg.clone())).await;
         }
     }
 }
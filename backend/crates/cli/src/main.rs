@@ -13,6 +13,25 @@ enum Commands {
     Version,
     /// Run filesystem ingestion pipeline.
     Ingest,
+    /// Watch INGESTION_ROOT and index changed files as they are saved, renamed or removed.
+    Watch {
+        /// Quiet period in milliseconds before a batch of changes is indexed.
+        #[arg(long)]
+        debounce_ms: Option<u64>,
+    },
+    /// Fit a ranking profile for a namespace from recorded answer feedback.
+    TrainRanking {
+        #[arg(long, default_value = "default")]
+        namespace: String,
+        /// Minimum number of labelled candidates required to fit a profile.
+        #[arg(long)]
+        min_samples: Option<usize>,
+    },
+    /// Re-activate the previous ranking profile version for a namespace.
+    RollbackRanking {
+        #[arg(long, default_value = "default")]
+        namespace: String,
+    },
 }
 
 #[tokio::main]
@@ -26,6 +45,41 @@ async fn main() {
                 eprintln!("Ingestion failed: {err}");
             }
         }
+        Some(Commands::Watch { debounce_ms }) => {
+            let mut cfg = mnemo_ingest::watcher::WatchConfig::from_env();
+            if let Some(ms) = debounce_ms {
+                cfg.debounce = std::time::Duration::from_millis(ms);
+            }
+            if let Err(err) = mnemo_ingest::watcher::watch_filesystem(cfg).await {
+                eprintln!("Watch failed: {err}");
+            }
+        }
+        Some(Commands::TrainRanking { namespace, min_samples }) => {
+            let mut cfg = mnemo_core::ranking::learning::LearningConfig::default();
+            if let Some(min) = min_samples {
+                cfg.min_samples = min;
+            }
+            match mnemo_core::ranking::learning::train_namespace(&namespace, &cfg).await {
+                Ok(outcome) => match (outcome.version, outcome.profile) {
+                    (Some(version), Some(profile)) => println!(
+                        "Saved ranking profile v{version} for {namespace} ({} samples): {profile:?}",
+                        outcome.samples
+                    ),
+                    _ => println!(
+                        "Not enough feedback for {namespace} ({} samples); profile unchanged",
+                        outcome.samples
+                    ),
+                },
+                Err(err) => eprintln!("Ranking training failed: {err}"),
+            }
+        }
+        Some(Commands::RollbackRanking { namespace }) => {
+            match mnemo_core::ranking::profile::rollback(&namespace).await {
+                Ok(Some(version)) => println!("Ranking profile for {namespace} is now v{version}"),
+                Ok(None) => println!("Ranking profile for {namespace} reset to baseline weights"),
+                Err(err) => eprintln!("Ranking rollback failed: {err}"),
+            }
+        }
         None => println!("Gaia Mnemosyne CLI initialized"),
     }
 }
@@ -0,0 +1,168 @@
+use std::collections::HashMap;
+
+use once_cell::sync::Lazy;
+use serde::Deserialize;
+use serde_json::{Value, json};
+
+/// What a single principal may read.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct PrincipalGrant {
+    /// Namespaces the principal may query. `*` grants every namespace.
+    #[serde(default)]
+    pub namespaces: Vec<String>,
+    /// When non-empty, only chunks carrying at least one of these tags are visible.
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// API keys that authenticate as this principal.
+    #[serde(default)]
+    pub api_keys: Vec<String>,
+}
+
+/// Principal → grant mapping loaded from `config/access.yaml`.
+///
+/// ```yaml
+/// principals:
+///   team-a:
+///     api_keys: ["key-a"]
+///     namespaces: ["team-a", "shared"]
+///   ci-bot:
+///     namespaces: ["*"]
+///     tags: ["public"]
+/// ```
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct AccessPolicy {
+    #[serde(default)]
+    pub principals: HashMap<String, PrincipalGrant>,
+}
+
+static POLICY: Lazy<Option<AccessPolicy>> = Lazy::new(|| {
+    let path = std::env::var("MNEMO_ACCESS_POLICY").unwrap_or_else(|_| "config/access.yaml".into());
+    AccessPolicy::load_from(&path)
+});
+
+impl AccessPolicy {
+    /// The process-wide policy. `None` means no policy file exists and access is open.
+    pub fn global() -> Option<&'static AccessPolicy> {
+        POLICY.as_ref()
+    }
+
+    pub fn load_from(path: &str) -> Option<Self> {
+        let contents = std::fs::read_to_string(path).ok()?;
+        match serde_yaml::from_str::<AccessPolicy>(&contents) {
+            Ok(policy) => Some(policy),
+            Err(err) => {
+                // A broken policy must not silently open everything up.
+                tracing::error!("Invalid access policy {}: {}; denying all principals", path, err);
+                Some(AccessPolicy::default())
+            }
+        }
+    }
+
+    /// Resolve the principal owning an API key.
+    pub fn principal_for_api_key(&self, key: &str) -> Option<&str> {
+        self.principals
+            .iter()
+            .find(|(_, grant)| grant.api_keys.iter().any(|k| k == key))
+            .map(|(name, _)| name.as_str())
+    }
+
+    /// Scope for a known principal; `None` for principals not in the policy.
+    pub fn scope_for(&self, principal: &str) -> Option<AccessScope> {
+        let grant = self.principals.get(principal)?;
+        let namespaces = if grant.namespaces.iter().any(|n| n == "*") {
+            None
+        } else {
+            Some(grant.namespaces.clone())
+        };
+        let tags = if grant.tags.is_empty() { None } else { Some(grant.tags.clone()) };
+        Some(AccessScope { principal: Some(principal.to_string()), namespaces, tags })
+    }
+}
+
+/// Namespaces and tags visible to the caller of a request. `None` lists mean unrestricted.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AccessScope {
+    pub principal: Option<String>,
+    pub namespaces: Option<Vec<String>>,
+    pub tags: Option<Vec<String>>,
+}
+
+impl AccessScope {
+    /// Scope used when no access policy is configured.
+    pub fn unrestricted() -> Self {
+        Self::default()
+    }
+
+    pub fn is_unrestricted(&self) -> bool {
+        self.namespaces.is_none() && self.tags.is_none()
+    }
+
+    pub fn allows_namespace(&self, namespace: &str) -> bool {
+        self.namespaces.as_ref().is_none_or(|allowed| allowed.iter().any(|n| n == namespace))
+    }
+
+    pub fn allows_tags(&self, tags: &[String]) -> bool {
+        self.tags.as_ref().is_none_or(|allowed| tags.iter().any(|t| allowed.contains(t)))
+    }
+
+    /// Whether a subscriber with this scope may receive an event about a request made in
+    /// `namespace` by `principal`: with an access policy only its own requests, in namespaces
+    /// it is granted.
+    pub fn may_receive(&self, namespace: &str, principal: Option<&str>) -> bool {
+        self.allows_namespace(namespace)
+            && (self.principal.is_none() || self.principal.as_deref() == principal)
+    }
+
+    /// Mandatory Qdrant `must` conditions for a search in `namespace`.
+    /// Callers must check `allows_namespace` first; the namespace condition is always included.
+    pub fn qdrant_must(&self, namespace: &str) -> Vec<Value> {
+        let mut must = vec![json!({ "key": "namespace", "match": { "value": namespace } })];
+        if let Some(tags) = &self.tags {
+            must.push(json!({ "key": "tags", "match": { "any": tags } }));
+        }
+        must
+    }
+
+    /// SurrealQL condition restricting a record field (e.g. `in.namespace`) to the allowed
+    /// namespaces, or `None` when no restriction applies.
+    pub fn surreal_namespace_condition(&self, field: &str) -> Option<String> {
+        let allowed = self.namespaces.as_ref()?;
+        Some(format!("{} INSIDE [{}]", field, surreal_list(allowed)))
+    }
+
+    /// SurrealQL condition requiring a `tags` field (e.g. `out.tags`) to hold an allowed tag,
+    /// or `None` when no restriction applies.
+    pub fn surreal_tag_condition(&self, field: &str) -> Option<String> {
+        let allowed = self.tags.as_ref()?;
+        Some(format!("{} CONTAINSANY [{}]", field, surreal_list(allowed)))
+    }
+
+    /// Namespace and tag conditions on a graph record, addressed by `prefix` (`""` for the
+    /// record itself, `"in."` or `"out."` for an edge endpoint). `None` when unrestricted.
+    pub fn surreal_record_condition(&self, prefix: &str) -> Option<String> {
+        let conditions: Vec<String> = [
+            self.surreal_namespace_condition(&format!("{prefix}namespace")),
+            self.surreal_tag_condition(&format!("{prefix}tags")),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        if conditions.is_empty() { None } else { Some(conditions.join(" AND ")) }
+    }
+
+    /// Condition keeping only edges whose endpoints are both visible.
+    pub fn surreal_edge_condition(&self) -> Option<String> {
+        let ins = self.surreal_record_condition("in.")?;
+        let outs = self.surreal_record_condition("out.")?;
+        Some(format!("{} AND {}", ins, outs))
+    }
+}
+
+/// Quoted SurrealQL string list.
+fn surreal_list(values: &[String]) -> String {
+    values
+        .iter()
+        .map(|v| format!("'{}'", v.replace('\\', "\\\\").replace('\'', "\\'")))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
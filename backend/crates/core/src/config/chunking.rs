@@ -0,0 +1,65 @@
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// Token budget for one file type.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+pub struct ChunkSizeConfig {
+    /// Upper bound per chunk, in tokenizer tokens.
+    pub max_tokens: usize,
+    /// Tokens repeated from the end of the previous chunk at the start of the next.
+    #[serde(default)]
+    pub overlap_tokens: usize,
+    /// A trailing chunk smaller than this is merged into its predecessor when it fits.
+    #[serde(default)]
+    pub min_tokens: usize,
+}
+
+impl Default for ChunkSizeConfig {
+    fn default() -> Self {
+        Self { max_tokens: 300, overlap_tokens: 40, min_tokens: 50 }
+    }
+}
+
+/// Chunking settings loaded from `config/chunking.yaml`.
+///
+/// ```yaml
+/// tokenizer: approx        # approx (≈4 chars/token) or whitespace
+/// default: { max_tokens: 300, overlap_tokens: 40, min_tokens: 50 }
+/// file_types:
+///   code: { max_tokens: 400, overlap_tokens: 20, min_tokens: 40 }
+/// ```
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ChunkingConfig {
+    #[serde(default)]
+    pub tokenizer: Option<String>,
+    #[serde(default)]
+    pub default: ChunkSizeConfig,
+    #[serde(default)]
+    pub file_types: HashMap<String, ChunkSizeConfig>,
+}
+
+impl ChunkingConfig {
+    pub fn load_default() -> Self {
+        let path = std::env::var("MNEMO_CHUNKING_CONFIG")
+            .unwrap_or_else(|_| "config/chunking.yaml".into());
+        Self::load_from(&path)
+    }
+
+    pub fn load_from(path: &str) -> Self {
+        let Ok(contents) = std::fs::read_to_string(path) else {
+            return Self::default();
+        };
+        match serde_yaml::from_str::<ChunkingConfig>(&contents) {
+            Ok(cfg) => cfg,
+            Err(err) => {
+                tracing::warn!("Invalid chunking config {}: {}; using defaults", path, err);
+                Self::default()
+            }
+        }
+    }
+
+    /// Sizes for a file type key (`markdown`, `code`, ...), falling back to `default`.
+    pub fn for_file_type(&self, key: &str) -> ChunkSizeConfig {
+        self.file_types.get(key).copied().unwrap_or(self.default)
+    }
+}
@@ -0,0 +1,84 @@
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// File selection rules for one filesystem root.
+///
+/// Unset fields fall back to `defaults`, then to the provider's built-in extension list, 5 MB
+/// cap and `.gitignore`/`.mnemoignore`; include and exclude globs from `defaults` and the root
+/// both apply.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct FilesystemRootConfig {
+    /// Globs relative to the root; when non-empty, only matching files are read.
+    #[serde(default)]
+    pub include: Vec<String>,
+    /// Globs relative to the root for files and directories to skip.
+    #[serde(default)]
+    pub exclude: Vec<String>,
+    /// Allowed extensions without the dot, e.g. `md`.
+    #[serde(default)]
+    pub extensions: Option<Vec<String>>,
+    #[serde(default)]
+    pub max_file_bytes: Option<u64>,
+    /// Ignore file names honored in every directory, in gitignore syntax.
+    #[serde(default)]
+    pub ignore_files: Option<Vec<String>>,
+}
+
+/// `filesystem` section of a profile (`config/profiles/<profile>.yaml`).
+///
+/// ```yaml
+/// filesystem:
+///   defaults: { exclude: ["**/node_modules"], max_file_bytes: 5242880 }
+///   roots:
+///     /app/data: { include: ["docs/**"], extensions: [md, pdf] }
+/// ```
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct FilesystemConfig {
+    #[serde(default)]
+    pub defaults: FilesystemRootConfig,
+    #[serde(default)]
+    pub roots: HashMap<String, FilesystemRootConfig>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct ProfileFile {
+    #[serde(default)]
+    filesystem: FilesystemConfig,
+}
+
+impl FilesystemConfig {
+    /// Section of the profile named by `MNEMO_PROFILE` (default `default`).
+    pub fn load_default() -> Self {
+        let profile = std::env::var("MNEMO_PROFILE").unwrap_or("default".into());
+        Self::load_from(&format!("config/profiles/{}.yaml", profile))
+    }
+
+    pub fn load_from(path: &str) -> Self {
+        let Ok(contents) = std::fs::read_to_string(path) else {
+            return Self::default();
+        };
+        match serde_yaml::from_str::<ProfileFile>(&contents) {
+            Ok(profile) => profile.filesystem,
+            Err(err) => {
+                tracing::warn!("Invalid filesystem config in {}: {}; using defaults", path, err);
+                Self::default()
+            }
+        }
+    }
+
+    /// Rules for a root path: its own entry layered over `defaults`.
+    pub fn for_root(&self, root: &str) -> FilesystemRootConfig {
+        let Some(own) = self.roots.get(root).or_else(|| self.roots.get(root.trim_end_matches('/')))
+        else {
+            return self.defaults.clone();
+        };
+        let defaults = &self.defaults;
+        FilesystemRootConfig {
+            include: defaults.include.iter().chain(&own.include).cloned().collect(),
+            exclude: defaults.exclude.iter().chain(&own.exclude).cloned().collect(),
+            extensions: own.extensions.clone().or_else(|| defaults.extensions.clone()),
+            max_file_bytes: own.max_file_bytes.or(defaults.max_file_bytes),
+            ignore_files: own.ignore_files.clone().or_else(|| defaults.ignore_files.clone()),
+        }
+    }
+}
@@ -18,6 +18,8 @@ impl MnemoConfig {
     }
 }
 
+pub mod chunking;
+pub mod filesystem;
 pub mod ingestion_profile;
 pub mod namespace;
 pub mod profile_loader;
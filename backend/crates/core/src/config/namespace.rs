@@ -1,16 +1,31 @@
 use serde::Deserialize;
 use std::collections::HashMap;
+use std::sync::LazyLock;
+
+static SHARED: LazyLock<NamespaceConfigs> = LazyLock::new(NamespaceConfigs::load_default);
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct NamespaceConfig {
     pub vector_top_k: usize,
     pub graph_depth: u8,
     pub enable_ontology: bool,
+    /// Candidates whose final score is below this are not used as answer context.
+    #[serde(default = "default_min_score")]
+    pub min_score: f32,
+}
+
+fn default_min_score() -> f32 {
+    std::env::var("MNEMO_RAG_MIN_SCORE").ok().and_then(|v| v.parse().ok()).unwrap_or(0.2)
 }
 
 impl Default for NamespaceConfig {
     fn default() -> Self {
-        Self { vector_top_k: 20, graph_depth: 1, enable_ontology: true }
+        Self {
+            vector_top_k: 20,
+            graph_depth: 1,
+            enable_ontology: true,
+            min_score: default_min_score(),
+        }
     }
 }
 
@@ -21,6 +36,11 @@ pub struct NamespaceConfigs {
 }
 
 impl NamespaceConfigs {
+    /// `config/namespace.yaml`, read on first use and kept for the life of the process.
+    pub fn shared() -> &'static Self {
+        &SHARED
+    }
+
     pub fn load_default() -> Self {
         Self::load_from("config/namespace.yaml")
     }
@@ -9,7 +9,11 @@ pub struct ProviderConfig {
 /// Aggregated provider configuration.
 pub struct ProvidersConfig {
     pub filesystem: ProviderConfig,
+    /// GitHub repository; options `repo` (`owner/name`) and `threads` (`true` to ingest issues,
+    /// pull requests and discussions).
     pub github: ProviderConfig,
+    /// Local git clone; options `path`, `ref` (default `HEAD`) and `namespace`.
+    pub git: ProviderConfig,
 }
 
 impl ProvidersConfig {
@@ -18,6 +22,7 @@ impl ProvidersConfig {
         Self {
             filesystem: ProviderConfig { enabled: false, options: HashMap::new() },
             github: ProviderConfig { enabled: false, options: HashMap::new() },
+            git: ProviderConfig { enabled: false, options: HashMap::new() },
         }
     }
 }
@@ -48,7 +48,7 @@ pub fn can_transition(from: &JobStatus, to: &JobStatus) -> bool {
 
 static PG_POOL: OnceCell<PgPool> = OnceCell::const_new();
 
-async fn pool() -> MnemoResult<&'static PgPool> {
+pub(crate) async fn pool() -> MnemoResult<&'static PgPool> {
     PG_POOL
         .get_or_try_init(|| async {
             let url = std::env::var("DATABASE_URL")
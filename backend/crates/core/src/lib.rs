@@ -1,5 +1,6 @@
 // Placeholder library for mnemo-core crate.
 
+pub mod access;
 pub mod config;
 pub mod error;
 pub mod graph;
@@ -12,6 +13,7 @@ pub mod ontology;
 pub mod rag;
 pub mod rag_session;
 pub mod ranking;
+pub mod screening;
 pub mod search;
 pub mod traits;
 pub mod utils;
@@ -10,4 +10,6 @@ pub struct Chunk {
     pub namespace: String,
     pub sparse_indices: Vec<u32>,
     pub sparse_values: Vec<f32>,
+    /// Structural provenance from chunking (e.g. code symbol and scope), stored with the vector.
+    pub metadata: serde_json::Map<String, serde_json::Value>,
 }
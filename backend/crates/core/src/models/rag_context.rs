@@ -1,5 +1,21 @@
 use crate::rag::orchestrator::DebugCandidate;
 use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Outcome of the retrieval stage, so callers can tell real context from an empty result.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RetrievalStatus {
+    /// At least one candidate cleared the namespace's minimum score.
+    Ok,
+    /// The search succeeded but nothing relevant enough was found.
+    #[default]
+    NoResults,
+    /// The vector backend could not be queried; results may be missing.
+    DegradedBackend,
+    /// The caller's access scope does not include the requested namespace.
+    Forbidden,
+}
 
 /// Aggregated context pulled from various retrieval sources.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -11,4 +27,19 @@ pub struct RAGContext {
     pub ontology_tags: Vec<String>,
     #[serde(default)]
     pub debug_candidates: Vec<DebugCandidate>,
+    /// Id to reference this answer in `/v1/rag/feedback`.
+    #[serde(default)]
+    pub query_id: Option<Uuid>,
+    #[serde(default)]
+    pub status: RetrievalStatus,
+    /// 0..1 estimate derived from the candidate score distribution.
+    #[serde(default)]
+    pub confidence: f32,
+}
+
+impl RAGContext {
+    /// Whether there is enough context to generate an answer from.
+    pub fn is_sufficient(&self) -> bool {
+        self.status == RetrievalStatus::Ok
+    }
 }
@@ -0,0 +1,285 @@
+use std::sync::Arc;
+
+use mnemo_inference::traits::InferenceEngine;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::config::namespace::NamespaceConfigs;
+use crate::models::rag_context::RetrievalStatus;
+use crate::rag::orchestrator::{DebugCandidate, RAGOrchestrator, confidence_score};
+use crate::ws::WS_HUB;
+
+/// Next move proposed by the planner LLM.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum AgentAction {
+    /// Run another retrieval pass with a focused sub-query.
+    Search { query: String },
+    /// Hop to a document that already produced evidence and pull more of its chunks.
+    Expand { document: String },
+    /// Enough evidence has been gathered.
+    Stop {
+        #[serde(default)]
+        reason: String,
+    },
+}
+
+/// One iteration of the loop, as returned to the client and streamed over WS.
+#[derive(Debug, Clone, Serialize)]
+pub struct AgentStep {
+    pub step: usize,
+    pub action: AgentAction,
+    /// Candidates returned by this step's retrieval.
+    pub retrieved: usize,
+    /// Candidates that were not already part of the evidence.
+    pub new_evidence: usize,
+    /// Documents an `expand` hop reached through the graph, besides the expanded one.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub linked: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AgenticResult {
+    pub query_id: Uuid,
+    pub status: RetrievalStatus,
+    pub confidence: f32,
+    pub stop_reason: String,
+    pub trace: Vec<AgentStep>,
+    /// Accumulated candidates, best first.
+    pub evidence: Vec<DebugCandidate>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct AgenticConfig {
+    /// Maximum number of retrieval steps, including the initial search.
+    pub max_steps: usize,
+    /// Evidence kept (best first) and shown to the planner.
+    pub max_evidence: usize,
+}
+
+/// Most documents an `expand` hop pulls in besides the expanded one.
+const MAX_HOP_DOCUMENTS: usize = 10;
+
+impl Default for AgenticConfig {
+    fn default() -> Self {
+        let max_steps = std::env::var("MNEMO_AGENT_MAX_STEPS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(4usize)
+            .clamp(1, 12);
+        Self { max_steps, max_evidence: 20 }
+    }
+}
+
+/// Iterative retrieval: the planner proposes sub-queries or document hops, the orchestrator
+/// retrieves for each, and evidence accumulates until the planner stops or the budget runs out.
+/// A hop follows the graph from a document to the documents linked to it.
+pub struct AgenticRetriever<'a> {
+    orchestrator: &'a RAGOrchestrator,
+    planner: Option<Arc<dyn InferenceEngine + Send + Sync>>,
+    config: AgenticConfig,
+}
+
+impl<'a> AgenticRetriever<'a> {
+    /// Without a planner the loop degrades to a single retrieval pass.
+    pub fn new(
+        orchestrator: &'a RAGOrchestrator,
+        planner: Option<Arc<dyn InferenceEngine + Send + Sync>>,
+    ) -> Self {
+        Self { orchestrator, planner, config: AgenticConfig::default() }
+    }
+
+    pub fn with_config(mut self, config: AgenticConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    pub async fn run(&self, question: &str) -> AgenticResult {
+        let query_id = Uuid::new_v4();
+        let mut evidence: Vec<DebugCandidate> = Vec::new();
+        let mut trace: Vec<AgentStep> = Vec::new();
+        let mut degraded = false;
+        let mut forbidden = false;
+        let mut action = AgentAction::Search { query: question.to_string() };
+        let mut stop_reason = String::from("step_budget_exhausted");
+
+        for step in 0..self.config.max_steps {
+            let mut linked = Vec::new();
+            let (retrieved, status) = match &action {
+                AgentAction::Search { query } => self.orchestrator.retrieve(query, None).await,
+                AgentAction::Expand { document } => {
+                    linked = self.orchestrator.linked_documents(document, MAX_HOP_DOCUMENTS).await;
+                    let must = vec![hop_filter(document, &linked)];
+                    self.orchestrator.retrieve_filtered(question, None, must).await
+                }
+                AgentAction::Stop { .. } => break,
+            };
+            if status == RetrievalStatus::Forbidden {
+                forbidden = true;
+                stop_reason = "namespace_forbidden".into();
+                break;
+            }
+            degraded |= status == RetrievalStatus::DegradedBackend;
+            let retrieved_count = retrieved.len();
+            let new_evidence = merge_evidence(&mut evidence, retrieved, self.config.max_evidence);
+            let record = AgentStep {
+                step,
+                action: action.clone(),
+                retrieved: retrieved_count,
+                new_evidence,
+                linked,
+            };
+            self.broadcast(
+                json!({ "event": "rag_agent_step", "query_id": query_id, "step": record }),
+            );
+            trace.push(record);
+
+            if step + 1 >= self.config.max_steps {
+                break;
+            }
+            let Some(planner) = &self.planner else {
+                stop_reason = "planner_unavailable".into();
+                break;
+            };
+            let reply = planner.infer(planner_prompt(question, &trace, &evidence)).await;
+            match parse_action(&reply) {
+                Some(AgentAction::Stop { reason }) => {
+                    stop_reason = if reason.is_empty() { "planner_stop".into() } else { reason };
+                    break;
+                }
+                Some(next) if trace.iter().any(|t| t.action == next) => {
+                    stop_reason = "repeated_action".into();
+                    break;
+                }
+                Some(next) => action = next,
+                None => {
+                    tracing::debug!("Unparseable planner reply: {}", reply);
+                    stop_reason = "planner_reply_unparseable".into();
+                    break;
+                }
+            }
+        }
+
+        let min_score =
+            NamespaceConfigs::shared().for_namespace(self.orchestrator.namespace()).min_score;
+        let confidence =
+            confidence_score(&evidence.iter().map(|c| c.final_score).collect::<Vec<_>>());
+        let status = if forbidden {
+            RetrievalStatus::Forbidden
+        } else if degraded {
+            RetrievalStatus::DegradedBackend
+        } else if evidence.iter().any(|c| !c.quarantined && c.final_score >= min_score) {
+            RetrievalStatus::Ok
+        } else {
+            RetrievalStatus::NoResults
+        };
+
+        self.orchestrator.log_query_with_id(query_id, question, &evidence);
+        self.broadcast(json!({
+            "event": "rag_agent_done",
+            "query_id": query_id,
+            "steps": trace.len(),
+            "stop_reason": stop_reason,
+            "status": status,
+        }));
+
+        AgenticResult { query_id, status, confidence, stop_reason, trace, evidence }
+    }
+
+    /// Tag an event with the namespace and principal of the request, so `/ws/rag` forwards it
+    /// only to subscribers allowed to see it.
+    fn broadcast(&self, mut event: serde_json::Value) {
+        event["namespace"] = json!(self.orchestrator.namespace());
+        event["principal"] = json!(self.orchestrator.scope().principal);
+        WS_HUB.broadcast(event.to_string());
+    }
+}
+
+/// Qdrant condition matching the chunks of the expanded document and the documents linked to it.
+pub fn hop_filter(document: &str, linked: &[String]) -> serde_json::Value {
+    let mut paths = vec![document];
+    paths.extend(linked.iter().map(String::as_str).filter(|p| *p != document));
+    json!({ "key": "path", "match": { "any": paths } })
+}
+
+/// Add new candidates (deduplicated by point id, keeping the higher score) and keep the best
+/// `cap`. Returns how many candidates were new.
+fn merge_evidence(
+    evidence: &mut Vec<DebugCandidate>,
+    found: Vec<DebugCandidate>,
+    cap: usize,
+) -> usize {
+    let mut added = 0;
+    for candidate in found {
+        let existing = evidence.iter_mut().find(|e| {
+            if candidate.point_id.is_empty() {
+                e.chunk == candidate.chunk
+            } else {
+                e.point_id == candidate.point_id
+            }
+        });
+        match existing {
+            Some(e) if candidate.final_score > e.final_score => *e = candidate,
+            Some(_) => {}
+            None => {
+                evidence.push(candidate);
+                added += 1;
+            }
+        }
+    }
+    evidence.sort_by(|a, b| {
+        b.final_score.partial_cmp(&a.final_score).unwrap_or(std::cmp::Ordering::Equal)
+    });
+    evidence.truncate(cap);
+    added
+}
+
+/// Prompt asking the planner for the next action. Quarantined or screening-flagged evidence is
+/// never shown to the planner, so injected text cannot steer the plan.
+pub fn planner_prompt(question: &str, trace: &[AgentStep], evidence: &[DebugCandidate]) -> String {
+    let steps = trace
+        .iter()
+        .map(|s| {
+            format!(
+                "- {} -> {} new",
+                serde_json::to_string(&s.action).unwrap_or_default(),
+                s.new_evidence
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    let found = evidence
+        .iter()
+        .filter(|c| !c.quarantined && c.screening_flags.is_empty())
+        .take(8)
+        .map(|c| {
+            let snippet: String = c.chunk.chars().take(240).collect();
+            format!("- [{}] {}", c.document, snippet.replace('\n', " "))
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    format!(
+        "You are planning retrieval for the question below. Decide the next step.\n\
+         Reply with exactly one JSON object and nothing else:\n\
+         {{\"action\":\"search\",\"query\":\"<focused sub-query>\"}}\n\
+         {{\"action\":\"expand\",\"document\":\"<document path from the evidence>\"}}\n\
+         {{\"action\":\"stop\",\"reason\":\"<why the evidence is sufficient>\"}}\n\n\
+         Question: {question}\n\nSteps so far:\n{steps}\n\nEvidence so far:\n{found}\n"
+    )
+}
+
+/// Extract the first JSON object from a planner reply and decode it as an action.
+pub fn parse_action(reply: &str) -> Option<AgentAction> {
+    let start = reply.find('{')?;
+    let end = reply.rfind('}')?;
+    if end < start {
+        return None;
+    }
+    let action: AgentAction = serde_json::from_str(&reply[start..=end]).ok()?;
+    match &action {
+        AgentAction::Search { query } if query.trim().is_empty() => None,
+        AgentAction::Expand { document } if document.trim().is_empty() => None,
+        _ => Some(action),
+    }
+}
@@ -1,4 +1,4 @@
-use super::super::models::rag_context::RAGContext;
+use super::super::models::rag_context::{RAGContext, RetrievalStatus};
 
 /// Placeholder context assembler combining retrieval sources.
 pub struct ContextAssembler;
@@ -16,6 +16,9 @@ impl ContextAssembler {
             graph_neighbors: vec![],
             ontology_tags: vec![],
             debug_candidates: vec![],
+            query_id: None,
+            status: RetrievalStatus::NoResults,
+            confidence: 0.0,
         }
     }
 }
@@ -1,3 +1,4 @@
+pub mod agentic;
 pub mod api;
 pub mod assembler;
 pub mod cache;
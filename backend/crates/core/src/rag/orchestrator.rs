@@ -1,25 +1,41 @@
 use crate::{
-    models::rag_context::RAGContext,
+    access::AccessScope,
+    config::namespace::NamespaceConfigs,
+    models::rag_context::{RAGContext, RetrievalStatus},
     rag::{
         cache,
         keyword::{score_keyword, sparse_vector},
         query_preprocessor,
     },
     rag_session::RagSession,
+    ranking::{feedback, profile},
+    screening::{self, DOWNWEIGHT_FACTOR, Screener, ScreeningMode},
     traits::ranking_engine::RankingEngine,
 };
 use reqwest::Client;
 use serde::Serialize;
 use serde_json::json;
+use sha2::{Digest, Sha256};
 use std::collections::HashSet;
+use std::time::Duration;
+use uuid::Uuid;
 
-/// Placeholder orchestrator for RAG query execution.
-pub struct RAGOrchestrator;
+const QUERY_LOG_TIMEOUT: Duration = Duration::from_millis(500);
 
-struct SimpleRankingEngine;
+/// Runs RAG retrieval for a namespace within the caller's access scope: hybrid Qdrant search,
+/// ranking with the namespace's active profile, screening, and query logging for feedback.
+pub struct RAGOrchestrator {
+    namespace: String,
+    scope: AccessScope,
+}
 
 #[derive(Debug, Clone, Serialize, serde::Deserialize)]
 pub struct DebugCandidate {
+    /// Qdrant point id; used as the citation id in answer feedback.
+    #[serde(default)]
+    pub point_id: String,
+    #[serde(default)]
+    pub document: String,
     pub chunk: String,
     pub vector_score: f32,
     pub keyword_score: f32,
@@ -28,24 +44,45 @@ pub struct DebugCandidate {
     pub final_score: f32,
     pub tags: Vec<String>,
     pub neighbors_count: usize,
-}
-
-impl RankingEngine for SimpleRankingEngine {
-    fn score(
-        &self,
-        vector_score: f32,
-        keyword_score: f32,
-        graph_score: f32,
-        knowledge_score: f32,
-    ) -> f32 {
-        // Simple weighted sum; adjust weights later via config.
-        vector_score * 0.6 + keyword_score * 0.2 + graph_score * 0.1 + knowledge_score * 0.1
-    }
+    /// Prompt-injection screening flags (rule names or `classifier`).
+    #[serde(default)]
+    pub screening_flags: Vec<String>,
+    /// Flagged and excluded from the answer context (`MNEMO_SCREENING_MODE=quarantine`).
+    #[serde(default)]
+    pub quarantined: bool,
 }
 
 impl RAGOrchestrator {
     pub fn new() -> Self {
-        Self
+        Self { namespace: "default".into(), scope: AccessScope::unrestricted() }
+    }
+
+    pub fn namespace(&self) -> &str {
+        &self.namespace
+    }
+
+    pub fn scope(&self) -> &AccessScope {
+        &self.scope
+    }
+
+    /// Scope retrieval and ranking-profile lookup to a namespace.
+    pub fn with_namespace(mut self, namespace: impl Into<String>) -> Self {
+        self.namespace = namespace.into();
+        self
+    }
+
+    /// Restrict retrieval and graph expansion to what the caller may see.
+    pub fn with_scope(mut self, scope: AccessScope) -> Self {
+        self.scope = scope;
+        self
+    }
+
+    /// Cached contexts are only shared between callers with the same namespace and scope.
+    fn cache_key(&self, query: &str) -> String {
+        if self.namespace == "default" && self.scope.is_unrestricted() {
+            return query.to_string();
+        }
+        format!("{}|{:?}|{:?}|{}", self.namespace, self.scope.namespaces, self.scope.tags, query)
     }
 
     pub async fn run(&self, query: &str) -> RAGContext {
@@ -53,16 +90,23 @@ impl RAGOrchestrator {
     }
 
     pub async fn run_with_context(&self, query: &str, session: Option<&RagSession>) -> RAGContext {
-        if let Some(cached) = cache::get_cached(query).await {
+        if let Some(mut cached) = cache::get_cached(&self.cache_key(query)).await {
+            cached.query_id = Some(self.log_query(query, &cached.debug_candidates));
             return cached;
         }
 
-        let candidates = self.gather_candidates(query, session).await;
+        let (candidates, backend_status) = self.retrieve(query, session).await;
+        let query_id = self.log_query(query, &candidates);
+        let min_score = NamespaceConfigs::shared().for_namespace(&self.namespace).min_score;
+        let confidence =
+            confidence_score(&candidates.iter().map(|c| c.final_score).collect::<Vec<_>>());
 
         let mut project_chunks = Vec::new();
         let mut domain_chunks = Vec::new();
         let mut company_chunks = Vec::new();
-        for candidate in candidates.iter().take(10) {
+        for candidate in
+            candidates.iter().filter(|c| !c.quarantined && c.final_score >= min_score).take(10)
+        {
             if candidate.tags.iter().any(|t| t.contains("project")) {
                 project_chunks.push(candidate.chunk.clone());
             } else if candidate.tags.iter().any(|t| t.contains("domain")) {
@@ -74,12 +118,14 @@ impl RAGOrchestrator {
             }
         }
 
-        // Fallback if nothing returned from vector search
-        if project_chunks.is_empty() && domain_chunks.is_empty() && company_chunks.is_empty() {
-            project_chunks.push("test_project_chunk".into());
-            domain_chunks.push("test_domain_chunk".into());
-            company_chunks.push("test_company_chunk".into());
-        }
+        let has_context =
+            !(project_chunks.is_empty() && domain_chunks.is_empty() && company_chunks.is_empty());
+        let status = match backend_status {
+            RetrievalStatus::DegradedBackend => RetrievalStatus::DegradedBackend,
+            RetrievalStatus::Forbidden => RetrievalStatus::Forbidden,
+            _ if has_context => RetrievalStatus::Ok,
+            _ => RetrievalStatus::NoResults,
+        };
 
         let ctx = RAGContext {
             project_chunks,
@@ -88,19 +134,126 @@ impl RAGOrchestrator {
             graph_neighbors: vec![],
             ontology_tags: vec![],
             debug_candidates: candidates,
+            query_id: Some(query_id),
+            status,
+            confidence,
         };
 
-        cache::set_cached(query, &ctx).await;
+        // Don't pin a transient backend failure, or a refusal, in the cache.
+        if !matches!(status, RetrievalStatus::DegradedBackend | RetrievalStatus::Forbidden) {
+            cache::set_cached(&self.cache_key(query), &ctx).await;
+        }
         ctx
     }
 
+    /// Assign a fresh query id and store the scored candidates so feedback can refer to them.
+    /// Logging is best-effort: the id is returned even if Postgres is unavailable.
+    pub fn log_query(&self, query: &str, candidates: &[DebugCandidate]) -> Uuid {
+        let query_id = Uuid::new_v4();
+        self.log_query_with_id(query_id, query, candidates);
+        query_id
+    }
+
+    /// Best-effort logging under a caller-chosen query id. The write runs in the background so
+    /// a slow or unreachable Postgres never delays the answer.
+    pub fn log_query_with_id(&self, query_id: Uuid, query: &str, candidates: &[DebugCandidate]) {
+        let namespace = self.namespace.clone();
+        let query = query.to_string();
+        let candidates = candidates.to_vec();
+        tokio::spawn(async move {
+            let log = feedback::log_query(query_id, &namespace, &query, &candidates);
+            match tokio::time::timeout(QUERY_LOG_TIMEOUT, log).await {
+                Ok(Ok(())) => {}
+                Ok(Err(err)) => tracing::debug!("RAG query log failed: {}", err),
+                Err(_) => tracing::debug!("RAG query log timed out"),
+            }
+        });
+    }
+
+    /// Paths of the documents one graph hop from `document`: linked to it through `edge`, directly
+    /// or via a shared person or channel, and holding chunks the caller may see through
+    /// `contains`. At most `limit` paths; empty when the graph is unreachable.
+    pub async fn linked_documents(&self, document: &str, limit: usize) -> Vec<String> {
+        let file_id = format!("file:{}", hex_digest(document));
+        let rows = surreal_rows(&format!(
+            "SELECT in, out FROM edge WHERE in = {file_id} OR out = {file_id} LIMIT 100;"
+        ))
+        .await;
+        let (mut files, entities): (HashSet<String>, HashSet<String>) = edge_endpoints(&rows)
+            .into_iter()
+            .filter(|id| *id != file_id)
+            .partition(|id| id.starts_with("file:"));
+        if !entities.is_empty() {
+            let entities = entities.into_iter().collect::<Vec<_>>().join(", ");
+            let rows = surreal_rows(&format!(
+                "SELECT in, out FROM edge WHERE in INSIDE [{entities}] AND out != {file_id} LIMIT 100;"
+            ))
+            .await;
+            files.extend(edge_endpoints(&rows).into_iter().filter(|id| id.starts_with("file:")));
+        }
+        if files.is_empty() {
+            return Vec::new();
+        }
+
+        let files = files.into_iter().collect::<Vec<_>>().join(", ");
+        let visible = [
+            self.scope.surreal_record_condition("in."),
+            self.scope.surreal_record_condition("out."),
+        ]
+        .into_iter()
+        .flatten()
+        .map(|c| format!(" AND {c}"))
+        .collect::<String>();
+        let rows = surreal_rows(&format!(
+            "SELECT in.path AS path FROM contains WHERE in INSIDE [{files}]{visible} LIMIT 500;"
+        ))
+        .await;
+        let mut paths: Vec<String> = Vec::new();
+        for path in rows.iter().filter_map(|r| r.get("path").and_then(|v| v.as_str())) {
+            if path != document && !paths.iter().any(|p| p == path) {
+                paths.push(path.to_string());
+            }
+        }
+        paths.truncate(limit);
+        paths
+    }
+
     /// Return scored candidates with explanations for debugging.
     pub async fn gather_candidates(
         &self,
         query: &str,
         session: Option<&RagSession>,
     ) -> Vec<DebugCandidate> {
-        let namespace = "default";
+        self.retrieve(query, session).await.0
+    }
+
+    /// Scored candidates, best first, together with whether the vector backend answered.
+    /// The status is `Ok` or `DegradedBackend`; the minimum-score check happens in
+    /// `run_with_context`.
+    pub async fn retrieve(
+        &self,
+        query: &str,
+        session: Option<&RagSession>,
+    ) -> (Vec<DebugCandidate>, RetrievalStatus) {
+        self.retrieve_filtered(query, session, Vec::new()).await
+    }
+
+    /// Like `retrieve`, with extra Qdrant `must` conditions on top of the access scope.
+    pub async fn retrieve_filtered(
+        &self,
+        query: &str,
+        session: Option<&RagSession>,
+        extra_must: Vec<serde_json::Value>,
+    ) -> (Vec<DebugCandidate>, RetrievalStatus) {
+        let namespace = self.namespace.as_str();
+        if !self.scope.allows_namespace(namespace) {
+            tracing::warn!(
+                "Principal {:?} is not allowed to query namespace {}",
+                self.scope.principal,
+                namespace
+            );
+            return (Vec::new(), RetrievalStatus::Forbidden);
+        }
         let qdrant_url = select_endpoint(namespace);
 
         let mut previous_queries: Vec<String> = Vec::new();
@@ -111,7 +264,7 @@ impl RAGOrchestrator {
         }
         let cleaned_query = query_preprocessor::normalize(query);
         let inferred_tags = infer_tags(&cleaned_query);
-        let graph_neighbors = fetch_graph_neighbors(2).await;
+        let graph_neighbors = fetch_graph_neighbors(2, &self.scope).await;
         let extended_query = build_extended_query(
             &cleaned_query,
             &previous_queries,
@@ -120,152 +273,214 @@ impl RAGOrchestrator {
         );
         let query_vec = vec![0.1_f32; 1536];
         let (q_indices, q_values) = sparse_vector(&extended_query);
-        let ranker = SimpleRankingEngine;
+        let ranker = profile::active_or_baseline(namespace).await;
+        let screening_mode = ScreeningMode::from_env();
         let mut explanations = Vec::new();
 
+        let mut must = self.scope.qdrant_must(namespace);
+        must.extend(extra_must);
         let payload = json!({
             "vector": { "name": "dense", "vector": query_vec },
             "sparse_vector": { "indices": q_indices, "values": q_values },
             "limit": 5,
             "with_payload": true,
-            "filter": { "must": [ { "key": "namespace", "match": { "value": namespace } } ] }
+            "filter": { "must": must }
         });
 
-        if let Ok(resp) = Client::new()
-            .post(format!("{}/collections/mnemo_chunks/points/search", qdrant_url))
-            .json(&payload)
-            .send()
-            .await
-        {
-            if let Ok(body) = resp.json::<serde_json::Value>().await {
-                if let Some(arr) = body.get("result").and_then(|r| r.as_array()) {
-                    for item in arr {
-                        let vector_score =
-                            item.get("score").and_then(|v| v.as_f64()).unwrap_or(0.0) as f32;
-                        let point_id = item.get("id").and_then(|v| v.as_str()).unwrap_or_default();
-                        let mut text = String::new();
-                        let mut doc_path = String::new();
-                        let mut namespace_val = namespace.to_string();
-                        let mut tags: Vec<String> = Vec::new();
-
-                        if let Some(entry) = cache::get_chunk_cached(point_id).await {
-                            text = entry.text;
-                            doc_path = entry.file_path;
-                            tags = entry.tags;
-                        } else if let Some(payload) = item.get("payload") {
-                            text = payload
-                                .get("text")
-                                .or_else(|| payload.get("chunk_text"))
-                                .and_then(|v| v.as_str())
-                                .unwrap_or_default()
-                                .to_string();
-                            doc_path = payload
-                                .get("document")
-                                .or_else(|| payload.get("document_path"))
-                                .and_then(|v| v.as_str())
-                                .unwrap_or_default()
-                                .to_string();
-                            namespace_val = payload
-                                .get("namespace")
-                                .and_then(|v| v.as_str())
-                                .unwrap_or(namespace)
-                                .to_string();
-                            tags = payload
-                                .get("tags")
-                                .and_then(|v| {
-                                    if let Some(arr) = v.as_array() {
-                                        Some(
-                                            arr.iter()
-                                                .filter_map(|x| x.as_str().map(|s| s.to_string()))
-                                                .collect::<Vec<_>>(),
-                                        )
-                                    } else if let Some(s) = v.as_str() {
-                                        Some(
-                                            s.split(',')
-                                                .map(|t| t.trim().to_string())
-                                                .filter(|t| !t.is_empty())
-                                                .collect(),
-                                        )
-                                    } else {
-                                        None
-                                    }
+        let hits = search_points(&qdrant_url, &payload).await;
+        let status =
+            if hits.is_some() { RetrievalStatus::Ok } else { RetrievalStatus::DegradedBackend };
+        if let Some(arr) = hits.as_ref() {
+            for item in arr {
+                let vector_score = item.get("score").and_then(|v| v.as_f64()).unwrap_or(0.0) as f32;
+                let point_id = match item.get("id") {
+                    Some(serde_json::Value::String(s)) => s.clone(),
+                    Some(v) if !v.is_null() => v.to_string(),
+                    _ => String::new(),
+                };
+                let point_id = point_id.as_str();
+                let mut text = String::new();
+                let mut doc_path = String::new();
+                let mut namespace_val = namespace.to_string();
+                let mut tags: Vec<String> = Vec::new();
+
+                if let Some(entry) = cache::get_chunk_cached(point_id).await {
+                    text = entry.text;
+                    doc_path = entry.file_path;
+                    tags = entry.tags;
+                } else if let Some(payload) = item.get("payload") {
+                    text = payload
+                        .get("text")
+                        .or_else(|| payload.get("chunk_text"))
+                        .and_then(|v| v.as_str())
+                        .unwrap_or_default()
+                        .to_string();
+                    doc_path = payload
+                        .get("document")
+                        .or_else(|| payload.get("document_path"))
+                        .or_else(|| payload.get("path"))
+                        .and_then(|v| v.as_str())
+                        .unwrap_or_default()
+                        .to_string();
+                    namespace_val = payload
+                        .get("namespace")
+                        .and_then(|v| v.as_str())
+                        .unwrap_or(namespace)
+                        .to_string();
+                    tags = payload
+                        .get("tags")
+                        .and_then(|v| {
+                            if let Some(arr) = v.as_array() {
+                                Some(
+                                    arr.iter()
+                                        .filter_map(|x| x.as_str().map(|s| s.to_string()))
+                                        .collect::<Vec<_>>(),
+                                )
+                            } else {
+                                v.as_str().map(|s| {
+                                    s.split(',')
+                                        .map(|t| t.trim().to_string())
+                                        .filter(|t| !t.is_empty())
+                                        .collect()
                                 })
-                                .unwrap_or_default();
-                            cache::set_chunk_cached(
-                                point_id,
-                                &cache::ChunkCacheEntry {
-                                    text: text.clone(),
-                                    tags: tags.clone(),
-                                    file_path: doc_path.clone(),
-                                },
-                            )
-                            .await;
-                        }
-
-                        let mut keyword_score = if extended_query.is_empty() {
-                            0.0
-                        } else {
-                            score_keyword(&extended_query, &text)
-                        };
-                        if is_code_file(&doc_path) {
-                            keyword_score *= 0.9;
-                        }
-                        if text.trim_start().starts_with('#') {
-                            keyword_score *= 1.10;
-                        }
-                        if namespace_val.starts_with("openapi") {
-                            keyword_score *= 1.15;
-                        }
-
-                        // Ontology weighting: simple boosts.
-                        let knowledge_score = if tags.iter().any(|t| t.contains("project")) {
-                            1.0
-                        } else if tags.iter().any(|t| t.contains("domain")) {
-                            0.8
-                        } else if tags.iter().any(|t| t.contains("company")) {
-                            0.6
-                        } else {
-                            0.0
-                        };
-
-                        let file_id = format!("file:{}", doc_path);
-                        let (graph_score, neighbors_count) = graph_score_for(&file_id, 1).await;
-                        let final_score =
-                            ranker.score(vector_score, keyword_score, graph_score, knowledge_score);
-
-                        explanations.push(DebugCandidate {
-                            chunk: text,
-                            vector_score,
-                            keyword_score,
-                            graph_score,
-                            ontology_score: knowledge_score,
-                            final_score,
+                            }
+                        })
+                        .unwrap_or_default();
+                    cache::set_chunk_cached(
+                        point_id,
+                        &cache::ChunkCacheEntry {
+                            text: text.clone(),
                             tags: tags.clone(),
-                            neighbors_count,
-                        });
-                    }
+                            file_path: doc_path.clone(),
+                        },
+                    )
+                    .await;
+                }
+
+                // The Qdrant filter already enforces this; cached entries are checked again.
+                if !self.scope.allows_tags(&tags) {
+                    continue;
+                }
+
+                let mut keyword_score = if extended_query.is_empty() {
+                    0.0
+                } else {
+                    score_keyword(&extended_query, &text)
+                };
+                if is_code_file(&doc_path) {
+                    keyword_score *= 0.9;
+                }
+                if text.trim_start().starts_with('#') {
+                    keyword_score *= 1.10;
+                }
+                if namespace_val.starts_with("openapi") {
+                    keyword_score *= 1.15;
                 }
+
+                // Ontology weighting: simple boosts.
+                let knowledge_score = if tags.iter().any(|t| t.contains("project")) {
+                    1.0
+                } else if tags.iter().any(|t| t.contains("domain")) {
+                    0.8
+                } else if tags.iter().any(|t| t.contains("company")) {
+                    0.6
+                } else {
+                    0.0
+                };
+
+                let file_id = format!("file:{}", doc_path);
+                let (graph_score, neighbors_count) =
+                    graph_score_for(&file_id, 1, &self.scope).await;
+                let final_score =
+                    ranker.score(vector_score, keyword_score, graph_score, knowledge_score);
+
+                explanations.push(DebugCandidate {
+                    point_id: point_id.to_string(),
+                    document: doc_path.clone(),
+                    chunk: text,
+                    vector_score,
+                    keyword_score,
+                    graph_score,
+                    ontology_score: knowledge_score,
+                    final_score,
+                    tags: tags.clone(),
+                    neighbors_count,
+                    // Flags stored at ingest time; the retrieved text is screened again below.
+                    screening_flags: screening::flags_from_tags(&tags),
+                    quarantined: false,
+                });
             }
         }
 
-        if explanations.is_empty() {
-            explanations.push(DebugCandidate {
-                chunk: "test_project_chunk".into(),
-                vector_score: 0.0,
-                keyword_score: 0.0,
-                graph_score: 0.0,
-                ontology_score: 0.0,
-                final_score: 0.1,
-                tags: vec!["project".into()],
-                neighbors_count: 0,
-            });
+        if screening_mode != ScreeningMode::Off {
+            let texts = explanations.iter().map(|c| c.chunk.clone()).collect();
+            let screened = Screener::global().screen_all(texts).await;
+            for (candidate, flags) in explanations.iter_mut().zip(screened) {
+                for flag in flags {
+                    if !candidate.screening_flags.contains(&flag) {
+                        candidate.screening_flags.push(flag);
+                    }
+                }
+                if candidate.screening_flags.is_empty() {
+                    continue;
+                }
+                match screening_mode {
+                    ScreeningMode::Downweight => candidate.final_score *= DOWNWEIGHT_FACTOR,
+                    ScreeningMode::Quarantine => candidate.quarantined = true,
+                    ScreeningMode::Off => {}
+                }
+            }
         }
 
         explanations.sort_by(|a, b| {
             b.final_score.partial_cmp(&a.final_score).unwrap_or(std::cmp::Ordering::Equal)
         });
-        explanations
+        (explanations, status)
+    }
+}
+
+/// Run the Qdrant search. `None` means the backend was unreachable or answered with an error,
+/// as opposed to `Some(vec![])` for a search that simply matched nothing.
+async fn search_points(
+    qdrant_url: &str,
+    payload: &serde_json::Value,
+) -> Option<Vec<serde_json::Value>> {
+    let resp = match Client::new()
+        .post(format!("{}/collections/mnemo_chunks/points/search", qdrant_url))
+        .json(payload)
+        .send()
+        .await
+    {
+        Ok(resp) => resp,
+        Err(err) => {
+            tracing::warn!("Qdrant search failed at {}: {}", qdrant_url, err);
+            return None;
+        }
+    };
+    if !resp.status().is_success() {
+        tracing::warn!("Qdrant search returned {} at {}", resp.status(), qdrant_url);
+        return None;
+    }
+    let body = resp.json::<serde_json::Value>().await.ok()?;
+    body.get("result").and_then(|r| r.as_array()).cloned()
+}
+
+/// Confidence in 0..1 from the final scores: the best score, discounted when it barely
+/// stands out from the rest of the candidates.
+pub fn confidence_score(scores: &[f32]) -> f32 {
+    let mut sorted: Vec<f32> = scores.iter().copied().filter(|s| s.is_finite()).collect();
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    sorted.sort_by(|a, b| b.partial_cmp(a).unwrap_or(std::cmp::Ordering::Equal));
+    let top = sorted[0].clamp(0.0, 1.0);
+    if sorted.len() == 1 || top <= f32::EPSILON {
+        return top;
     }
+    let rest = &sorted[1..];
+    let mean_rest = rest.iter().sum::<f32>() / rest.len() as f32;
+    let separation = ((top - mean_rest) / top).clamp(0.0, 1.0);
+    (top * (0.7 + 0.3 * separation)).clamp(0.0, 1.0)
 }
 
 fn build_extended_query(
@@ -309,10 +524,15 @@ fn infer_tags(query: &str) -> Vec<String> {
     tags.into_iter().collect()
 }
 
-async fn fetch_graph_neighbors(depth: u8) -> Vec<String> {
+async fn fetch_graph_neighbors(depth: u8, scope: &AccessScope) -> Vec<String> {
     let client = Client::new();
     let limit = 50 * depth.max(1) as usize;
-    let sql = format!("SELECT DISTINCT in, out FROM edge LIMIT {};", limit);
+    let sql = match scope.surreal_edge_condition() {
+        Some(clause) => {
+            format!("SELECT DISTINCT in, out FROM edge WHERE {} LIMIT {};", clause, limit)
+        }
+        None => format!("SELECT DISTINCT in, out FROM edge LIMIT {};", limit),
+    };
     if let Ok(resp) = client.post("http://surrealdb:8000/sql").body(sql).send().await {
         if let Ok(val) = resp.json::<serde_json::Value>().await {
             let mut neighbors = HashSet::new();
@@ -332,12 +552,51 @@ async fn fetch_graph_neighbors(depth: u8) -> Vec<String> {
     Vec::new()
 }
 
-async fn graph_score_for(file_id: &str, depth: u8) -> (f32, usize) {
+/// Rows of a SurrealQL statement; empty when the graph is unreachable.
+async fn surreal_rows(sql: &str) -> Vec<serde_json::Value> {
+    let Ok(resp) =
+        Client::new().post("http://surrealdb:8000/sql").body(sql.to_string()).send().await
+    else {
+        return Vec::new();
+    };
+    let Ok(val) = resp.json::<serde_json::Value>().await else { return Vec::new() };
+    // One result object per statement, or a bare result object.
+    let result = match &val {
+        serde_json::Value::Array(results) => results.first().and_then(|r| r.get("result")),
+        _ => val.get("result"),
+    };
+    result.and_then(|v| v.as_array()).cloned().unwrap_or_default()
+}
+
+/// Record ids at either end of `edge` rows. Only plain `table:id` ids are kept, since they are
+/// spliced into follow-up statements.
+fn edge_endpoints(rows: &[serde_json::Value]) -> HashSet<String> {
+    rows.iter()
+        .flat_map(|row| [row.get("in"), row.get("out")])
+        .filter_map(|v| v?.as_str())
+        .filter(|id| {
+            id.split_once(':').is_some_and(|(table, key)| {
+                !table.is_empty()
+                    && !key.is_empty()
+                    && table.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+                    && key.chars().all(|c| c.is_ascii_alphanumeric())
+            })
+        })
+        .map(str::to_string)
+        .collect()
+}
+
+fn hex_digest(input: &str) -> String {
+    Sha256::digest(input.as_bytes()).iter().map(|b| format!("{b:02x}")).collect()
+}
+
+async fn graph_score_for(file_id: &str, depth: u8, scope: &AccessScope) -> (f32, usize) {
     let client = Client::new();
     let limit = 100 * depth.max(1) as usize;
+    let scoped = scope.surreal_edge_condition().map(|c| format!(" AND {}", c)).unwrap_or_default();
     let sql = format!(
-        "SELECT * FROM edge WHERE in = '{}' OR out = '{}' LIMIT {};",
-        file_id, file_id, limit
+        "SELECT * FROM edge WHERE (in = '{}' OR out = '{}'){} LIMIT {};",
+        file_id, file_id, scoped, limit
     );
     if let Ok(resp) = client.post("http://surrealdb:8000/sql").body(sql).send().await {
         if let Ok(val) = resp.json::<serde_json::Value>().await {
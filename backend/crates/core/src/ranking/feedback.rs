@@ -0,0 +1,129 @@
+use serde::{Deserialize, Serialize};
+use sqlx::Row;
+use uuid::Uuid;
+
+use crate::error::{MnemoError, MnemoResult};
+use crate::jobs::pool;
+use crate::rag::orchestrator::DebugCandidate;
+use crate::ranking::learning::FeedbackSample;
+
+/// Thumbs up / thumbs down verdict on an answer.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FeedbackRating {
+    Up,
+    Down,
+}
+
+impl std::fmt::Display for FeedbackRating {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            FeedbackRating::Up => "up",
+            FeedbackRating::Down => "down",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// Feedback submitted by a client for a previously answered query.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnswerFeedback {
+    pub query_id: Uuid,
+    pub rating: FeedbackRating,
+    /// Point ids of the candidates the user found useful.
+    #[serde(default)]
+    pub selected_citations: Vec<String>,
+    #[serde(default)]
+    pub comment: Option<String>,
+}
+
+/// Persist the scored candidates of a query so later feedback can be turned into samples.
+pub async fn log_query(
+    query_id: Uuid,
+    namespace: &str,
+    query: &str,
+    candidates: &[DebugCandidate],
+) -> MnemoResult<()> {
+    let pool = pool().await?;
+    let candidates = serde_json::to_value(candidates)
+        .map_err(|e| MnemoError::Message(format!("encode candidates failed: {e}")))?;
+    sqlx::query(
+        "INSERT INTO rag_queries(id, namespace, query, candidates) VALUES ($1, $2, $3, $4)
+         ON CONFLICT (id) DO NOTHING",
+    )
+    .bind(query_id)
+    .bind(namespace)
+    .bind(query)
+    .bind(candidates)
+    .execute(pool)
+    .await
+    .map_err(|e| MnemoError::Message(format!("log_query failed: {e}")))?;
+    Ok(())
+}
+
+/// Namespace a logged query was answered in; `None` when the query id is unknown.
+pub async fn query_namespace(query_id: Uuid) -> MnemoResult<Option<String>> {
+    let pool = pool().await?;
+    sqlx::query_scalar("SELECT namespace FROM rag_queries WHERE id = $1")
+        .bind(query_id)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| MnemoError::Message(format!("feedback query lookup failed: {e}")))
+}
+
+/// Record feedback for a query logged in `namespace` (see `query_namespace`).
+///
+/// The cached ranking profile of the namespace is dropped, so a profile trained or rolled back
+/// since (e.g. from the CLI) applies without waiting for the cache to expire.
+pub async fn record_feedback(feedback: &AnswerFeedback, namespace: &str) -> MnemoResult<()> {
+    let pool = pool().await?;
+    sqlx::query(
+        "INSERT INTO rag_feedback(query_id, rating, selected_citations, comment)
+         VALUES ($1, $2, $3, $4)",
+    )
+    .bind(feedback.query_id)
+    .bind(feedback.rating.to_string())
+    .bind(&feedback.selected_citations)
+    .bind(&feedback.comment)
+    .execute(pool)
+    .await
+    .map_err(|e| MnemoError::Message(format!("record_feedback failed: {e}")))?;
+    crate::ranking::profile::invalidate(namespace);
+    Ok(())
+}
+
+/// Turn stored feedback for a namespace into labelled training samples.
+///
+/// A thumbs-up marks the selected citations as relevant (or every candidate when nothing was
+/// selected); all other candidates, and every candidate of a thumbs-down answer, are negatives.
+pub async fn load_samples(namespace: &str) -> MnemoResult<Vec<FeedbackSample>> {
+    let pool = pool().await?;
+    let rows = sqlx::query(
+        "SELECT f.rating, f.selected_citations, q.candidates
+         FROM rag_feedback f JOIN rag_queries q ON q.id = f.query_id
+         WHERE q.namespace = $1",
+    )
+    .bind(namespace)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| MnemoError::Message(format!("load feedback samples failed: {e}")))?;
+
+    let mut samples = Vec::new();
+    for row in rows {
+        let rating: String = row.try_get("rating").unwrap_or_default();
+        let selected: Vec<String> = row.try_get("selected_citations").unwrap_or_default();
+        let candidates: serde_json::Value = row.try_get("candidates").unwrap_or_default();
+        let candidates: Vec<DebugCandidate> =
+            serde_json::from_value(candidates).unwrap_or_default();
+        let positive_rating = rating == "up";
+        for c in candidates {
+            let relevant =
+                positive_rating && (selected.is_empty() || selected.contains(&c.point_id));
+            samples.push(FeedbackSample {
+                signals: [c.vector_score, c.keyword_score, c.graph_score, c.ontology_score],
+                label: relevant,
+            });
+        }
+    }
+    Ok(samples)
+}
@@ -0,0 +1,102 @@
+use crate::error::MnemoResult;
+use crate::ranking::{feedback, profile, profile::RankingProfile};
+
+/// One labelled candidate: the four ranking signals and whether the user found it relevant.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FeedbackSample {
+    /// vector, keyword, graph, knowledge
+    pub signals: [f32; 4],
+    pub label: bool,
+}
+
+/// Hyperparameters for the offline weight fit.
+#[derive(Debug, Clone, Copy)]
+pub struct LearningConfig {
+    pub epochs: usize,
+    pub learning_rate: f32,
+    pub l2: f32,
+    /// Minimum number of samples (with both labels present) before a profile is produced.
+    pub min_samples: usize,
+}
+
+impl Default for LearningConfig {
+    fn default() -> Self {
+        Self { epochs: 500, learning_rate: 0.1, l2: 0.001, min_samples: 20 }
+    }
+}
+
+/// Outcome of a training run for a namespace.
+#[derive(Debug, Clone)]
+pub struct TrainingOutcome {
+    pub namespace: String,
+    pub samples: usize,
+    /// `None` when there was not enough feedback to fit a profile.
+    pub version: Option<i32>,
+    pub profile: Option<RankingProfile>,
+}
+
+fn sigmoid(z: f32) -> f32 {
+    1.0 / (1.0 + (-z).exp())
+}
+
+/// Fit a logistic regression over the four ranking signals and turn the coefficients into
+/// normalized ranking weights. Returns `None` when there is too little or one-sided feedback.
+pub fn fit_profile(samples: &[FeedbackSample], cfg: &LearningConfig) -> Option<RankingProfile> {
+    let positives = samples.iter().filter(|s| s.label).count();
+    if samples.len() < cfg.min_samples.max(1) || positives == 0 || positives == samples.len() {
+        return None;
+    }
+
+    let n = samples.len() as f32;
+    let mut w = [0.0_f32; 4];
+    let mut bias = 0.0_f32;
+    for _ in 0..cfg.epochs {
+        let mut grad_w = [0.0_f32; 4];
+        let mut grad_b = 0.0_f32;
+        for s in samples {
+            let x = s.signals.map(|v| if v.is_finite() { v } else { 0.0 });
+            let z = bias + w.iter().zip(x.iter()).map(|(a, b)| a * b).sum::<f32>();
+            let err = sigmoid(z) - if s.label { 1.0 } else { 0.0 };
+            for (g, xi) in grad_w.iter_mut().zip(x.iter()) {
+                *g += err * xi;
+            }
+            grad_b += err;
+        }
+        for (wi, g) in w.iter_mut().zip(grad_w.iter()) {
+            *wi -= cfg.learning_rate * (g / n + cfg.l2 * *wi);
+        }
+        bias -= cfg.learning_rate * grad_b / n;
+    }
+
+    Some(RankingProfile { vector: w[0], keyword: w[1], graph: w[2], knowledge: w[3] }.normalized())
+}
+
+/// Fit a profile from the stored feedback of a namespace and save it as the new active version.
+pub async fn train_namespace(
+    namespace: &str,
+    cfg: &LearningConfig,
+) -> MnemoResult<TrainingOutcome> {
+    let samples = feedback::load_samples(namespace).await?;
+    let Some(fitted) = fit_profile(&samples, cfg) else {
+        return Ok(TrainingOutcome {
+            namespace: namespace.to_string(),
+            samples: samples.len(),
+            version: None,
+            profile: None,
+        });
+    };
+    let version = profile::save_version(namespace, &fitted, samples.len()).await?;
+    tracing::info!(
+        "Trained ranking profile v{} for {} from {} samples: {:?}",
+        version,
+        namespace,
+        samples.len(),
+        fitted
+    );
+    Ok(TrainingOutcome {
+        namespace: namespace.to_string(),
+        samples: samples.len(),
+        version: Some(version),
+        profile: Some(fitted),
+    })
+}
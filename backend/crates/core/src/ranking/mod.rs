@@ -1 +1,3 @@
+pub mod feedback;
+pub mod learning;
 pub mod profile;
@@ -1,4 +1,18 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use chrono::Utc;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use sqlx::Row;
+
+use crate::error::{MnemoError, MnemoResult};
+use crate::jobs::pool;
+use crate::traits::ranking_engine::RankingEngine;
+
 /// Ranking weights for combining multiple signals.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct RankingProfile {
     pub vector: f32,
     pub keyword: f32,
@@ -11,4 +25,224 @@ impl RankingProfile {
     pub fn load(_path: &str) -> Self {
         Self { vector: 0.55, keyword: 0.20, graph: 0.15, knowledge: 0.10 }
     }
+
+    /// Weights used by the orchestrator when no learned profile is stored.
+    pub fn baseline() -> Self {
+        Self { vector: 0.6, keyword: 0.2, graph: 0.1, knowledge: 0.1 }
+    }
+
+    /// Clamp negatives to zero and rescale so the weights sum to 1.
+    /// Falls back to the baseline when every weight is zero.
+    pub fn normalized(self) -> Self {
+        let w = [self.vector, self.keyword, self.graph, self.knowledge]
+            .map(|v| if v.is_finite() { v.max(0.0) } else { 0.0 });
+        let sum: f32 = w.iter().sum();
+        if sum <= f32::EPSILON {
+            return Self::baseline();
+        }
+        Self { vector: w[0] / sum, keyword: w[1] / sum, graph: w[2] / sum, knowledge: w[3] / sum }
+    }
+}
+
+impl RankingEngine for RankingProfile {
+    fn score(
+        &self,
+        vector_score: f32,
+        keyword_score: f32,
+        graph_score: f32,
+        knowledge_score: f32,
+    ) -> f32 {
+        vector_score * self.vector
+            + keyword_score * self.keyword
+            + graph_score * self.graph
+            + knowledge_score * self.knowledge
+    }
+}
+
+/// A persisted, versioned ranking profile for a namespace.
+#[derive(Debug, Clone, Serialize)]
+pub struct StoredRankingProfile {
+    pub namespace: String,
+    pub version: i32,
+    pub profile: RankingProfile,
+    pub samples: i64,
+    pub active: bool,
+    pub created_at: String,
+}
+
+const PROFILE_CACHE_TTL: Duration = Duration::from_secs(60);
+const PROFILE_LOOKUP_TIMEOUT: Duration = Duration::from_millis(500);
+
+static ACTIVE_CACHE: Lazy<Mutex<HashMap<String, (Instant, RankingProfile)>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+fn row_to_profile(r: &sqlx::postgres::PgRow) -> StoredRankingProfile {
+    let weights: serde_json::Value = r.try_get("weights").unwrap_or_default();
+    let profile = serde_json::from_value(weights).unwrap_or_else(|_| RankingProfile::baseline());
+    let created_at: chrono::DateTime<Utc> = r.try_get("created_at").unwrap_or_else(|_| Utc::now());
+    StoredRankingProfile {
+        namespace: r.try_get("namespace").unwrap_or_default(),
+        version: r.try_get("version").unwrap_or(0),
+        profile,
+        samples: r.try_get::<i32, _>("samples").unwrap_or(0) as i64,
+        active: r.try_get("active").unwrap_or(false),
+        created_at: created_at.to_rfc3339(),
+    }
+}
+
+/// Load the active stored profile for a namespace, if any.
+pub async fn load_active(namespace: &str) -> MnemoResult<Option<StoredRankingProfile>> {
+    let pool = pool().await?;
+    let row = sqlx::query(
+        "SELECT namespace, version, weights, samples, active, created_at \
+         FROM ranking_profiles WHERE namespace = $1 AND active ORDER BY version DESC LIMIT 1",
+    )
+    .bind(namespace)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| MnemoError::Message(format!("load ranking profile failed: {e}")))?;
+    Ok(row.as_ref().map(row_to_profile))
+}
+
+/// Resolve the ranking weights for a namespace, falling back to the baseline when nothing is
+/// stored or Postgres is unreachable. Results are cached in-process for a short TTL.
+pub async fn active_or_baseline(namespace: &str) -> RankingProfile {
+    if let Ok(cache) = ACTIVE_CACHE.lock()
+        && let Some((at, profile)) = cache.get(namespace)
+        && at.elapsed() < PROFILE_CACHE_TTL
+    {
+        return *profile;
+    }
+    let profile = match tokio::time::timeout(PROFILE_LOOKUP_TIMEOUT, load_active(namespace)).await {
+        Ok(Ok(Some(stored))) => stored.profile,
+        Ok(Ok(None)) => RankingProfile::baseline(),
+        Ok(Err(err)) => {
+            tracing::debug!("Ranking profile lookup failed for {}: {}", namespace, err);
+            RankingProfile::baseline()
+        }
+        Err(_) => {
+            tracing::debug!("Ranking profile lookup timed out for {}", namespace);
+            RankingProfile::baseline()
+        }
+    };
+    if let Ok(mut cache) = ACTIVE_CACHE.lock() {
+        cache.insert(namespace.to_string(), (Instant::now(), profile));
+    }
+    profile
+}
+
+/// Drop the cached profile of a namespace so the next query reads the stored one.
+pub fn invalidate(namespace: &str) {
+    if let Ok(mut cache) = ACTIVE_CACHE.lock() {
+        cache.remove(namespace);
+    }
+}
+
+/// List every stored version for a namespace, newest first.
+pub async fn list_versions(namespace: &str) -> MnemoResult<Vec<StoredRankingProfile>> {
+    let pool = pool().await?;
+    let rows = sqlx::query(
+        "SELECT namespace, version, weights, samples, active, created_at \
+         FROM ranking_profiles WHERE namespace = $1 ORDER BY version DESC",
+    )
+    .bind(namespace)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| MnemoError::Message(format!("list ranking profiles failed: {e}")))?;
+    Ok(rows.iter().map(row_to_profile).collect())
+}
+
+/// Save a new profile version for a namespace and make it the active one.
+/// Returns the new version number.
+pub async fn save_version(
+    namespace: &str,
+    profile: &RankingProfile,
+    samples: usize,
+) -> MnemoResult<i32> {
+    let pool = pool().await?;
+    let weights = serde_json::to_value(profile)
+        .map_err(|e| MnemoError::Message(format!("encode ranking profile failed: {e}")))?;
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|e| MnemoError::Message(format!("save ranking profile failed: {e}")))?;
+    let next: i32 = sqlx::query_scalar(
+        "SELECT COALESCE(MAX(version), 0) + 1 FROM ranking_profiles WHERE namespace = $1",
+    )
+    .bind(namespace)
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|e| MnemoError::Message(format!("next ranking profile version failed: {e}")))?;
+    sqlx::query("UPDATE ranking_profiles SET active = false WHERE namespace = $1")
+        .bind(namespace)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| MnemoError::Message(format!("deactivate ranking profiles failed: {e}")))?;
+    sqlx::query(
+        "INSERT INTO ranking_profiles(namespace, version, weights, samples, active, created_at) \
+         VALUES ($1, $2, $3, $4, true, $5)",
+    )
+    .bind(namespace)
+    .bind(next)
+    .bind(weights)
+    .bind(samples as i32)
+    .bind(Utc::now())
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| MnemoError::Message(format!("insert ranking profile failed: {e}")))?;
+    tx.commit()
+        .await
+        .map_err(|e| MnemoError::Message(format!("commit ranking profile failed: {e}")))?;
+    invalidate(namespace);
+    Ok(next)
+}
+
+/// Deactivate the current profile and re-activate the previous version.
+/// Returns the version that is active afterwards; `None` means the baseline weights apply.
+pub async fn rollback(namespace: &str) -> MnemoResult<Option<i32>> {
+    let pool = pool().await?;
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|e| MnemoError::Message(format!("rollback ranking profile failed: {e}")))?;
+    let current: Option<i32> = sqlx::query_scalar(
+        "SELECT version FROM ranking_profiles WHERE namespace = $1 AND active \
+         ORDER BY version DESC LIMIT 1",
+    )
+    .bind(namespace)
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|e| MnemoError::Message(format!("fetch active ranking profile failed: {e}")))?;
+    let Some(current) = current else {
+        return Ok(None);
+    };
+    let previous: Option<i32> = sqlx::query_scalar(
+        "SELECT version FROM ranking_profiles WHERE namespace = $1 AND version < $2 \
+         ORDER BY version DESC LIMIT 1",
+    )
+    .bind(namespace)
+    .bind(current)
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|e| MnemoError::Message(format!("fetch previous ranking profile failed: {e}")))?;
+    sqlx::query("UPDATE ranking_profiles SET active = false WHERE namespace = $1")
+        .bind(namespace)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| MnemoError::Message(format!("deactivate ranking profiles failed: {e}")))?;
+    if let Some(prev) = previous {
+        sqlx::query(
+            "UPDATE ranking_profiles SET active = true WHERE namespace = $1 AND version = $2",
+        )
+        .bind(namespace)
+        .bind(prev)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| MnemoError::Message(format!("activate ranking profile failed: {e}")))?;
+    }
+    tx.commit()
+        .await
+        .map_err(|e| MnemoError::Message(format!("commit ranking rollback failed: {e}")))?;
+    invalidate(namespace);
+    Ok(previous)
 }
@@ -0,0 +1,184 @@
+use std::sync::Arc;
+
+use futures::StreamExt;
+use mnemo_inference::engines::tensorzero::{TensorZeroConfig, TensorZeroEngine};
+use mnemo_inference::traits::InferenceEngine;
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+/// Tag prefix used to persist screening flags in chunk payload tags.
+pub const FLAG_TAG_PREFIX: &str = "injection:";
+
+/// Flag emitted when the optional classifier labels a chunk as an injection attempt.
+pub const CLASSIFIER_FLAG: &str = "classifier";
+
+static GLOBAL: Lazy<Screener> = Lazy::new(Screener::from_env);
+
+const CLASSIFIER_LABELS: [&str; 2] = ["benign", "prompt_injection"];
+
+/// Classifier requests in flight when screening a batch.
+const CLASSIFIER_CONCURRENCY: usize = 8;
+
+static RULES: Lazy<Vec<(&'static str, Regex)>> = Lazy::new(|| {
+    [
+        (
+            "ignore_instructions",
+            r"(?i)\b(ignore|disregard|forget|override)\s+(all\s+|any\s+)?(of\s+)?(the\s+|your\s+)?(previous|prior|above|earlier|preceding)\s+(instructions?|prompts?|messages?|rules|context)",
+        ),
+        (
+            "role_override",
+            r"(?i)\byou\s+are\s+now\s+(an?\s+|in\s+)?(unrestricted|unfiltered|jailbroken|dan\b|developer\s+mode)|\bfrom\s+now\s+on,?\s+you\s+(will|must)\s+(ignore|obey|only)\b|\bnew\s+instructions\s*:",
+        ),
+        (
+            "system_prompt",
+            r"(?im)(reveal|print|repeat|show|leak)\s+(me\s+)?(your|the)\s+(system\s+prompt|hidden\s+instructions)|^\s*(system|assistant)\s*:\s",
+        ),
+        (
+            "tool_directive",
+            r"(?i)<\s*/?\s*(tool_call|function_call|tool_use|function_calls)\b|\b(call|invoke|execute)\s+the\s+[\w.-]+\s+tool\b",
+        ),
+        (
+            "hidden_html",
+            r#"(?is)<!--.{0,400}?\b(ignore|instruction|assistant|system|prompt)\b.{0,400}?-->|style\s*=\s*["'][^"']*(display\s*:\s*none|visibility\s*:\s*hidden|font-size\s*:\s*0)"#,
+        ),
+    ]
+    .into_iter()
+    .map(|(name, pattern)| (name, Regex::new(pattern).expect("valid screening rule")))
+    .collect()
+});
+
+/// Joiner used inside emoji sequences such as 👩‍💻, and otherwise as an invisible character.
+const ZERO_WIDTH_JOINER: char = '\u{200D}';
+
+static EMOJI: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^\p{Extended_Pictographic}$").expect("valid emoji class"));
+
+/// Whether `text` holds invisible characters. A zero-width joiner between two emoji (after
+/// optional variation selector or skin tone) is part of the emoji and not flagged.
+fn has_hidden_unicode(text: &str) -> bool {
+    let is_emoji = |c: char| EMOJI.is_match(c.encode_utf8(&mut [0; 4]));
+    let chars: Vec<char> = text.chars().collect();
+    chars.iter().enumerate().any(|(i, &c)| match c {
+        '\u{200B}' | '\u{200C}' | '\u{2060}' | '\u{E0000}'..='\u{E007F}' => true,
+        ZERO_WIDTH_JOINER => {
+            let before = chars[..i]
+                .iter()
+                .rev()
+                .find(|&&p| !matches!(p, '\u{FE0F}' | '\u{1F3FB}'..='\u{1F3FF}'));
+            !(before.is_some_and(|&p| is_emoji(p))
+                && chars.get(i + 1).is_some_and(|&n| is_emoji(n)))
+        }
+        _ => false,
+    })
+}
+
+/// What to do with retrieved chunks that were flagged.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScreeningMode {
+    Off,
+    /// Keep flagged chunks but multiply their final score by `DOWNWEIGHT_FACTOR`.
+    Downweight,
+    /// Keep flagged chunks out of the answer context entirely.
+    Quarantine,
+}
+
+pub const DOWNWEIGHT_FACTOR: f32 = 0.3;
+
+impl ScreeningMode {
+    /// `MNEMO_SCREENING_MODE`: `off`, `downweight` (default) or `quarantine`.
+    pub fn from_env() -> Self {
+        match std::env::var("MNEMO_SCREENING_MODE")
+            .unwrap_or_default()
+            .to_ascii_lowercase()
+            .as_str()
+        {
+            "off" => ScreeningMode::Off,
+            "quarantine" => ScreeningMode::Quarantine,
+            _ => ScreeningMode::Downweight,
+        }
+    }
+}
+
+/// Flags instruction-like content in chunk text with a rule set and an optional classifier.
+#[derive(Clone, Default)]
+pub struct Screener {
+    classifier: Option<Arc<dyn InferenceEngine + Send + Sync>>,
+}
+
+impl Screener {
+    /// Rule-only screener.
+    pub fn new() -> Self {
+        Self { classifier: None }
+    }
+
+    pub fn with_classifier(mut self, engine: Arc<dyn InferenceEngine + Send + Sync>) -> Self {
+        self.classifier = Some(engine);
+        self
+    }
+
+    /// Rule-only unless `MNEMO_SCREENING_CLASSIFIER=tensorzero` and TensorZero is configured.
+    pub fn from_env() -> Self {
+        let screener = Self::new();
+        if std::env::var("MNEMO_SCREENING_CLASSIFIER").ok().as_deref() != Some("tensorzero") {
+            return screener;
+        }
+        match TensorZeroConfig::from_env().and_then(TensorZeroEngine::new) {
+            Ok(engine) => screener.with_classifier(Arc::new(engine)),
+            Err(err) => {
+                tracing::warn!("Screening classifier unavailable, using rules only: {}", err);
+                screener
+            }
+        }
+    }
+
+    /// Process-wide screener configured from the environment.
+    pub fn global() -> &'static Screener {
+        &GLOBAL
+    }
+
+    /// Names of the rules matching `text`.
+    pub fn screen_rules(&self, text: &str) -> Vec<String> {
+        let mut flags: Vec<String> = RULES
+            .iter()
+            .filter(|(_, re)| re.is_match(text))
+            .map(|(name, _)| name.to_string())
+            .collect();
+        if has_hidden_unicode(text) {
+            flags.push("hidden_unicode".to_string());
+        }
+        flags
+    }
+
+    /// Rule flags plus `classifier` when the optional classifier labels the text as an injection.
+    pub async fn screen(&self, text: &str) -> Vec<String> {
+        let mut flags = self.screen_rules(text);
+        if let Some(engine) = &self.classifier {
+            let labels = CLASSIFIER_LABELS.iter().map(|l| l.to_string()).collect();
+            let label = engine.classify(text.to_string(), labels).await;
+            if label.to_ascii_lowercase().contains("injection") {
+                flags.push(CLASSIFIER_FLAG.to_string());
+            }
+        }
+        flags
+    }
+
+    /// `screen` for each text, in order, with up to `CLASSIFIER_CONCURRENCY` classifier
+    /// requests in flight.
+    pub async fn screen_all(&self, texts: Vec<String>) -> Vec<Vec<String>> {
+        futures::stream::iter(texts)
+            .map(|text| async move { self.screen(&text).await })
+            .buffered(CLASSIFIER_CONCURRENCY)
+            .collect()
+            .await
+    }
+}
+
+/// Screening flags previously stored as `injection:*` tags.
+pub fn flags_from_tags(tags: &[String]) -> Vec<String> {
+    tags.iter().filter_map(|t| t.strip_prefix(FLAG_TAG_PREFIX)).map(|f| f.to_string()).collect()
+}
+
+/// Encode flags as chunk tags.
+pub fn flags_to_tags(flags: &[String]) -> Vec<String> {
+    flags.iter().map(|f| format!("{}{}", FLAG_TAG_PREFIX, f)).collect()
+}
@@ -1,6 +1,12 @@
+use std::sync::LazyLock;
+
 use regex::Regex;
 use unicode_normalization::UnicodeNormalization;
 
+static REPEAT_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"([=\-_#*]){4,}").unwrap());
+static SPACES_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"[ \t]{2,}").unwrap());
+static NEWLINES_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\n{3,}").unwrap());
+
 pub struct TextNormalizer;
 
 impl TextNormalizer {
@@ -20,8 +26,7 @@ impl TextNormalizer {
             .collect();
 
         // Trim long repeated separator characters (e.g., "-----" -> "---").
-        let repeat_re = Regex::new(r"([=\-_#*]){4,}").unwrap();
-        text = repeat_re
+        text = REPEAT_RE
             .replace_all(&text, |caps: &regex::Captures| {
                 let ch = caps.get(0).and_then(|m| m.as_str().chars().next()).unwrap_or('-');
                 format!("{ch}{ch}{ch}")
@@ -29,10 +34,8 @@ impl TextNormalizer {
             .into_owned();
 
         // Collapse excessive whitespace while preserving paragraph breaks.
-        let spaces_re = Regex::new(r"[ \t]{2,}").unwrap();
-        text = spaces_re.replace_all(&text, " ").into_owned();
-        let newline_re = Regex::new(r"\n{3,}").unwrap();
-        text = newline_re.replace_all(&text, "\n\n").into_owned();
+        text = SPACES_RE.replace_all(&text, " ").into_owned();
+        text = NEWLINES_RE.replace_all(&text, "\n\n").into_owned();
 
         text.trim().to_string()
     }
@@ -1,17 +1,21 @@
-use reqwest::Client;
+use std::time::Duration;
+
+use reqwest::{Client, StatusCode};
 use serde::{Deserialize, Serialize};
 use tracing::warn;
 
 use crate::error::InferenceError;
 
 #[derive(Debug, Serialize)]
-struct EmbeddingRequest {
-    model: String,
-    input: String,
+struct EmbeddingRequest<'a> {
+    model: &'a str,
+    input: &'a [String],
 }
 
 #[derive(Debug, Deserialize)]
 struct EmbeddingData {
+    #[serde(default)]
+    index: Option<usize>,
     embedding: Vec<f32>,
 }
 
@@ -20,6 +24,55 @@ struct EmbeddingResponse {
     data: Vec<EmbeddingData>,
 }
 
+/// How failed embedding requests are retried: exponential backoff from `base_delay`, or the
+/// server's `Retry-After` when it sends one, never waiting longer than `max_delay`.
+#[derive(Clone, Debug)]
+pub struct RetryPolicy {
+    pub max_retries: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(60),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// `MNEMO_EMBED_MAX_RETRIES` (default 3) and `MNEMO_EMBED_RETRY_BASE_MS` (default 500).
+    pub fn from_env() -> Self {
+        let mut policy = Self::default();
+        if let Some(retries) =
+            std::env::var("MNEMO_EMBED_MAX_RETRIES").ok().and_then(|v| v.parse().ok())
+        {
+            policy.max_retries = retries;
+        }
+        if let Some(ms) =
+            std::env::var("MNEMO_EMBED_RETRY_BASE_MS").ok().and_then(|v| v.parse().ok())
+        {
+            policy.base_delay = Duration::from_millis(ms);
+        }
+        policy
+    }
+
+    /// Wait before retry number `attempt` (0-based).
+    pub fn delay(&self, attempt: u32, retry_after: Option<Duration>) -> Duration {
+        let backoff = self.base_delay.saturating_mul(2_u32.saturating_pow(attempt));
+        retry_after.unwrap_or(backoff).min(self.max_delay)
+    }
+}
+
+/// `Retry-After` given in seconds; HTTP dates are not supported and fall back to backoff.
+pub fn parse_retry_after(value: &str) -> Option<Duration> {
+    let secs: f64 = value.trim().parse().ok()?;
+    (secs.is_finite() && secs >= 0.0).then(|| Duration::from_secs_f64(secs))
+}
+
 /// Embedding client that targets OpenAI-compatible `/v1/embeddings` endpoints.
 /// TensorZero is used when it exposes embeddings; otherwise we can fall back
 /// to a direct Ollama-compatible endpoint.
@@ -32,6 +85,7 @@ pub struct TensorZeroEmbedder {
     fallback_url: Option<String>,
     fallback_models: Vec<String>,
     fallback_api_key: Option<String>,
+    retry: RetryPolicy,
 }
 
 impl TensorZeroEmbedder {
@@ -44,9 +98,15 @@ impl TensorZeroEmbedder {
             fallback_url: None,
             fallback_models: Vec::new(),
             fallback_api_key: None,
+            retry: RetryPolicy::default(),
         }
     }
 
+    pub fn with_retry(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
     pub fn from_env() -> Result<Self, InferenceError> {
         let url = std::env::var("TENSORZERO_URL")
             .or_else(|_| std::env::var("MNEMO_LLM_URL"))
@@ -58,21 +118,16 @@ impl TensorZeroEmbedder {
             .split(',')
             .filter_map(|s| {
                 let trimmed = s.trim();
-                if trimmed.is_empty() {
-                    None
-                } else {
-                    Some(trimmed.to_string())
-                }
+                if trimmed.is_empty() { None } else { Some(trimmed.to_string()) }
             })
             .collect();
-        if models.is_empty() {
-            if let Ok(single) = std::env::var("TENSORZERO_EMBED_MODEL")
+        if models.is_empty()
+            && let Ok(single) = std::env::var("TENSORZERO_EMBED_MODEL")
                 .or_else(|_| std::env::var("MNEMO_EMBED_MODEL"))
-            {
-                let trimmed = single.trim();
-                if !trimmed.is_empty() {
-                    models.push(trimmed.to_string());
-                }
+        {
+            let trimmed = single.trim();
+            if !trimmed.is_empty() {
+                models.push(trimmed.to_string());
             }
         }
         if models.is_empty() {
@@ -97,11 +152,7 @@ impl TensorZeroEmbedder {
             .split(',')
             .filter_map(|s| {
                 let trimmed = s.trim();
-                if trimmed.is_empty() {
-                    None
-                } else {
-                    Some(trimmed.to_string())
-                }
+                if trimmed.is_empty() { None } else { Some(trimmed.to_string()) }
             })
             .collect();
         let fallback_api_key = std::env::var("TENSORZERO_EMBED_FALLBACK_API_KEY")
@@ -114,39 +165,58 @@ impl TensorZeroEmbedder {
         embedder.fallback_url = fallback_url;
         embedder.fallback_models = fallback_models;
         embedder.fallback_api_key = fallback_api_key;
+        embedder.retry = RetryPolicy::from_env();
         Ok(embedder)
     }
 
+    /// First configured model; embeddings are cached under the model that produced them.
+    pub fn primary_model(&self) -> &str {
+        self.models.first().map(String::as_str).unwrap_or_default()
+    }
+
     pub async fn embed(&self, text: &str) -> Result<Vec<f32>, InferenceError> {
+        self.embed_with_model(text).await.map(|(_, vec)| vec)
+    }
+
+    /// Embed `text`, returning the model that answered along with the vector.
+    pub async fn embed_with_model(&self, text: &str) -> Result<(String, Vec<f32>), InferenceError> {
+        let (model, mut vecs) = self.embed_batch(&[text.to_string()]).await?;
+        Ok((model, vecs.remove(0)))
+    }
+
+    /// Embed `texts` in one request with an array `input`, returning the model that answered
+    /// and one vector per text, in order. Each model is retried per the [`RetryPolicy`] on
+    /// connection errors, 429 and 5xx before the next model is tried.
+    pub async fn embed_batch(
+        &self,
+        texts: &[String],
+    ) -> Result<(String, Vec<Vec<f32>>), InferenceError> {
+        if texts.is_empty() {
+            return Err(InferenceError::Other("no texts to embed".into()));
+        }
         let mut last_err: Option<InferenceError> = None;
 
-        if let Some(vec) = self
-            .embed_with(
-                &self.url,
-                &self.models,
-                self.api_key.as_ref(),
-                text,
-                &mut last_err,
-            )
+        if let Some(found) = self
+            .embed_with(&self.url, &self.models, self.api_key.as_ref(), texts, &mut last_err)
             .await
         {
-            return Ok(vec);
+            return Ok(found);
         }
 
         if let Some(fallback_url) = &self.fallback_url {
             if self.fallback_models.is_empty() {
                 warn!("TensorZero embed fallback URL set but no fallback models configured");
-            } else if let Some(vec) = self
+            } else if let Some(found) = self
                 .embed_with(
                     fallback_url,
                     &self.fallback_models,
                     self.fallback_api_key.as_ref(),
-                    text,
+                    texts,
                     &mut last_err,
                 )
                 .await
             {
-                return Ok(vec);
+                return Ok(found);
             }
         }
 
@@ -158,62 +228,107 @@ impl TensorZeroEmbedder {
         base_url: &str,
         models: &[String],
         api_key: Option<&String>,
-        text: &str,
+        texts: &[String],
         last_err: &mut Option<InferenceError>,
-    ) -> Option<Vec<f32>> {
+    ) -> Option<(String, Vec<Vec<f32>>)> {
         let url = embeddings_url(base_url);
 
         for model in models {
-            let body = EmbeddingRequest {
-                model: model.clone(),
-                input: text.to_string(),
-            };
-
-            let mut req = self.client.post(&url).json(&body);
-            if let Some(key) = api_key {
-                req = req.bearer_auth(key);
+            let mut attempt = 0;
+            loop {
+                match self.request(&url, model, api_key, texts).await {
+                    Ok(vecs) => return Some((model.clone(), vecs)),
+                    Err(failure) => {
+                        *last_err = Some(failure.error);
+                        if !failure.retryable || attempt >= self.retry.max_retries {
+                            break;
+                        }
+                        let delay = self.retry.delay(attempt, failure.retry_after);
+                        warn!(
+                            "embed request to {} failed (model={}, attempt {}), retrying in {:?}",
+                            url,
+                            model,
+                            attempt + 1,
+                            delay
+                        );
+                        tokio::time::sleep(delay).await;
+                        attempt += 1;
+                    }
+                }
             }
+        }
 
-            let resp = req.send().await;
-            let resp = match resp {
-                Ok(r) => r,
-                Err(e) => {
-                    *last_err = Some(e.into());
-                    continue;
-                }
-            };
+        None
+    }
+
+    async fn request(
+        &self,
+        url: &str,
+        model: &str,
+        api_key: Option<&String>,
+        texts: &[String],
+    ) -> Result<Vec<Vec<f32>>, RequestFailure> {
+        let body = EmbeddingRequest { model, input: texts };
+
+        let mut req = self.client.post(url).json(&body);
+        if let Some(key) = api_key {
+            req = req.bearer_auth(key);
+        }
+
+        let resp = req.send().await.map_err(|e| RequestFailure {
+            retryable: e.is_connect() || e.is_timeout() || e.is_request(),
+            retry_after: None,
+            error: e.into(),
+        })?;
 
-            let status = resp.status();
-            if !status.is_success() {
-                let body = resp.text().await.unwrap_or_default();
-                *last_err = Some(InferenceError::Status(format!(
+        let status = resp.status();
+        if !status.is_success() {
+            let retry_after = resp
+                .headers()
+                .get(reqwest::header::RETRY_AFTER)
+                .and_then(|v| v.to_str().ok())
+                .and_then(parse_retry_after);
+            let body = resp.text().await.unwrap_or_default();
+            return Err(RequestFailure {
+                retryable: status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error(),
+                retry_after,
+                error: InferenceError::Status(format!(
                     "embed error: model={} status={} body={}",
                     model, status, body
-                )));
-                continue;
-            }
-
-            let parsed: Result<EmbeddingResponse, _> = resp.json().await;
-            match parsed {
-                Ok(parsed) => {
-                    if let Some(first) = parsed.data.first() {
-                        return Some(first.embedding.clone());
-                    }
-                    *last_err = Some(InferenceError::Other(
-                        "embedding response had empty data".into(),
-                    ));
-                }
-                Err(e) => {
-                    *last_err = Some(e.into());
-                    continue;
-                }
-            }
+                )),
+            });
         }
 
-        None
+        let parsed: EmbeddingResponse = resp.json().await.map_err(|e| RequestFailure {
+            retryable: false,
+            retry_after: None,
+            error: e.into(),
+        })?;
+        let mut data = parsed.data;
+        if data.len() != texts.len() {
+            return Err(RequestFailure {
+                retryable: false,
+                retry_after: None,
+                error: InferenceError::Other(format!(
+                    "embedding response had {} vectors for {} inputs",
+                    data.len(),
+                    texts.len()
+                )),
+            });
+        }
+        // Servers may answer out of order; `index` ties each vector to its input.
+        data.sort_by_key(|d| d.index.unwrap_or(0));
+        Ok(data.into_iter().map(|d| d.embedding).collect())
     }
 }
 
+/// A failed embedding request and whether it is worth retrying.
+struct RequestFailure {
+    error: InferenceError,
+    retryable: bool,
+    retry_after: Option<Duration>,
+}
+
 fn embeddings_url(base: &str) -> String {
     let trimmed = base.trim_end_matches('/');
     if trimmed.ends_with("/v1/embeddings") {
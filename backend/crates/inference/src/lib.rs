@@ -6,5 +6,5 @@ pub mod model_router;
 pub mod traits;
 pub mod error;
 
-pub use engines::tensorzero_embed::TensorZeroEmbedder;
+pub use engines::tensorzero_embed::{RetryPolicy, TensorZeroEmbedder};
 pub use error::InferenceError;
@@ -1,12 +1,13 @@
 use std::sync::Arc;
 
 use crate::metrics::IngestionMetrics;
+use crate::pipeline::prune_step::{PruneStep, prune_documents, removed_documents};
 use crate::pipeline::{
     chunk_step::ChunkStep, embedding_step::EmbeddingStep, executor::PipelineExecutor,
     graph_builder_step::GraphBuilderStep, ontology_step::OntologyStep, registry::PipelineRegistry,
-    vector_upsert_step::VectorUpsertStep,
+    screening_step::ScreeningStep, vector_upsert_step::VectorUpsertStep,
 };
-use crate::providers::filesystem::FilesystemProvider;
+use crate::providers::filesystem::{self, FilesystemProvider};
 use futures::FutureExt;
 use mnemo_core::config::MnemoConfig;
 use mnemo_core::error::{MnemoError, MnemoResult};
@@ -51,8 +52,10 @@ impl IngestionController {
             meta_store.clone(),
         )));
         pipelines.add_step(Arc::new(ChunkStep));
+        pipelines.add_step(Arc::new(PruneStep::new(meta_store.clone())));
         pipelines.add_step(Arc::new(OntologyStep));
-        pipelines.add_step(Arc::new(EmbeddingStep));
+        pipelines.add_step(Arc::new(ScreeningStep));
+        pipelines.add_step(Arc::new(EmbeddingStep::new(meta_store.clone())));
         pipelines.add_step(Arc::new(VectorUpsertStep));
         pipelines.add_step(Arc::new(GraphBuilderStep::new(
             env::var("SURREALDB_URL").unwrap_or_else(|_| "http://localhost:8000".into()),
@@ -63,16 +66,32 @@ impl IngestionController {
     }
 
     /// Gather documents from configured providers (placeholder: filesystem only).
-    pub fn load_sources(&self) -> Vec<Document> {
+    /// Files the scan skips are recorded in `metrics` with their reason.
+    pub fn load_sources(&mut self) -> Vec<Document> {
         let root_path = std::env::var("INGESTION_ROOT").unwrap_or_else(|_| "/app/data".to_string());
         let roots = vec![root_path.into()];
         tracing::info!("Scanning root path: {}", roots[0]);
         let provider = FilesystemProvider::new(roots);
-        let docs = provider.scan();
+        let (docs, skipped) = provider.scan_with_report();
+        for file in skipped {
+            self.metrics.record_skip(file);
+        }
         tracing::info!("Total documents loaded: {}", docs.len());
         docs
     }
 
+    /// Delete documents indexed under the ingestion root that `docs`, a full scan of it, no
+    /// longer contains. An empty scan prunes nothing, as a missing root also scans empty.
+    pub async fn prune_removed(&mut self, docs: &[Document]) -> MnemoResult<()> {
+        if docs.is_empty() {
+            return Ok(());
+        }
+        let root_path = std::env::var("INGESTION_ROOT").unwrap_or_else(|_| "/app/data".to_string());
+        let removed = removed_documents(&self.meta_store, &[root_path], docs).await?;
+        prune_documents(&self.meta_store, filesystem::NAMESPACE, &removed, &None, &mut self.metrics)
+            .await
+    }
+
     /// Run the pipeline stack against provided documents.
     pub async fn run_pipeline(&mut self, documents: Vec<Document>) -> MnemoResult<()> {
         let mut data = crate::pipeline::data::PipelineData::new();
@@ -141,6 +160,7 @@ impl IngestionController {
             broadcast_error(None, msg);
             return Err(MnemoError::Message(msg.into()));
         }
+        self.prune_removed(&docs).await?;
         let res = self.run_pipeline(docs).await;
         if res.is_ok() {
             crate::metrics::store_last_metrics(self.metrics.clone());
@@ -1,5 +1,14 @@
 /// Unified representation of ingestion sources.
 pub enum DataSource {
-    GitHub { repo: String },
-    Filesystem { path: String },
+    GitHub {
+        repo: String,
+    },
+    Filesystem {
+        path: String,
+    },
+    /// Local git clone read at `reference` (branch, tag or commit).
+    GitRepo {
+        path: String,
+        reference: String,
+    },
 }
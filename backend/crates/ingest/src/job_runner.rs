@@ -3,6 +3,7 @@ use mnemo_core::jobs::{JobDTO, JobStatus, update_job_status};
 use mnemo_core::ws::WS_HUB;
 
 use crate::fs_ingest_runner::run_filesystem_ingestion;
+use crate::provider_ingest_runner::run_configured_providers;
 use futures::FutureExt;
 use serde_json::json;
 use std::panic::AssertUnwindSafe;
@@ -18,7 +19,11 @@ pub async fn run_job(job_id: &str) -> MnemoResult<JobDTO> {
     });
     WS_HUB.broadcast(payload.to_string());
 
-    let result = AssertUnwindSafe(run_filesystem_ingestion(Some(job_id)))
+    let ingestion = async {
+        run_filesystem_ingestion(Some(job_id)).await?;
+        run_configured_providers(Some(job_id)).await
+    };
+    let result = AssertUnwindSafe(ingestion)
         .catch_unwind()
         .await
         .map_err(|_| mnemo_core::error::MnemoError::Message("pipeline panicked".into()))
@@ -8,6 +8,8 @@ pub mod job_runner;
 pub mod jobs;
 pub mod metrics;
 pub mod pipeline;
+pub mod provider_ingest_runner;
 pub mod providers;
 pub mod runner;
+pub mod watcher;
 pub mod workers;
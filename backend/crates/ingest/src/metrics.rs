@@ -1,17 +1,86 @@
+use std::collections::BTreeMap;
+
+/// Skipped files listed per run; `skip_counts` keeps counting past this.
+pub const MAX_SKIPPED_FILES: usize = 1_000;
+
 /// Simple ingestion metrics collected during a pipeline run.
 #[derive(Default, Clone, Debug)]
 pub struct IngestionMetrics {
     pub documents_processed: usize,
     pub chunks_produced: usize,
+    /// Requests sent to the embedding service (batches and per-chunk retries).
     pub embedding_calls: usize,
+    /// Chunks whose embedding came from the embedding cache.
+    pub embedding_cache_hits: usize,
+    /// Chunks sent to the embedding service after a cache miss.
+    pub embedding_cache_misses: usize,
+    /// Chunks dropped after their embedding failed on its own; retried on the next scan.
+    pub embedding_failures: usize,
     pub qdrant_writes: usize,
     pub duration_ms: u64,
+    /// Documents no longer in their source, deleted from every store.
+    pub documents_removed: usize,
+    /// Chunks deleted with removed documents or past the end of a shrunken document.
+    pub chunks_removed: usize,
+    /// Files passed over by the scan, with the reason, up to [`MAX_SKIPPED_FILES`].
+    pub skipped_files: Vec<SkippedFile>,
+    /// Skipped files per reason.
+    pub skip_counts: BTreeMap<SkipReason, usize>,
 }
 
 impl IngestionMetrics {
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// Share of embedded chunks served from the cache, 0.0 when nothing was embedded.
+    pub fn embedding_cache_hit_rate(&self) -> f64 {
+        let total = self.embedding_cache_hits + self.embedding_cache_misses;
+        if total == 0 { 0.0 } else { self.embedding_cache_hits as f64 / total as f64 }
+    }
+
+    pub fn record_skip(&mut self, skipped: SkippedFile) {
+        *self.skip_counts.entry(skipped.reason).or_default() += 1;
+        if self.skipped_files.len() < MAX_SKIPPED_FILES {
+            self.skipped_files.push(skipped);
+        }
+    }
+}
+
+/// Why a filesystem scan did not read a file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SkipReason {
+    /// Matched a `.gitignore` or `.mnemoignore` rule; ignored directories are listed once.
+    Ignored,
+    /// Matched an exclude glob.
+    Excluded,
+    /// Include globs are set and none matched.
+    NotIncluded,
+    /// Extension not in the allowlist.
+    Extension,
+    /// Over the size limit.
+    TooLarge,
+    /// Binary, or its extractor could not read it.
+    Unreadable,
+}
+
+impl SkipReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SkipReason::Ignored => "ignored",
+            SkipReason::Excluded => "excluded",
+            SkipReason::NotIncluded => "not_included",
+            SkipReason::Extension => "extension",
+            SkipReason::TooLarge => "too_large",
+            SkipReason::Unreadable => "unreadable",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SkippedFile {
+    pub path: String,
+    pub reason: SkipReason,
 }
 
 use once_cell::sync::Lazy;
@@ -1,4 +1,12 @@
+use pulldown_cmark::{Event, HeadingLevel, Options, Parser, Tag, TagEnd};
 use regex::Regex;
+use serde_json::{Map, Value, json};
+
+use super::code_chunker::{self, CodeLanguage};
+use super::segmenter::Segmenter;
+use crate::providers::notebook::notebook_cells;
+use crate::providers::pdf::PAGE_BREAK;
+use crate::providers::tabular::table_parts;
 
 /// File types used for tailored chunking.
 #[derive(Clone, Copy, Debug)]
@@ -10,9 +18,57 @@ pub enum FileType {
     Yaml,
     Pdf,
     Docx,
+    Notebook,
+    Table,
     Unknown,
 }
 
+impl FileType {
+    /// Key used for per-file-type settings in `config/chunking.yaml`.
+    pub fn config_key(&self) -> &'static str {
+        match self {
+            FileType::Markdown => "markdown",
+            FileType::Code => "code",
+            FileType::Text => "text",
+            FileType::Json => "json",
+            FileType::Yaml => "yaml",
+            FileType::Pdf => "pdf",
+            FileType::Docx => "docx",
+            FileType::Notebook => "notebook",
+            FileType::Table => "table",
+            FileType::Unknown => "unknown",
+        }
+    }
+}
+
+/// Chunk text plus the structural metadata that produced it.
+#[derive(Clone, Debug, Default)]
+pub struct BuiltChunk {
+    pub text: String,
+    pub metadata: Map<String, Value>,
+}
+
+/// Markdown body text under one heading path.
+struct MarkdownSection {
+    /// Heading titles from the outermost down to the section's own heading.
+    breadcrumb: Vec<String>,
+    body: String,
+}
+
+impl MarkdownSection {
+    fn breadcrumb_path(&self) -> String {
+        self.breadcrumb.join(" > ")
+    }
+
+    fn with_breadcrumb(&self, text: &str) -> String {
+        if self.breadcrumb.is_empty() {
+            text.to_string()
+        } else {
+            format!("{}\n\n{}", self.breadcrumb_path(), text)
+        }
+    }
+}
+
 pub struct ChunkBuilder;
 
 impl ChunkBuilder {
@@ -34,6 +90,8 @@ impl ChunkBuilder {
             "txt" => FileType::Text,
             "pdf" => FileType::Pdf,
             "docx" => FileType::Docx,
+            "ipynb" => FileType::Notebook,
+            "csv" | "tsv" | "jsonl" | "ndjson" | "parquet" => FileType::Table,
             _ => FileType::Unknown,
         }
     }
@@ -46,13 +104,21 @@ impl ChunkBuilder {
             "json" => FileType::Json,
             "yaml" | "yml" => FileType::Yaml,
             "text" => FileType::Text,
+            "pdf" => FileType::Pdf,
+            "docx" => FileType::Docx,
+            "notebook" | "ipynb" => FileType::Notebook,
+            "table" => FileType::Table,
             _ => FileType::Unknown,
         }
     }
 
     /// Build chunks based on file type heuristics.
     pub fn build(text: &str, file_type: FileType) -> Vec<String> {
-        let mut raw = match file_type {
+        if matches!(file_type, FileType::Table) {
+            // Row groups are already sized to the token budget.
+            return Self::table_document_chunks(text).into_iter().map(|c| c.text).collect();
+        }
+        let raw = match file_type {
             FileType::Markdown => Self::markdown_chunks(text),
             FileType::Code => Self::code_chunks(text),
             FileType::Text => Self::text_chunks(text),
@@ -60,38 +126,163 @@ impl ChunkBuilder {
             FileType::Yaml => Self::yaml_chunks(text),
             FileType::Pdf => Self::pdf_chunks(text),
             FileType::Docx => Self::docx_chunks(text),
-            FileType::Unknown => vec![text.to_string()],
+            FileType::Notebook => {
+                notebook_cells(text).map(|(_, _, body)| body.trim().to_string()).collect()
+            }
+            FileType::Table | FileType::Unknown => vec![text.to_string()],
         };
 
-        // Normalize into ~800-1200 char segments.
-        raw = Self::segment(raw, 800, 1200);
-        // Drop empties.
-        raw.into_iter().filter(|c| !c.trim().is_empty()).collect()
-    }
-
-    fn segment(chunks: Vec<String>, min_len: usize, max_len: usize) -> Vec<String> {
-        let mut out = Vec::new();
-        for ch in chunks {
-            if ch.len() <= max_len {
-                out.push(ch);
-                continue;
-            }
-            let mut start = 0;
-            while start < ch.len() {
-                let end = (start + max_len).min(ch.len());
-                let slice = &ch[start..end];
-                if slice.len() >= min_len {
-                    out.push(slice.to_string());
-                }
-                start = end;
+        // Normalize into token-bounded segments sized per file type.
+        Segmenter::for_file_type(file_type.config_key()).segment_all(raw)
+    }
+
+    /// Build chunks for a document, using syntax-tree chunking for supported code languages,
+    /// heading-aware sections for Markdown and DOCX, page numbers for PDFs, cells for
+    /// notebooks and row groups for tables.
+    ///
+    /// `language` is the document's declared language, if any; otherwise it is inferred from
+    /// `path`. Code in unsupported languages, or that fails to parse, uses the regex heuristics.
+    pub fn build_document(
+        text: &str,
+        file_type: FileType,
+        path: &str,
+        language: Option<&str>,
+    ) -> Vec<BuiltChunk> {
+        // DOCX text is extracted as Markdown, so it shares the heading-aware chunking.
+        if matches!(file_type, FileType::Markdown | FileType::Docx) {
+            let chunks = Self::markdown_document_chunks(text, file_type);
+            if !chunks.is_empty() {
+                return chunks;
+            }
+        }
+        if matches!(file_type, FileType::Pdf) {
+            let chunks = Self::pdf_document_chunks(text);
+            if !chunks.is_empty() {
+                return chunks;
+            }
+        }
+        if matches!(file_type, FileType::Notebook) {
+            return Self::notebook_document_chunks(text);
+        }
+        if matches!(file_type, FileType::Table) {
+            return Self::table_document_chunks(text);
+        }
+        if matches!(file_type, FileType::Code) {
+            // The extension picks the grammar dialect: `.tsx` is reported as `typescript`.
+            let code_lang = CodeLanguage::from_path(path)
+                .or_else(|| language.and_then(CodeLanguage::from_name));
+            if let Some(chunks) = code_lang.and_then(|lang| Self::syntax_chunks(text, lang)) {
+                return chunks;
+            }
+        }
+        Self::build(text, file_type)
+            .into_iter()
+            .map(|text| BuiltChunk { text, metadata: Map::new() })
+            .collect()
+    }
+
+    fn syntax_chunks(text: &str, language: CodeLanguage) -> Option<Vec<BuiltChunk>> {
+        let items = code_chunker::chunk_code(text, language)?;
+        if items.is_empty() {
+            return None;
+        }
+        let segmenter = Segmenter::for_file_type(FileType::Code.config_key());
+        let mut chunks = Vec::new();
+        for item in items {
+            let mut metadata = Map::new();
+            metadata.insert("code_language".into(), json!(language.name()));
+            metadata.insert("code_kind".into(), json!(item.kind));
+            if !item.name.is_empty() {
+                metadata.insert("code_symbol".into(), json!(item.name));
+            }
+            if !item.scope.is_empty() {
+                metadata.insert("code_scope".into(), json!(item.scope_path(language)));
+            }
+            metadata.insert("start_line".into(), json!(item.start_line));
+            metadata.insert("end_line".into(), json!(item.end_line));
+            // Oversized items (long functions, big classes) are still split to the token budget.
+            for segment in segmenter.segment(&item.text) {
+                chunks.push(BuiltChunk { text: segment, metadata: metadata.clone() });
+            }
+        }
+        Some(chunks)
+    }
+
+    /// Chunk PDF text page by page, recording the 1-based page number of each chunk.
+    fn pdf_document_chunks(text: &str) -> Vec<BuiltChunk> {
+        let segmenter = Segmenter::for_file_type(FileType::Pdf.config_key());
+        let mut chunks = Vec::new();
+        for (idx, page) in text.split(PAGE_BREAK).enumerate() {
+            let mut metadata = Map::new();
+            metadata.insert("page".into(), json!(idx + 1));
+            let paragraphs = page
+                .split("\n\n")
+                .map(|p| p.trim().to_string())
+                .filter(|p| !p.is_empty())
+                .collect();
+            for segment in segmenter.segment_all(paragraphs) {
+                chunks.push(BuiltChunk { text: segment, metadata: metadata.clone() });
+            }
+        }
+        chunks
+    }
+
+    /// Chunk notebooks cell by cell, recording the 0-based cell index and cell type.
+    fn notebook_document_chunks(text: &str) -> Vec<BuiltChunk> {
+        let segmenter = Segmenter::for_file_type(FileType::Notebook.config_key());
+        let mut chunks = Vec::new();
+        for (idx, cell_type, body) in notebook_cells(text) {
+            let mut metadata = Map::new();
+            metadata.insert("cell_index".into(), json!(idx));
+            metadata.insert("cell_type".into(), json!(cell_type));
+            for segment in segmenter.segment(body) {
+                chunks.push(BuiltChunk { text: segment, metadata: metadata.clone() });
             }
         }
-        out
+        chunks
+    }
+
+    /// Chunk tables into groups of consecutive rows that fit the token budget, each repeating
+    /// the table header and column types, recording the 1-based first and last row.
+    fn table_document_chunks(text: &str) -> Vec<BuiltChunk> {
+        let Some((header, rows)) = table_parts(text) else {
+            return Self::text_chunks(text)
+                .into_iter()
+                .map(|text| BuiltChunk { text, metadata: Map::new() })
+                .collect();
+        };
+        let segmenter = Segmenter::for_file_type(FileType::Table.config_key());
+        let mut chunks = Vec::new();
+        let mut push = |first: usize, group: &[&str]| {
+            let mut metadata = Map::new();
+            metadata.insert("row_start".into(), json!(first + 1));
+            metadata.insert("row_end".into(), json!(first + group.len()));
+            chunks.push(BuiltChunk { text: format!("{header}\n{}", group.join("\n")), metadata });
+        };
+        let mut start = 0;
+        let mut current = header.to_string();
+        for (idx, row) in rows.iter().enumerate() {
+            // A row that does not fit on its own still gets a chunk of its own.
+            if idx > start && !segmenter.fits(&format!("{current}\n{row}")) {
+                push(start, &rows[start..idx]);
+                start = idx;
+                current = header.to_string();
+            }
+            current.push('\n');
+            current.push_str(row);
+        }
+        if start < rows.len() {
+            push(start, &rows[start..]);
+        }
+        if chunks.is_empty() {
+            chunks.push(BuiltChunk { text: header.to_string(), metadata: Map::new() });
+        }
+        chunks
     }
 
     fn pdf_chunks(text: &str) -> Vec<String> {
         // PDFs often have form feeds or page markers; split on page breaks first.
-        let pages: Vec<&str> = text.split('\u{0c}').collect();
+        let pages: Vec<&str> = text.split(PAGE_BREAK).collect();
         let mut chunks = Vec::new();
         for page in pages {
             for para in page.split("\n\n") {
@@ -123,25 +314,82 @@ impl ChunkBuilder {
     }
 
     fn markdown_chunks(text: &str) -> Vec<String> {
-        let mut chunks = Vec::new();
-        let mut current = String::new();
-        for line in text.lines() {
-            if line.starts_with('#') && !current.trim().is_empty() {
-                chunks.push(current.trim().to_string());
-                current.clear();
-            }
-            current.push_str(line);
-            current.push('\n');
-        }
-        if !current.trim().is_empty() {
-            chunks.push(current.trim().to_string());
-        }
+        let mut chunks: Vec<String> = Self::markdown_sections(text)
+            .into_iter()
+            .map(|section| section.with_breadcrumb(section.body.trim()))
+            .collect();
         if chunks.is_empty() {
             chunks.push(text.to_string());
         }
         chunks
     }
 
+    fn markdown_document_chunks(text: &str, file_type: FileType) -> Vec<BuiltChunk> {
+        let segmenter = Segmenter::for_file_type(file_type.config_key());
+        let mut chunks = Vec::new();
+        for section in Self::markdown_sections(text) {
+            let mut metadata = Map::new();
+            if !section.breadcrumb.is_empty() {
+                metadata.insert("heading_path".into(), json!(section.breadcrumb_path()));
+            }
+            // The breadcrumb is repeated on every segment so each embeds with its context.
+            for segment in segmenter.segment(&section.body) {
+                chunks.push(BuiltChunk {
+                    text: section.with_breadcrumb(&segment),
+                    metadata: metadata.clone(),
+                });
+            }
+        }
+        chunks
+    }
+
+    /// Split Markdown at headings, tracking the heading hierarchy above each section.
+    ///
+    /// Parsed with pulldown-cmark so `#` lines inside code fences do not start sections and
+    /// tables and lists stay within their section.
+    fn markdown_sections(text: &str) -> Vec<MarkdownSection> {
+        let options =
+            Options::ENABLE_TABLES | Options::ENABLE_TASKLISTS | Options::ENABLE_STRIKETHROUGH;
+        let mut sections = Vec::new();
+        let mut headings: Vec<(HeadingLevel, String)> = Vec::new();
+        let mut title: Option<(HeadingLevel, String)> = None;
+        let mut body_start = 0;
+
+        let mut push = |headings: &[(HeadingLevel, String)], body: &str| {
+            if !body.trim().is_empty() {
+                sections.push(MarkdownSection {
+                    breadcrumb: headings.iter().map(|(_, h)| h.clone()).collect(),
+                    body: body.trim().to_string(),
+                });
+            }
+        };
+
+        for (event, range) in Parser::new_ext(text, options).into_offset_iter() {
+            match event {
+                Event::Start(Tag::Heading { level, .. }) => {
+                    push(&headings, &text[body_start..range.start]);
+                    title = Some((level, String::new()));
+                }
+                Event::Text(t) | Event::Code(t) => {
+                    if let Some((_, buf)) = title.as_mut() {
+                        buf.push_str(&t);
+                    }
+                }
+                Event::End(TagEnd::Heading(_)) => {
+                    if let Some((level, name)) = title.take() {
+                        headings.retain(|(l, _)| *l < level);
+                        headings.push((level, name.trim().to_string()));
+                    }
+                    body_start = range.end;
+                }
+                _ => {}
+            }
+        }
+        push(&headings, &text[body_start..]);
+        sections
+    }
+
+    /// Line-based fallback for languages without a syntax-tree grammar.
     fn code_chunks(text: &str) -> Vec<String> {
         let re = Regex::new(r"(?m)^(pub\s+fn\s|fn\s|impl\s|def\s|class\s)").unwrap();
         let mut chunks = Vec::new();
@@ -37,21 +37,27 @@ impl PipelineStep for ChunkStep {
                 .map(|lang| ChunkBuilder::detect_language(lang))
                 .unwrap_or_else(|| ChunkBuilder::detect(&doc.path));
             task::spawn(async move {
-                ChunkBuilder::build(&doc.content, ftype)
-                    .into_iter()
-                    .enumerate()
-                    .map(|(idx, chunk_text)| Chunk {
-                        document_path: doc.path.clone(),
-                        text: chunk_text,
-                        tags: Vec::new(),
-                        embedding: None,
-                        chunk_index: idx,
-                        vector_id: None,
-                        namespace: doc.namespace.clone(),
-                        sparse_indices: Vec::new(),
-                        sparse_values: Vec::new(),
-                    })
-                    .collect::<Vec<_>>()
+                ChunkBuilder::build_document(
+                    &doc.content,
+                    ftype,
+                    &doc.path,
+                    doc.language.as_deref(),
+                )
+                .into_iter()
+                .enumerate()
+                .map(|(idx, built)| Chunk {
+                    document_path: doc.path.clone(),
+                    text: built.text,
+                    tags: Vec::new(),
+                    embedding: None,
+                    chunk_index: idx,
+                    vector_id: None,
+                    namespace: doc.namespace.clone(),
+                    sparse_indices: Vec::new(),
+                    sparse_values: Vec::new(),
+                    metadata: built.metadata,
+                })
+                .collect::<Vec<_>>()
             })
         });
 
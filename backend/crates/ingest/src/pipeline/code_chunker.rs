@@ -0,0 +1,378 @@
+use tree_sitter::{Language, Node, Parser};
+
+/// Languages with syntax-tree chunking.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CodeLanguage {
+    Rust,
+    TypeScript,
+    Tsx,
+    JavaScript,
+    Python,
+    Go,
+    Java,
+}
+
+impl CodeLanguage {
+    pub fn from_path(path: &str) -> Option<Self> {
+        let base = path.split('#').next().unwrap_or(path);
+        let ext = std::path::Path::new(base).extension()?.to_str()?.to_ascii_lowercase();
+        match ext.as_str() {
+            "rs" => Some(Self::Rust),
+            "ts" | "mts" | "cts" => Some(Self::TypeScript),
+            "tsx" => Some(Self::Tsx),
+            "js" | "jsx" | "mjs" | "cjs" => Some(Self::JavaScript),
+            "py" => Some(Self::Python),
+            "go" => Some(Self::Go),
+            "java" => Some(Self::Java),
+            _ => None,
+        }
+    }
+
+    pub fn from_name(language: &str) -> Option<Self> {
+        match language.to_ascii_lowercase().as_str() {
+            "rust" => Some(Self::Rust),
+            "typescript" => Some(Self::TypeScript),
+            "tsx" => Some(Self::Tsx),
+            "javascript" => Some(Self::JavaScript),
+            "python" => Some(Self::Python),
+            "go" => Some(Self::Go),
+            "java" => Some(Self::Java),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Rust => "rust",
+            Self::TypeScript => "typescript",
+            Self::Tsx => "tsx",
+            Self::JavaScript => "javascript",
+            Self::Python => "python",
+            Self::Go => "go",
+            Self::Java => "java",
+        }
+    }
+
+    fn grammar(&self) -> Language {
+        match self {
+            Self::Rust => tree_sitter_rust::LANGUAGE.into(),
+            Self::TypeScript => tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into(),
+            Self::Tsx => tree_sitter_typescript::LANGUAGE_TSX.into(),
+            Self::JavaScript => tree_sitter_javascript::LANGUAGE.into(),
+            Self::Python => tree_sitter_python::LANGUAGE.into(),
+            Self::Go => tree_sitter_go::LANGUAGE.into(),
+            Self::Java => tree_sitter_java::LANGUAGE.into(),
+        }
+    }
+
+    /// Separator used when joining the enclosing scope path.
+    fn scope_separator(&self) -> &'static str {
+        match self {
+            Self::Rust => "::",
+            _ => ".",
+        }
+    }
+}
+
+/// One syntactic unit of a source file.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CodeItem {
+    pub text: String,
+    /// Tree-sitter node kind, e.g. `function_item` or `class_definition`.
+    pub kind: String,
+    /// Item name; empty for groups of top-level statements.
+    pub name: String,
+    /// Enclosing modules, impls and classes, outermost first.
+    pub scope: Vec<String>,
+    /// 1-based, inclusive.
+    pub start_line: usize,
+    pub end_line: usize,
+}
+
+impl CodeItem {
+    pub fn scope_path(&self, language: CodeLanguage) -> String {
+        self.scope.join(language.scope_separator())
+    }
+}
+
+enum NodeRole<'t> {
+    /// Emitted as its own chunk.
+    Item { name: String },
+    /// Its body is walked for nested items under a new scope segment.
+    Container { name: String, body: Node<'t> },
+    /// Leading comment or attribute attached to the following item.
+    Doc,
+    /// Anything else; grouped with neighbouring statements.
+    Other,
+}
+
+/// Split source into one item per function/method/type, each with its leading doc comments.
+///
+/// Returns `None` when the file does not parse cleanly so callers can fall back to heuristics.
+pub fn chunk_code(source: &str, language: CodeLanguage) -> Option<Vec<CodeItem>> {
+    let mut parser = Parser::new();
+    parser.set_language(&language.grammar()).ok()?;
+    let tree = parser.parse(source, None)?;
+    let root = tree.root_node();
+    if root.has_error() {
+        return None;
+    }
+    let mut walker = Walker { source, language, items: Vec::new() };
+    walker.walk(root, &[], None);
+    Some(walker.items)
+}
+
+struct Walker<'s> {
+    source: &'s str,
+    language: CodeLanguage,
+    items: Vec<CodeItem>,
+}
+
+/// Pending run of non-item source at one nesting level.
+struct Group {
+    kind: String,
+    name: String,
+    /// 0-based rows of the first and last line.
+    rows: Option<(usize, usize)>,
+    end_byte: usize,
+    text: String,
+}
+
+impl Group {
+    fn module() -> Self {
+        Self {
+            kind: "module".into(),
+            name: String::new(),
+            rows: None,
+            end_byte: 0,
+            text: String::new(),
+        }
+    }
+}
+
+impl<'s> Walker<'s> {
+    /// Walk the children of `parent`. `header` carries a container's own declaration (doc
+    /// comments and signature) so it is kept with the container's non-item members.
+    fn walk(&mut self, parent: Node, scope: &[String], header: Option<Group>) {
+        let mut group = header.unwrap_or_else(Group::module);
+        let mut doc_start: Option<Node> = None;
+        let mut cursor = parent.walk();
+        let children: Vec<Node> = parent.named_children(&mut cursor).collect();
+
+        for child in children {
+            let (role, inner) = self.role(child);
+            match role {
+                NodeRole::Doc => {
+                    doc_start.get_or_insert(child);
+                }
+                NodeRole::Other => {
+                    let start = doc_start.take().unwrap_or(child);
+                    self.append(&mut group, start, child);
+                }
+                NodeRole::Item { name } => {
+                    self.flush(&mut group, scope);
+                    let start = doc_start.take().unwrap_or(child);
+                    self.items.push(CodeItem {
+                        text: self.source[start.start_byte()..child.end_byte()].to_string(),
+                        kind: inner.kind().to_string(),
+                        name,
+                        scope: scope.to_vec(),
+                        start_line: start.start_position().row + 1,
+                        end_line: child.end_position().row + 1,
+                    });
+                }
+                NodeRole::Container { name, body } => {
+                    self.flush(&mut group, scope);
+                    let start = doc_start.take().unwrap_or(child);
+                    let header = Group {
+                        kind: inner.kind().to_string(),
+                        name: name.clone(),
+                        rows: Some((start.start_position().row, body.start_position().row)),
+                        end_byte: body.start_byte(),
+                        text: self.source[start.start_byte()..body.start_byte()]
+                            .trim_end()
+                            .to_string(),
+                    };
+                    let mut nested = scope.to_vec();
+                    nested.push(name);
+                    self.walk(body, &nested, Some(header));
+                }
+            }
+        }
+        // Trailing comments with no item after them stay with the surrounding statements.
+        if let Some(doc) = doc_start {
+            let mut cursor = parent.walk();
+            if let Some(last) = parent.named_children(&mut cursor).last() {
+                self.append(&mut group, doc, last);
+            }
+        }
+        self.flush(&mut group, scope);
+    }
+
+    /// Add the source from `start` through `end` to the pending group.
+    fn append(&self, group: &mut Group, start: Node, end: Node) {
+        let first_row = group.rows.map_or(start.start_position().row, |(first, _)| first);
+        group.rows = Some((first_row, end.end_position().row));
+        if !group.text.is_empty() {
+            // Keep the original spacing when only whitespace separates the nodes.
+            let between = self.source.get(group.end_byte..start.start_byte()).unwrap_or("\n");
+            group.text.push_str(if between.trim().is_empty() { between } else { "\n" });
+        }
+        group.text.push_str(&self.source[start.start_byte()..end.end_byte()]);
+        group.end_byte = end.end_byte();
+    }
+
+    fn flush(&mut self, group: &mut Group, scope: &[String]) {
+        let text = std::mem::take(&mut group.text);
+        let rows = group.rows.take();
+        let kind = std::mem::replace(&mut group.kind, "module".into());
+        let name = std::mem::take(&mut group.name);
+        let Some((first, last)) = rows else { return };
+        if text.trim().is_empty() {
+            return;
+        }
+        let mut item_scope = scope.to_vec();
+        if kind != "module" {
+            // A container's header is reported as the container item, not inside it.
+            item_scope.pop();
+        }
+        self.items.push(CodeItem {
+            text,
+            kind,
+            name,
+            scope: item_scope,
+            start_line: first + 1,
+            end_line: last + 1,
+        });
+    }
+
+    /// Classify a node, looking through wrappers such as `export` statements and decorators.
+    /// Returns the role and the node that determined it.
+    fn role<'t>(&self, node: Node<'t>) -> (NodeRole<'t>, Node<'t>) {
+        let inner = match node.kind() {
+            "export_statement" => node.child_by_field_name("declaration"),
+            "decorated_definition" => node.child_by_field_name("definition"),
+            _ => None,
+        };
+        if let Some(inner) = inner {
+            let (role, _) = self.role(inner);
+            return (role, inner);
+        }
+        let name = || self.field_text(node, "name");
+        let role = match (self.language, node.kind()) {
+            (_, "comment" | "line_comment" | "block_comment" | "attribute_item") => NodeRole::Doc,
+            (_, "decorator") => NodeRole::Doc,
+
+            (CodeLanguage::Rust, "mod_item" | "trait_item") => {
+                match node.child_by_field_name("body") {
+                    Some(body) => NodeRole::Container { name: name(), body },
+                    None => NodeRole::Item { name: name() },
+                }
+            }
+            (CodeLanguage::Rust, "impl_item") => match node.child_by_field_name("body") {
+                Some(body) => NodeRole::Container { name: self.field_text(node, "type"), body },
+                None => NodeRole::Other,
+            },
+            (
+                CodeLanguage::Rust,
+                "function_item"
+                | "function_signature_item"
+                | "struct_item"
+                | "enum_item"
+                | "union_item"
+                | "type_item"
+                | "const_item"
+                | "static_item"
+                | "macro_definition",
+            ) => NodeRole::Item { name: name() },
+
+            (
+                CodeLanguage::TypeScript | CodeLanguage::Tsx | CodeLanguage::JavaScript,
+                "class_declaration" | "abstract_class_declaration" | "class",
+            ) => match node.child_by_field_name("body") {
+                Some(body) => NodeRole::Container { name: name(), body },
+                None => NodeRole::Item { name: name() },
+            },
+            (
+                CodeLanguage::TypeScript | CodeLanguage::Tsx | CodeLanguage::JavaScript,
+                "function_declaration"
+                | "generator_function_declaration"
+                | "method_definition"
+                | "interface_declaration"
+                | "type_alias_declaration"
+                | "enum_declaration"
+                | "abstract_method_signature",
+            ) => NodeRole::Item { name: name() },
+            (
+                CodeLanguage::TypeScript | CodeLanguage::Tsx | CodeLanguage::JavaScript,
+                "lexical_declaration" | "variable_declaration",
+            ) => {
+                self.function_binding(node).map_or(NodeRole::Other, |name| NodeRole::Item { name })
+            }
+
+            (CodeLanguage::Python, "class_definition") => match node.child_by_field_name("body") {
+                Some(body) => NodeRole::Container { name: name(), body },
+                None => NodeRole::Item { name: name() },
+            },
+            (CodeLanguage::Python, "function_definition") => NodeRole::Item { name: name() },
+
+            (CodeLanguage::Go, "function_declaration") => NodeRole::Item { name: name() },
+            (CodeLanguage::Go, "method_declaration") => {
+                let receiver = node
+                    .child_by_field_name("receiver")
+                    .map(|r| self.node_text(r))
+                    .and_then(|r| r.split_whitespace().last().map(|t| t.to_string()))
+                    .map(|t| t.trim_matches(|c| c == '*' || c == ')' || c == '(').to_string())
+                    .unwrap_or_default();
+                let method = name();
+                let name =
+                    if receiver.is_empty() { method } else { format!("{receiver}.{method}") };
+                NodeRole::Item { name }
+            }
+            (CodeLanguage::Go, "type_declaration") => {
+                let mut cursor = node.walk();
+                let name = node
+                    .named_children(&mut cursor)
+                    .find_map(|spec| spec.child_by_field_name("name"))
+                    .map(|n| self.node_text(n).to_string())
+                    .unwrap_or_default();
+                NodeRole::Item { name }
+            }
+
+            (
+                CodeLanguage::Java,
+                "class_declaration"
+                | "interface_declaration"
+                | "enum_declaration"
+                | "record_declaration",
+            ) => match node.child_by_field_name("body") {
+                Some(body) => NodeRole::Container { name: name(), body },
+                None => NodeRole::Item { name: name() },
+            },
+            (CodeLanguage::Java, "method_declaration" | "constructor_declaration") => {
+                NodeRole::Item { name: name() }
+            }
+
+            _ => NodeRole::Other,
+        };
+        (role, node)
+    }
+
+    /// `const handler = () => ...` style bindings count as functions.
+    fn function_binding(&self, node: Node) -> Option<String> {
+        let mut cursor = node.walk();
+        let declarator =
+            node.named_children(&mut cursor).find(|c| c.kind() == "variable_declarator")?;
+        let value = declarator.child_by_field_name("value")?;
+        matches!(value.kind(), "arrow_function" | "function_expression" | "function" | "class")
+            .then(|| self.field_text(declarator, "name"))
+    }
+
+    fn field_text(&self, node: Node, field: &str) -> String {
+        node.child_by_field_name(field).map(|n| self.node_text(n).to_string()).unwrap_or_default()
+    }
+
+    fn node_text(&self, node: Node) -> &'s str {
+        &self.source[node.start_byte()..node.end_byte()]
+    }
+}
@@ -1,17 +1,60 @@
+use std::collections::{BTreeSet, HashMap};
+use std::sync::Arc;
+
 use async_trait::async_trait;
-use futures::future::join_all;
+use futures::stream::{self, StreamExt};
 use mnemo_core::error::MnemoResult;
+use mnemo_core::models::chunk::Chunk;
+use mnemo_core::models::document::Document;
 use mnemo_core::rag::keyword::sparse_vector;
 use mnemo_core::ws::WS_HUB;
 use mnemo_inference::model_router::select_model;
-use mnemo_inference::TensorZeroEmbedder;
+use mnemo_inference::{InferenceError, RetryPolicy, TensorZeroEmbedder};
+use mnemo_storage::cache::embedding_cache::EmbeddingCache;
+use mnemo_storage::metadata::postgres::PostgresMetadataStore;
 use serde_json::json;
-use tokio::task;
-use std::sync::Arc;
 
 use super::{data::PipelineData, step::PipelineStep};
 
-pub struct EmbeddingStep;
+const DEFAULT_BATCH_SIZE: usize = 32;
+const DEFAULT_CONCURRENCY: usize = 4;
+
+/// The model that answered and the vector, or why a text could not be embedded.
+type Embedded = Result<(String, Vec<f32>), InferenceError>;
+
+/// Embeds chunks in batched `/v1/embeddings` requests, a few batches at a time.
+///
+/// Cached embeddings are reused. A batch that still fails after retries is retried one chunk
+/// at a time; chunks that fail alone are dropped from the run and their documents lose their
+/// fingerprint, so the next scan embeds them again.
+pub struct EmbeddingStep {
+    pub store: PostgresMetadataStore,
+    pub cache: EmbeddingCache,
+}
+
+impl EmbeddingStep {
+    /// Step using the cache configured by `MNEMO_EMBED_CACHE`.
+    pub fn new(store: PostgresMetadataStore) -> Self {
+        Self { store, cache: EmbeddingCache::from_env() }
+    }
+
+    pub fn with_cache(mut self, cache: EmbeddingCache) -> Self {
+        self.cache = cache;
+        self
+    }
+}
+
+/// Vectors for one run's chunks.
+pub struct EmbeddedChunks {
+    /// One vector per chunk, in chunk order; empty for chunks that failed.
+    pub embeddings: Vec<Vec<f32>>,
+    /// `(chunk index, error)` for chunks that failed on their own, by index.
+    pub failed: Vec<(usize, String)>,
+    /// Chunks answered from the cache.
+    pub cache_hits: usize,
+    /// Embedding requests sent: one per batch, plus one per chunk of a batch retried alone.
+    pub requests: usize,
+}
 
 fn broadcast_step(job_id: &Option<String>, step: &str, status: &str) {
     if let Some(id) = job_id {
@@ -21,6 +64,24 @@ fn broadcast_step(job_id: &Option<String>, step: &str, status: &str) {
     }
 }
 
+/// Chunks per request from `MNEMO_EMBED_BATCH_SIZE` (default 32).
+fn batch_size() -> usize {
+    std::env::var("MNEMO_EMBED_BATCH_SIZE")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .filter(|n| *n > 0)
+        .unwrap_or(DEFAULT_BATCH_SIZE)
+}
+
+/// Requests in flight from `MNEMO_EMBED_CONCURRENCY` (default 4).
+fn concurrency() -> usize {
+    std::env::var("MNEMO_EMBED_CONCURRENCY")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .filter(|n| *n > 0)
+        .unwrap_or(DEFAULT_CONCURRENCY)
+}
+
 #[async_trait]
 impl PipelineStep for EmbeddingStep {
     async fn process(&self, mut data: PipelineData) -> MnemoResult<PipelineData> {
@@ -34,98 +95,231 @@ impl PipelineStep for EmbeddingStep {
             ));
         }
 
-        // Prepare lookup for document metadata
-        let mut doc_lookup = std::collections::HashMap::new();
-        for doc in &data.documents {
-            doc_lookup.insert(
-                doc.path.clone(),
-                (
-                    doc.file_type.clone(),
-                    doc.namespace.clone(),
-                    doc.file_size.unwrap_or(0),
-                    doc.language.clone(),
-                ),
-            );
+        let embedder = Arc::new(
+            TensorZeroEmbedder::from_env()
+                .map_err(|e| mnemo_core::error::MnemoError::Message(e.to_string()))?,
+        );
+        let EmbeddedChunks { embeddings, failed, cache_hits: hits, requests } =
+            embed_chunks(embedder, &self.cache, &data.documents, &data.chunks).await;
+        let misses = data.chunks.len() - hits;
+
+        let mut failed_indices = BTreeSet::new();
+        if !failed.is_empty() {
+            self.forget_failed(&data, &failed, &job_id).await;
+            if failed.len() == data.chunks.len() {
+                broadcast_step(&job_id, "embeddings", "failed");
+                return Err(mnemo_core::error::MnemoError::Message(format!(
+                    "embedding failed for all {} chunks: {}",
+                    failed.len(),
+                    failed[0].1
+                )));
+            }
+            failed_indices.extend(failed.iter().map(|(i, _)| *i));
+            data.metrics.embedding_failures += failed.len();
         }
 
-        // Group chunks by selected model to minimize engine switching.
-        let mut by_model: std::collections::HashMap<String, Vec<(usize, String)>> =
-            std::collections::HashMap::new();
-        for (idx, chunk) in data.chunks.iter().enumerate() {
-            let (file_type, namespace, size, language) = doc_lookup
-                .get(&chunk.document_path)
-                .cloned()
-                .unwrap_or((None, "default".to_string(), 0, None));
-            let model = select_model(
-                file_type.as_deref(),
-                Some(namespace.as_str()),
-                language.as_deref(),
-                Some(size),
+        let chunks = std::mem::take(&mut data.chunks);
+        for (idx, (mut chunk, emb)) in chunks.into_iter().zip(embeddings).enumerate() {
+            if failed_indices.contains(&idx) {
+                continue;
+            }
+            chunk.embedding = Some(emb);
+            let (indices, values) = sparse_vector(&chunk.text);
+            chunk.sparse_indices = indices;
+            chunk.sparse_values = values;
+            data.chunks.push(chunk);
+        }
+        data.metrics.embedding_calls += requests;
+        data.metrics.embedding_cache_hits += hits;
+        data.metrics.embedding_cache_misses += misses;
+        tracing::info!(
+            "Embeddings ready for {} chunks ({} from cache, {} failed)",
+            data.chunks.len(),
+            hits,
+            failed.len()
+        );
+        WS_HUB.broadcast(
+            json!({"event":"log","message":format!("Embeddings ready for {} chunks ({} from cache, {} failed)", data.chunks.len(), hits, failed.len()), "job_id": job_id})
+                .to_string(),
+        );
+        broadcast_step(&job_id, "embeddings", "done");
+        Ok(data)
+    }
+}
+
+impl EmbeddingStep {
+    /// Report failed chunks and clear the fingerprints of their documents, and of the archives
+    /// holding them, so the next scan picks them up again.
+    async fn forget_failed(
+        &self,
+        data: &PipelineData,
+        failed: &[(usize, String)],
+        job_id: &Option<String>,
+    ) {
+        for (i, error) in failed {
+            let chunk = &data.chunks[*i];
+            tracing::warn!(
+                "Embedding failed for {} chunk {}: {}",
+                chunk.document_path,
+                chunk.chunk_index,
+                error
+            );
+            WS_HUB.broadcast(
+                json!({"event":"embedding_failed","job_id":job_id,"path":chunk.document_path,"chunk_index":chunk.chunk_index,"error":error})
+                    .to_string(),
             );
-            by_model.entry(model).or_default().push((idx, chunk.text.clone()));
         }
+        for path in forgotten_paths(&data.documents, &data.chunks, failed) {
+            if let Err(err) = self.store.clear_fingerprint(path).await {
+                tracing::warn!("Could not clear fingerprint of {}: {}", path, err);
+            }
+        }
+    }
+}
 
-        let embedder = Arc::new(
-            TensorZeroEmbedder::from_env()
-                .map_err(|e| mnemo_core::error::MnemoError::Message(e.to_string()))?,
+/// Fingerprints to clear for `failed` chunks: their documents, and the archives holding them.
+pub fn forgotten_paths<'a>(
+    documents: &'a [Document],
+    chunks: &'a [Chunk],
+    failed: &[(usize, String)],
+) -> BTreeSet<&'a str> {
+    let paths: BTreeSet<&str> =
+        failed.iter().map(|(i, _)| chunks[*i].document_path.as_str()).collect();
+    let archives = documents
+        .iter()
+        .filter(|d| paths.contains(d.path.as_str()))
+        .filter_map(|d| d.metadata.as_ref()?.get("archive")?.as_str());
+    archives.chain(paths.iter().copied()).collect()
+}
+
+/// Embed `chunks`, reusing cached vectors and caching new ones under the model that answered.
+///
+/// Misses are grouped by the model `select_model` picks for their document, split into
+/// batches of `MNEMO_EMBED_BATCH_SIZE` and sent `MNEMO_EMBED_CONCURRENCY` at a time.
+pub async fn embed_chunks(
+    embedder: Arc<TensorZeroEmbedder>,
+    cache: &EmbeddingCache,
+    documents: &[Document],
+    chunks: &[Chunk],
+) -> EmbeddedChunks {
+    // Prepare lookup for document metadata
+    let mut doc_lookup = HashMap::new();
+    for doc in documents {
+        doc_lookup.insert(
+            doc.path.clone(),
+            (
+                doc.file_type.clone(),
+                doc.namespace.clone(),
+                doc.file_size.unwrap_or(0),
+                doc.language.clone(),
+            ),
         );
+    }
 
-        let mut embeddings: Vec<Vec<f32>> = vec![Vec::new(); data.chunks.len()];
+    // Unchanged chunk text, even inside a changed document, reuses its cached embedding.
+    let cache_model = embedder.primary_model().to_string();
+    let texts: Vec<&str> = chunks.iter().map(|c| c.text.as_str()).collect();
+    let cached = cache.get_many(&cache_model, &texts).await;
+    let mut embeddings: Vec<Vec<f32>> = vec![Vec::new(); chunks.len()];
+    let mut hits = 0;
+    for (idx, hit) in cached.into_iter().enumerate() {
+        if let Some(emb) = hit {
+            embeddings[idx] = emb;
+            hits += 1;
+        }
+    }
 
-        let mut tasks = Vec::new();
-        for (_model, entries) in by_model {
-            let indices: Vec<usize> = entries.iter().map(|(i, _)| *i).collect();
-            let texts: Vec<String> = entries.into_iter().map(|(_, t)| t).collect();
-            let embedder = embedder.clone();
-            tasks.push(task::spawn(async move {
-                let mut vecs = Vec::new();
-                for t in texts {
-                    match embedder.embed(&t).await {
-                        Ok(v) => vecs.push(v),
-                        Err(e) => {
-                            return Err(e);
-                        }
-                    }
-                }
-                Ok::<(Vec<usize>, Vec<Vec<f32>>), mnemo_inference::InferenceError>((indices, vecs))
-            }));
+    // Group chunks by selected model to minimize engine switching.
+    let mut by_model: HashMap<String, Vec<(usize, String)>> = HashMap::new();
+    for (idx, chunk) in chunks.iter().enumerate() {
+        if !embeddings[idx].is_empty() {
+            continue;
         }
+        let (file_type, namespace, size, language) = doc_lookup
+            .get(&chunk.document_path)
+            .cloned()
+            .unwrap_or((None, "default".to_string(), 0, None));
+        let model = select_model(
+            file_type.as_deref(),
+            Some(namespace.as_str()),
+            language.as_deref(),
+            Some(size),
+        );
+        by_model.entry(model).or_default().push((idx, chunk.text.clone()));
+    }
 
-        for res in join_all(tasks).await {
-            match res {
-                Ok(Ok((indices, vecs))) => {
-                    for (i, emb) in indices.into_iter().zip(vecs.into_iter()) {
-                        if i < embeddings.len() {
-                            embeddings[i] = emb;
-                        }
-                    }
-                }
-                Ok(Err(e)) => {
-                    broadcast_step(&job_id, "embeddings", "failed");
-                    return Err(mnemo_core::error::MnemoError::Message(e.to_string()));
-                }
-                Err(e) => {
-                    broadcast_step(&job_id, "embeddings", "failed");
-                    return Err(mnemo_core::error::MnemoError::Message(e.to_string()));
+    let size = batch_size();
+    let mut batches: Vec<(Vec<usize>, Vec<String>)> = Vec::new();
+    for (_model, entries) in by_model {
+        for batch in entries.chunks(size) {
+            batches.push(batch.iter().cloned().unzip());
+        }
+    }
+    let results: Vec<(Vec<usize>, Vec<Embedded>, usize)> = stream::iter(batches)
+        .map(|(indices, texts)| {
+            let embedder = embedder.clone();
+            async move {
+                let (outcomes, sent) = embed_isolated(&embedder, texts).await;
+                (indices, outcomes, sent)
+            }
+        })
+        .buffer_unordered(concurrency())
+        .collect()
+        .await;
+
+    // Model that answered -> chunk indices embedded by it in this run.
+    let mut fresh: HashMap<String, Vec<usize>> = HashMap::new();
+    let mut failed: Vec<(usize, String)> = Vec::new();
+    let mut requests = 0;
+    for (indices, outcomes, sent) in results {
+        requests += sent;
+        for (i, outcome) in indices.into_iter().zip(outcomes) {
+            match outcome {
+                Ok((model, emb)) => {
+                    fresh.entry(model).or_default().push(i);
+                    embeddings[i] = emb;
                 }
+                Err(e) => failed.push((i, e.to_string())),
             }
         }
+    }
 
-        for (idx, emb) in embeddings.into_iter().enumerate() {
-            if let Some(chunk) = data.chunks.get_mut(idx) {
-                chunk.embedding = Some(emb);
-                let (indices, values) = sparse_vector(&chunk.text);
-                chunk.sparse_indices = indices;
-                chunk.sparse_values = values;
-            }
+    for (model, indices) in &fresh {
+        let entries: Vec<(&str, &[f32])> =
+            indices.iter().map(|&i| (chunks[i].text.as_str(), embeddings[i].as_slice())).collect();
+        cache.set_many(model, &entries).await;
+    }
+
+    failed.sort_by_key(|(i, _)| *i);
+    EmbeddedChunks { embeddings, failed, cache_hits: hits, requests }
+}
+
+/// Embed one batch. When the batch request fails after its retries, each text is tried once
+/// on its own so a single bad input or a flaky moment only costs the chunks that fail alone.
+/// Also returns how many requests were sent.
+async fn embed_isolated(
+    embedder: &TensorZeroEmbedder,
+    texts: Vec<String>,
+) -> (Vec<Embedded>, usize) {
+    let err = match embedder.embed_batch(&texts).await {
+        Ok((model, vecs)) => {
+            return (vecs.into_iter().map(|v| Ok((model.clone(), v))).collect(), 1);
         }
-        data.metrics.embedding_calls += data.chunks.len();
-        tracing::info!("Embeddings generated for {} chunks", data.chunks.len());
-        let _ = WS_HUB.broadcast(
-            json!({"event":"log","message":format!("Embeddings generated for {} chunks", data.chunks.len()), "job_id": job_id})
-                .to_string(),
-        );
-        broadcast_step(&job_id, "embeddings", "done");
-        Ok(data)
+        Err(err) => err,
+    };
+    if texts.len() == 1 {
+        return (vec![Err(err)], 1);
+    }
+    tracing::warn!(
+        "Embedding batch of {} failed ({}); retrying chunks one by one",
+        texts.len(),
+        err
+    );
+    let single =
+        embedder.clone().with_retry(RetryPolicy { max_retries: 0, ..RetryPolicy::default() });
+    let mut outcomes = Vec::with_capacity(texts.len());
+    for text in &texts {
+        outcomes.push(single.embed_with_model(text).await);
     }
+    (outcomes, 1 + texts.len())
 }
@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use async_trait::async_trait;
 use mnemo_core::error::MnemoResult;
 use mnemo_core::ws::WS_HUB;
@@ -15,6 +17,9 @@ fn broadcast_step(job_id: &Option<String>, step: &str, status: &str) {
 }
 
 /// Skips documents whose fingerprints are unchanged based on metadata store.
+///
+/// Members of an archive also carry the archive's own fingerprint; when it matches the one
+/// stored under the archive path, all of its members are skipped without per-member lookups.
 pub struct FingerprintStep {
     pub store: PostgresMetadataStore,
 }
@@ -32,7 +37,24 @@ impl PipelineStep for FingerprintStep {
         broadcast_step(&job_id, "fingerprints", "running");
 
         let mut filtered = Vec::new();
+        // Archive path -> (fingerprint in this run, unchanged since the last run).
+        let mut archives: HashMap<String, (String, bool)> = HashMap::new();
         for doc in data.documents.drain(..) {
+            if let Some((archive, fingerprint)) = archive_source(&doc) {
+                let unchanged = match archives.get(archive) {
+                    Some((_, unchanged)) => *unchanged,
+                    None => {
+                        let stored = self.store.get_fingerprint(archive).await?;
+                        let unchanged = stored.as_deref() == Some(fingerprint);
+                        archives.insert(archive.to_string(), (fingerprint.to_string(), unchanged));
+                        unchanged
+                    }
+                };
+                if unchanged {
+                    tracing::info!("Skipping member of unchanged archive: {}", doc.path);
+                    continue;
+                }
+            }
             let existing = self.store.get_fingerprint(&doc.path).await?;
             if let Some(hash) = existing {
                 if hash == doc.fingerprint {
@@ -53,8 +75,20 @@ impl PipelineStep for FingerprintStep {
             );
             filtered.push(doc);
         }
+        for (archive, (fingerprint, unchanged)) in archives {
+            if !unchanged {
+                self.store.set_fingerprint(&archive, &fingerprint).await?;
+                tracing::info!("Archive fingerprint stored: {}", archive);
+            }
+        }
         data.documents = filtered;
         broadcast_step(&job_id, "fingerprints", "done");
         Ok(data)
     }
 }
+
+/// Archive path and archive fingerprint of a document read from inside an archive.
+fn archive_source(doc: &mnemo_core::models::document::Document) -> Option<(&str, &str)> {
+    let meta = doc.metadata.as_ref()?;
+    Some((meta.get("archive")?.as_str()?, meta.get("archive_fingerprint")?.as_str()?))
+}
@@ -29,7 +29,48 @@ impl GraphBuilderStep {
     }
 }
 
-fn hash_id(input: &str) -> String {
+/// Document metadata keys holding related document paths, and the `relation` of the `edge`
+/// records each one feeds.
+pub(crate) const LINK_EDGES: &[(&str, &str)] = &[
+    // Pull request -> files it touches.
+    ("linked_files", "touches"),
+    // Wiki page -> parent page, linked pages and attachments.
+    ("wiki_parent", "child_of"),
+    ("wiki_links", "links_to"),
+    ("attachments", "has_attachment"),
+    // API operation, message or type -> named schemas and types it references.
+    ("schemas", "uses_schema"),
+    // AsyncAPI operation -> messages it sends or receives.
+    ("messages", "uses_message"),
+];
+
+/// Document metadata keys naming people or places, the node table each value becomes, and the
+/// `relation` of the `edge` records linking that node to the document.
+const ENTITY_EDGES: &[(&str, &str, &str)] = &[
+    // Mail and chat threads -> the people who wrote in them and the channel they belong to.
+    ("participant_ids", "person", "participated_in"),
+    ("channel", "channel", "has_thread"),
+];
+
+/// A metadata value holding a single string or a list of strings.
+fn metadata_strings<'a>(metadata: Option<&'a serde_json::Value>, key: &str) -> Vec<&'a str> {
+    match metadata.and_then(|m| m.get(key)) {
+        Some(serde_json::Value::String(value)) => vec![value.as_str()],
+        Some(serde_json::Value::Array(values)) => {
+            values.iter().filter_map(|v| v.as_str()).collect()
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// SurrealQL array literal of strings.
+fn string_array<'a>(values: impl IntoIterator<Item = &'a String>) -> String {
+    let quoted: Vec<String> =
+        values.into_iter().map(|v| format!("'{}'", v.replace('\'', "''"))).collect();
+    format!("[{}]", quoted.join(", "))
+}
+
+pub(crate) fn hash_id(input: &str) -> String {
     let mut hasher = Sha256::new();
     hasher.update(input.as_bytes());
     let result = hasher.finalize();
@@ -44,6 +85,15 @@ impl PipelineStep for GraphBuilderStep {
         let result: MnemoResult<PipelineData> = async {
             let store = SurrealStore::get().await?;
             let mut statements: Vec<String> = Vec::new();
+            // Entity nodes shared by many documents are written once per batch.
+            let mut entities: std::collections::BTreeMap<String, (String, String)> =
+                std::collections::BTreeMap::new();
+            // Files carry the tags of their chunks so tag grants can scope graph reads.
+            let mut file_tags: std::collections::HashMap<&str, std::collections::BTreeSet<&String>> =
+                std::collections::HashMap::new();
+            for chunk in &data.chunks {
+                file_tags.entry(chunk.document_path.as_str()).or_default().extend(&chunk.tags);
+            }
 
             for doc in &data.documents {
                 let file_id = format!("file:{}", hash_id(&doc.path));
@@ -51,12 +101,48 @@ impl PipelineStep for GraphBuilderStep {
                 let ns = doc.namespace.replace('\'', "''");
                 // Use delete+insert to avoid Surreal 2.x upsert syntax issues.
                 statements.push(format!("DELETE FROM file WHERE id = {file_id};"));
+                let tags = string_array(file_tags.remove(doc.path.as_str()).unwrap_or_default());
                 statements.push(format!(
-                    "INSERT INTO file (id, path, namespace) VALUES ({file_id}, '{path}', '{ns}');",
+                    "INSERT INTO file (id, path, namespace, tags) VALUES ({file_id}, '{path}', '{ns}', {tags});",
                     file_id = file_id,
                     path = path,
                     ns = ns
                 ));
+                let metadata = doc.metadata.as_ref();
+                for (key, relation) in LINK_EDGES {
+                    for target in metadata_strings(metadata, key) {
+                        let target_id = format!("file:{}", hash_id(target));
+                        let edge_id = format!(
+                            "edge:{}",
+                            hash_id(&format!("{relation}:{file_id}->{target_id}"))
+                        );
+                        statements.push(format!("DELETE FROM edge WHERE id = {edge_id};"));
+                        statements.push(format!(
+                            "INSERT INTO edge (id, in, out, relation) VALUES ({edge_id}, {file_id}, {target_id}, '{relation}');"
+                        ));
+                    }
+                }
+                for (key, table, relation) in ENTITY_EDGES {
+                    for name in metadata_strings(metadata, key) {
+                        let entity_id = format!("{table}:{}", hash_id(name));
+                        let edge_id = format!(
+                            "edge:{}",
+                            hash_id(&format!("{relation}:{entity_id}->{file_id}"))
+                        );
+                        let name = name.replace('\'', "''");
+                        entities.insert(entity_id.clone(), (table.to_string(), name));
+                        statements.push(format!("DELETE FROM edge WHERE id = {edge_id};"));
+                        statements.push(format!(
+                            "INSERT INTO edge (id, in, out, relation) VALUES ({edge_id}, {entity_id}, {file_id}, '{relation}');"
+                        ));
+                    }
+                }
+            }
+            for (entity_id, (table, name)) in &entities {
+                statements.push(format!("DELETE FROM {table} WHERE id = {entity_id};"));
+                statements.push(format!(
+                    "INSERT INTO {table} (id, name) VALUES ({entity_id}, '{name}');"
+                ));
             }
 
             for chunk in &data.chunks {
@@ -69,11 +155,12 @@ impl PipelineStep for GraphBuilderStep {
                 let ns = chunk.namespace.replace('\'', "''");
                 statements.push(format!("DELETE FROM chunk WHERE id = {chunk_id};", chunk_id = chunk_id));
                 statements.push(format!(
-                    "INSERT INTO chunk (id, path, namespace, chunk_index) VALUES ({chunk_id}, '{path}', '{ns}', {idx});",
+                    "INSERT INTO chunk (id, path, namespace, chunk_index, tags) VALUES ({chunk_id}, '{path}', '{ns}', {idx}, {tags});",
                     chunk_id = chunk_id,
                     path = path,
                     ns = ns,
                     idx = chunk.chunk_index as i32,
+                    tags = string_array(&chunk.tags),
                 ));
                 let edge_id = format!("contains:{}", hash_id(&format!("{file_id}->{chunk_id}")));
                 statements.push(format!("DELETE FROM contains WHERE id = {edge_id};", edge_id = edge_id));
@@ -1,11 +1,15 @@
 pub mod chunk_builder;
 pub mod chunk_step;
+pub mod code_chunker;
 pub mod data;
 pub mod embedding_step;
 pub mod executor;
 pub mod fingerprint_step;
 pub mod graph_builder_step;
 pub mod ontology_step;
+pub mod prune_step;
 pub mod registry;
+pub mod screening_step;
+pub mod segmenter;
 pub mod step;
 pub mod vector_upsert_step;
@@ -0,0 +1,195 @@
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::env;
+
+use async_trait::async_trait;
+use mnemo_core::error::MnemoResult;
+use mnemo_core::models::document::Document;
+use mnemo_core::ws::WS_HUB;
+use mnemo_storage::metadata::postgres::PostgresMetadataStore;
+use mnemo_storage::surreal_store::SurrealStore;
+use mnemo_storage::vector::qdrant::QdrantVectorStore;
+use mnemo_storage::vector::vector_engine::VectorEngine;
+use serde_json::json;
+
+use super::graph_builder_step::hash_id;
+use super::{data::PipelineData, step::PipelineStep};
+use crate::metrics::IngestionMetrics;
+use crate::providers::filesystem;
+use crate::providers::registry::PruneScope;
+
+fn broadcast_step(job_id: &Option<String>, step: &str, status: &str) {
+    if let Some(id) = job_id {
+        WS_HUB.broadcast(
+            json!({"event":"ingest_step","job_id":id,"step":step,"status":status}).to_string(),
+        );
+    }
+}
+
+/// Deletes chunks past the end of each re-chunked document from every store.
+///
+/// Runs after chunking: a document that now has `n` chunks loses its old chunks `n..` in
+/// Qdrant, Surreal and Postgres, which upserts alone would leave behind.
+pub struct PruneStep {
+    pub store: PostgresMetadataStore,
+}
+
+impl PruneStep {
+    pub fn new(store: PostgresMetadataStore) -> Self {
+        Self { store }
+    }
+}
+
+#[async_trait]
+impl PipelineStep for PruneStep {
+    async fn process(&self, mut data: PipelineData) -> MnemoResult<PipelineData> {
+        let job_id = data.job_id.clone();
+        broadcast_step(&job_id, "prune", "running");
+
+        // Path -> (namespace, chunk count after re-chunking).
+        let mut counts: BTreeMap<&str, (&str, usize)> =
+            data.documents.iter().map(|d| (d.path.as_str(), (d.namespace.as_str(), 0))).collect();
+        for chunk in &data.chunks {
+            if let Some((_, count)) = counts.get_mut(chunk.document_path.as_str()) {
+                *count = (*count).max(chunk.chunk_index + 1);
+            }
+        }
+        let mut removed = 0;
+        for (path, (namespace, count)) in counts {
+            let stale =
+                delete_chunks(&self.store, namespace, path, count).await.inspect_err(|_| {
+                    broadcast_step(&job_id, "prune", "failed");
+                })?;
+            if stale > 0 {
+                tracing::info!("Pruned {} stale chunks of {}", stale, path);
+                let _ = WS_HUB.broadcast(
+                    json!({"event":"chunks_pruned","job_id":job_id,"path":path,"from_index":count,"chunks":stale})
+                        .to_string(),
+                );
+            }
+            removed += stale;
+        }
+        data.metrics.chunks_removed += removed;
+        broadcast_step(&job_id, "prune", "done");
+        Ok(data)
+    }
+}
+
+/// Previously indexed filesystem documents under `scopes` that are missing from `docs`.
+///
+/// A scope is a root for a full scan, or the changed and removed paths of a watch batch.
+pub async fn removed_documents(
+    store: &PostgresMetadataStore,
+    scopes: &[String],
+    docs: &[Document],
+) -> MnemoResult<Vec<String>> {
+    let mut known = Vec::new();
+    for scope in scopes {
+        known.extend(store.fingerprint_paths_under(filesystem::NAMESPACE, scope).await?);
+    }
+    Ok(missing_documents(known, docs))
+}
+
+/// Previously indexed documents in a provider's scope that its last load neither returned
+/// in `docs` nor `retained`.
+pub async fn removed_from_scope(
+    store: &PostgresMetadataStore,
+    scope: &PruneScope,
+    docs: &[Document],
+    retained: &[String],
+) -> MnemoResult<Vec<String>> {
+    let known = store.fingerprint_paths_with_prefix(&scope.namespace, &scope.prefix).await?;
+    Ok(unlisted_documents(known, scope, docs, retained))
+}
+
+/// Known paths in `scope` that are neither in `docs` nor `retained`, sorted and deduplicated.
+pub fn unlisted_documents(
+    known: Vec<String>,
+    scope: &PruneScope,
+    docs: &[Document],
+    retained: &[String],
+) -> Vec<String> {
+    let mut seen: HashSet<&str> = docs.iter().map(|d| d.path.as_str()).collect();
+    seen.extend(retained.iter().map(|p| p.as_str()));
+    let missing: BTreeSet<String> = known
+        .into_iter()
+        .filter(|path| path.starts_with(&scope.prefix) && !seen.contains(path.as_str()))
+        .collect();
+    missing.into_iter().collect()
+}
+
+/// Known paths a scan should have produced but did not, sorted and deduplicated.
+///
+/// Archives of scanned members count as present, and only paths a filesystem scan produces
+/// are considered: whole files, `#segment_N` segments and `!/` archive members.
+pub fn missing_documents(known: Vec<String>, docs: &[Document]) -> Vec<String> {
+    let mut seen: HashSet<&str> = docs.iter().map(|d| d.path.as_str()).collect();
+    seen.extend(docs.iter().filter_map(|d| d.metadata.as_ref()?.get("archive")?.as_str()));
+    let missing: BTreeSet<String> = known
+        .into_iter()
+        .filter(|path| path.split_once('#').is_none_or(|(_, part)| part.starts_with("segment_")))
+        .filter(|path| !seen.contains(path.as_str()))
+        .collect();
+    missing.into_iter().collect()
+}
+
+/// Delete documents of `namespace` from every store: Qdrant points, Surreal chunks, edges and
+/// the file node, then Postgres rows and finally the fingerprint, so a document whose deletion
+/// fails part-way stays known and is found again by the next scan.
+pub async fn prune_documents(
+    store: &PostgresMetadataStore,
+    namespace: &str,
+    paths: &[String],
+    job_id: &Option<String>,
+    metrics: &mut IngestionMetrics,
+) -> MnemoResult<()> {
+    for path in paths {
+        let chunks = delete_chunks(store, namespace, path, 0).await?;
+        delete_graph_file(path).await?;
+        store.delete_document(path).await?;
+        metrics.documents_removed += 1;
+        metrics.chunks_removed += chunks;
+        tracing::info!("Removed document {} ({} chunks)", path, chunks);
+        WS_HUB.broadcast(
+            json!({"event":"document_removed","job_id":job_id,"path":path,"chunks":chunks})
+                .to_string(),
+        );
+    }
+    Ok(())
+}
+
+/// Delete a document's chunks from index `from` on in every store; returns how many Surreal
+/// chunk records went.
+async fn delete_chunks(
+    store: &PostgresMetadataStore,
+    namespace: &str,
+    path: &str,
+    from: usize,
+) -> MnemoResult<usize> {
+    let qdrant_url = env::var("QDRANT_URL").unwrap_or_else(|_| "http://localhost:6333".to_string());
+    VectorEngine::new(QdrantVectorStore { url: qdrant_url })
+        .delete_chunks(namespace, path, from)
+        .await?;
+
+    let surreal = SurrealStore::get().await?;
+    let file_id = format!("file:{}", hash_id(path));
+    let escaped = path.replace('\'', "''");
+    surreal
+        .exec(&format!("DELETE FROM contains WHERE in = {file_id} AND out.chunk_index >= {from};"))
+        .await?;
+    let deleted = surreal
+        .select_all(&format!(
+            "DELETE FROM chunk WHERE path = '{escaped}' AND chunk_index >= {from} RETURN BEFORE;"
+        ))
+        .await?;
+
+    store.delete_chunks_from(path, from).await?;
+    Ok(deleted.len())
+}
+
+/// Delete a document's file node and every edge into or out of it.
+async fn delete_graph_file(path: &str) -> MnemoResult<()> {
+    let surreal = SurrealStore::get().await?;
+    let file_id = format!("file:{}", hash_id(path));
+    surreal.exec(&format!("DELETE FROM edge WHERE in = {file_id} OR out = {file_id};")).await?;
+    surreal.exec(&format!("DELETE FROM file WHERE id = {file_id};")).await
+}
@@ -0,0 +1,55 @@
+use async_trait::async_trait;
+use mnemo_core::error::MnemoResult;
+use mnemo_core::screening::{self, Screener};
+use mnemo_core::ws::WS_HUB;
+use serde_json::json;
+
+use super::{data::PipelineData, step::PipelineStep};
+
+/// Flags instruction-like chunk text and records the flags as `injection:*` tags, so they end up
+/// in the Qdrant payload next to the ontology tags.
+pub struct ScreeningStep;
+
+fn broadcast_step(job_id: &Option<String>, status: &str) {
+    if let Some(id) = job_id {
+        WS_HUB.broadcast(
+            json!({"event":"ingest_step","job_id":id,"step":"screening","status":status})
+                .to_string(),
+        );
+    }
+}
+
+#[async_trait]
+impl PipelineStep for ScreeningStep {
+    async fn process(&self, mut data: PipelineData) -> MnemoResult<PipelineData> {
+        broadcast_step(&data.job_id, "running");
+        let screener = Screener::global();
+        let texts = data.chunks.iter().map(|c| c.text.clone()).collect();
+        let screened = screener.screen_all(texts).await;
+        let mut flagged = 0usize;
+        for (idx, (chunk, flags)) in data.chunks.iter_mut().zip(screened).enumerate() {
+            if flags.is_empty() {
+                continue;
+            }
+            flagged += 1;
+            for tag in screening::flags_to_tags(&flags) {
+                if !chunk.tags.contains(&tag) {
+                    chunk.tags.push(tag);
+                }
+            }
+            data.metadata.insert(format!("screening_flags_{}", idx), flags.join(","));
+        }
+        if flagged > 0 {
+            WS_HUB.broadcast(
+                json!({
+                    "event":"log",
+                    "message": format!("Screening flagged {} chunk(s) for instruction-like content", flagged),
+                    "job_id": data.job_id
+                })
+                .to_string(),
+            );
+        }
+        broadcast_step(&data.job_id, "done");
+        Ok(data)
+    }
+}
@@ -0,0 +1,215 @@
+use std::sync::Arc;
+
+use mnemo_core::config::chunking::{ChunkSizeConfig, ChunkingConfig};
+use once_cell::sync::Lazy;
+
+/// Counts tokens for chunk sizing. Implementations must be cheap; they run on every unit.
+pub trait Tokenizer: Send + Sync {
+    fn count(&self, text: &str) -> usize;
+}
+
+/// Model-agnostic estimate of roughly four characters per token.
+pub struct ApproxTokenizer;
+
+impl Tokenizer for ApproxTokenizer {
+    fn count(&self, text: &str) -> usize {
+        text.chars().count().div_ceil(4)
+    }
+}
+
+/// One token per whitespace-separated word.
+pub struct WhitespaceTokenizer;
+
+impl Tokenizer for WhitespaceTokenizer {
+    fn count(&self, text: &str) -> usize {
+        text.split_whitespace().count()
+    }
+}
+
+static CONFIG: Lazy<ChunkingConfig> = Lazy::new(ChunkingConfig::load_default);
+
+fn tokenizer_from_config(cfg: &ChunkingConfig) -> Arc<dyn Tokenizer> {
+    match cfg.tokenizer.as_deref() {
+        Some("whitespace") => Arc::new(WhitespaceTokenizer),
+        _ => Arc::new(ApproxTokenizer),
+    }
+}
+
+/// Packs text into token-bounded chunks at line, sentence or word boundaries, with overlap.
+///
+/// Splitting only ever happens on `char` boundaries and every input character ends up in some
+/// chunk; the only text removed is leading/trailing whitespace of each chunk.
+pub struct Segmenter {
+    tokenizer: Arc<dyn Tokenizer>,
+    sizes: ChunkSizeConfig,
+}
+
+impl Segmenter {
+    pub fn new(tokenizer: Arc<dyn Tokenizer>, sizes: ChunkSizeConfig) -> Self {
+        let max_tokens = sizes.max_tokens.max(1);
+        let sizes = ChunkSizeConfig {
+            max_tokens,
+            // Overlap must leave room for new content or the segmenter would never advance.
+            overlap_tokens: sizes.overlap_tokens.min(max_tokens / 2),
+            min_tokens: sizes.min_tokens.min(max_tokens),
+        };
+        Self { tokenizer, sizes }
+    }
+
+    /// Segmenter for a file type key, using `config/chunking.yaml`.
+    pub fn for_file_type(key: &str) -> Self {
+        Self::new(tokenizer_from_config(&CONFIG), CONFIG.for_file_type(key))
+    }
+
+    /// Segment several pre-split blocks (e.g. markdown sections) independently.
+    pub fn segment_all(&self, blocks: Vec<String>) -> Vec<String> {
+        blocks.iter().flat_map(|b| self.segment(b)).collect()
+    }
+
+    /// Whether `text` fits in one chunk.
+    pub fn fits(&self, text: &str) -> bool {
+        self.tokens(text) <= self.sizes.max_tokens
+    }
+
+    pub fn segment(&self, text: &str) -> Vec<String> {
+        if text.trim().is_empty() {
+            return Vec::new();
+        }
+        if self.tokens(text) <= self.sizes.max_tokens {
+            return vec![text.trim().to_string()];
+        }
+
+        let units = self.units(text);
+        let mut chunks: Vec<Vec<&str>> = Vec::new();
+        let mut current: Vec<&str> = Vec::new();
+        let mut current_tokens = 0usize;
+        // Number of leading units in `current` carried over from the previous chunk.
+        let mut carried = 0usize;
+
+        for unit in units {
+            let unit_tokens = self.tokens(unit);
+            if current_tokens + unit_tokens > self.sizes.max_tokens && current.len() > carried {
+                let overlap = self.overlap_tail(&current);
+                chunks.push(std::mem::take(&mut current));
+                current_tokens = overlap.iter().map(|u| self.tokens(u)).sum();
+                carried = overlap.len();
+                current = overlap;
+                // Drop overlap that would not leave room for the next unit.
+                while !current.is_empty() && current_tokens + unit_tokens > self.sizes.max_tokens {
+                    current_tokens -= self.tokens(current.remove(0));
+                    carried -= 1;
+                }
+            }
+            current.push(unit);
+            current_tokens += unit_tokens;
+        }
+        if current.len() > carried {
+            let tail_tokens: usize = current[carried..].iter().map(|u| self.tokens(u)).sum();
+            let merged = chunks.last().map(|prev| {
+                prev.iter().map(|u| self.tokens(u)).sum::<usize>() + tail_tokens
+                    <= self.sizes.max_tokens
+            });
+            if tail_tokens < self.sizes.min_tokens && merged == Some(true) {
+                if let Some(prev) = chunks.last_mut() {
+                    prev.extend_from_slice(&current[carried..]);
+                }
+            } else {
+                chunks.push(current);
+            }
+        }
+
+        chunks
+            .into_iter()
+            .map(|units| units.concat().trim().to_string())
+            .filter(|c| !c.is_empty())
+            .collect()
+    }
+
+    fn tokens(&self, text: &str) -> usize {
+        self.tokenizer.count(text)
+    }
+
+    /// Trailing units of a finished chunk that fit in the overlap budget.
+    fn overlap_tail<'t>(&self, units: &[&'t str]) -> Vec<&'t str> {
+        let mut tail = Vec::new();
+        let mut tokens = 0;
+        for unit in units.iter().rev() {
+            let t = self.tokens(unit);
+            if tokens + t > self.sizes.overlap_tokens {
+                break;
+            }
+            tokens += t;
+            tail.insert(0, *unit);
+        }
+        tail
+    }
+
+    /// Split into the coarsest units that fit the token budget: lines, then sentences, then
+    /// words, then character runs. Concatenating the units yields `text` exactly.
+    fn units<'t>(&self, text: &'t str) -> Vec<&'t str> {
+        let mut out = Vec::new();
+        for line in text.split_inclusive('\n') {
+            if self.tokens(line) <= self.sizes.max_tokens {
+                out.push(line);
+                continue;
+            }
+            for sentence in split_sentences(line) {
+                if self.tokens(sentence) <= self.sizes.max_tokens {
+                    out.push(sentence);
+                    continue;
+                }
+                for word in sentence.split_inclusive(char::is_whitespace) {
+                    if self.tokens(word) <= self.sizes.max_tokens {
+                        out.push(word);
+                    } else {
+                        out.extend(self.char_runs(word));
+                    }
+                }
+            }
+        }
+        out
+    }
+
+    /// Cut an oversized word into runs that fit, on `char` boundaries.
+    fn char_runs<'t>(&self, word: &'t str) -> Vec<&'t str> {
+        let mut runs = Vec::new();
+        let mut start = 0;
+        let mut end = 0;
+        for (idx, ch) in word.char_indices() {
+            let next = idx + ch.len_utf8();
+            if end > start && self.tokens(&word[start..next]) > self.sizes.max_tokens {
+                runs.push(&word[start..end]);
+                start = end;
+            }
+            end = next;
+        }
+        if start < word.len() {
+            runs.push(&word[start..]);
+        }
+        runs
+    }
+}
+
+/// Split after sentence-ending punctuation followed by whitespace, keeping the whitespace with
+/// the preceding sentence.
+fn split_sentences(text: &str) -> Vec<&str> {
+    let mut out = Vec::new();
+    let mut start = 0;
+    let mut prev_terminal = false;
+    let mut in_gap = false;
+    for (idx, ch) in text.char_indices() {
+        if in_gap && !ch.is_whitespace() {
+            out.push(&text[start..idx]);
+            start = idx;
+            in_gap = false;
+        }
+        if prev_terminal && ch.is_whitespace() {
+            in_gap = true;
+        }
+        prev_terminal = matches!(ch, '.' | '!' | '?' | '。' | '！' | '？');
+    }
+    if start < text.len() {
+        out.push(&text[start..]);
+    }
+    out
+}
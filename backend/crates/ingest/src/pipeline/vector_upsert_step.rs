@@ -61,6 +61,7 @@ impl PipelineStep for VectorUpsertStep {
                     &chunk.namespace,
                     &tags,
                     chunk.chunk_index,
+                    &chunk.metadata,
                 )
                 .await;
             if let Err(e) = res {
@@ -0,0 +1,80 @@
+use mnemo_core::config::providers::ProvidersConfig;
+use mnemo_core::error::MnemoResult;
+use mnemo_core::ws::WS_HUB;
+use serde_json::json;
+
+use crate::fs_ingest_runner::{filesystem_pipeline, metadata_store};
+use crate::metrics::store_last_metrics;
+use crate::pipeline::data::PipelineData;
+use crate::pipeline::executor::PipelineExecutor;
+use crate::pipeline::prune_step::{prune_documents, removed_from_scope};
+use crate::providers::registry::{Provider, ProviderRegistry};
+
+/// Runs the pipeline over the documents of every provider enabled in the configuration.
+///
+/// The filesystem provider is left to `run_filesystem_ingestion`, which scans the same root.
+pub async fn run_configured_providers(job_id: Option<&str>) -> MnemoResult<()> {
+    let store = metadata_store()?;
+    let providers: Vec<_> = ProviderRegistry::new()
+        .load_active_providers(&ProvidersConfig::load(), Some(store))
+        .into_iter()
+        .filter(|p| p.name() != "filesystem")
+        .collect();
+    run_providers(&providers, job_id).await
+}
+
+/// Runs the pipeline over each provider's documents in turn.
+///
+/// Documents a provider reports deleted at the source, or that a load reaching the source no
+/// longer found in the provider's namespace and path scope, are removed from every store. The
+/// provider records its sync state only after its documents went through every step, so a
+/// failed run is loaded again in full by the next one.
+pub async fn run_providers(
+    providers: &[Box<dyn Provider + Send + Sync>],
+    job_id: Option<&str>,
+) -> MnemoResult<()> {
+    let meta_store = metadata_store()?;
+    let executor = PipelineExecutor::new(filesystem_pipeline(&meta_store));
+    for provider in providers {
+        let name = provider.name();
+        let docs = provider.load_documents().await;
+        WS_HUB.broadcast(
+            json!({"event":"log","message":format!("Provider {} found {} documents", name, docs.len()),"job_id":job_id})
+                .to_string(),
+        );
+
+        let mut data = PipelineData::new();
+        data.job_id = job_id.map(|s| s.to_string());
+        data.metrics.documents_processed = docs.len();
+        data.documents = docs;
+        if let Some(scope) = provider.prune_scope() {
+            let mut removed = provider.deleted_paths();
+            let retained = provider.retained_paths();
+            // An empty load is indistinguishable from an unreachable source.
+            if !(data.documents.is_empty() && retained.is_empty()) {
+                removed.extend(
+                    removed_from_scope(&meta_store, &scope, &data.documents, &retained).await?,
+                );
+            }
+            removed.sort();
+            removed.dedup();
+            prune_documents(
+                &meta_store,
+                &scope.namespace,
+                &removed,
+                &data.job_id,
+                &mut data.metrics,
+            )
+            .await?;
+        }
+
+        if !data.documents.is_empty() {
+            let output = executor.execute_with_data(data).await.inspect_err(|e| {
+                tracing::error!("Provider {} ingestion failed: {}", name, e);
+            })?;
+            store_last_metrics(output.metrics);
+        }
+        provider.commit_sync().await;
+    }
+    Ok(())
+}
@@ -0,0 +1,196 @@
+use std::io::Read;
+use std::path::{Component, Path};
+
+/// Separator between an archive path and a member path, as in `bundle.zip!/docs/guide.md`.
+pub const MEMBER_SEPARATOR: &str = "!/";
+
+/// Bounds on what is expanded from one archive, guarding against zip bombs.
+#[derive(Clone, Copy, Debug)]
+pub struct ArchiveLimits {
+    /// Largest archive file read at all.
+    pub max_archive_bytes: u64,
+    /// Members read per archive; later members are skipped.
+    pub max_entries: usize,
+    /// Largest uncompressed member; bigger members are skipped.
+    pub max_entry_bytes: u64,
+    /// Uncompressed bytes read per archive; members past the budget are skipped.
+    pub max_total_bytes: u64,
+}
+
+impl Default for ArchiveLimits {
+    fn default() -> Self {
+        Self {
+            max_archive_bytes: 50 * 1024 * 1024,
+            max_entries: 5_000,
+            max_entry_bytes: 5 * 1024 * 1024,
+            max_total_bytes: 200 * 1024 * 1024,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ArchiveKind {
+    Zip,
+    Tar,
+    TarGz,
+}
+
+impl ArchiveKind {
+    /// Archive kind from the file name: `.zip`, `.tar`, `.tar.gz` or `.tgz`.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let name = path.file_name()?.to_string_lossy().to_ascii_lowercase();
+        if name.ends_with(".zip") {
+            Some(ArchiveKind::Zip)
+        } else if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+            Some(ArchiveKind::TarGz)
+        } else if name.ends_with(".tar") {
+            Some(ArchiveKind::Tar)
+        } else {
+            None
+        }
+    }
+}
+
+/// A regular file read from an archive.
+#[derive(Clone, Debug)]
+pub struct ArchiveMember {
+    /// Relative `/`-separated path inside the archive.
+    pub path: String,
+    pub bytes: Vec<u8>,
+}
+
+/// Path of an archive member, e.g. `bundle.zip!/docs/guide.md`.
+pub fn member_path(archive: &str, member: &str) -> String {
+    format!("{archive}{MEMBER_SEPARATOR}{member}")
+}
+
+/// Read the regular files of an archive in memory, within `limits`.
+///
+/// Members with absolute paths or `..` components (zip-slip) and links are skipped, as are
+/// nested archives. An unreadable archive yields no members.
+pub fn expand_archive(
+    kind: ArchiveKind,
+    bytes: &[u8],
+    limits: &ArchiveLimits,
+) -> Vec<ArchiveMember> {
+    let mut budget = Budget { limits, entries: 0, total_bytes: 0 };
+    match kind {
+        ArchiveKind::Zip => expand_zip(bytes, &mut budget),
+        ArchiveKind::Tar => expand_tar(bytes, &mut budget),
+        ArchiveKind::TarGz => expand_tar(flate2::read::GzDecoder::new(bytes), &mut budget),
+    }
+}
+
+/// Entry and byte counts against the limits of one archive.
+struct Budget<'l> {
+    limits: &'l ArchiveLimits,
+    entries: usize,
+    total_bytes: u64,
+}
+
+impl Budget<'_> {
+    /// Whether another entry may be read; logs once when the entry limit is reached.
+    fn take_entry(&mut self) -> bool {
+        self.entries += 1;
+        if self.entries == self.limits.max_entries + 1 {
+            tracing::warn!(
+                "Archive has more than {} entries; skipping the rest",
+                self.limits.max_entries
+            );
+        }
+        self.entries <= self.limits.max_entries
+    }
+
+    /// Read a member of `declared` uncompressed size, enforcing the per-entry and total limits
+    /// on the bytes actually read rather than the declared size.
+    fn read(&mut self, path: &str, declared: u64, reader: impl Read) -> Option<Vec<u8>> {
+        let remaining = self.limits.max_total_bytes.saturating_sub(self.total_bytes);
+        let cap = self.limits.max_entry_bytes.min(remaining);
+        if declared > cap {
+            tracing::debug!("Archive member over size limit: {}", path);
+            return None;
+        }
+        let mut bytes = Vec::with_capacity(declared as usize);
+        reader.take(cap + 1).read_to_end(&mut bytes).ok()?;
+        if bytes.len() as u64 > cap {
+            tracing::debug!("Archive member over size limit: {}", path);
+            return None;
+        }
+        self.total_bytes += bytes.len() as u64;
+        Some(bytes)
+    }
+}
+
+fn expand_zip(bytes: &[u8], budget: &mut Budget<'_>) -> Vec<ArchiveMember> {
+    let mut members = Vec::new();
+    let Ok(mut archive) = zip::ZipArchive::new(std::io::Cursor::new(bytes)) else {
+        tracing::warn!("Unreadable zip archive");
+        return members;
+    };
+    for i in 0..archive.len() {
+        let Ok(entry) = archive.by_index(i) else { continue };
+        if entry.is_dir() {
+            continue;
+        }
+        let Some(path) = safe_member_path(Path::new(entry.name())) else {
+            tracing::warn!("Skipping unsafe archive member path: {}", entry.name());
+            continue;
+        };
+        if ArchiveKind::from_path(Path::new(&path)).is_some() || !budget.take_entry() {
+            continue;
+        }
+        let declared = entry.size();
+        if let Some(bytes) = budget.read(&path, declared, entry) {
+            members.push(ArchiveMember { path, bytes });
+        }
+    }
+    members
+}
+
+fn expand_tar(reader: impl Read, budget: &mut Budget<'_>) -> Vec<ArchiveMember> {
+    let mut members = Vec::new();
+    let mut archive = tar::Archive::new(reader);
+    let Ok(entries) = archive.entries() else {
+        tracing::warn!("Unreadable tar archive");
+        return members;
+    };
+    for entry in entries {
+        let Ok(entry) = entry else {
+            tracing::warn!("Corrupt tar archive; stopping at the last readable entry");
+            break;
+        };
+        // Symlinks and hard links could point outside the archive; only regular files count.
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+        let Some(path) = entry.path().ok().and_then(|p| safe_member_path(&p)) else {
+            tracing::warn!("Skipping unsafe archive member path");
+            continue;
+        };
+        if ArchiveKind::from_path(Path::new(&path)).is_some() || !budget.take_entry() {
+            continue;
+        }
+        let declared = entry.header().size().unwrap_or(0);
+        if let Some(bytes) = budget.read(&path, declared, entry) {
+            members.push(ArchiveMember { path, bytes });
+        }
+    }
+    members
+}
+
+/// `/`-joined relative path, or `None` for absolute paths, drive prefixes and `..` components.
+fn safe_member_path(path: &Path) -> Option<String> {
+    let mut parts = Vec::new();
+    for component in path.components() {
+        match component {
+            Component::Normal(part) => parts.push(part.to_string_lossy().into_owned()),
+            Component::CurDir => {}
+            Component::ParentDir | Component::RootDir | Component::Prefix(_) => return None,
+        }
+    }
+    // Backslash-separated names from Windows-built zips are not split by `Path` on Unix.
+    if parts.iter().any(|p| p.split('\\').any(|s| s == "..")) {
+        return None;
+    }
+    (!parts.is_empty()).then(|| parts.join("/"))
+}
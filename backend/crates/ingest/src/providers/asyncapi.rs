@@ -0,0 +1,264 @@
+use std::collections::BTreeSet;
+
+use async_trait::async_trait;
+use mnemo_core::models::document::Document;
+use serde_json::{Map, Value, json};
+
+use super::contract::{contract_document, fetch_source, parse_structured};
+use super::openapi::{SchemaWriter, Spec};
+
+const MESSAGE_PREFIX: &str = "#/components/messages/";
+
+/// `$ref` hops followed when naming a message.
+const MAX_REF_HOPS: usize = 16;
+
+/// Provider for AsyncAPI 2.x and 3.x specs from local files or URLs.
+///
+/// Emits one document per operation with its channel and message payloads, one per component
+/// message and one per component schema.
+pub struct AsyncApiProvider {
+    pub source: String,
+}
+
+/// An operation normalized across AsyncAPI 2 (`publish`/`subscribe` under a channel) and 3
+/// (top-level `operations` with `send`/`receive`).
+struct Operation<'a> {
+    id: Option<&'a str>,
+    action: String,
+    channel: String,
+    channel_description: Option<&'a str>,
+    operation: &'a Value,
+    messages: Vec<&'a Value>,
+}
+
+impl AsyncApiProvider {
+    pub fn new(source: String) -> Self {
+        Self { source }
+    }
+
+    fn namespace(&self) -> String {
+        "asyncapi".to_string()
+    }
+
+    pub fn parse_spec(&self, raw: &str) -> Vec<Document> {
+        let value = parse_structured(raw);
+        let Some(version) = value.get("asyncapi").and_then(|v| v.as_str()) else {
+            return Vec::new();
+        };
+        let spec = Spec::with_schema_prefix(&value, "#/components/schemas/");
+        let namespace = self.namespace();
+        let base_meta = |mut meta: Map<String, Value>| {
+            meta.insert("asyncapi_version".into(), json!(version));
+            meta.insert("api_title".into(), json!(value.pointer("/info/title")));
+            meta
+        };
+        let mut docs = Vec::new();
+
+        let operations =
+            if version.starts_with('2') { operations_v2(&spec) } else { operations_v3(&spec) };
+        for op in operations {
+            let mut writer = SchemaWriter::new(&spec);
+            let mut out = format!("# {} {}\n\n", op.action.to_uppercase(), op.channel);
+            for key in ["summary", "description"] {
+                if let Some(text) = op.operation.get(key).and_then(|v| v.as_str()) {
+                    out.push_str(&format!("{}\n\n", text.trim()));
+                }
+            }
+            if let Some(id) = op.id {
+                out.push_str(&format!("Operation ID: `{id}`\n"));
+            }
+            out.push_str(&format!("Channel: `{}`\n", op.channel));
+            if let Some(description) = op.channel_description {
+                out.push_str(&format!("{}\n", description.trim()));
+            }
+            let mut messages = BTreeSet::new();
+            for message in &op.messages {
+                let name = message_name(&spec, message);
+                if let Some(name) = name {
+                    messages.insert(message_path(name));
+                }
+                out.push_str(&match name {
+                    Some(name) => format!("\n## Message `{name}`\n\n"),
+                    None => "\n## Message\n\n".to_string(),
+                });
+                render_message(&mut writer, spec.resolve(message), &mut out);
+            }
+            let mut meta = Map::new();
+            meta.insert("action".into(), json!(op.action));
+            meta.insert("channel".into(), json!(op.channel));
+            meta.insert("operation_id".into(), json!(op.id));
+            meta.insert("messages".into(), json!(messages));
+            meta.insert("schemas".into(), json!(schema_paths(&writer.used)));
+            docs.push(contract_document(
+                format!("asyncapi {} {}", op.action.to_uppercase(), op.channel),
+                &out,
+                "asyncapi",
+                base_meta(meta),
+                &namespace,
+            ));
+        }
+
+        let component_messages = value.pointer("/components/messages").and_then(|m| m.as_object());
+        for (name, message) in component_messages.into_iter().flatten() {
+            let mut writer = SchemaWriter::new(&spec);
+            let mut out = format!("# {name}\n\n");
+            render_message(&mut writer, spec.resolve(message), &mut out);
+            let mut meta = Map::new();
+            meta.insert("message_name".into(), json!(name));
+            meta.insert("schemas".into(), json!(schema_paths(&writer.used)));
+            docs.push(contract_document(
+                message_path(name),
+                &out,
+                "asyncapi",
+                base_meta(meta),
+                &namespace,
+            ));
+        }
+
+        for (name, schema) in spec.schemas().into_iter().flatten() {
+            let (content, mut meta, mut used) = spec.schema_document(name, schema);
+            used.remove(name.as_str());
+            meta.insert("schemas".into(), json!(schema_paths(&used)));
+            docs.push(contract_document(
+                format!("asyncapi schema {name}"),
+                &content,
+                "asyncapi",
+                base_meta(meta),
+                &namespace,
+            ));
+        }
+
+        docs
+    }
+}
+
+fn message_path(name: &str) -> String {
+    format!("asyncapi message {name}")
+}
+
+fn schema_paths(names: &BTreeSet<String>) -> Vec<String> {
+    names.iter().map(|n| format!("asyncapi schema {n}")).collect()
+}
+
+/// `publish` and `subscribe` operations under each channel; `oneOf` lists alternative messages.
+fn operations_v2<'a>(spec: &Spec<'a>) -> Vec<Operation<'a>> {
+    let mut operations = Vec::new();
+    let channels = spec.root.get("channels").and_then(|c| c.as_object());
+    for (address, channel) in channels.into_iter().flatten() {
+        let channel = spec.resolve(channel);
+        for action in ["publish", "subscribe"] {
+            let Some(operation) = channel.get(action).map(|o| spec.resolve(o)) else { continue };
+            let messages = match operation.get("message").map(|m| spec.resolve(m)) {
+                Some(message) => match message.get("oneOf").and_then(|o| o.as_array()) {
+                    Some(options) => options.iter().collect(),
+                    // Keep the unresolved value so a component reference still names it.
+                    None => operation.get("message").into_iter().collect(),
+                },
+                None => Vec::new(),
+            };
+            operations.push(Operation {
+                id: operation.get("operationId").and_then(|v| v.as_str()),
+                action: action.to_string(),
+                channel: address.clone(),
+                channel_description: channel.get("description").and_then(|v| v.as_str()),
+                operation,
+                messages,
+            });
+        }
+    }
+    operations
+}
+
+/// Top-level `operations`, each pointing at a channel and optionally a subset of its messages.
+fn operations_v3<'a>(spec: &Spec<'a>) -> Vec<Operation<'a>> {
+    let mut operations = Vec::new();
+    let defined = spec.root.get("operations").and_then(|o| o.as_object());
+    for (id, operation) in defined.into_iter().flatten() {
+        let operation = spec.resolve(operation);
+        let channel_ref = operation.get("channel");
+        let channel = channel_ref.map(|c| spec.resolve(c));
+        // `address` may be null for dynamic channels; fall back to the channel's key.
+        let address = channel
+            .and_then(|c| c.get("address"))
+            .and_then(|a| a.as_str())
+            .map(|a| a.to_string())
+            .or_else(|| {
+                let reference = channel_ref?.get("$ref")?.as_str()?;
+                Some(reference.rsplit('/').next()?.replace("~1", "/").replace("~0", "~"))
+            })
+            .unwrap_or_else(|| id.clone());
+        let messages: Vec<&Value> = match operation.get("messages").and_then(|m| m.as_array()) {
+            Some(listed) => listed.iter().collect(),
+            None => channel
+                .and_then(|c| c.get("messages"))
+                .and_then(|m| m.as_object())
+                .map(|m| m.values().collect())
+                .unwrap_or_default(),
+        };
+        operations.push(Operation {
+            id: Some(id.as_str()),
+            action: operation.get("action").and_then(|a| a.as_str()).unwrap_or("send").to_string(),
+            channel: address,
+            channel_description: channel
+                .and_then(|c| c.get("description"))
+                .and_then(|v| v.as_str()),
+            operation,
+            messages,
+        });
+    }
+    operations
+}
+
+/// Component name of a message, following references through channels; else its `name`.
+fn message_name<'a>(spec: &Spec<'a>, message: &'a Value) -> Option<&'a str> {
+    let mut current = message;
+    for _ in 0..MAX_REF_HOPS {
+        let Some(reference) = current.get("$ref").and_then(|r| r.as_str()) else { break };
+        if let Some(name) = reference.strip_prefix(MESSAGE_PREFIX) {
+            return Some(name);
+        }
+        match reference.strip_prefix('#').and_then(|p| spec.root.pointer(p)) {
+            Some(next) => current = next,
+            None => break,
+        }
+    }
+    spec.resolve(message).get("name").and_then(|n| n.as_str())
+}
+
+fn render_message<'a>(writer: &mut SchemaWriter<'_, 'a>, message: &'a Value, out: &mut String) {
+    for key in ["title", "summary", "description"] {
+        if let Some(text) = message.get(key).and_then(|v| v.as_str()) {
+            out.push_str(&format!("{}\n\n", text.trim()));
+        }
+    }
+    if let Some(content_type) = message.get("contentType").and_then(|v| v.as_str()) {
+        out.push_str(&format!("Content type: {content_type}\n"));
+    }
+    if let Some(payload) = message.get("payload") {
+        out.push_str("\n### Payload\n\n");
+        writer.body(payload, out);
+    }
+    if let Some(headers) = message.get("headers") {
+        out.push_str("\n### Headers\n\n");
+        writer.body(headers, out);
+    }
+}
+
+#[async_trait]
+impl super::registry::Provider for AsyncApiProvider {
+    fn name(&self) -> String {
+        "asyncapi".into()
+    }
+
+    fn priority(&self) -> u8 {
+        5
+    }
+
+    async fn load_documents(&self) -> Vec<Document> {
+        if let Some(raw) = fetch_source(&self.source).await {
+            self.parse_spec(&raw)
+        } else {
+            Vec::new()
+        }
+    }
+}
@@ -0,0 +1,351 @@
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use mnemo_core::models::document::Document;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde_json::{Map, Value, json};
+
+use super::conversation::{Conversation, ConversationMessage, strip_quoted, title_from};
+use super::wiki::{ExportFiles, decode_entities};
+
+/// Slack markup: `<@U123>` mentions, `<#C123|general>` channels and `<url|label>` links.
+static SLACK_MARKUP_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"<([@#!]?)([^<>|]+)(?:\|([^<>]*))?>").unwrap());
+
+/// Message subtypes that are channel bookkeeping rather than conversation.
+const SKIPPED_SLACK_SUBTYPES: &[&str] = &[
+    "channel_join",
+    "channel_leave",
+    "channel_topic",
+    "channel_purpose",
+    "channel_name",
+    "channel_archive",
+    "channel_unarchive",
+    "group_join",
+    "group_leave",
+    "pinned_item",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChatFormat {
+    /// Workspace export: `users.json`, `channels.json` and one folder of daily JSON files per
+    /// channel.
+    Slack,
+    /// Bulk export: JSON lines of `channel`, `post` and `direct_post` records, replies nested
+    /// in their root post.
+    Mattermost,
+}
+
+impl ChatFormat {
+    fn source(self) -> &'static str {
+        match self {
+            ChatFormat::Slack => "slack",
+            ChatFormat::Mattermost => "mattermost",
+        }
+    }
+}
+
+/// Provider for Slack and Mattermost exports (directory, `.zip`, or a Mattermost `.jsonl`).
+///
+/// Each thread (Slack `thread_ts`, Mattermost root post with replies) becomes one document.
+/// Top-level messages without replies are grouped per channel and day.
+pub struct ChatExportProvider {
+    pub export_path: String,
+    pub format: ChatFormat,
+    pub namespace: String,
+}
+
+/// Messages of one thread or channel-day, before rendering.
+struct ChatThread {
+    channel: String,
+    title: Option<String>,
+    messages: Vec<ConversationMessage>,
+    extra: Map<String, Value>,
+}
+
+impl ChatExportProvider {
+    pub fn new(export_path: String, format: ChatFormat, namespace: String) -> Self {
+        Self { export_path, format, namespace }
+    }
+
+    pub fn load_documents(&self) -> Vec<Document> {
+        let source = self.format.source();
+        let threads = match self.format {
+            ChatFormat::Slack => self.open().map(|files| slack_threads(&files)),
+            ChatFormat::Mattermost => {
+                self.mattermost_lines().map(|lines| mattermost_threads(&lines))
+            }
+        };
+        let Some(threads) = threads else { return Vec::new() };
+        tracing::info!("{} export {}: {} threads", source, self.export_path, threads.len());
+
+        threads
+            .into_iter()
+            .filter_map(|(key, thread)| {
+                let title = thread
+                    .title
+                    .or_else(|| {
+                        thread.messages.iter().map(|m| title_from(&m.text)).find(|t| !t.is_empty())
+                    })
+                    .unwrap_or_else(|| format!("#{}", thread.channel));
+                Conversation {
+                    path: format!("{source}:{}/{key}", self.export_path),
+                    title,
+                    channel: Some(thread.channel),
+                    messages: thread.messages,
+                    extra: thread.extra,
+                }
+                .into_document(source, &self.namespace)
+            })
+            .collect()
+    }
+
+    fn open(&self) -> Option<ExportFiles> {
+        match ExportFiles::open(Path::new(&self.export_path)) {
+            Ok(files) => Some(files),
+            Err(e) => {
+                tracing::error!("Chat export unreadable ({}): {}", self.export_path, e);
+                None
+            }
+        }
+    }
+
+    /// Lines of a `.jsonl` file, or of every `.jsonl` file in an export directory or zip.
+    fn mattermost_lines(&self) -> Option<Vec<String>> {
+        if self.export_path.ends_with(".jsonl") {
+            return match std::fs::read_to_string(&self.export_path) {
+                Ok(text) => Some(text.lines().map(|l| l.to_string()).collect()),
+                Err(e) => {
+                    tracing::error!("Mattermost export unreadable ({}): {}", self.export_path, e);
+                    None
+                }
+            };
+        }
+        let files = self.open()?;
+        Some(
+            files
+                .paths()
+                .into_iter()
+                .filter(|p| p.ends_with(".jsonl"))
+                .filter_map(|p| files.text(p))
+                .flat_map(|text| text.lines().map(|l| l.to_string()).collect::<Vec<_>>())
+                .collect(),
+        )
+    }
+}
+
+/// Threads keyed by `{channel}/{thread_ts}`, and channel-days keyed by `{channel}/{date}`.
+fn slack_threads(files: &ExportFiles) -> BTreeMap<String, ChatThread> {
+    let json = |name: &str| -> Vec<Value> {
+        files
+            .paths()
+            .into_iter()
+            .find(|p| *p == name || p.ends_with(&format!("/{name}")))
+            .and_then(|p| files.text(p))
+            .and_then(|t| serde_json::from_str::<Vec<Value>>(&t).ok())
+            .unwrap_or_default()
+    };
+    let users: HashMap<String, String> = json("users.json")
+        .iter()
+        .filter_map(|u| Some((u.get("id")?.as_str()?.to_string(), slack_user_name(u)?)))
+        .collect();
+    let channel_ids: HashMap<String, String> = ["channels.json", "groups.json"]
+        .iter()
+        .flat_map(|name| json(name))
+        .filter_map(|c| {
+            Some((c.get("id")?.as_str()?.to_string(), c.get("name")?.as_str()?.to_string()))
+        })
+        .collect();
+
+    let mut threads: BTreeMap<String, ChatThread> = BTreeMap::new();
+    for path in files.paths() {
+        // `<channel>/<YYYY-MM-DD>.json`, possibly under a top-level export folder.
+        let mut segments = path.rsplit('/');
+        let (Some(file), Some(channel)) = (segments.next(), segments.next()) else { continue };
+        let Some(day) = file.strip_suffix(".json") else { continue };
+        if chrono::NaiveDate::parse_from_str(day, "%Y-%m-%d").is_err() {
+            continue;
+        }
+        let Some(messages) =
+            files.text(path).and_then(|t| serde_json::from_str::<Vec<Value>>(&t).ok())
+        else {
+            continue;
+        };
+        for message in &messages {
+            let subtype = message.get("subtype").and_then(|v| v.as_str()).unwrap_or_default();
+            if SKIPPED_SLACK_SUBTYPES.contains(&subtype) {
+                continue;
+            }
+            let str_field = |key: &str| message.get(key).and_then(|v| v.as_str());
+            let Some(ts) = str_field("ts") else { continue };
+            let author = str_field("user")
+                .and_then(|id| users.get(id).cloned())
+                .or_else(|| message.get("user_profile").and_then(slack_user_name))
+                .or_else(|| str_field("username").map(|s| s.to_string()))
+                .or_else(|| {
+                    message
+                        .pointer("/bot_profile/name")
+                        .and_then(|v| v.as_str())
+                        .map(|s| s.to_string())
+                })
+                .unwrap_or_else(|| "unknown".into());
+            let text = strip_quoted(&slack_text(
+                str_field("text").unwrap_or_default(),
+                &users,
+                &channel_ids,
+            ));
+            let (key, title) = match str_field("thread_ts") {
+                Some(thread_ts) => (format!("{channel}/{thread_ts}"), None),
+                None => (format!("{channel}/{day}"), Some(format!("#{channel} on {day}"))),
+            };
+            let thread = threads.entry(key).or_insert_with(|| {
+                let mut extra = Map::new();
+                match str_field("thread_ts") {
+                    Some(thread_ts) => extra.insert("thread_ts".into(), json!(thread_ts)),
+                    None => extra.insert("day".into(), json!(day)),
+                };
+                ChatThread { channel: format!("#{channel}"), title, messages: Vec::new(), extra }
+            });
+            thread.messages.push(ConversationMessage {
+                author,
+                author_id: None,
+                sent_at: slack_time(ts),
+                text,
+            });
+        }
+    }
+    threads
+}
+
+fn slack_user_name(user: &Value) -> Option<String> {
+    ["/profile/display_name", "/display_name", "/profile/real_name", "/real_name", "/name"]
+        .iter()
+        .filter_map(|p| user.pointer(p).and_then(|v| v.as_str()))
+        .map(|s| s.trim())
+        .find(|s| !s.is_empty())
+        .map(|s| s.to_string())
+}
+
+/// `ts` is seconds since the epoch with a microsecond fraction, e.g. `1714557600.000100`.
+fn slack_time(ts: &str) -> Option<DateTime<Utc>> {
+    let (secs, fraction) = ts.split_once('.').unwrap_or((ts, "0"));
+    let micros: u32 = format!("{fraction:0<6}").get(..6)?.parse().ok()?;
+    DateTime::from_timestamp(secs.parse().ok()?, micros * 1_000)
+}
+
+/// Plain text from Slack markup, with mentions resolved to names. Quotes arrive escaped as
+/// `&gt;` and are decoded here so quote stripping sees them.
+fn slack_text(
+    text: &str,
+    users: &HashMap<String, String>,
+    channels: &HashMap<String, String>,
+) -> String {
+    let text = SLACK_MARKUP_RE.replace_all(text, |caps: &regex::Captures| {
+        let target = &caps[2];
+        let label = caps.get(3).map(|l| l.as_str()).filter(|l| !l.is_empty());
+        match &caps[1] {
+            "@" => {
+                format!("@{}", label.or(users.get(target).map(|s| s.as_str())).unwrap_or(target))
+            }
+            "#" => {
+                format!("#{}", label.or(channels.get(target).map(|s| s.as_str())).unwrap_or(target))
+            }
+            // `<!here>`, `<!channel>`, `<!subteam^ID|@team>`.
+            "!" => label.map(|l| l.to_string()).unwrap_or_else(|| format!("@{target}")),
+            _ => label.unwrap_or(target).to_string(),
+        }
+    });
+    decode_entities(&text)
+}
+
+/// Threads keyed by `{team}/{channel}/{create_at}` of the root post; posts without replies
+/// are grouped per channel-day.
+fn mattermost_threads(lines: &[String]) -> BTreeMap<String, ChatThread> {
+    let mut threads: BTreeMap<String, ChatThread> = BTreeMap::new();
+    for line in lines {
+        let Ok(record) = serde_json::from_str::<Value>(line) else { continue };
+        let (post, team, channel) = match record.get("type").and_then(|v| v.as_str()) {
+            Some("post") => {
+                let Some(post) = record.get("post") else { continue };
+                let field = |key: &str| post.get(key).and_then(|v| v.as_str()).unwrap_or_default();
+                (post, field("team").to_string(), format!("~{}", field("channel")))
+            }
+            Some("direct_post") => {
+                let Some(post) = record.get("direct_post") else { continue };
+                let members: Vec<&str> = post
+                    .get("channel_members")
+                    .and_then(|m| m.as_array())
+                    .map(|m| m.iter().filter_map(|v| v.as_str()).collect())
+                    .unwrap_or_default();
+                (post, "direct".to_string(), format!("@{}", members.join(",")))
+            }
+            _ => continue,
+        };
+        let Some(root) = mattermost_message(post) else { continue };
+        let created = post.get("create_at").and_then(|v| v.as_i64()).unwrap_or_default();
+        let replies: Vec<ConversationMessage> = post
+            .get("replies")
+            .and_then(|r| r.as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(mattermost_message)
+            .collect();
+
+        let channel_key = channel.trim_start_matches(['~', '@']);
+        let (key, title, extra) = if replies.is_empty() {
+            let day = root.sent_at.map(|t| t.format("%Y-%m-%d").to_string()).unwrap_or_default();
+            (
+                format!("{team}/{channel_key}/{day}"),
+                Some(format!("{channel} on {day}")),
+                json!({ "day": day }),
+            )
+        } else {
+            (format!("{team}/{channel_key}/{created}"), None, json!({ "root_create_at": created }))
+        };
+        let thread = threads.entry(key).or_insert_with(|| {
+            let mut extra = extra.as_object().cloned().unwrap_or_default();
+            extra.insert("team".into(), json!(team));
+            ChatThread { channel: channel.clone(), title, messages: Vec::new(), extra }
+        });
+        thread.messages.push(root);
+        thread.messages.extend(replies);
+    }
+    threads
+}
+
+fn mattermost_message(post: &Value) -> Option<ConversationMessage> {
+    let text = post.get("message")?.as_str()?;
+    Some(ConversationMessage {
+        author: post.get("user").and_then(|v| v.as_str()).unwrap_or("unknown").to_string(),
+        author_id: None,
+        sent_at: post
+            .get("create_at")
+            .and_then(|v| v.as_i64())
+            .and_then(DateTime::from_timestamp_millis),
+        text: strip_quoted(text),
+    })
+}
+
+#[async_trait::async_trait]
+impl super::registry::Provider for ChatExportProvider {
+    fn name(&self) -> String {
+        self.format.source().into()
+    }
+
+    fn priority(&self) -> u8 {
+        4
+    }
+
+    async fn load_documents(&self) -> Vec<Document> {
+        ChatExportProvider::load_documents(self)
+    }
+
+    fn prune_scope(&self) -> Option<super::registry::PruneScope> {
+        Some(super::registry::PruneScope {
+            namespace: self.namespace.clone(),
+            prefix: format!("{}:{}/", self.format.source(), self.export_path),
+        })
+    }
+}
@@ -0,0 +1,345 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use chrono::NaiveDateTime;
+use mnemo_core::models::document::Document;
+use once_cell::sync::Lazy;
+use quick_xml::Reader;
+use quick_xml::events::{BytesStart, Event};
+use regex::Regex;
+use serde_json::{Map, json};
+
+use super::wiki::{
+    ExportFiles, WikiAttachment, WikiPage, decode_entities, html_to_markdown, into_documents,
+    resolve_link,
+};
+
+static TITLE_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?is)<title>(.*?)</title>").unwrap());
+static BREADCRUMBS_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"(?is)<ol[^>]*id="breadcrumbs"[^>]*>(.*?)</ol>"#).unwrap());
+static HREF_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r#"(?i)href="([^"]*)""#).unwrap());
+
+/// Provider for a Confluence space export, either the HTML export or the XML export
+/// (`entities.xml` plus `attachments/`), as a directory or `.zip`.
+pub struct ConfluenceProvider {
+    pub export_path: String,
+    pub namespace: String,
+}
+
+impl ConfluenceProvider {
+    pub fn new(export_path: String, namespace: String) -> Self {
+        Self { export_path, namespace }
+    }
+
+    pub fn load_documents(&self) -> Vec<Document> {
+        let files = match ExportFiles::open(Path::new(&self.export_path)) {
+            Ok(files) => files,
+            Err(e) => {
+                tracing::error!("Confluence export unreadable ({}): {}", self.export_path, e);
+                return Vec::new();
+            }
+        };
+        let entities = files
+            .paths()
+            .into_iter()
+            .find(|p| *p == "entities.xml" || p.ends_with("/entities.xml"));
+        let pages = match entities {
+            Some(entities) => xml_pages(&files, entities),
+            None => html_pages(&files),
+        };
+        tracing::info!("Confluence export {}: {} pages", self.export_path, pages.len());
+        into_documents("confluence", &self.export_path, &self.namespace, &files, pages)
+    }
+}
+
+/// Pages of an HTML export: one `Title_<id>.html` per page, with the ancestry in the
+/// breadcrumb list and attachments under `attachments/<page id>/`.
+fn html_pages(files: &ExportFiles) -> Vec<WikiPage> {
+    let mut pages = Vec::new();
+    for key in files.paths().into_iter().filter(|p| p.ends_with(".html")) {
+        // `index.html` is the generated space overview, not a page.
+        if key == "index.html" || key.ends_with("/index.html") {
+            continue;
+        }
+        let Some(html) = files.text(key) else { continue };
+        let raw_title = TITLE_RE
+            .captures(&html)
+            .map(|c| decode_entities(c[1].trim()))
+            .unwrap_or_else(|| super::wiki::file_stem(key).to_string());
+        // Titles read "Space : Page".
+        let (space, title) = match raw_title.split_once(" : ") {
+            Some((space, title)) => (Some(space.trim().to_string()), title.trim().to_string()),
+            None => (None, raw_title.clone()),
+        };
+
+        let parent = BREADCRUMBS_RE.captures(&html).and_then(|c| {
+            HREF_RE
+                .captures_iter(&c[1])
+                .filter_map(|h| resolve_link(key, &h[1]))
+                .filter(|k| !k.ends_with("index.html") && k != key && files.contains(k))
+                .last()
+        });
+
+        let text = html_to_markdown(main_content(&html));
+        let mut links = Vec::new();
+        let mut attachments = Vec::new();
+        let hrefs = text
+            .hrefs
+            .iter()
+            .cloned()
+            .chain(HREF_RE.captures_iter(&html).map(|c| c[1].to_string()));
+        for href in hrefs {
+            let Some(target) = resolve_link(key, &href) else { continue };
+            if !files.contains(&target) || target == key {
+                continue;
+            }
+            if target.ends_with(".html") {
+                if !target.ends_with("index.html") && !links.contains(&target) {
+                    links.push(target);
+                }
+            } else if target.contains("attachments/")
+                && !attachments.iter().any(|a: &WikiAttachment| a.file == target)
+            {
+                attachments.push(WikiAttachment { key: target.clone(), file: target });
+            }
+        }
+        // Breadcrumb links are ancestry, not references.
+        links.retain(|l| Some(l) != parent.as_ref());
+
+        let mut extra = Map::new();
+        if let Some(space) = &space {
+            extra.insert("confluence_space".into(), json!(space));
+        }
+        pages.push(WikiPage {
+            key: key.to_string(),
+            title,
+            markdown: text.markdown,
+            parent,
+            crumb_prefix: space.into_iter().collect(),
+            links,
+            attachments,
+            modified_at: files.modified(key),
+            extra,
+        });
+    }
+    pages
+}
+
+/// Page body of an HTML export page, without the header, breadcrumbs and attachment list.
+fn main_content(html: &str) -> &str {
+    let Some(start) = html.find("id=\"main-content\"") else { return html };
+    let start = html[start..].find('>').map(|i| start + i + 1).unwrap_or(start);
+    let end = ["<div class=\"pageSection group\">", "<div id=\"footer\""]
+        .iter()
+        .filter_map(|marker| html[start..].find(marker).map(|i| start + i))
+        .min()
+        .unwrap_or(html.len());
+    &html[start..end]
+}
+
+/// A Hibernate object from `entities.xml`: its class, id and scalar or reference properties.
+struct XmlObject {
+    class: String,
+    id: String,
+    props: HashMap<String, String>,
+}
+
+impl XmlObject {
+    fn prop(&self, name: &str) -> Option<&str> {
+        self.props.get(name).map(|v| v.trim()).filter(|v| !v.is_empty())
+    }
+
+    /// Current version of live content; historical versions point at their `originalVersion`.
+    fn is_current(&self) -> bool {
+        self.prop("originalVersion").is_none()
+            && self.prop("contentStatus").is_none_or(|s| s == "current")
+    }
+}
+
+fn parse_entities(xml: &str) -> Vec<XmlObject> {
+    let mut reader = Reader::from_str(xml);
+    let mut objects = Vec::new();
+    let mut current: Option<XmlObject> = None;
+    // Element names below the current `<object>`.
+    let mut path: Vec<Vec<u8>> = Vec::new();
+    let mut prop: Option<String> = None;
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) => {
+                let name = e.local_name().as_ref().to_vec();
+                if current.is_none() {
+                    if name == b"object" {
+                        let class = attr(&e, b"class").unwrap_or_default();
+                        current =
+                            Some(XmlObject { class, id: String::new(), props: HashMap::new() });
+                        path.clear();
+                    }
+                    continue;
+                }
+                if path.is_empty() && name == b"property" {
+                    prop = attr(&e, b"name");
+                }
+                path.push(name);
+            }
+            Ok(Event::End(_)) if current.is_some() => {
+                if path.pop().is_none() {
+                    objects.extend(current.take());
+                } else if path.is_empty() {
+                    prop = None;
+                }
+            }
+            Ok(Event::Text(t)) => {
+                if let Ok(text) = t.unescape() {
+                    append_value(&mut current, &path, &prop, &text);
+                }
+            }
+            Ok(Event::CData(t)) => {
+                let text = String::from_utf8_lossy(&t.into_inner()).into_owned();
+                append_value(&mut current, &path, &prop, &text);
+            }
+            Ok(Event::Eof) | Err(_) => break,
+            _ => {}
+        }
+    }
+    objects
+}
+
+/// Record text for the object id, a scalar property, or the id of a referenced object.
+fn append_value(
+    current: &mut Option<XmlObject>,
+    path: &[Vec<u8>],
+    prop: &Option<String>,
+    text: &str,
+) {
+    let Some(object) = current.as_mut() else { return };
+    let names: Vec<&[u8]> = path.iter().map(|p| p.as_slice()).collect();
+    match (names.as_slice(), prop) {
+        ([b"id"], _) => object.id.push_str(text.trim()),
+        ([b"property"] | [b"property", b"id"], Some(prop)) => {
+            object.props.entry(prop.clone()).or_default().push_str(text)
+        }
+        _ => {}
+    }
+}
+
+fn attr(e: &BytesStart, local: &[u8]) -> Option<String> {
+    e.attributes()
+        .flatten()
+        .find(|a| a.key.local_name().as_ref() == local)
+        .and_then(|a| a.unescape_value().ok())
+        .map(|v| v.into_owned())
+}
+
+/// Pages of an XML export. Bodies are storage-format XHTML; internal links name their target
+/// by title and attachments live at `attachments/<page id>/<attachment id>/<version>`.
+fn xml_pages(files: &ExportFiles, entities: &str) -> Vec<WikiPage> {
+    let Some(xml) = files.text(entities) else { return Vec::new() };
+    let prefix = entities.strip_suffix("entities.xml").unwrap_or_default();
+    let objects = parse_entities(&xml);
+
+    let spaces: HashMap<&str, &str> = objects
+        .iter()
+        .filter(|o| o.class == "Space")
+        .filter_map(|o| Some((o.id.as_str(), o.prop("name")?)))
+        .collect();
+    let pages: Vec<&XmlObject> =
+        objects.iter().filter(|o| o.class == "Page" && o.is_current()).collect();
+    let title_to_id: HashMap<&str, &str> =
+        pages.iter().filter_map(|p| Some((p.prop("title")?, p.id.as_str()))).collect();
+    let mut bodies: HashMap<&str, &str> = HashMap::new();
+    for body in objects.iter().filter(|o| o.class == "BodyContent") {
+        if let (Some(content), Some(text)) = (body.prop("content"), body.props.get("body")) {
+            bodies.insert(content, text);
+        }
+    }
+    let mut page_attachments: HashMap<&str, Vec<(&str, &str)>> = HashMap::new();
+    for attachment in objects.iter().filter(|o| o.class == "Attachment" && o.is_current()) {
+        let container = attachment.prop("containerContent").or_else(|| attachment.prop("content"));
+        if let (Some(container), Some(title)) = (container, attachment.prop("title")) {
+            page_attachments.entry(container).or_default().push((attachment.id.as_str(), title));
+        }
+    }
+    // (page id, attachment id) -> newest stored version.
+    let mut attachment_files: HashMap<(String, String), (u64, String)> = HashMap::new();
+    for path in files.paths() {
+        let Some(rest) = path.strip_prefix(prefix).and_then(|p| p.strip_prefix("attachments/"))
+        else {
+            continue;
+        };
+        let parts: Vec<&str> = rest.split('/').collect();
+        let [page_id, attachment_id, version] = parts.as_slice() else { continue };
+        let version = version.parse::<u64>().unwrap_or(0);
+        let entry = attachment_files
+            .entry((page_id.to_string(), attachment_id.to_string()))
+            .or_insert((version, path.to_string()));
+        if version > entry.0 {
+            *entry = (version, path.to_string());
+        }
+    }
+
+    pages
+        .iter()
+        .map(|page| {
+            let text =
+                bodies.get(page.id.as_str()).map(|b| html_to_markdown(b)).unwrap_or_default();
+            let links = text
+                .page_titles
+                .iter()
+                .filter_map(|t| title_to_id.get(t.as_str()))
+                .map(|id| format!("pages/{id}"))
+                .collect();
+            let attachments = page_attachments
+                .get(page.id.as_str())
+                .into_iter()
+                .flatten()
+                .filter_map(|(attachment_id, title)| {
+                    let (_, file) =
+                        attachment_files.get(&(page.id.clone(), attachment_id.to_string()))?;
+                    Some(WikiAttachment {
+                        key: format!("attachments/{}/{}/{}", page.id, attachment_id, title),
+                        file: file.clone(),
+                    })
+                })
+                .collect();
+            let space = page.prop("space").and_then(|id| spaces.get(id)).map(|s| s.to_string());
+            let mut extra = Map::new();
+            extra.insert("confluence_page_id".into(), json!(page.id));
+            if let Some(space) = &space {
+                extra.insert("confluence_space".into(), json!(space));
+            }
+            WikiPage {
+                key: format!("pages/{}", page.id),
+                title: page.prop("title").unwrap_or("Untitled").to_string(),
+                markdown: text.markdown,
+                parent: page.prop("parent").map(|id| format!("pages/{id}")),
+                crumb_prefix: space.into_iter().collect(),
+                links,
+                attachments,
+                modified_at: page
+                    .prop("lastModificationDate")
+                    .and_then(|d| NaiveDateTime::parse_from_str(d, "%Y-%m-%d %H:%M:%S%.f").ok())
+                    .map(|d| d.and_utc()),
+                extra,
+            }
+        })
+        .collect()
+}
+
+#[async_trait::async_trait]
+impl super::registry::Provider for ConfluenceProvider {
+    fn name(&self) -> String {
+        "confluence".into()
+    }
+
+    fn priority(&self) -> u8 {
+        4
+    }
+
+    async fn load_documents(&self) -> Vec<Document> {
+        ConfluenceProvider::load_documents(self)
+    }
+
+    fn prune_scope(&self) -> Option<super::registry::PruneScope> {
+        Some(super::wiki::export_scope("confluence", &self.export_path, &self.namespace))
+    }
+}
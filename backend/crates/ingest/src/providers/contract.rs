@@ -0,0 +1,77 @@
+use mnemo_core::models::document::Document;
+use mnemo_core::utils::text_normalizer::TextNormalizer;
+use serde_json::{Map, Value};
+use sha2::{Digest, Sha256};
+use walkdir::WalkDir;
+
+/// Text of a contract given as a local file or an `http(s)` URL.
+pub(crate) async fn fetch_source(source: &str) -> Option<String> {
+    if source.starts_with("http://") || source.starts_with("https://") {
+        let client = reqwest::Client::new();
+        client.get(source).send().await.ok()?.text().await.ok()
+    } else {
+        std::fs::read_to_string(source).ok()
+    }
+}
+
+/// Contract files as `(path, text)`: a URL or single file, or every file under a directory
+/// with one of `extensions`, in path order.
+pub(crate) async fn fetch_sources(source: &str, extensions: &[&str]) -> Vec<(String, String)> {
+    let root = std::path::Path::new(source);
+    if !root.is_dir() {
+        return fetch_source(source)
+            .await
+            .map(|text| (source.to_string(), text))
+            .into_iter()
+            .collect();
+    }
+    WalkDir::new(root)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter(|e| e.file_type().is_file())
+        .filter(|e| {
+            e.path()
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .is_some_and(|ext| extensions.contains(&ext.to_ascii_lowercase().as_str()))
+        })
+        .filter_map(|e| {
+            let text = std::fs::read_to_string(e.path()).ok()?;
+            Some((e.path().display().to_string(), text))
+        })
+        .collect()
+}
+
+/// JSON or YAML spec text as a JSON value; `Null` when it is neither.
+pub(crate) fn parse_structured(raw: &str) -> Value {
+    serde_json::from_str(raw).unwrap_or_else(|_| {
+        serde_yaml::from_str(raw)
+            .map(|v: serde_yaml::Value| serde_json::to_value(v).unwrap_or(Value::Null))
+            .unwrap_or(Value::Null)
+    })
+}
+
+/// A normalized contract document (operation, message or type).
+pub(crate) fn contract_document(
+    path: String,
+    content: &str,
+    file_type: &str,
+    meta: Map<String, Value>,
+    namespace: &str,
+) -> Document {
+    let normalized = TextNormalizer::normalize(content);
+    let mut hasher = Sha256::new();
+    hasher.update(normalized.as_bytes());
+    Document {
+        path,
+        fingerprint: format!("{:x}", hasher.finalize()),
+        file_size: Some(normalized.len() as i64),
+        content: normalized,
+        namespace: namespace.to_string(),
+        modified_at: None,
+        file_type: Some(file_type.to_string()),
+        language: Some(file_type.to_string()),
+        metadata: Some(Value::Object(meta)),
+    }
+}
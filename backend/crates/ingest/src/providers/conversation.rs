@@ -0,0 +1,135 @@
+use chrono::{DateTime, Utc};
+use mnemo_core::models::document::Document;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde_json::{Map, Value, json};
+use sha2::{Digest, Sha256};
+
+static ORIGINAL_MESSAGE_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)^-{2,}\s*(original|forwarded) message\s*-{2,}$").unwrap());
+static ATTRIBUTION_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"^On\b.*\bwrote:$").unwrap());
+
+/// Titles longer than this are cut at a word boundary.
+const MAX_TITLE_CHARS: usize = 80;
+
+/// One message of a mail or chat thread.
+pub(crate) struct ConversationMessage {
+    pub author: String,
+    /// Stable identity of the author, such as a mail address; `author` when absent.
+    pub author_id: Option<String>,
+    pub sent_at: Option<DateTime<Utc>>,
+    pub text: String,
+}
+
+/// A reconstructed thread, rendered as one Markdown document with a section per message.
+pub(crate) struct Conversation {
+    pub path: String,
+    pub title: String,
+    pub channel: Option<String>,
+    pub messages: Vec<ConversationMessage>,
+    /// Source-specific metadata (subject, message ids, team).
+    pub extra: Map<String, Value>,
+}
+
+impl Conversation {
+    /// `None` when every message is empty after quote stripping.
+    pub(crate) fn into_document(mut self, source: &str, namespace: &str) -> Option<Document> {
+        self.messages.retain(|m| !m.text.trim().is_empty());
+        if self.messages.is_empty() {
+            return None;
+        }
+        self.messages.sort_by_key(|m| m.sent_at);
+
+        let mut participants: Vec<String> = Vec::new();
+        let mut participant_ids: Vec<String> = Vec::new();
+        for message in &self.messages {
+            if !participants.contains(&message.author) {
+                participants.push(message.author.clone());
+            }
+            let id = message.author_id.as_ref().unwrap_or(&message.author);
+            if !participant_ids.contains(id) {
+                participant_ids.push(id.clone());
+            }
+        }
+        let started_at = self.messages.iter().find_map(|m| m.sent_at);
+        let last_at = self.messages.iter().rev().find_map(|m| m.sent_at);
+
+        let mut content = format!("# {}\n\n", self.title);
+        if let Some(channel) = &self.channel {
+            content.push_str(&format!("Channel: {channel}\n\n"));
+        }
+        for message in &self.messages {
+            match message.sent_at {
+                Some(at) => content.push_str(&format!(
+                    "## {} ({})\n\n",
+                    message.author,
+                    at.format("%Y-%m-%d %H:%M UTC")
+                )),
+                None => content.push_str(&format!("## {}\n\n", message.author)),
+            }
+            content.push_str(message.text.trim());
+            content.push_str("\n\n");
+        }
+        let content = content.trim_end().to_string();
+
+        let mut meta = self.extra;
+        meta.insert("conversation_source".into(), json!(source));
+        meta.insert("title".into(), json!(self.title));
+        meta.insert("channel".into(), json!(self.channel));
+        meta.insert("participants".into(), json!(participants));
+        // Person nodes are keyed by these, so one sender under two display names stays one node.
+        meta.insert("participant_ids".into(), json!(participant_ids));
+        meta.insert("message_count".into(), json!(self.messages.len()));
+        meta.insert("started_at".into(), json!(started_at.map(|t| t.to_rfc3339())));
+        meta.insert("last_message_at".into(), json!(last_at.map(|t| t.to_rfc3339())));
+
+        let mut hasher = Sha256::new();
+        hasher.update(content.as_bytes());
+        Some(Document {
+            path: self.path,
+            fingerprint: format!("{:x}", hasher.finalize()),
+            file_size: Some(content.len() as i64),
+            content,
+            namespace: namespace.to_string(),
+            modified_at: last_at,
+            file_type: Some(source.to_string()),
+            language: Some("markdown".into()),
+            metadata: Some(Value::Object(meta)),
+        })
+    }
+}
+
+/// Drop quoted replies: `>` lines, the "On ... wrote:" line introducing them, and everything
+/// from an "Original Message" separator on.
+pub(crate) fn strip_quoted(text: &str) -> String {
+    let lines: Vec<&str> = text.lines().collect();
+    let mut kept = Vec::new();
+    for (i, line) in lines.iter().enumerate() {
+        let trimmed = line.trim();
+        if ORIGINAL_MESSAGE_RE.is_match(trimmed) {
+            break;
+        }
+        if trimmed.starts_with('>') {
+            continue;
+        }
+        if ATTRIBUTION_RE.is_match(trimmed) {
+            let next = lines[i + 1..].iter().map(|l| l.trim()).find(|l| !l.is_empty());
+            if next.is_none_or(|l| l.starts_with('>')) {
+                continue;
+            }
+        }
+        kept.push(*line);
+    }
+    kept.join("\n").trim().to_string()
+}
+
+/// First line of `text`, shortened to a title.
+pub(crate) fn title_from(text: &str) -> String {
+    let line = text.lines().map(|l| l.trim()).find(|l| !l.is_empty()).unwrap_or_default();
+    if line.chars().count() <= MAX_TITLE_CHARS {
+        return line.to_string();
+    }
+    let cut: String = line.chars().take(MAX_TITLE_CHARS).collect();
+    let cut = cut.rsplit_once(' ').map(|(head, _)| head).unwrap_or(&cut);
+    format!("{cut}…")
+}
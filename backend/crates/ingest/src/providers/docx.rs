@@ -1,9 +1,15 @@
+use std::collections::{HashMap, HashSet};
+use std::io::Read;
+
 use mnemo_core::models::document::Document;
 use mnemo_core::utils::text_normalizer::TextNormalizer;
+use quick_xml::Reader;
+use quick_xml::events::{BytesStart, Event};
+use serde_json::{Map, Value, json};
 use sha2::{Digest, Sha256};
-use std::io::Read;
 
-/// Simple DOCX provider that extracts paragraph text from document.xml.
+/// DOCX provider that renders `word/document.xml` as Markdown, keeping headings, lists and
+/// tables.
 pub struct DocxProvider {
     pub paths: Vec<String>,
     pub namespace: String,
@@ -17,97 +23,412 @@ impl DocxProvider {
     pub fn load_documents(&self) -> Vec<Document> {
         let mut docs = Vec::new();
         for path in &self.paths {
-            if let Ok(file) = std::fs::File::open(path) {
-                if let Ok(mut zip) = zip::ZipArchive::new(file) {
-                    let (author, created) = read_core_props(&mut zip);
-                    if let Ok(mut doc_xml) = zip.by_name("word/document.xml") {
-                        let mut xml = String::new();
-                        let _ = doc_xml.read_to_string(&mut xml);
-                        let text = extract_text_from_docx_xml(&xml);
-                        let text = TextNormalizer::normalize(&text);
-                        let mut hasher = Sha256::new();
-                        hasher.update(text.as_bytes());
-                        docs.push(Document {
-                            path: path.clone(),
-                            content: text,
-                            fingerprint: format!("{:x}", hasher.finalize()),
-                            namespace: self.namespace.clone(),
-                            modified_at: None,
-                            file_size: None,
-                            file_type: Some("docx".into()),
-                            language: Some("docx".into()),
-                            metadata: Some(serde_json::json!({
-                                "author": author,
-                                "created": created
-                            })),
-                        });
+            let Ok(bytes) = std::fs::read(path) else {
+                continue;
+            };
+            let Some(text) = extract_docx_text(&bytes) else {
+                tracing::warn!("Skipping unreadable docx file: {}", path);
+                continue;
+            };
+            let mut hasher = Sha256::new();
+            hasher.update(text.as_bytes());
+            docs.push(Document {
+                path: path.clone(),
+                content: text,
+                fingerprint: format!("{:x}", hasher.finalize()),
+                namespace: self.namespace.clone(),
+                modified_at: None,
+                file_size: Some(bytes.len() as i64),
+                file_type: Some("docx".into()),
+                language: Some("docx".into()),
+                metadata: Some(Value::Object(docx_properties(&bytes))),
+            });
+        }
+        docs
+    }
+}
+
+/// Render a DOCX body as Markdown, or `None` if the archive has no readable document body.
+///
+/// Paragraph text is normalized as it is collected, so the result must not be normalized
+/// again: that would collapse list indentation and deep heading markers.
+pub fn extract_docx_text(bytes: &[u8]) -> Option<String> {
+    let mut zip = zip::ZipArchive::new(std::io::Cursor::new(bytes)).ok()?;
+    let document = read_part(&mut zip, "word/document.xml")?;
+    let styles = read_part(&mut zip, "word/styles.xml").map(|xml| heading_styles(&xml));
+    let numbering = read_part(&mut zip, "word/numbering.xml").map(|xml| Numbering::parse(&xml));
+    render_document(&document, &styles.unwrap_or_default(), &numbering.unwrap_or_default())
+}
+
+/// Document properties from `docProps/core.xml` and `docProps/app.xml`.
+pub fn docx_properties(bytes: &[u8]) -> Map<String, Value> {
+    let mut props = Map::new();
+    let Ok(mut zip) = zip::ZipArchive::new(std::io::Cursor::new(bytes)) else {
+        return props;
+    };
+    if let Some(xml) = read_part(&mut zip, "docProps/core.xml") {
+        for (name, value) in leaf_values(&xml) {
+            let key = match name.as_str() {
+                "creator" => "author",
+                "title" => "title",
+                "subject" => "subject",
+                "description" => "description",
+                "keywords" => "keywords",
+                "category" => "category",
+                "language" => "language",
+                "lastModifiedBy" => "last_modified_by",
+                "revision" => "revision",
+                "created" => "created",
+                "modified" => "modified",
+                _ => continue,
+            };
+            props.insert(key.into(), json!(value));
+        }
+    }
+    if let Some(xml) = read_part(&mut zip, "docProps/app.xml") {
+        for (name, value) in leaf_values(&xml) {
+            match name.as_str() {
+                "Application" => props.insert("application".into(), json!(value)),
+                "Company" => props.insert("company".into(), json!(value)),
+                "Pages" | "Words" => {
+                    let count =
+                        value.parse::<u64>().map(Value::from).unwrap_or_else(|_| json!(value));
+                    props.insert(name.to_ascii_lowercase(), count)
+                }
+                _ => continue,
+            };
+        }
+    }
+    props
+}
+
+fn read_part<R: Read + std::io::Seek>(zip: &mut zip::ZipArchive<R>, name: &str) -> Option<String> {
+    let mut part = zip.by_name(name).ok()?;
+    let mut xml = String::new();
+    part.read_to_string(&mut xml).ok()?;
+    Some(xml)
+}
+
+/// Value of the `w:val`-style attribute on an element.
+fn val_attr(e: &BytesStart) -> Option<String> {
+    attr(e, b"val")
+}
+
+fn attr(e: &BytesStart, local: &[u8]) -> Option<String> {
+    e.attributes()
+        .flatten()
+        .find(|a| a.key.local_name().as_ref() == local)
+        .and_then(|a| a.unescape_value().ok())
+        .map(|v| v.into_owned())
+}
+
+/// Heading level implied by a style name or id such as "heading 2", "Heading2" or "Title".
+fn heading_level_from_name(name: &str) -> Option<usize> {
+    let lower = name.to_ascii_lowercase();
+    if lower == "title" {
+        return Some(1);
+    }
+    let level = lower.strip_prefix("heading")?.trim().parse::<usize>().ok()?;
+    (1..=9).contains(&level).then_some(level)
+}
+
+/// Map paragraph style ids to heading levels, using the style name or its outline level.
+fn heading_styles(xml: &str) -> HashMap<String, usize> {
+    let mut headings = HashMap::new();
+    let mut reader = Reader::from_str(xml);
+    let mut current: Option<(String, Option<usize>)> = None;
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) if e.local_name().as_ref() == b"style" => {
+                current = attr(&e, b"styleId").map(|id| {
+                    let level = heading_level_from_name(&id);
+                    (id, level)
+                });
+            }
+            Ok(Event::Empty(e)) => {
+                let Some((_, level)) = current.as_mut() else { continue };
+                match e.local_name().as_ref() {
+                    b"name" => {
+                        if let Some(by_name) =
+                            val_attr(&e).and_then(|n| heading_level_from_name(&n))
+                        {
+                            *level = Some(by_name);
+                        }
                     }
+                    b"outlineLvl" if level.is_none() => {
+                        *level = val_attr(&e)
+                            .and_then(|v| v.parse::<usize>().ok())
+                            .filter(|v| *v < 9)
+                            .map(|v| v + 1);
+                    }
+                    _ => {}
                 }
             }
+            Ok(Event::End(e)) if e.local_name().as_ref() == b"style" => {
+                if let Some((id, Some(level))) = current.take() {
+                    headings.insert(id, level);
+                }
+            }
+            Ok(Event::Eof) | Err(_) => break,
+            _ => {}
         }
-        docs
     }
+    headings
 }
 
-fn extract_text_from_docx_xml(xml: &str) -> String {
-    // DOCX paragraphs are in <w:p> with runs <w:t>. This is a simple textual scrape.
-    let mut out = String::new();
-    let mut inside_t = false;
-    let mut buf = String::new();
-    for c in xml.chars() {
-        match c {
-            '<' => {
-                if inside_t && !buf.trim().is_empty() {
-                    out.push_str(&buf);
+/// List definitions from `numbering.xml`, used to tell bullets from numbered items.
+#[derive(Default)]
+struct Numbering {
+    /// `numId` -> `abstractNumId`.
+    instances: HashMap<String, String>,
+    /// `(abstractNumId, ilvl)` pairs that use a numeric format.
+    ordered: HashSet<(String, String)>,
+}
+
+impl Numbering {
+    fn parse(xml: &str) -> Self {
+        let mut numbering = Self::default();
+        let mut reader = Reader::from_str(xml);
+        let mut abstract_id: Option<String> = None;
+        let mut level: Option<String> = None;
+        let mut num_id: Option<String> = None;
+        loop {
+            match reader.read_event() {
+                Ok(Event::Start(e)) => match e.local_name().as_ref() {
+                    b"abstractNum" => abstract_id = attr(&e, b"abstractNumId"),
+                    b"lvl" => level = attr(&e, b"ilvl"),
+                    b"num" => num_id = attr(&e, b"numId"),
+                    _ => {}
+                },
+                Ok(Event::Empty(e)) => match e.local_name().as_ref() {
+                    b"numFmt" => {
+                        let ordered = val_attr(&e).is_some_and(|f| f != "bullet" && f != "none");
+                        if let (true, Some(a), Some(l)) = (ordered, &abstract_id, &level) {
+                            numbering.ordered.insert((a.clone(), l.clone()));
+                        }
+                    }
+                    b"abstractNumId" => {
+                        if let (Some(n), Some(a)) = (&num_id, val_attr(&e)) {
+                            numbering.instances.insert(n.clone(), a);
+                        }
+                    }
+                    _ => {}
+                },
+                Ok(Event::End(e)) => match e.local_name().as_ref() {
+                    b"abstractNum" => abstract_id = None,
+                    b"lvl" => level = None,
+                    b"num" => num_id = None,
+                    _ => {}
+                },
+                Ok(Event::Eof) | Err(_) => break,
+                _ => {}
+            }
+        }
+        numbering
+    }
+
+    fn is_ordered(&self, num_id: &str, level: &str) -> bool {
+        self.instances
+            .get(num_id)
+            .is_some_and(|a| self.ordered.contains(&(a.clone(), level.to_string())))
+    }
+}
+
+#[derive(Default)]
+struct Paragraph {
+    text: String,
+    style: Option<String>,
+    num_id: Option<String>,
+    list_level: Option<String>,
+}
+
+/// One rendered top-level block; consecutive list items are joined without a blank line.
+struct Block {
+    markdown: String,
+    list_item: bool,
+}
+
+#[derive(Default)]
+struct Table {
+    rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    fn current_cell(&mut self) -> Option<&mut String> {
+        self.rows.last_mut().and_then(|row| row.last_mut())
+    }
+
+    fn render(&self) -> Option<String> {
+        let width = self.rows.iter().map(Vec::len).max().filter(|w| *w > 0)?;
+        let line = |row: &[String]| {
+            let cells = (0..width)
+                .map(|i| row.get(i).map(|c| c.replace('|', "\\|")).unwrap_or_default())
+                .collect::<Vec<_>>();
+            format!("| {} |", cells.join(" | "))
+        };
+        let mut lines = vec![line(&self.rows[0]), format!("|{}", " --- |".repeat(width))];
+        lines.extend(self.rows[1..].iter().map(|row| line(row)));
+        Some(lines.join("\n"))
+    }
+
+    /// Plain text of a table nested inside another table's cell.
+    fn flatten(&self) -> String {
+        self.rows.iter().flatten().filter(|c| !c.is_empty()).cloned().collect::<Vec<_>>().join(" ")
+    }
+}
+
+fn render_document(
+    xml: &str,
+    headings: &HashMap<String, usize>,
+    numbering: &Numbering,
+) -> Option<String> {
+    let mut reader = Reader::from_str(xml);
+    let mut blocks: Vec<Block> = Vec::new();
+    // Stacks: text boxes nest paragraphs and tables can nest inside cells.
+    let mut paragraphs: Vec<Paragraph> = Vec::new();
+    let mut tables: Vec<Table> = Vec::new();
+    let mut in_text = false;
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) => match e.local_name().as_ref() {
+                b"p" => paragraphs.push(Paragraph::default()),
+                b"t" => in_text = true,
+                b"tbl" => tables.push(Table::default()),
+                b"tr" => {
+                    if let Some(table) = tables.last_mut() {
+                        table.rows.push(Vec::new());
+                    }
+                }
+                b"tc" => {
+                    if let Some(row) = tables.last_mut().and_then(|t| t.rows.last_mut()) {
+                        row.push(String::new());
+                    }
+                }
+                _ => {}
+            },
+            Ok(Event::Empty(e)) => {
+                let Some(paragraph) = paragraphs.last_mut() else { continue };
+                match e.local_name().as_ref() {
+                    b"pStyle" => paragraph.style = val_attr(&e),
+                    b"numId" => paragraph.num_id = val_attr(&e),
+                    b"ilvl" => paragraph.list_level = val_attr(&e),
+                    b"tab" => paragraph.text.push(' '),
+                    b"br" | b"cr" => paragraph.text.push('\n'),
+                    _ => {}
                 }
-                buf.clear();
-                inside_t = false;
             }
-            '>' => {
-                let tag = buf.clone();
-                if tag.starts_with("w:t") {
-                    inside_t = true;
-                } else if tag.starts_with("/w:t") {
-                    inside_t = false;
-                    out.push(' ');
+            Ok(Event::Text(t)) if in_text => {
+                if let (Some(paragraph), Ok(text)) = (paragraphs.last_mut(), t.unescape()) {
+                    paragraph.text.push_str(&text);
                 }
-                buf.clear();
             }
-            _ => {
-                if inside_t {
-                    buf.push(c);
-                } else {
-                    buf.push(c);
+            Ok(Event::End(e)) => match e.local_name().as_ref() {
+                b"t" => in_text = false,
+                b"p" => {
+                    let Some(paragraph) = paragraphs.pop() else { continue };
+                    let text = TextNormalizer::normalize(&paragraph.text);
+                    if text.is_empty() {
+                        continue;
+                    }
+                    if let Some(outer) = paragraphs.last_mut() {
+                        // Text box content flows into the enclosing paragraph.
+                        outer.text.push(' ');
+                        outer.text.push_str(&text);
+                    } else if let Some(cell) = tables.last_mut().and_then(Table::current_cell) {
+                        if !cell.is_empty() {
+                            cell.push(' ');
+                        }
+                        cell.push_str(&text.replace('\n', " "));
+                    } else {
+                        blocks.push(render_paragraph(&paragraph, text, headings, numbering));
+                    }
+                }
+                b"tbl" => {
+                    let Some(table) = tables.pop() else { continue };
+                    if let Some(cell) = tables.last_mut().and_then(Table::current_cell) {
+                        if !cell.is_empty() {
+                            cell.push(' ');
+                        }
+                        cell.push_str(&table.flatten());
+                    } else if let Some(markdown) = table.render() {
+                        blocks.push(Block { markdown, list_item: false });
+                    }
                 }
+                _ => {}
+            },
+            Ok(Event::Eof) => break,
+            Err(e) => {
+                tracing::warn!("DOCX XML parse error: {}", e);
+                return None;
             }
+            _ => {}
+        }
+    }
+
+    let mut out = String::new();
+    let mut previous_list = false;
+    for block in blocks {
+        if !out.is_empty() {
+            out.push_str(if previous_list && block.list_item { "\n" } else { "\n\n" });
         }
+        out.push_str(&block.markdown);
+        previous_list = block.list_item;
     }
-    out.trim().to_string()
+    Some(out)
 }
 
-fn read_core_props<R: std::io::Read + std::io::Seek>(
-    zip: &mut zip::ZipArchive<R>,
-) -> (Option<String>, Option<String>) {
-    if let Ok(mut core) = zip.by_name("docProps/core.xml") {
-        let mut xml = String::new();
-        let _ = core.read_to_string(&mut xml);
-        let author = tag_value(&xml, "dc:creator");
-        let created = tag_value(&xml, "dcterms:created");
-        return (author, created);
+fn render_paragraph(
+    paragraph: &Paragraph,
+    text: String,
+    headings: &HashMap<String, usize>,
+    numbering: &Numbering,
+) -> Block {
+    let heading = paragraph
+        .style
+        .as_deref()
+        .and_then(|s| headings.get(s).copied().or_else(|| heading_level_from_name(s)));
+    if let Some(level) = heading {
+        let markdown = format!("{} {}", "#".repeat(level.min(6)), text.replace('\n', " "));
+        return Block { markdown, list_item: false };
+    }
+    match paragraph.num_id.as_deref() {
+        // numId 0 explicitly removes numbering from a paragraph.
+        Some(num_id) if num_id != "0" => {
+            let level = paragraph.list_level.as_deref().unwrap_or("0");
+            let depth = level.parse::<usize>().unwrap_or(0);
+            let marker = if numbering.is_ordered(num_id, level) { "1." } else { "-" };
+            let markdown = format!("{}{} {}", "  ".repeat(depth), marker, text.replace('\n', " "));
+            Block { markdown, list_item: true }
+        }
+        _ => Block { markdown: text, list_item: false },
     }
-    (None, None)
 }
 
-fn tag_value(xml: &str, tag: &str) -> Option<String> {
-    let start = format!("<{}>", tag);
-    let end = format!("</{}>", tag);
-    if let Some(i) = xml.find(&start) {
-        if let Some(j) = xml[i + start.len()..].find(&end) {
-            let val = &xml[i + start.len()..i + start.len() + j];
-            return Some(val.trim().to_string());
+/// Text of every leaf element keyed by local name, e.g. `dc:creator` -> `creator`.
+fn leaf_values(xml: &str) -> Vec<(String, String)> {
+    let mut values = Vec::new();
+    let mut reader = Reader::from_str(xml);
+    let mut current: Option<String> = None;
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) => {
+                current = Some(String::from_utf8_lossy(e.local_name().as_ref()).into_owned());
+            }
+            Ok(Event::Text(t)) => {
+                if let (Some(name), Ok(text)) = (current.as_ref(), t.unescape()) {
+                    let text = text.trim();
+                    if !text.is_empty() {
+                        values.push((name.clone(), text.to_string()));
+                    }
+                }
+            }
+            Ok(Event::End(_)) => current = None,
+            Ok(Event::Eof) | Err(_) => break,
+            _ => {}
         }
     }
-    None
+    values
 }
 
 #[async_trait::async_trait]
@@ -0,0 +1,170 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use ignore::Match;
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use mnemo_core::config::filesystem::FilesystemRootConfig;
+
+use super::filesystem::{exceeds_text_limit, raw_size_limit};
+use crate::metrics::SkipReason;
+
+/// Ignore files read in every directory when a root does not name its own.
+const DEFAULT_IGNORE_FILES: &[&str] = &[".gitignore", ".mnemoignore"];
+
+/// Compiled selection rules for one filesystem root.
+pub(crate) struct RootFilter {
+    root: PathBuf,
+    include: Option<GlobSet>,
+    exclude: GlobSet,
+    extensions: Vec<String>,
+    max_file_bytes: u64,
+    ignore_files: Vec<String>,
+    /// Ignore files per directory, parsed on first use.
+    ignores: HashMap<PathBuf, Vec<Gitignore>>,
+}
+
+impl RootFilter {
+    pub(crate) fn new(
+        root: &Path,
+        config: &FilesystemRootConfig,
+        default_extensions: &[&str],
+        default_max_bytes: u64,
+    ) -> Self {
+        let extensions = match &config.extensions {
+            Some(list) => {
+                list.iter().map(|e| e.trim_start_matches('.').to_ascii_lowercase()).collect()
+            }
+            None => default_extensions.iter().map(|e| e.to_string()).collect(),
+        };
+        let ignore_files = match &config.ignore_files {
+            Some(names) => names.clone(),
+            None => DEFAULT_IGNORE_FILES.iter().map(|n| n.to_string()).collect(),
+        };
+        Self {
+            root: root.to_path_buf(),
+            include: (!config.include.is_empty()).then(|| glob_set(&config.include)),
+            exclude: glob_set(&config.exclude),
+            extensions,
+            max_file_bytes: config.max_file_bytes.unwrap_or(default_max_bytes),
+            ignore_files,
+            ignores: HashMap::new(),
+        }
+    }
+
+    /// Reason to prune a directory and everything under it.
+    pub(crate) fn skip_dir(&mut self, path: &Path) -> Option<SkipReason> {
+        if path == self.root {
+            return None;
+        }
+        if self.ignored(path, true) {
+            return Some(SkipReason::Ignored);
+        }
+        self.exclude.is_match(self.relative(path)).then_some(SkipReason::Excluded)
+    }
+
+    /// Whether a directory between the root and `path` is ignored or excluded.
+    pub(crate) fn skip_ancestors(&mut self, path: &Path) -> bool {
+        let Some(parent) = path.parent() else { return false };
+        let ancestors: Vec<PathBuf> = parent
+            .ancestors()
+            .take_while(|dir| dir.starts_with(&self.root) && *dir != self.root)
+            .map(Path::to_path_buf)
+            .collect();
+        ancestors.iter().rev().any(|dir| self.skip_dir(dir).is_some())
+    }
+
+    /// Reason to skip a file by path alone: ignore rules and globs.
+    pub(crate) fn skip_path(&mut self, path: &Path) -> Option<SkipReason> {
+        if self.ignored(path, false) {
+            return Some(SkipReason::Ignored);
+        }
+        let relative = self.relative(path);
+        if self.exclude.is_match(&relative) {
+            return Some(SkipReason::Excluded);
+        }
+        match &self.include {
+            Some(include) if !include.is_match(&relative) => Some(SkipReason::NotIncluded),
+            _ => None,
+        }
+    }
+
+    /// Reason to skip a file (or archive member) by extension and size.
+    pub(crate) fn skip_content(&self, path: &Path, size: u64) -> Option<SkipReason> {
+        let ext = path.extension().and_then(|e| e.to_str()).map(|e| e.to_ascii_lowercase());
+        if !ext.as_ref().is_some_and(|ext| self.extensions.contains(ext)) {
+            return Some(SkipReason::Extension);
+        }
+        let limit = raw_size_limit(ext.as_deref().unwrap_or_default(), self.max_file_bytes);
+        (size > limit).then_some(SkipReason::TooLarge)
+    }
+
+    /// Reason to drop a file after extraction: notebook text over the size cap.
+    pub(crate) fn skip_extracted(&self, path: &Path, text_len: usize) -> Option<SkipReason> {
+        exceeds_text_limit(path, text_len, self.max_file_bytes).then_some(SkipReason::TooLarge)
+    }
+
+    fn relative(&self, path: &Path) -> String {
+        let relative = path.strip_prefix(&self.root).unwrap_or(path);
+        relative
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy().into_owned())
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+
+    /// Whether ignore files from the root down to the path's directory ignore it; the deepest
+    /// matching rule wins, so a nested `!pattern` re-includes what a parent ignored.
+    fn ignored(&mut self, path: &Path, is_dir: bool) -> bool {
+        let Some(parent) = path.parent() else { return false };
+        let Ok(below_root) = parent.strip_prefix(&self.root) else { return false };
+        let mut dirs = vec![self.root.clone()];
+        let mut current = self.root.clone();
+        for component in below_root.components() {
+            current.push(component);
+            dirs.push(current.clone());
+        }
+        let mut ignored = false;
+        for dir in dirs {
+            for matcher in self.matchers(&dir) {
+                match matcher.matched(path, is_dir) {
+                    Match::Ignore(_) => ignored = true,
+                    Match::Whitelist(_) => ignored = false,
+                    Match::None => {}
+                }
+            }
+        }
+        ignored
+    }
+
+    fn matchers(&mut self, dir: &Path) -> &[Gitignore] {
+        let names = &self.ignore_files;
+        self.ignores.entry(dir.to_path_buf()).or_insert_with(|| {
+            names
+                .iter()
+                .map(|name| dir.join(name))
+                .filter(|file| file.is_file())
+                .filter_map(|file| {
+                    let mut builder = GitignoreBuilder::new(dir);
+                    if let Some(err) = builder.add(&file) {
+                        tracing::warn!("Invalid ignore file {}: {}", file.display(), err);
+                    }
+                    builder.build().ok()
+                })
+                .collect()
+        })
+    }
+}
+
+fn glob_set(patterns: &[String]) -> GlobSet {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        match Glob::new(pattern) {
+            Ok(glob) => {
+                builder.add(glob);
+            }
+            Err(err) => tracing::warn!("Invalid glob {}: {}", pattern, err),
+        }
+    }
+    builder.build().unwrap_or_else(|_| GlobSet::empty())
+}
@@ -1,128 +1,204 @@
 use std::fs;
-use std::io::Read;
 use std::path::{Path, PathBuf};
 
 use chrono::{DateTime, Utc};
+use mnemo_core::config::filesystem::FilesystemConfig;
 use mnemo_core::models::document::Document;
 use mnemo_core::utils::text_normalizer::TextNormalizer;
 use sha2::{Digest, Sha256};
 use walkdir::WalkDir;
 
-use super::pdf::{count_pdf_pages, extract_pdf_title};
+use super::archive::{ArchiveKind, ArchiveLimits, expand_archive, member_path};
+use super::docx::{docx_properties, extract_docx_text};
+use super::file_filter::RootFilter;
+use super::notebook::{
+    DEFAULT_MAX_OUTPUT_CHARS, MAX_NOTEBOOK_BYTES, extract_notebook, extract_notebook_text,
+};
+use super::pdf::{PAGE_BREAK, extract_pdf_text, extract_pdf_title};
+use super::tabular::{DEFAULT_MAX_ROWS, TABLE_EXTENSIONS, extract_table, table_name};
+use crate::metrics::{SkipReason, SkippedFile};
 
 const MAX_FILE_BYTES: u64 = 5 * 1024 * 1024;
 const ALLOWED_EXTENSIONS: &[&str] = &[
     "md", "txt", "json", "yaml", "yml", "rs", "toml", "pdf", "docx", "ts", "tsx", "js", "py", "go",
-    "java", "rb",
+    "java", "rb", "ipynb", "csv", "tsv", "jsonl", "ndjson", "parquet",
 ];
+/// Namespace of every filesystem document.
+pub const NAMESPACE: &str = "local";
 const LARGE_THRESHOLD: usize = 200 * 1024;
 const SEGMENT_SIZE: usize = 50 * 1024;
 
+/// Provider for local directories.
+///
+/// Each root is walked with its rules from the profile's `filesystem` section: `.gitignore`
+/// and `.mnemoignore` files, include/exclude globs, an extension allowlist and a size limit.
 pub struct FilesystemProvider {
     pub root_paths: Vec<String>,
+    /// Bounds for `.zip`, `.tar` and `.tar.gz` archives expanded during the scan.
+    pub archive_limits: ArchiveLimits,
+    pub config: FilesystemConfig,
+}
+
+/// A file accepted for indexing, read from disk or from inside an archive.
+struct Candidate {
+    /// Filesystem path, or `archive!/member` for archive members.
+    path: PathBuf,
+    content: String,
+    /// Format-specific metadata (PDF title, notebook kernel, table schema, source archive).
+    metadata: Option<serde_json::Value>,
+    /// File whose modification time applies: the archive itself for members.
+    stat_path: PathBuf,
+    size: u64,
 }
 
 impl FilesystemProvider {
     pub fn new(root_paths: Vec<String>) -> Self {
-        if root_paths.is_empty() {
-            Self { root_paths: vec!["/app/data".into()] }
-        } else {
-            Self { root_paths }
+        let root_paths = if root_paths.is_empty() { vec!["/app/data".into()] } else { root_paths };
+        Self {
+            root_paths,
+            archive_limits: ArchiveLimits::default(),
+            config: FilesystemConfig::load_default(),
         }
     }
 
+    pub fn with_config(mut self, config: FilesystemConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    pub fn with_archive_limits(mut self, archive_limits: ArchiveLimits) -> Self {
+        self.archive_limits = archive_limits;
+        self
+    }
+
     pub fn scan(&self) -> Vec<Document> {
-        let mut files: Vec<(PathBuf, String)> = Vec::new();
+        self.scan_with_report().0
+    }
+
+    /// Scan every root, returning the documents and the files passed over with the reason.
+    pub fn scan_with_report(&self) -> (Vec<Document>, Vec<SkippedFile>) {
+        let mut files: Vec<Candidate> = Vec::new();
+        let mut skipped: Vec<SkippedFile> = Vec::new();
         for root in &self.root_paths {
             let root_path = PathBuf::from(root);
             if !root_path.exists() {
                 tracing::error!("Ingestion root missing: {}", root);
-                return Vec::new();
+                return (Vec::new(), skipped);
             }
             tracing::info!("WalkDir scanning root: {}", root_path.display());
-            collect_files(&root_path, &mut files);
+            let mut filter = RootFilter::new(
+                &root_path,
+                &self.config.for_root(root),
+                ALLOWED_EXTENSIONS,
+                MAX_FILE_BYTES,
+            );
+            let mut scan = Scan { filter: &mut filter, files: &mut files, skipped: &mut skipped };
+            scan.collect(&root_path, &self.archive_limits);
+        }
+        if !skipped.is_empty() {
+            tracing::info!("WalkDir skipped {} files", skipped.len());
         }
         tracing::info!("WalkDir collected {} file candidates", files.len());
+        (into_documents(files), skipped)
+    }
 
-        let mut docs = Vec::new();
-        for (path, content) in files.into_iter() {
-            let path_str = path.to_string_lossy().to_string();
-            let normalized = TextNormalizer::normalize(&content);
-            let metadata = fs::metadata(&path).ok();
-            let modified_at =
-                metadata.as_ref().and_then(|m| m.modified().ok()).map(|t| DateTime::<Utc>::from(t));
-            let file_size = metadata.as_ref().map(|m| m.len() as i64);
-            let file_type =
-                path.extension().and_then(|e| e.to_str()).map(|s| s.to_ascii_lowercase());
-            let language = detect_language(&path_str, &normalized, file_type.as_deref());
-            let mut meta: Option<serde_json::Value> = None;
-
-            if let Some(ft) = file_type.as_deref() {
-                if ft == "pdf" {
-                    if let Ok(bytes) = fs::read(&path) {
-                        let pages = count_pdf_pages(&bytes);
-                        let title = extract_pdf_title(&bytes);
-                        meta = Some(serde_json::json!({
-                            "number_of_pages": pages,
-                            "title": title
-                        }));
-                    }
-                } else if ft == "docx" {
-                    if let Ok(bytes) = fs::read(&path) {
-                        if let Some((author, created)) = extract_docx_metadata(&bytes) {
-                            meta = Some(serde_json::json!({
-                                "author": author,
-                                "created": created
-                            }));
-                        }
-                    }
+    /// Rescan only the given files or directories, under the same rules as a full scan.
+    ///
+    /// Paths outside every root, missing paths and paths inside an ignored or excluded
+    /// directory yield nothing.
+    pub fn scan_paths(&self, paths: &[PathBuf]) -> (Vec<Document>, Vec<SkippedFile>) {
+        let mut files: Vec<Candidate> = Vec::new();
+        let mut skipped: Vec<SkippedFile> = Vec::new();
+        for root in &self.root_paths {
+            let root_path = PathBuf::from(root);
+            let under_root: Vec<&PathBuf> =
+                paths.iter().filter(|p| p.starts_with(&root_path) && p.exists()).collect();
+            if under_root.is_empty() {
+                continue;
+            }
+            let mut filter = RootFilter::new(
+                &root_path,
+                &self.config.for_root(root),
+                ALLOWED_EXTENSIONS,
+                MAX_FILE_BYTES,
+            );
+            for path in under_root {
+                if filter.skip_ancestors(path) {
+                    continue;
                 }
+                let mut scan =
+                    Scan { filter: &mut filter, files: &mut files, skipped: &mut skipped };
+                scan.collect(path, &self.archive_limits);
             }
+        }
+        (into_documents(files), skipped)
+    }
+}
+
+/// Normalize candidates into documents, splitting large plain-text files into segments.
+fn into_documents(files: Vec<Candidate>) -> Vec<Document> {
+    let mut docs = Vec::new();
+    for Candidate { path, content, metadata: meta, stat_path, size } in files.into_iter() {
+        let path_str = path.to_string_lossy().to_string();
+        let file_type = path.extension().and_then(|e| e.to_str()).map(|s| s.to_ascii_lowercase());
+        // Page, cell and row numbers are derived from breaks in the whole text.
+        let keep_whole = matches!(file_type.as_deref(), Some("pdf" | "ipynb"))
+            || file_type.as_deref().is_some_and(|ft| TABLE_EXTENSIONS.contains(&ft));
+        let normalized = normalize_content(&path, content);
+        let modified_at = fs::metadata(&stat_path)
+            .ok()
+            .and_then(|m| m.modified().ok())
+            .map(DateTime::<Utc>::from);
+        let file_size = Some(size as i64);
+        let language = detect_language(&path_str, &normalized, file_type.as_deref());
 
-            if normalized.len() > LARGE_THRESHOLD {
-                let mut start = 0;
-                let mut idx = 0;
-                while start < normalized.len() {
-                    let end = (start + SEGMENT_SIZE).min(normalized.len());
-                    let seg = &normalized[start..end];
-                    let mut hasher = Sha256::new();
-                    hasher.update(seg.as_bytes());
-                    docs.push(Document {
-                        path: format!("{}#segment_{}", path_str, idx),
-                        content: seg.to_string(),
-                        fingerprint: format!("{:x}", hasher.finalize()),
-                        namespace: "local".to_string(),
-                        modified_at,
-                        file_size,
-                        file_type: file_type.clone(),
-                        language: language.clone(),
-                        metadata: meta.clone(),
-                    });
-                    idx += 1;
-                    start = end;
+        if normalized.len() > LARGE_THRESHOLD && !keep_whole {
+            let mut start = 0;
+            let mut idx = 0;
+            while start < normalized.len() {
+                let mut end = (start + SEGMENT_SIZE).min(normalized.len());
+                // Never cut a multi-byte character in half.
+                while !normalized.is_char_boundary(end) {
+                    end -= 1;
                 }
-            } else {
+                let seg = &normalized[start..end];
                 let mut hasher = Sha256::new();
-                hasher.update(normalized.as_bytes());
+                hasher.update(seg.as_bytes());
                 docs.push(Document {
-                    path: path_str,
-                    content: normalized,
+                    path: format!("{}#segment_{}", path_str, idx),
+                    content: seg.to_string(),
                     fingerprint: format!("{:x}", hasher.finalize()),
-                    namespace: "local".to_string(),
+                    namespace: NAMESPACE.to_string(),
                     modified_at,
                     file_size,
                     file_type: file_type.clone(),
                     language: language.clone(),
                     metadata: meta.clone(),
                 });
+                idx += 1;
+                start = end;
             }
+        } else {
+            let mut hasher = Sha256::new();
+            hasher.update(normalized.as_bytes());
+            docs.push(Document {
+                path: path_str,
+                content: normalized,
+                fingerprint: format!("{:x}", hasher.finalize()),
+                namespace: NAMESPACE.to_string(),
+                modified_at,
+                file_size,
+                file_type: file_type.clone(),
+                language: language.clone(),
+                metadata: meta.clone(),
+            });
         }
-        tracing::info!("FilesystemProvider produced {} documents", docs.len());
-        docs
     }
+    tracing::info!("FilesystemProvider produced {} documents", docs.len());
+    docs
 }
 
-fn detect_language(_path: &str, content: &str, ext: Option<&str>) -> Option<String> {
+pub(crate) fn detect_language(_path: &str, content: &str, ext: Option<&str>) -> Option<String> {
     if let Some(first_line) = content.lines().next() {
         if first_line.starts_with("#!") {
             if first_line.contains("python") {
@@ -151,6 +227,8 @@ fn detect_language(_path: &str, content: &str, ext: Option<&str>) -> Option<Stri
             "txt" => Some("text".into()),
             "pdf" => Some("pdf".into()),
             "docx" => Some("docx".into()),
+            "ipynb" => Some("notebook".into()),
+            "csv" | "tsv" | "jsonl" | "ndjson" | "parquet" => Some("table".into()),
             _ => None,
         };
     }
@@ -169,123 +247,244 @@ fn detect_language(_path: &str, content: &str, ext: Option<&str>) -> Option<Stri
     None
 }
 
-fn collect_files(path: &Path, files: &mut Vec<(PathBuf, String)>) {
-    for entry in
-        WalkDir::new(path).into_iter().filter_map(Result::ok).filter(|e| e.file_type().is_file())
-    {
-        let entry_path = entry.into_path();
-        let Ok(metadata) = fs::metadata(&entry_path) else {
-            continue;
-        };
+/// Walk state for one root.
+struct Scan<'a> {
+    filter: &'a mut RootFilter,
+    files: &'a mut Vec<Candidate>,
+    skipped: &'a mut Vec<SkippedFile>,
+}
 
-        if !is_allowed(&entry_path, metadata.len()) {
-            tracing::debug!("File rejected (ext/size): {}", entry_path.display());
-            continue;
-        }
-        tracing::info!("Found file candidate: {}", entry_path.display());
+impl Scan<'_> {
+    fn skip(&mut self, path: &Path, reason: SkipReason) {
+        tracing::debug!("File skipped ({}): {}", reason.as_str(), path.display());
+        self.skipped.push(SkippedFile { path: path.to_string_lossy().to_string(), reason });
+    }
 
-        let ext = entry_path
-            .extension()
-            .and_then(|e| e.to_str())
-            .map(|s| s.to_ascii_lowercase())
-            .unwrap_or_default();
+    fn collect(&mut self, root: &Path, limits: &ArchiveLimits) {
+        let mut walk = WalkDir::new(root).into_iter();
+        while let Some(entry) = walk.next() {
+            let Ok(entry) = entry else { continue };
+            let entry_path = entry.path().to_path_buf();
+            if entry.file_type().is_dir() {
+                if let Some(reason) = self.filter.skip_dir(&entry_path) {
+                    self.skip(&entry_path, reason);
+                    walk.skip_current_dir();
+                }
+                continue;
+            }
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            let Ok(metadata) = fs::metadata(&entry_path) else {
+                continue;
+            };
+            if let Some(reason) = self.filter.skip_path(&entry_path) {
+                self.skip(&entry_path, reason);
+                continue;
+            }
 
-        if ext == "pdf" || ext == "docx" || ext == "doc" {
-            tracing::debug!("Skipping binary file ({}): {}", ext, entry_path.display());
-            continue;
+            if let Some(kind) = ArchiveKind::from_path(&entry_path) {
+                if metadata.len() > limits.max_archive_bytes {
+                    self.skip(&entry_path, SkipReason::TooLarge);
+                } else if let Ok(bytes) = fs::read(&entry_path) {
+                    self.collect_archive(&entry_path, kind, &bytes, limits);
+                }
+                continue;
+            }
+
+            if let Some(reason) = self.filter.skip_content(&entry_path, metadata.len()) {
+                self.skip(&entry_path, reason);
+                continue;
+            }
+            tracing::info!("Found file candidate: {}", entry_path.display());
+
+            let Ok(bytes) = fs::read(&entry_path) else { continue };
+            let Some(candidate) = read_candidate(entry_path.clone(), &entry_path, bytes) else {
+                self.skip(&entry_path, SkipReason::Unreadable);
+                continue;
+            };
+            if let Some(reason) = self.filter.skip_extracted(&entry_path, candidate.content.len()) {
+                self.skip(&entry_path, reason);
+                continue;
+            }
+            tracing::debug!("File accepted: {}", entry_path.display());
+            self.files.push(candidate);
         }
+    }
 
-        if let Ok(bytes) = fs::read(&entry_path) {
-            if !is_text(&bytes) {
-                tracing::debug!("File rejected (binary): {}", entry_path.display());
+    /// Expand an archive in memory and collect its members as `archive!/member` paths.
+    ///
+    /// Members carry `archive`, `archive_member` and `archive_fingerprint` metadata; the
+    /// fingerprint step skips every member of an archive whose fingerprint is unchanged.
+    /// Members are filtered by the root's extension and size rules.
+    fn collect_archive(
+        &mut self,
+        archive: &Path,
+        kind: ArchiveKind,
+        bytes: &[u8],
+        limits: &ArchiveLimits,
+    ) {
+        let archive_str = archive.to_string_lossy().to_string();
+        let mut hasher = Sha256::new();
+        hasher.update(bytes);
+        let fingerprint = format!("{:x}", hasher.finalize());
+        let members = expand_archive(kind, bytes, limits);
+        tracing::info!("Expanded archive {} ({} members)", archive.display(), members.len());
+        for member in members {
+            let path = PathBuf::from(member_path(&archive_str, &member.path));
+            if let Some(reason) = self.filter.skip_content(&path, member.bytes.len() as u64) {
+                self.skip(&path, reason);
                 continue;
             }
-            if let Ok(content) = String::from_utf8(bytes) {
-                tracing::debug!("File accepted: {}", entry_path.display());
-                files.push((entry_path, content));
+            let Some(mut candidate) = read_candidate(path.clone(), archive, member.bytes) else {
+                self.skip(&path, SkipReason::Unreadable);
+                continue;
+            };
+            if let Some(reason) = self.filter.skip_extracted(&path, candidate.content.len()) {
+                self.skip(&path, reason);
+                continue;
             }
+            let mut meta = match candidate.metadata.take() {
+                Some(serde_json::Value::Object(meta)) => meta,
+                _ => serde_json::Map::new(),
+            };
+            meta.insert("archive".into(), serde_json::json!(archive_str));
+            meta.insert("archive_member".into(), serde_json::json!(member.path));
+            meta.insert("archive_fingerprint".into(), serde_json::json!(fingerprint));
+            candidate.metadata = Some(serde_json::Value::Object(meta));
+            self.files.push(candidate);
         }
     }
 }
 
-fn is_allowed(path: &Path, size: u64) -> bool {
-    if size > MAX_FILE_BYTES {
-        return false;
+/// Extract a file's text and format-specific metadata; `None` if it is binary or unreadable.
+fn read_candidate(path: PathBuf, stat_path: &Path, bytes: Vec<u8>) -> Option<Candidate> {
+    let ext = path.extension().and_then(|e| e.to_str()).map(|s| s.to_ascii_lowercase());
+    let size = bytes.len() as u64;
+    if ext.as_deref() == Some("ipynb") {
+        let Some((content, meta)) = extract_notebook(&bytes, Some(DEFAULT_MAX_OUTPUT_CHARS)) else {
+            tracing::warn!("Skipping unreadable notebook: {}", path.display());
+            return None;
+        };
+        let metadata = Some(serde_json::Value::Object(meta));
+        return Some(Candidate {
+            path,
+            content,
+            metadata,
+            stat_path: stat_path.to_path_buf(),
+            size,
+        });
     }
-
-    let Some(ext) = path.extension().and_then(|ext| ext.to_str()) else {
-        return false;
+    let needs_bytes = matches!(ext.as_deref(), Some("pdf" | "docx"))
+        || ext.as_deref().is_some_and(|ft| TABLE_EXTENSIONS.contains(&ft));
+    let kept = needs_bytes.then(|| bytes.clone());
+    let content = extract_content(&path, bytes)?;
+    let metadata = match (ext.as_deref(), kept) {
+        (Some("pdf"), Some(bytes)) => {
+            let pages = content.split(PAGE_BREAK).count();
+            let title = extract_pdf_title(&bytes);
+            Some(serde_json::json!({
+                "number_of_pages": pages,
+                "title": title
+            }))
+        }
+        (Some("docx"), Some(bytes)) => {
+            let props = docx_properties(&bytes);
+            (!props.is_empty()).then_some(serde_json::Value::Object(props))
+        }
+        (Some(_), Some(bytes)) => {
+            extract_table(&path.to_string_lossy(), &bytes, Some(DEFAULT_MAX_ROWS))
+                .map(|table| serde_json::Value::Object(table.metadata()))
+        }
+        _ => None,
     };
-
-    let ext_lower = ext.to_ascii_lowercase();
-    ALLOWED_EXTENSIONS.contains(&ext_lower.as_str())
+    Some(Candidate { path, content, metadata, stat_path: stat_path.to_path_buf(), size })
 }
 
-fn is_text(bytes: &[u8]) -> bool {
-    !bytes.iter().any(|b| *b == 0)
-}
+/// Decode file bytes into indexable text, routing PDF, DOCX, notebooks and tables to their
+/// extractors.
+///
+/// Returns `None` for binary or unreadable files; pass the result to [`normalize_content`].
+pub(crate) fn extract_content(path: &Path, bytes: Vec<u8>) -> Option<String> {
+    let ext = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|s| s.to_ascii_lowercase())
+        .unwrap_or_default();
 
-#[allow(dead_code)]
-fn extract_docx_text_from_bytes(bytes: &[u8]) -> Option<String> {
-    use std::io::Cursor;
-    let cursor = Cursor::new(bytes);
-    if let Ok(mut archive) = zip::ZipArchive::new(cursor) {
-        if let Ok(mut doc_xml) = archive.by_name("word/document.xml") {
-            let mut xml = String::new();
-            let _ = doc_xml.read_to_string(&mut xml);
-            return Some(extract_docx_text(&xml));
+    if ext == "pdf" || ext == "docx" {
+        let path_str = path.to_string_lossy();
+        let extracted = if ext == "pdf" {
+            extract_pdf_text(&path_str, &bytes)
+        } else {
+            extract_docx_text(&bytes)
+        };
+        if extracted.is_none() {
+            tracing::warn!("Skipping unreadable {} file: {}", ext, path_str);
         }
+        return extracted;
     }
-    None
-}
 
-#[allow(dead_code)]
-fn extract_docx_text(xml: &str) -> String {
-    let mut out = String::new();
-    let mut inside_t = false;
-    let mut buf = String::new();
-    for c in xml.chars() {
-        match c {
-            '<' => {
-                if inside_t && !buf.trim().is_empty() {
-                    out.push_str(&buf);
-                }
-                buf.clear();
-                inside_t = false;
-            }
-            '>' => {
-                if inside_t && !buf.trim().is_empty() {
-                    out.push(' ');
-                    out.push_str(&buf);
-                }
-                buf.clear();
-                inside_t = true;
-            }
-            _ => buf.push(c),
+    if ext == "ipynb" {
+        let extracted = extract_notebook_text(&bytes, Some(DEFAULT_MAX_OUTPUT_CHARS));
+        if extracted.is_none() {
+            tracing::warn!("Skipping unreadable notebook: {}", path.display());
         }
+        return extracted;
     }
-    out
-}
 
-fn extract_docx_metadata(bytes: &[u8]) -> Option<(String, String)> {
-    use std::io::Cursor;
-    let cursor = Cursor::new(bytes);
-    if let Ok(mut archive) = zip::ZipArchive::new(cursor) {
-        if let Ok(mut core_xml) = archive.by_name("docProps/core.xml") {
-            let mut xml = String::new();
-            let _ = core_xml.read_to_string(&mut xml);
-            let author = extract_tag(&xml, "dc:creator").unwrap_or_default();
-            let created = extract_tag(&xml, "dcterms:created").unwrap_or_default();
-            return Some((author, created));
+    if TABLE_EXTENSIONS.contains(&ext.as_str()) {
+        let path_str = path.to_string_lossy();
+        let extracted = extract_table(&path_str, &bytes, Some(DEFAULT_MAX_ROWS));
+        if extracted.is_none() {
+            tracing::warn!("Skipping unreadable table: {}", path_str);
         }
+        return extracted.map(|table| table.render(&table_name(&path_str)));
     }
-    None
+
+    if !is_text(&bytes) {
+        tracing::debug!("File rejected (binary): {}", path.display());
+        return None;
+    }
+    String::from_utf8(bytes).ok()
 }
 
-fn extract_tag(xml: &str, tag: &str) -> Option<String> {
-    let start = format!("<{tag}>");
-    let end = format!("</{tag}>");
-    let s = xml.find(&start)?;
-    let e = xml[s + start.len()..].find(&end)?;
-    Some(xml[s + start.len()..s + start.len() + e].to_string())
+/// Normalize text from [`extract_content`].
+///
+/// Extracted PDF/DOCX/notebook/table text is already normalized piecewise; renormalizing would
+/// trim page, cell and row breaks, collapse Markdown list indentation and flatten code in
+/// notebook cells.
+pub(crate) fn normalize_content(path: &Path, content: String) -> String {
+    let ext = path.extension().and_then(|e| e.to_str()).map(|s| s.to_ascii_lowercase());
+    match ext.as_deref() {
+        Some("pdf" | "docx" | "ipynb") => content,
+        Some(ext) if TABLE_EXTENSIONS.contains(&ext) => content,
+        _ => TextNormalizer::normalize(&content),
+    }
+}
+
+pub(crate) fn is_allowed(path: &Path, size: u64) -> bool {
+    let Some(ext) = path.extension().and_then(|ext| ext.to_str()) else {
+        return false;
+    };
+
+    let ext_lower = ext.to_ascii_lowercase();
+    size <= raw_size_limit(&ext_lower, MAX_FILE_BYTES)
+        && ALLOWED_EXTENSIONS.contains(&ext_lower.as_str())
+}
+
+/// Size cap for a file's raw bytes: notebooks are checked against `max_bytes` only once
+/// their outputs are stripped, see [`exceeds_text_limit`].
+pub(crate) fn raw_size_limit(ext: &str, max_bytes: u64) -> u64 {
+    if ext == "ipynb" { MAX_NOTEBOOK_BYTES.max(max_bytes) } else { max_bytes }
+}
+
+/// Whether extracted notebook text is still over the file cap.
+pub(crate) fn exceeds_text_limit(path: &Path, text_len: usize, max_bytes: u64) -> bool {
+    let is_notebook = path.extension().is_some_and(|e| e.eq_ignore_ascii_case("ipynb"));
+    is_notebook && text_len as u64 > max_bytes
+}
+
+fn is_text(bytes: &[u8]) -> bool {
+    !bytes.iter().any(|b| *b == 0)
 }
@@ -0,0 +1,404 @@
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::Mutex;
+
+use chrono::{DateTime, Utc};
+use git2::{Delta, ObjectType, Oid, Repository, Sort, Tree, TreeWalkMode, TreeWalkResult};
+use ignore::Match;
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use mnemo_core::models::document::Document;
+use mnemo_storage::metadata::postgres::PostgresMetadataStore;
+use sha2::{Digest, Sha256};
+
+use super::filesystem::{detect_language, extract_content, is_allowed, normalize_content};
+use super::registry::PruneScope;
+
+/// Provider that reads files at a ref of a local git clone, with last-commit metadata per file.
+///
+/// The commit read by a load is recorded by `commit_sync` once its documents are indexed, in the
+/// metadata store when one is attached and in memory otherwise; later loads then only return
+/// files changed since that commit, report deleted files through `deleted_paths` and the
+/// unchanged ones through `retained_paths`.
+pub struct GitRepoProvider {
+    pub repo_path: String,
+    /// Branch, tag or commit to ingest, e.g. `HEAD`, `main` or `v1.2.0`.
+    pub reference: String,
+    pub namespace: String,
+    pub store: Option<PostgresMetadataStore>,
+    sync: Mutex<SyncState>,
+}
+
+/// Commit of the last indexed load and what the latest load found.
+#[derive(Default)]
+struct SyncState {
+    synced: Option<String>,
+    pending: Option<String>,
+    deleted: Vec<String>,
+    unchanged: Vec<String>,
+}
+
+/// Last commit that touched a file.
+struct LastCommit {
+    sha: String,
+    author: String,
+    email: String,
+    time: Option<DateTime<Utc>>,
+}
+
+/// Files read at one commit, and the paths deleted or unchanged since the base commit.
+struct Snapshot {
+    head: String,
+    documents: Vec<Document>,
+    deleted: Vec<String>,
+    unchanged: Vec<String>,
+}
+
+impl GitRepoProvider {
+    pub fn new(repo_path: String, reference: String, namespace: String) -> Self {
+        Self { repo_path, reference, namespace, store: None, sync: Mutex::default() }
+    }
+
+    /// Enable incremental runs by tracking the last indexed commit in Postgres.
+    pub fn with_store(mut self, store: PostgresMetadataStore) -> Self {
+        self.store = Some(store);
+        self
+    }
+
+    pub async fn load_documents(&self) -> Vec<Document> {
+        let since = match &self.store {
+            Some(store) => match store.get_git_commit(&self.repo_path, &self.reference).await {
+                Ok(sha) => sha,
+                Err(e) => {
+                    tracing::warn!("Git sync state unavailable, doing a full read: {}", e);
+                    None
+                }
+            },
+            None => self.synced(),
+        };
+
+        let repo_path = self.repo_path.clone();
+        let reference = self.reference.clone();
+        let namespace = self.namespace.clone();
+        let snapshot = tokio::task::spawn_blocking(move || {
+            read_snapshot(&repo_path, &reference, &namespace, since.as_deref())
+        })
+        .await;
+
+        // A failed read reports nothing deleted or unchanged, so nothing gets pruned.
+        let synced = self.synced();
+        *self.sync.lock().unwrap() = SyncState { synced, ..SyncState::default() };
+        match snapshot {
+            Ok(Ok(snapshot)) => {
+                tracing::info!(
+                    "GitRepoProvider produced {} documents ({} deleted) from {}@{}",
+                    snapshot.documents.len(),
+                    snapshot.deleted.len(),
+                    self.repo_path,
+                    self.reference
+                );
+                let mut sync = self.sync.lock().unwrap();
+                sync.pending = Some(snapshot.head);
+                sync.deleted = snapshot.deleted;
+                sync.unchanged = snapshot.unchanged;
+                snapshot.documents
+            }
+            Ok(Err(e)) => {
+                tracing::error!("Git read failed for {}@{}: {}", self.repo_path, self.reference, e);
+                Vec::new()
+            }
+            Err(e) => {
+                tracing::error!("Git read task failed for {}: {}", self.repo_path, e);
+                Vec::new()
+            }
+        }
+    }
+
+    fn synced(&self) -> Option<String> {
+        self.sync.lock().unwrap().synced.clone()
+    }
+
+    /// Paths of indexed files the last load found deleted since the synced commit.
+    pub fn deleted_paths(&self) -> Vec<String> {
+        self.sync.lock().unwrap().deleted.clone()
+    }
+
+    /// Paths of files the last load skipped as unchanged since the synced commit.
+    pub fn retained_paths(&self) -> Vec<String> {
+        self.sync.lock().unwrap().unchanged.clone()
+    }
+
+    /// Documents of this clone: `<repo path>/...` in its namespace.
+    pub fn prune_scope(&self) -> PruneScope {
+        PruneScope {
+            namespace: self.namespace.clone(),
+            prefix: format!("{}/", self.repo_path.trim_end_matches('/')),
+        }
+    }
+
+    /// Record the commit read by the last load as indexed, so the next load starts from it.
+    pub async fn commit_sync(&self) {
+        let Some(head) = self.sync.lock().unwrap().pending.take() else { return };
+        if let Some(store) = &self.store
+            && let Err(e) = store.set_git_commit(&self.repo_path, &self.reference, &head).await
+        {
+            tracing::warn!("Failed to record git sync state: {}", e);
+            return;
+        }
+        self.sync.lock().unwrap().synced = Some(head);
+    }
+}
+
+fn read_snapshot(
+    repo_path: &str,
+    reference: &str,
+    namespace: &str,
+    since: Option<&str>,
+) -> Result<Snapshot, git2::Error> {
+    let repo = Repository::open(repo_path)?;
+    let head = repo.revparse_single(reference)?.peel_to_commit()?;
+    let tree = head.tree()?;
+    let head_sha = head.id().to_string();
+
+    // An unknown base (e.g. after a force push or gc) falls back to a full read.
+    let base =
+        since.and_then(|sha| Oid::from_str(sha).ok()).and_then(|oid| repo.find_commit(oid).ok());
+    let tracked = all_paths(&tree)?;
+    let (candidates, deleted) = match &base {
+        Some(base) if base.id() == head.id() => {
+            tracing::info!("Git ref {}@{} unchanged since last sync", repo_path, reference);
+            (Vec::new(), Vec::new())
+        }
+        Some(base) => changed_paths(&repo, &base.tree()?, &tree)?,
+        None => (tracked.clone(), Vec::new()),
+    };
+    let ignores = TreeIgnores::new(&repo, &tree, &tracked);
+    let root = repo_path.trim_end_matches('/');
+    let changed: HashSet<&str> = candidates.iter().map(|p| p.as_str()).collect();
+    let unchanged = match &base {
+        Some(_) => tracked
+            .iter()
+            .filter(|rel| !changed.contains(rel.as_str()))
+            .map(|rel| format!("{}/{}", root, rel))
+            .collect(),
+        None => Vec::new(),
+    };
+
+    let mut files = Vec::new();
+    for rel in candidates {
+        let rel_path = Path::new(&rel);
+        // Committed files can still match ignore rules (force-added build output, vendored code).
+        if ignores.is_ignored(&rel) {
+            tracing::debug!("Git file ignored by .gitignore: {}", rel);
+            continue;
+        }
+        let blob = tree.get_path(rel_path)?.to_object(&repo)?.peel_to_blob()?;
+        if !is_allowed(rel_path, blob.size() as u64) {
+            tracing::debug!("Git file rejected (ext/size): {}", rel);
+            continue;
+        }
+        if let Some(content) = extract_content(rel_path, blob.content().to_vec()) {
+            files.push((rel, content));
+        }
+    }
+
+    let paths: Vec<&str> = files.iter().map(|(rel, _)| rel.as_str()).collect();
+    let last_commits = last_commits(&repo, head.id(), &paths)?;
+
+    let documents = files
+        .into_iter()
+        .map(|(rel, content)| {
+            let rel_path = Path::new(&rel);
+            let normalized = normalize_content(rel_path, content);
+            let path = format!("{}/{}", root, rel);
+            let file_type =
+                rel_path.extension().and_then(|e| e.to_str()).map(|s| s.to_ascii_lowercase());
+            let language = detect_language(&path, &normalized, file_type.as_deref());
+            let last = last_commits.get(rel.as_str());
+            let mut hasher = Sha256::new();
+            hasher.update(normalized.as_bytes());
+            Document {
+                path,
+                fingerprint: format!("{:x}", hasher.finalize()),
+                file_size: Some(normalized.len() as i64),
+                content: normalized,
+                namespace: namespace.to_string(),
+                modified_at: last.and_then(|c| c.time),
+                file_type,
+                language,
+                metadata: Some(serde_json::json!({
+                    "git_repo": root,
+                    "git_ref": reference,
+                    "git_head": head_sha,
+                    "git_path": rel,
+                    "last_commit_sha": last.map(|c| c.sha.clone()),
+                    "last_commit_author": last.map(|c| c.author.clone()),
+                    "last_commit_email": last.map(|c| c.email.clone()),
+                    "last_commit_date": last.and_then(|c| c.time).map(|t| t.to_rfc3339()),
+                })),
+            }
+        })
+        .collect();
+    let deleted = deleted.into_iter().map(|rel| format!("{}/{}", root, rel)).collect();
+
+    Ok(Snapshot { head: head_sha, documents, deleted, unchanged })
+}
+
+/// `.gitignore` rules as committed in a tree, rather than the working tree's.
+struct TreeIgnores {
+    /// Matchers keyed by the directory holding the `.gitignore`, shallowest first.
+    matchers: Vec<(String, Gitignore)>,
+}
+
+impl TreeIgnores {
+    fn new(repo: &Repository, tree: &Tree, tracked: &[String]) -> Self {
+        let mut matchers = Vec::new();
+        for rel in tracked.iter().filter(|p| p.rsplit('/').next() == Some(".gitignore")) {
+            let dir = rel.strip_suffix(".gitignore").unwrap_or_default().trim_end_matches('/');
+            let Ok(blob) =
+                tree.get_path(Path::new(rel)).and_then(|e| e.to_object(repo)?.peel_to_blob())
+            else {
+                continue;
+            };
+            let mut builder = GitignoreBuilder::new(dir);
+            for line in String::from_utf8_lossy(blob.content()).lines() {
+                if let Err(err) = builder.add_line(None, line) {
+                    tracing::warn!("Invalid ignore rule in {}: {}", rel, err);
+                }
+            }
+            if let Ok(matcher) = builder.build() {
+                matchers.push((dir.to_string(), matcher));
+            }
+        }
+        matchers.sort_by_key(|(dir, _)| dir.matches('/').count() + usize::from(!dir.is_empty()));
+        Self { matchers }
+    }
+
+    /// Whether the deepest rule matching `rel` or one of its directories ignores it.
+    fn is_ignored(&self, rel: &str) -> bool {
+        let mut ignored = false;
+        for (dir, matcher) in &self.matchers {
+            if !dir.is_empty() && !rel.starts_with(&format!("{dir}/")) {
+                continue;
+            }
+            match matcher.matched_path_or_any_parents(rel, false) {
+                Match::Ignore(_) => ignored = true,
+                Match::Whitelist(_) => ignored = false,
+                Match::None => {}
+            }
+        }
+        ignored
+    }
+}
+
+fn all_paths(tree: &Tree) -> Result<Vec<String>, git2::Error> {
+    let mut paths = Vec::new();
+    tree.walk(TreeWalkMode::PreOrder, |root, entry| {
+        if let (Some(ObjectType::Blob), Some(name)) = (entry.kind(), entry.name()) {
+            paths.push(format!("{root}{name}"));
+        }
+        TreeWalkResult::Ok
+    })?;
+    Ok(paths)
+}
+
+/// Paths added or modified between two trees, and paths deleted.
+fn changed_paths(
+    repo: &Repository,
+    old: &Tree,
+    new: &Tree,
+) -> Result<(Vec<String>, Vec<String>), git2::Error> {
+    let diff = repo.diff_tree_to_tree(Some(old), Some(new), None)?;
+    let mut changed = Vec::new();
+    let mut deleted = Vec::new();
+    for delta in diff.deltas() {
+        let file =
+            if delta.status() == Delta::Deleted { delta.old_file() } else { delta.new_file() };
+        let Some(path) = file.path().map(|p| p.to_string_lossy().to_string()) else { continue };
+        if delta.status() == Delta::Deleted {
+            tracing::info!("Git file deleted since last sync: {}", path);
+            deleted.push(path);
+        } else {
+            changed.push(path);
+        }
+    }
+    Ok((changed, deleted))
+}
+
+/// Walk history from `head` and record the newest commit touching each path.
+///
+/// Merge commits are compared against their first parent only.
+fn last_commits(
+    repo: &Repository,
+    head: Oid,
+    paths: &[&str],
+) -> Result<HashMap<String, LastCommit>, git2::Error> {
+    let mut pending: HashSet<&str> = paths.iter().copied().collect();
+    let mut found = HashMap::new();
+    if pending.is_empty() {
+        return Ok(found);
+    }
+
+    let mut walk = repo.revwalk()?;
+    walk.push(head)?;
+    walk.set_sorting(Sort::TIME)?;
+    for oid in walk {
+        let commit = repo.find_commit(oid?)?;
+        let tree = commit.tree()?;
+        let parent_tree = match commit.parent(0) {
+            Ok(parent) => Some(parent.tree()?),
+            Err(_) => None,
+        };
+        let diff = repo.diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), None)?;
+        for delta in diff.deltas() {
+            let Some(path) = delta.new_file().path().and_then(|p| p.to_str()) else { continue };
+            if !pending.remove(path) {
+                continue;
+            }
+            let author = commit.author();
+            found.insert(
+                path.to_string(),
+                LastCommit {
+                    sha: commit.id().to_string(),
+                    author: author.name().unwrap_or_default().to_string(),
+                    email: author.email().unwrap_or_default().to_string(),
+                    time: DateTime::<Utc>::from_timestamp(commit.time().seconds(), 0),
+                },
+            );
+        }
+        if pending.is_empty() {
+            break;
+        }
+    }
+    Ok(found)
+}
+
+#[async_trait::async_trait]
+impl super::registry::Provider for GitRepoProvider {
+    fn name(&self) -> String {
+        "git".into()
+    }
+
+    fn priority(&self) -> u8 {
+        2
+    }
+
+    async fn load_documents(&self) -> Vec<Document> {
+        GitRepoProvider::load_documents(self).await
+    }
+
+    fn deleted_paths(&self) -> Vec<String> {
+        GitRepoProvider::deleted_paths(self)
+    }
+
+    fn prune_scope(&self) -> Option<PruneScope> {
+        Some(GitRepoProvider::prune_scope(self))
+    }
+
+    fn retained_paths(&self) -> Vec<String> {
+        GitRepoProvider::retained_paths(self)
+    }
+
+    async fn commit_sync(&self) {
+        GitRepoProvider::commit_sync(self).await
+    }
+}
@@ -1,91 +1,286 @@
 use async_trait::async_trait;
+use futures::StreamExt;
 use mnemo_core::models::document::Document;
-use sha2::{Digest, Sha256};
+use mnemo_storage::metadata::postgres::PostgresMetadataStore;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Mutex;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
-const LARGE_THRESHOLD: usize = 200 * 1024;
-const SEGMENT_SIZE: usize = 50 * 1024;
+use super::filesystem::{extract_content, is_allowed, normalize_content};
 
-/// GitHub provider that fetches repository files via GitHub REST v3.
+const DEFAULT_API_URL: &str = "https://api.github.com";
+const MAX_ATTEMPTS: u32 = 5;
+/// Longest rate-limit reset we wait for; beyond this the sync gives up until the next run.
+const MAX_RATE_LIMIT_WAIT_SECS: u64 = 120;
+const BLOB_CONCURRENCY: usize = 8;
+
+/// GitHub provider that lists a repository with the git trees API and downloads changed blobs.
+///
+/// Documents use the blob SHA as fingerprint, so unchanged blobs are skipped before download:
+/// against the stored fingerprints with a metadata store, otherwise against the last sync of
+/// this provider.
 pub struct GitHubProvider {
     pub repo: String,
+    /// REST API root; `GITHUB_API_URL` or `https://api.github.com` by default.
+    pub base_url: String,
+    /// Branch, tag or commit SHA to read.
+    pub reference: String,
+    pub store: Option<PostgresMetadataStore>,
+    /// Also ingest issues, pull requests and discussions as documents.
+    pub include_threads: bool,
+    /// Document paths of every file in the last tree listing; `None` unless it was complete.
+    listed: Mutex<Option<Vec<String>>>,
+    /// Thread `updated_at` cursor of the last load, recorded by `commit_sync`.
+    thread_cursor: Mutex<Option<String>>,
+    /// Blob SHAs by document path without a store: of the last load, and as last synced.
+    blob_shas: Mutex<BlobShas>,
+}
+
+#[derive(Default)]
+struct BlobShas {
+    pending: Option<HashMap<String, String>>,
+    synced: HashMap<String, String>,
+}
+
+/// Blob entry from a recursive tree listing.
+struct TreeBlob {
+    path: String,
+    sha: String,
+    size: u64,
 }
 
 impl GitHubProvider {
     pub fn new(repo: String) -> Self {
-        Self { repo }
-    }
-
-    fn list_files_recursive<'a>(
-        &'a self,
-        client: &'a reqwest::Client,
-        owner: &'a str,
-        repo: &'a str,
-        path: &'a str,
-    ) -> std::pin::Pin<Box<dyn std::future::Future<Output = Vec<(String, String)>> + Send + 'a>>
-    {
-        Box::pin(async move {
-            let url = format!("https://api.github.com/repos/{}/{}/contents/{}", owner, repo, path);
-            let mut attempt = 0;
-            let res = loop {
-                let resp = client.get(&url).send().await;
-                let Ok(resp) = resp else { return Vec::new() };
-                if resp.status() == reqwest::StatusCode::FORBIDDEN {
-                    if let Some(reset) = resp
-                        .headers()
-                        .get("X-RateLimit-Reset")
-                        .and_then(|h| h.to_str().ok())
-                        .and_then(|s| s.parse::<u64>().ok())
-                    {
-                        if let Ok(now) = SystemTime::now().duration_since(UNIX_EPOCH) {
-                            let wait_secs = reset.saturating_sub(now.as_secs()).max(1);
-                            tracing::warn!("GitHub rate limit hit, sleeping {}s", wait_secs);
-                            tokio::time::sleep(Duration::from_secs(wait_secs)).await;
-                            continue;
-                        }
-                    }
-                    let backoff = Duration::from_millis((2_u64.pow(attempt)).min(8000));
-                    tracing::warn!("GitHub 403, backoff {:?} attempt {}", backoff, attempt);
-                    tokio::time::sleep(backoff).await;
-                    attempt = attempt.saturating_add(1);
+        let base_url = std::env::var("GITHUB_API_URL").unwrap_or_else(|_| DEFAULT_API_URL.into());
+        Self {
+            repo,
+            base_url,
+            reference: "HEAD".into(),
+            store: None,
+            include_threads: false,
+            listed: Mutex::default(),
+            thread_cursor: Mutex::default(),
+            blob_shas: Mutex::default(),
+        }
+    }
+
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into();
+        self
+    }
+
+    pub fn with_reference(mut self, reference: impl Into<String>) -> Self {
+        self.reference = reference.into();
+        self
+    }
+
+    /// Skip blobs whose SHA matches the stored fingerprint of their document.
+    pub fn with_store(mut self, store: PostgresMetadataStore) -> Self {
+        self.store = Some(store);
+        self
+    }
+
+    /// Ingest issues, pull requests (with review comments) and discussions, incrementally by
+    /// `updated_at` when a store is set.
+    pub fn with_threads(mut self, include_threads: bool) -> Self {
+        self.include_threads = include_threads;
+        self
+    }
+
+    fn client(&self) -> Option<reqwest::Client> {
+        let token = std::env::var("GITHUB_TOKEN").unwrap_or_default();
+        let mut headers = reqwest::header::HeaderMap::new();
+        headers.insert(
+            reqwest::header::USER_AGENT,
+            reqwest::header::HeaderValue::from_static("gaia-mnemosyne"),
+        );
+        headers.insert(
+            reqwest::header::ACCEPT,
+            reqwest::header::HeaderValue::from_static("application/vnd.github+json"),
+        );
+        if !token.is_empty()
+            && let Ok(value) = reqwest::header::HeaderValue::from_str(&format!("token {}", token))
+        {
+            headers.insert(reqwest::header::AUTHORIZATION, value);
+        }
+        reqwest::Client::builder().default_headers(headers).build().ok()
+    }
+
+    pub(crate) fn api_url(&self, path: &str) -> String {
+        format!("{}/repos/{}/{}", self.base_url.trim_end_matches('/'), self.repo, path)
+    }
+
+    /// Document path for a repository file; unique across repositories.
+    pub fn document_path(&self, file_path: &str) -> String {
+        format!("github:{}/{}", self.repo, file_path)
+    }
+
+    /// Newest thread `updated_at` of the last load, if every thread listing completed; the
+    /// next load starts from it once `commit_sync` records it.
+    pub fn pending_thread_cursor(&self) -> Option<String> {
+        self.thread_cursor.lock().unwrap().clone()
+    }
+
+    pub(crate) fn set_thread_cursor(&self, cursor: Option<String>) {
+        *self.thread_cursor.lock().unwrap() = cursor;
+    }
+
+    /// Document paths of the files in the last tree listing, downloaded or not, unless the
+    /// listing failed or was truncated.
+    pub fn listed_paths(&self) -> Option<Vec<String>> {
+        self.listed.lock().unwrap().clone()
+    }
+
+    /// GET with bounded retries. Rate limits are waited out only up to
+    /// [`MAX_RATE_LIMIT_WAIT_SECS`]; other failures back off exponentially.
+    pub(crate) async fn get(
+        &self,
+        client: &reqwest::Client,
+        url: &str,
+        accept: Option<&'static str>,
+    ) -> Option<reqwest::Response> {
+        for attempt in 0..MAX_ATTEMPTS {
+            let mut request = client.get(url);
+            if let Some(accept) = accept {
+                request = request.header(reqwest::header::ACCEPT, accept);
+            }
+            let resp = match request.send().await {
+                Ok(resp) => resp,
+                Err(e) => {
+                    tracing::warn!("GitHub request failed ({}): {}", url, e);
+                    backoff(attempt).await;
                     continue;
                 }
-                break resp;
             };
-            let Ok(entries) = res.json::<serde_json::Value>().await else { return Vec::new() };
-            let mut files = Vec::new();
-            if let Some(arr) = entries.as_array() {
-                for item in arr {
-                    let item_type = item.get("type").and_then(|v| v.as_str()).unwrap_or("");
-                    let item_path =
-                        item.get("path").and_then(|v| v.as_str()).unwrap_or("").to_string();
-                    if item_type == "file" {
-                        if let Some(download_url) =
-                            item.get("download_url").and_then(|v| v.as_str())
-                        {
-                            if let Ok(resp) = client.get(download_url).send().await {
-                                if let Ok(content) = resp.text().await {
-                                    // rudimentary binary check: skip if contains null bytes
-                                    if !content.as_bytes().iter().any(|b| *b == 0) {
-                                        files.push((item_path, content));
-                                    }
-                                }
-                            }
-                        }
-                    } else if item_type == "dir" {
-                        let nested =
-                            self.list_files_recursive(client, owner, repo, &item_path).await;
-                        for n in nested {
-                            files.push(n);
-                        }
-                    }
+            let status = resp.status();
+            if status.is_success() {
+                return Some(resp);
+            }
+            let rate_limited = status == reqwest::StatusCode::TOO_MANY_REQUESTS
+                || (status == reqwest::StatusCode::FORBIDDEN
+                    && resp
+                        .headers()
+                        .get("X-RateLimit-Remaining")
+                        .is_some_and(|v| v.as_bytes() == b"0"));
+            if rate_limited {
+                let wait_secs = rate_limit_wait(resp.headers());
+                if wait_secs > MAX_RATE_LIMIT_WAIT_SECS {
+                    tracing::warn!(
+                        "GitHub rate limit resets in {}s; giving up on {}",
+                        wait_secs,
+                        url
+                    );
+                    return None;
                 }
+                tracing::warn!("GitHub rate limit hit, sleeping {}s", wait_secs);
+                tokio::time::sleep(Duration::from_secs(wait_secs)).await;
+                continue;
             }
-            files
+            if status.is_server_error() {
+                tracing::warn!("GitHub {} for {}, attempt {}", status, url, attempt + 1);
+                backoff(attempt).await;
+                continue;
+            }
+            tracing::warn!("GitHub {} for {}", status, url);
+            return None;
+        }
+        tracing::warn!("GitHub request gave up after {} attempts: {}", MAX_ATTEMPTS, url);
+        None
+    }
+
+    /// Tree SHA, blobs, and whether the listing is complete.
+    async fn list_tree(&self, client: &reqwest::Client) -> Option<(String, Vec<TreeBlob>, bool)> {
+        let url = self.api_url(&format!("git/trees/{}?recursive=1", self.reference));
+        let body = self.get(client, &url, None).await?.json::<serde_json::Value>().await.ok()?;
+        let truncated = body.get("truncated").and_then(|v| v.as_bool()).unwrap_or(false);
+        if truncated {
+            tracing::warn!(
+                "GitHub tree for {} is truncated; some files will be missing",
+                self.repo
+            );
+        }
+        let tree_sha = body.get("sha").and_then(|v| v.as_str()).unwrap_or_default().to_string();
+        let blobs = body
+            .get("tree")
+            .and_then(|v| v.as_array())
+            .map(|entries| {
+                entries
+                    .iter()
+                    .filter(|e| e.get("type").and_then(|v| v.as_str()) == Some("blob"))
+                    .filter_map(|e| {
+                        Some(TreeBlob {
+                            path: e.get("path")?.as_str()?.to_string(),
+                            sha: e.get("sha")?.as_str()?.to_string(),
+                            size: e.get("size").and_then(|v| v.as_u64()).unwrap_or(0),
+                        })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        Some((tree_sha, blobs, !truncated))
+    }
+
+    async fn is_unchanged(&self, blob: &TreeBlob) -> bool {
+        let path = self.document_path(&blob.path);
+        let Some(store) = &self.store else {
+            let shas = self.blob_shas.lock().unwrap();
+            return shas.synced.get(&path) == Some(&blob.sha);
+        };
+        matches!(store.get_fingerprint(&path).await, Ok(Some(hash)) if hash == blob.sha)
+    }
+
+    async fn load_blob(
+        &self,
+        client: &reqwest::Client,
+        blob: TreeBlob,
+        tree_sha: &str,
+    ) -> Option<Document> {
+        let url = self.api_url(&format!("git/blobs/{}", blob.sha));
+        let resp = self.get(client, &url, Some("application/vnd.github.raw")).await?;
+        let bytes = resp.bytes().await.ok()?.to_vec();
+        let file_path = Path::new(&blob.path);
+        let content = normalize_content(file_path, extract_content(file_path, bytes)?);
+        let file_type =
+            file_path.extension().and_then(|e| e.to_str()).map(|s| s.to_ascii_lowercase());
+        let language = detect_language(&blob.path, &content, file_type.as_deref());
+        Some(Document {
+            path: self.document_path(&blob.path),
+            content,
+            fingerprint: blob.sha.clone(),
+            namespace: self.repo.clone(),
+            modified_at: None,
+            file_size: Some(blob.size as i64),
+            file_type,
+            language,
+            metadata: Some(serde_json::json!({
+                "github_repo": self.repo,
+                "github_ref": self.reference,
+                "github_path": blob.path,
+                "blob_sha": blob.sha,
+                "tree_sha": tree_sha,
+            })),
         })
     }
 }
 
+async fn backoff(attempt: u32) {
+    tokio::time::sleep(Duration::from_millis(250 * 2_u64.pow(attempt)).min(Duration::from_secs(8)))
+        .await;
+}
+
+/// Seconds until the rate limit resets, from `Retry-After` or `X-RateLimit-Reset`.
+fn rate_limit_wait(headers: &reqwest::header::HeaderMap) -> u64 {
+    let header = |name: &str| {
+        headers.get(name).and_then(|h| h.to_str().ok()).and_then(|s| s.parse::<u64>().ok())
+    };
+    if let Some(secs) = header("Retry-After") {
+        return secs.max(1);
+    }
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+    header("X-RateLimit-Reset").map(|reset| reset.saturating_sub(now)).unwrap_or(60).max(1)
+}
+
 #[async_trait]
 impl super::registry::Provider for GitHubProvider {
     fn name(&self) -> String {
@@ -97,78 +292,83 @@ impl super::registry::Provider for GitHubProvider {
     }
 
     async fn load_documents(&self) -> Vec<Document> {
-        let token = std::env::var("GITHUB_TOKEN").unwrap_or_default();
-        let mut headers = reqwest::header::HeaderMap::new();
-        headers.insert(
-            reqwest::header::USER_AGENT,
-            reqwest::header::HeaderValue::from_static("gaia-mnemosyne"),
-        );
-        if !token.is_empty() {
-            if let Ok(value) = reqwest::header::HeaderValue::from_str(&format!("token {}", token)) {
-                headers.insert(reqwest::header::AUTHORIZATION, value);
+        *self.listed.lock().unwrap() = None;
+        self.blob_shas.lock().unwrap().pending = None;
+        let Some(client) = self.client() else { return Vec::new() };
+        if self.repo.split('/').count() != 2 {
+            tracing::error!("GitHub repo must be owner/name, got '{}'", self.repo);
+            return Vec::new();
+        }
+        let Some((tree_sha, blobs, complete)) = self.list_tree(&client).await else {
+            return Vec::new();
+        };
+
+        let mut wanted = Vec::new();
+        let mut listed = Vec::new();
+        let mut shas = HashMap::new();
+        for blob in blobs {
+            if !is_allowed(Path::new(&blob.path), blob.size) {
+                tracing::debug!("GitHub file rejected (ext/size): {}", blob.path);
+                continue;
+            }
+            listed.push(self.document_path(&blob.path));
+            shas.insert(self.document_path(&blob.path), blob.sha.clone());
+            if self.is_unchanged(&blob).await {
+                tracing::debug!("Skipping unchanged GitHub blob: {}", blob.path);
+                continue;
             }
+            wanted.push(blob);
+        }
+        if complete {
+            *self.listed.lock().unwrap() = Some(listed);
         }
+        if self.store.is_none() {
+            self.blob_shas.lock().unwrap().pending = Some(shas);
+        }
+        tracing::info!("GitHub {}: downloading {} changed blobs", self.repo, wanted.len());
 
-        let client = reqwest::Client::builder().default_headers(headers).build();
-        let Ok(client) = client else { return Vec::new() };
+        let mut docs: Vec<Document> = futures::stream::iter(wanted)
+            .map(|blob| self.load_blob(&client, blob, &tree_sha))
+            .buffer_unordered(BLOB_CONCURRENCY)
+            .filter_map(|doc| async move { doc })
+            .collect()
+            .await;
+        if self.include_threads {
+            docs.extend(super::github_threads::load_threads(self, &client).await);
+        }
+        tracing::info!("GitHubProvider produced {} documents", docs.len());
+        docs
+    }
 
-        let parts: Vec<&str> = self.repo.split('/').collect();
-        if parts.len() != 2 {
-            return Vec::new();
+    /// Repository files after a complete listing; threads live under `github:<repo>#` and
+    /// are not pruned.
+    fn prune_scope(&self) -> Option<super::registry::PruneScope> {
+        self.listed.lock().unwrap().as_ref()?;
+        Some(super::registry::PruneScope {
+            namespace: self.repo.clone(),
+            prefix: self.document_path(""),
+        })
+    }
+
+    fn retained_paths(&self) -> Vec<String> {
+        self.listed_paths().unwrap_or_default()
+    }
+
+    async fn commit_sync(&self) {
+        {
+            let mut shas = self.blob_shas.lock().unwrap();
+            if let Some(pending) = shas.pending.take() {
+                shas.synced = pending;
+            }
+        }
+        let Some(cursor) = self.thread_cursor.lock().unwrap().take() else { return };
+        if let Some(store) = &self.store
+            && let Err(e) = store
+                .set_sync_cursor(super::github_threads::SYNC_PROVIDER, &self.repo, &cursor)
+                .await
+        {
+            tracing::warn!("Failed to record GitHub thread cursor: {}", e);
         }
-        let owner = parts[0];
-        let repo = parts[1];
-
-        self.list_files_recursive(&client, owner, repo, "")
-            .await
-            .into_iter()
-            .map(|(path, content)| {
-                let mut docs = Vec::new();
-                let file_size = Some(content.len() as i64);
-                let file_type = path.split('.').last().map(|s| s.to_ascii_lowercase());
-                let language = detect_language(&path, &content, file_type.as_deref());
-                let metadata = None;
-                if content.len() > LARGE_THRESHOLD {
-                    let mut start = 0;
-                    let mut idx = 0;
-                    while start < content.len() {
-                        let end = (start + SEGMENT_SIZE).min(content.len());
-                        let seg = content[start..end].to_string();
-                        let mut hasher = Sha256::new();
-                        hasher.update(seg.as_bytes());
-                        docs.push(Document {
-                            path: format!("{}#segment_{}", path, idx),
-                            content: seg,
-                            fingerprint: format!("{:x}", hasher.finalize()),
-                            namespace: self.repo.clone(),
-                            modified_at: None,
-                            file_size,
-                            file_type: file_type.clone(),
-                            language: language.clone(),
-                            metadata: metadata.clone(),
-                        });
-                        idx += 1;
-                        start = end;
-                    }
-                } else {
-                    let mut hasher = Sha256::new();
-                    hasher.update(content.as_bytes());
-                    docs.push(Document {
-                        path,
-                        content,
-                        fingerprint: format!("{:x}", hasher.finalize()),
-                        namespace: self.repo.clone(),
-                        modified_at: None,
-                        file_size,
-                        file_type: file_type.clone(),
-                        language: language.clone(),
-                        metadata: metadata.clone(),
-                    });
-                }
-                docs
-            })
-            .flatten()
-            .collect()
     }
 }
 
@@ -185,6 +385,9 @@ fn detect_language(path: &str, content: &str, ext: Option<&str>) -> Option<Strin
             "md" => Some("markdown".into()),
             "json" => Some("json".into()),
             "yaml" | "yml" => Some("yaml".into()),
+            "pdf" => Some("pdf".into()),
+            "docx" => Some("docx".into()),
+            "ipynb" => Some("notebook".into()),
             _ => None,
         };
     }
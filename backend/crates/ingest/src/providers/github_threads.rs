@@ -0,0 +1,398 @@
+use chrono::{DateTime, Utc};
+use mnemo_core::models::document::Document;
+use serde_json::{Value, json};
+use sha2::{Digest, Sha256};
+
+use super::github::GitHubProvider;
+
+const PER_PAGE: usize = 100;
+const MAX_PAGES: usize = 50;
+const DISCUSSIONS_PER_PAGE: usize = 50;
+/// `provider_sync_state.provider` key; the scope is the `owner/name` repository.
+pub(crate) const SYNC_PROVIDER: &str = "github_threads";
+
+const DISCUSSIONS_QUERY: &str = r#"
+query($owner: String!, $name: String!, $after: String) {
+  repository(owner: $owner, name: $name) {
+    discussions(first: 50, after: $after, orderBy: {field: UPDATED_AT, direction: DESC}) {
+      pageInfo { hasNextPage endCursor }
+      nodes {
+        number title body url closed createdAt updatedAt
+        author { login }
+        category { name }
+        labels(first: 20) { nodes { name } }
+        comments(first: 50) { nodes { body createdAt author { login } } }
+      }
+    }
+  }
+}"#;
+
+/// Issue, pull request or discussion rendered as one Markdown document.
+struct Thread {
+    kind: ThreadKind,
+    number: u64,
+    title: String,
+    body: String,
+    author: String,
+    labels: Vec<String>,
+    state: String,
+    url: String,
+    created_at: String,
+    updated_at: String,
+    comments: Vec<Comment>,
+    /// Repository-relative paths of files a pull request touches.
+    linked_files: Vec<String>,
+}
+
+struct Comment {
+    author: String,
+    created_at: String,
+    body: String,
+    /// `path:line` for review comments.
+    location: Option<String>,
+}
+
+#[derive(Clone, Copy)]
+enum ThreadKind {
+    Issue,
+    PullRequest,
+    Discussion,
+}
+
+impl ThreadKind {
+    fn key(&self) -> &'static str {
+        match self {
+            ThreadKind::Issue => "issue",
+            ThreadKind::PullRequest => "pull_request",
+            ThreadKind::Discussion => "discussion",
+        }
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            ThreadKind::Issue => "Issue",
+            ThreadKind::PullRequest => "Pull request",
+            ThreadKind::Discussion => "Discussion",
+        }
+    }
+
+    /// Path segment matching the github.com URL layout.
+    fn path_segment(&self) -> &'static str {
+        match self {
+            ThreadKind::Issue => "issues",
+            ThreadKind::PullRequest => "pull",
+            ThreadKind::Discussion => "discussions",
+        }
+    }
+}
+
+impl Thread {
+    fn render(&self) -> String {
+        let mut out = format!("# {} #{}: {}\n\n", self.kind.label(), self.number, self.title);
+        out.push_str(&format!("State: {} · Author: {}", self.state, self.author));
+        if !self.labels.is_empty() {
+            out.push_str(&format!(" · Labels: {}", self.labels.join(", ")));
+        }
+        out.push_str("\n\n");
+        if !self.body.trim().is_empty() {
+            out.push_str(self.body.trim());
+            out.push_str("\n\n");
+        }
+        if !self.linked_files.is_empty() {
+            out.push_str("## Files\n\n");
+            for file in &self.linked_files {
+                out.push_str(&format!("- {file}\n"));
+            }
+            out.push('\n');
+        }
+        if !self.comments.is_empty() {
+            out.push_str("## Comments\n\n");
+            for comment in &self.comments {
+                match &comment.location {
+                    Some(location) => out.push_str(&format!(
+                        "### {} on {} ({})\n\n",
+                        comment.author, location, comment.created_at
+                    )),
+                    None => out
+                        .push_str(&format!("### {} ({})\n\n", comment.author, comment.created_at)),
+                }
+                out.push_str(comment.body.trim());
+                out.push_str("\n\n");
+            }
+        }
+        out.trim_end().to_string()
+    }
+
+    fn into_document(self, provider: &GitHubProvider) -> Document {
+        let content = self.render();
+        let mut hasher = Sha256::new();
+        hasher.update(content.as_bytes());
+        let linked_files: Vec<String> =
+            self.linked_files.iter().map(|f| provider.document_path(f)).collect();
+        Document {
+            // Kept apart from repository file paths (`github:<repo>/...`).
+            path: format!("github:{}#{}/{}", provider.repo, self.kind.path_segment(), self.number),
+            fingerprint: format!("{:x}", hasher.finalize()),
+            file_size: Some(content.len() as i64),
+            content,
+            namespace: provider.repo.clone(),
+            modified_at: DateTime::parse_from_rfc3339(&self.updated_at)
+                .ok()
+                .map(|t| t.with_timezone(&Utc)),
+            file_type: Some(self.kind.key().into()),
+            // Thread bodies are Markdown; this routes them to heading-aware chunking.
+            language: Some("markdown".into()),
+            metadata: Some(json!({
+                "github_repo": provider.repo,
+                "kind": self.kind.key(),
+                "number": self.number,
+                "title": self.title,
+                "author": self.author,
+                "labels": self.labels,
+                "state": self.state,
+                "url": self.url,
+                "created_at": self.created_at,
+                "updated_at": self.updated_at,
+                "linked_files": linked_files,
+            })),
+        }
+    }
+}
+
+/// Load issues, pull requests (with review comments and touched files) and discussions
+/// updated since the last sync.
+///
+/// The `updated_at` cursor is only offered to `commit_sync` when every listing completed, so a
+/// failed or truncated run is retried from the same point.
+pub(crate) async fn load_threads(
+    provider: &GitHubProvider,
+    client: &reqwest::Client,
+) -> Vec<Document> {
+    provider.set_thread_cursor(None);
+    let since = match &provider.store {
+        Some(store) => store.get_sync_cursor(SYNC_PROVIDER, &provider.repo).await.ok().flatten(),
+        None => None,
+    };
+    let (mut threads, issues_complete) = issue_threads(provider, client, since.as_deref()).await;
+    let (discussions, discussions_complete) =
+        discussion_threads(provider, client, since.as_deref()).await;
+    threads.extend(discussions);
+
+    // GitHub timestamps are UTC `...Z` strings, so they order lexicographically.
+    if issues_complete && discussions_complete {
+        provider.set_thread_cursor(threads.iter().map(|t| t.updated_at.clone()).max());
+    }
+    tracing::info!("GitHub {}: {} issue/PR/discussion threads", provider.repo, threads.len());
+    threads.into_iter().map(|t| t.into_document(provider)).collect()
+}
+
+async fn get_json(provider: &GitHubProvider, client: &reqwest::Client, url: &str) -> Option<Value> {
+    provider.get(client, url, None).await?.json::<Value>().await.ok()
+}
+
+/// Pages of a list endpoint, up to [`MAX_PAGES`], and whether they were all fetched.
+async fn get_list(
+    provider: &GitHubProvider,
+    client: &reqwest::Client,
+    path: &str,
+) -> (Vec<Value>, bool) {
+    let mut items = Vec::new();
+    let separator = if path.contains('?') { '&' } else { '?' };
+    for page in 1..=MAX_PAGES {
+        let url = provider.api_url(&format!("{path}{separator}per_page={PER_PAGE}&page={page}"));
+        let Some(batch) =
+            get_json(provider, client, &url).await.and_then(|v| v.as_array().cloned())
+        else {
+            return (items, false);
+        };
+        let done = batch.len() < PER_PAGE;
+        items.extend(batch);
+        if done {
+            return (items, true);
+        }
+    }
+    tracing::warn!("GitHub listing {} stopped after {} pages", path, MAX_PAGES);
+    (items, false)
+}
+
+async fn issue_threads(
+    provider: &GitHubProvider,
+    client: &reqwest::Client,
+    since: Option<&str>,
+) -> (Vec<Thread>, bool) {
+    let mut path = "issues?state=all&sort=updated&direction=asc".to_string();
+    if let Some(since) = since {
+        path.push_str(&format!("&since={since}"));
+    }
+    let (items, mut complete) = get_list(provider, client, &path).await;
+
+    let mut threads = Vec::new();
+    for item in items {
+        let number = item.get("number").and_then(|v| v.as_u64()).unwrap_or(0);
+        let is_pr = item.get("pull_request").is_some();
+        let merged = item
+            .get("pull_request")
+            .and_then(|pr| pr.get("merged_at"))
+            .is_some_and(|v| !v.is_null());
+        let mut thread = Thread {
+            kind: if is_pr { ThreadKind::PullRequest } else { ThreadKind::Issue },
+            number,
+            title: str_field(&item, "title"),
+            body: str_field(&item, "body"),
+            author: login(&item["user"]),
+            labels: names(&item["labels"]),
+            state: if merged { "merged".into() } else { str_field(&item, "state") },
+            url: str_field(&item, "html_url"),
+            created_at: str_field(&item, "created_at"),
+            updated_at: str_field(&item, "updated_at"),
+            comments: Vec::new(),
+            linked_files: Vec::new(),
+        };
+
+        if item.get("comments").and_then(|v| v.as_u64()).unwrap_or(0) > 0 {
+            let (comments, fetched) =
+                get_list(provider, client, &format!("issues/{number}/comments")).await;
+            complete &= fetched;
+            thread.comments.extend(comments.iter().map(|c| Comment {
+                author: login(&c["user"]),
+                created_at: str_field(c, "created_at"),
+                body: str_field(c, "body"),
+                location: None,
+            }));
+        }
+        if is_pr {
+            let (files, fetched) =
+                get_list(provider, client, &format!("pulls/{number}/files")).await;
+            complete &= fetched;
+            thread.linked_files = files.iter().map(|f| str_field(f, "filename")).collect();
+            let (reviews, fetched) =
+                get_list(provider, client, &format!("pulls/{number}/comments")).await;
+            complete &= fetched;
+            thread.comments.extend(reviews.iter().map(|c| {
+                let line = c.get("line").or_else(|| c.get("original_line"));
+                let path = str_field(c, "path");
+                Comment {
+                    author: login(&c["user"]),
+                    created_at: str_field(c, "created_at"),
+                    body: str_field(c, "body"),
+                    location: Some(match line.and_then(|l| l.as_u64()) {
+                        Some(line) => format!("{path}:{line}"),
+                        None => path,
+                    }),
+                }
+            }));
+        }
+        thread.comments.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+        threads.push(thread);
+    }
+    (threads, complete)
+}
+
+/// GraphQL endpoint next to the REST root (`/graphql`, or `/api/graphql` on GitHub Enterprise).
+fn graphql_url(base_url: &str) -> String {
+    let base = base_url.trim_end_matches('/');
+    match base.strip_suffix("/api/v3") {
+        Some(host) => format!("{host}/api/graphql"),
+        None => format!("{base}/graphql"),
+    }
+}
+
+async fn discussion_threads(
+    provider: &GitHubProvider,
+    client: &reqwest::Client,
+    since: Option<&str>,
+) -> (Vec<Thread>, bool) {
+    let Some((owner, name)) = provider.repo.split_once('/') else {
+        return (Vec::new(), true);
+    };
+    let url = graphql_url(&provider.base_url);
+    let mut threads = Vec::new();
+    let mut after: Option<String> = None;
+
+    for _ in 0..MAX_PAGES {
+        let body = json!({
+            "query": DISCUSSIONS_QUERY,
+            "variables": { "owner": owner, "name": name, "after": after },
+        });
+        let resp = match client.post(&url).json(&body).send().await {
+            Ok(resp) if resp.status().is_success() => resp,
+            Ok(resp) => {
+                tracing::warn!("GitHub discussions query failed: {}", resp.status());
+                return (threads, false);
+            }
+            Err(e) => {
+                tracing::warn!("GitHub discussions query failed: {}", e);
+                return (threads, false);
+            }
+        };
+        let Ok(value) = resp.json::<Value>().await else { return (threads, false) };
+        let discussions = &value["data"]["repository"]["discussions"];
+        if discussions.is_null() {
+            // Discussions disabled for the repository, or not visible to the token.
+            tracing::debug!("No discussions available for {}", provider.repo);
+            return (threads, true);
+        }
+        let nodes = discussions["nodes"].as_array().cloned().unwrap_or_default();
+        let page_len = nodes.len();
+        for node in nodes {
+            let updated_at = str_field(&node, "updatedAt");
+            // Newest first: everything after this was covered by the previous sync.
+            if since.is_some_and(|s| updated_at.as_str() < s) {
+                return (threads, true);
+            }
+            threads.push(Thread {
+                kind: ThreadKind::Discussion,
+                number: node.get("number").and_then(|v| v.as_u64()).unwrap_or(0),
+                title: str_field(&node, "title"),
+                body: str_field(&node, "body"),
+                author: login(&node["author"]),
+                labels: names(&node["labels"]["nodes"]),
+                state: if node["closed"].as_bool().unwrap_or(false) {
+                    "closed".into()
+                } else {
+                    "open".into()
+                },
+                url: str_field(&node, "url"),
+                created_at: str_field(&node, "createdAt"),
+                updated_at,
+                comments: node["comments"]["nodes"]
+                    .as_array()
+                    .map(|comments| {
+                        comments
+                            .iter()
+                            .map(|c| Comment {
+                                author: login(&c["author"]),
+                                created_at: str_field(c, "createdAt"),
+                                body: str_field(c, "body"),
+                                location: None,
+                            })
+                            .collect()
+                    })
+                    .unwrap_or_default(),
+                linked_files: Vec::new(),
+            });
+        }
+        let page_info = &discussions["pageInfo"];
+        if page_len < DISCUSSIONS_PER_PAGE || !page_info["hasNextPage"].as_bool().unwrap_or(false) {
+            return (threads, true);
+        }
+        after = page_info["endCursor"].as_str().map(|s| s.to_string());
+    }
+    // Older discussions past the cap were not seen, so the cursor must not move past them.
+    tracing::warn!("GitHub discussions of {} stopped after {} pages", provider.repo, MAX_PAGES);
+    (threads, false)
+}
+
+fn str_field(value: &Value, key: &str) -> String {
+    value.get(key).and_then(|v| v.as_str()).unwrap_or_default().to_string()
+}
+
+fn login(user: &Value) -> String {
+    user.get("login").and_then(|v| v.as_str()).unwrap_or("unknown").to_string()
+}
+
+fn names(list: &Value) -> Vec<String> {
+    list.as_array()
+        .map(|items| items.iter().map(|i| str_field(i, "name")).filter(|n| !n.is_empty()).collect())
+        .unwrap_or_default()
+}
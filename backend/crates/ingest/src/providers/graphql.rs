@@ -0,0 +1,348 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use async_trait::async_trait;
+use graphql_parser::schema::{
+    Definition, Directive, Field, InputValue, Type, TypeDefinition, TypeExtension,
+};
+use mnemo_core::models::document::Document;
+use serde_json::{Map, json};
+
+use super::contract::{contract_document, fetch_sources};
+
+const SDL_EXTENSIONS: &[&str] = &["graphql", "graphqls", "gql"];
+
+/// Provider for GraphQL SDL: a schema file, URL, or a directory of `.graphql`/`.graphqls`/`.gql`
+/// files merged into one schema (`extend type` included).
+///
+/// Fields of the root types (`Query`, `Mutation`, `Subscription`, or those named by `schema`)
+/// become operation documents; every other type becomes a type document.
+pub struct GraphQlProvider {
+    pub source: String,
+}
+
+/// A named type merged across definitions and extensions.
+#[derive(Default)]
+struct SdlType {
+    kind: &'static str,
+    description: Option<String>,
+    interfaces: Vec<String>,
+    fields: Vec<SdlField>,
+    /// Union members.
+    members: Vec<String>,
+    /// Enum values with descriptions.
+    values: Vec<(String, Option<String>)>,
+}
+
+struct SdlField {
+    name: String,
+    description: Option<String>,
+    args: Vec<SdlField>,
+    /// Type as written, e.g. `[User!]!`.
+    type_label: String,
+    /// Innermost named type, e.g. `User`.
+    type_name: String,
+    default: Option<String>,
+    deprecated: bool,
+}
+
+impl SdlField {
+    fn signature(&self) -> String {
+        if self.args.is_empty() {
+            return self.name.clone();
+        }
+        let args: Vec<String> =
+            self.args.iter().map(|a| format!("{}: {}", a.name, a.type_label)).collect();
+        format!("{}({})", self.name, args.join(", "))
+    }
+
+    fn line(&self) -> String {
+        let mut line = format!("- `{}` ({})", self.signature(), self.type_label);
+        if let Some(default) = &self.default {
+            line.push_str(&format!(" = {default}"));
+        }
+        if self.deprecated {
+            line.push_str(", deprecated");
+        }
+        if let Some(description) = self.description.as_deref().map(one_line) {
+            line.push_str(&format!(": {description}"));
+        }
+        line
+    }
+}
+
+impl GraphQlProvider {
+    pub fn new(source: String) -> Self {
+        Self { source }
+    }
+
+    fn namespace(&self) -> String {
+        "graphql".to_string()
+    }
+
+    /// Documents for SDL given as `(file, text)` pairs forming one schema.
+    pub fn parse_schema(&self, files: &[(String, String)]) -> Vec<Document> {
+        let mut types: BTreeMap<String, SdlType> = BTreeMap::new();
+        let mut roots: BTreeMap<&'static str, String> = BTreeMap::new();
+        for (file, text) in files {
+            let document = match graphql_parser::parse_schema::<String>(text) {
+                Ok(document) => document,
+                Err(e) => {
+                    tracing::warn!("Skipping unparsable GraphQL schema {}: {}", file, e);
+                    continue;
+                }
+            };
+            for definition in document.definitions {
+                match definition {
+                    Definition::SchemaDefinition(schema) => {
+                        let named = [
+                            ("query", schema.query),
+                            ("mutation", schema.mutation),
+                            ("subscription", schema.subscription),
+                        ];
+                        for (operation, name) in named {
+                            if let Some(name) = name {
+                                roots.insert(operation, name);
+                            }
+                        }
+                    }
+                    Definition::TypeDefinition(definition) => {
+                        merge_definition(&mut types, definition)
+                    }
+                    Definition::TypeExtension(extension) => merge_extension(&mut types, extension),
+                    Definition::DirectiveDefinition(_) => {}
+                }
+            }
+        }
+        for (operation, default) in
+            [("query", "Query"), ("mutation", "Mutation"), ("subscription", "Subscription")]
+        {
+            roots.entry(operation).or_insert_with(|| default.to_string());
+        }
+
+        let namespace = self.namespace();
+        let mut docs = Vec::new();
+
+        for (operation, root) in &roots {
+            let Some(root_type) = types.get(root) else { continue };
+            for field in &root_type.fields {
+                let mut out = format!("# {root}.{}\n\n", field.name);
+                if let Some(description) = &field.description {
+                    out.push_str(&format!("{}\n\n", description.trim()));
+                }
+                out.push_str(&format!("Operation: {operation}\n"));
+                out.push_str(&format!(
+                    "Signature: `{}: {}`\n",
+                    field.signature(),
+                    field.type_label
+                ));
+                if field.deprecated {
+                    out.push_str("Deprecated\n");
+                }
+                if !field.args.is_empty() {
+                    out.push_str("\n## Arguments\n\n");
+                    for arg in &field.args {
+                        out.push_str(&arg.line());
+                        out.push('\n');
+                    }
+                }
+                out.push_str(&format!("\n## Returns\n\n`{}`", field.type_label));
+                if let Some(description) =
+                    types.get(&field.type_name).and_then(|t| t.description.as_deref())
+                {
+                    out.push_str(&format!(": {}", one_line(description)));
+                }
+                out.push('\n');
+
+                let used = field.args.iter().map(|a| &a.type_name).chain([&field.type_name]);
+                let mut meta = Map::new();
+                meta.insert("graphql_operation".into(), json!(operation));
+                meta.insert("graphql_root".into(), json!(root));
+                meta.insert("graphql_field".into(), json!(field.name));
+                meta.insert("return_type".into(), json!(field.type_label));
+                meta.insert("deprecated".into(), json!(field.deprecated));
+                meta.insert("schemas".into(), json!(linked(used, root, &types)));
+                docs.push(contract_document(
+                    format!("graphql {root}.{}", field.name),
+                    &out,
+                    "graphql",
+                    meta,
+                    &namespace,
+                ));
+            }
+        }
+
+        for (name, sdl_type) in &types {
+            if roots.values().any(|root| root == name) {
+                continue;
+            }
+            let mut out = format!("# {name}\n\n");
+            if let Some(description) = &sdl_type.description {
+                out.push_str(&format!("{}\n\n", description.trim()));
+            }
+            out.push_str(&format!("Kind: {}\n", sdl_type.kind));
+            if !sdl_type.interfaces.is_empty() {
+                out.push_str(&format!("Implements: {}\n", sdl_type.interfaces.join(", ")));
+            }
+            if !sdl_type.members.is_empty() {
+                out.push_str(&format!("Members: {}\n", sdl_type.members.join(" | ")));
+            }
+            if !sdl_type.fields.is_empty() {
+                out.push_str("\n## Fields\n\n");
+                for field in &sdl_type.fields {
+                    out.push_str(&field.line());
+                    out.push('\n');
+                }
+            }
+            if !sdl_type.values.is_empty() {
+                out.push_str("\n## Values\n\n");
+                for (value, description) in &sdl_type.values {
+                    match description {
+                        Some(description) => {
+                            out.push_str(&format!("- `{value}`: {}\n", one_line(description)))
+                        }
+                        None => out.push_str(&format!("- `{value}`\n")),
+                    }
+                }
+            }
+
+            let used = sdl_type
+                .fields
+                .iter()
+                .flat_map(|f| f.args.iter().map(|a| &a.type_name).chain([&f.type_name]))
+                .chain(&sdl_type.interfaces)
+                .chain(&sdl_type.members);
+            let mut meta = Map::new();
+            meta.insert("graphql_kind".into(), json!(sdl_type.kind));
+            meta.insert("type_name".into(), json!(name));
+            meta.insert("schemas".into(), json!(linked(used, name, &types)));
+            docs.push(contract_document(type_path(name), &out, "graphql", meta, &namespace));
+        }
+
+        docs
+    }
+}
+
+fn type_path(name: &str) -> String {
+    format!("graphql type {name}")
+}
+
+/// Document paths of the schema's own types among `names`, other than `own`.
+fn linked<'t>(
+    names: impl Iterator<Item = &'t String>,
+    own: &str,
+    types: &BTreeMap<String, SdlType>,
+) -> Vec<String> {
+    let names: BTreeSet<&String> =
+        names.filter(|n| n.as_str() != own && types.contains_key(*n)).collect();
+    names.into_iter().map(|n| type_path(n)).collect()
+}
+
+fn one_line(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn named_type(ty: &Type<'_, String>) -> String {
+    match ty {
+        Type::NamedType(name) => name.clone(),
+        Type::ListType(inner) | Type::NonNullType(inner) => named_type(inner),
+    }
+}
+
+fn is_deprecated(directives: &[Directive<'_, String>]) -> bool {
+    directives.iter().any(|d| d.name == "deprecated")
+}
+
+fn convert_field(field: &Field<'_, String>) -> SdlField {
+    SdlField {
+        name: field.name.clone(),
+        description: field.description.clone(),
+        args: field.arguments.iter().map(convert_input).collect(),
+        type_label: field.field_type.to_string(),
+        type_name: named_type(&field.field_type),
+        default: None,
+        deprecated: is_deprecated(&field.directives),
+    }
+}
+
+fn convert_input(input: &InputValue<'_, String>) -> SdlField {
+    SdlField {
+        name: input.name.clone(),
+        description: input.description.clone(),
+        args: Vec::new(),
+        type_label: input.value_type.to_string(),
+        type_name: named_type(&input.value_type),
+        default: input.default_value.as_ref().map(|v| v.to_string()),
+        deprecated: is_deprecated(&input.directives),
+    }
+}
+
+fn merge_definition(types: &mut BTreeMap<String, SdlType>, definition: TypeDefinition<'_, String>) {
+    let (name, kind, description) = match &definition {
+        TypeDefinition::Scalar(t) => (&t.name, "scalar", &t.description),
+        TypeDefinition::Object(t) => (&t.name, "object", &t.description),
+        TypeDefinition::Interface(t) => (&t.name, "interface", &t.description),
+        TypeDefinition::Union(t) => (&t.name, "union", &t.description),
+        TypeDefinition::Enum(t) => (&t.name, "enum", &t.description),
+        TypeDefinition::InputObject(t) => (&t.name, "input", &t.description),
+    };
+    // An extension may have been read before its definition in another file.
+    let entry = types.entry(name.clone()).or_default();
+    entry.kind = kind;
+    if description.is_some() {
+        entry.description = description.clone();
+    }
+    match &definition {
+        TypeDefinition::Object(t) => {
+            entry.interfaces.extend(t.implements_interfaces.iter().cloned());
+            entry.fields.extend(t.fields.iter().map(convert_field));
+        }
+        TypeDefinition::Interface(t) => entry.fields.extend(t.fields.iter().map(convert_field)),
+        TypeDefinition::Union(t) => entry.members.extend(t.types.iter().cloned()),
+        TypeDefinition::Enum(t) => {
+            entry.values.extend(t.values.iter().map(|v| (v.name.clone(), v.description.clone())))
+        }
+        TypeDefinition::InputObject(t) => entry.fields.extend(t.fields.iter().map(convert_input)),
+        TypeDefinition::Scalar(_) => {}
+    }
+}
+
+fn merge_extension(types: &mut BTreeMap<String, SdlType>, extension: TypeExtension<'_, String>) {
+    let name = match &extension {
+        TypeExtension::Scalar(t) => &t.name,
+        TypeExtension::Object(t) => &t.name,
+        TypeExtension::Interface(t) => &t.name,
+        TypeExtension::Union(t) => &t.name,
+        TypeExtension::Enum(t) => &t.name,
+        TypeExtension::InputObject(t) => &t.name,
+    };
+    let entry = types.entry(name.clone()).or_default();
+    match &extension {
+        TypeExtension::Object(t) => {
+            entry.interfaces.extend(t.implements_interfaces.iter().cloned());
+            entry.fields.extend(t.fields.iter().map(convert_field));
+        }
+        TypeExtension::Interface(t) => entry.fields.extend(t.fields.iter().map(convert_field)),
+        TypeExtension::Union(t) => entry.members.extend(t.types.iter().cloned()),
+        TypeExtension::Enum(t) => {
+            entry.values.extend(t.values.iter().map(|v| (v.name.clone(), v.description.clone())))
+        }
+        TypeExtension::InputObject(t) => entry.fields.extend(t.fields.iter().map(convert_input)),
+        TypeExtension::Scalar(_) => {}
+    }
+}
+
+#[async_trait]
+impl super::registry::Provider for GraphQlProvider {
+    fn name(&self) -> String {
+        "graphql".into()
+    }
+
+    fn priority(&self) -> u8 {
+        5
+    }
+
+    async fn load_documents(&self) -> Vec<Document> {
+        let files = fetch_sources(&self.source, SDL_EXTENSIONS).await;
+        self.parse_schema(&files)
+    }
+}
@@ -0,0 +1,300 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use mailparse::{DispositionType, MailAddr, MailHeaderMap, ParsedMail};
+use mnemo_core::models::document::Document;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde_json::{Map, json};
+use sha2::{Digest, Sha256};
+use walkdir::WalkDir;
+
+use super::conversation::{Conversation, ConversationMessage, strip_quoted};
+use super::wiki::html_to_markdown;
+
+static MESSAGE_ID_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"<([^<>\s]+)>").unwrap());
+static REPLY_PREFIX_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)^\s*((re|fwd?|aw|sv)(\[\d+\])?\s*:\s*)+").unwrap());
+/// mboxrd escapes body lines starting with `From ` (after any `>`) with one more `>`.
+static ESCAPED_FROM_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?m)^>(>*From )").unwrap());
+
+/// Provider for email archives: an mbox file, or a Maildir (messages under `cur/` and `new/`,
+/// including nested folders). A directory of `.mbox` files is read as well.
+///
+/// Messages are grouped into threads by `References` and `In-Reply-To`; each thread becomes
+/// one document with quoted replies stripped.
+pub struct MailboxProvider {
+    pub path: String,
+    pub namespace: String,
+}
+
+struct Mail {
+    id: String,
+    in_reply_to: Option<String>,
+    /// Oldest first, so the first entry is the thread root.
+    references: Vec<String>,
+    subject: String,
+    from: String,
+    from_addr: Option<String>,
+    recipients: Vec<String>,
+    sent_at: Option<DateTime<Utc>>,
+    body: String,
+}
+
+impl MailboxProvider {
+    pub fn new(path: String, namespace: String) -> Self {
+        Self { path, namespace }
+    }
+
+    pub fn load_documents(&self) -> Vec<Document> {
+        let root = Path::new(&self.path);
+        if !root.exists() {
+            tracing::error!("Mailbox not found: {}", self.path);
+            return Vec::new();
+        }
+        let raw = if root.is_dir() { read_dir_messages(root) } else { read_mbox_file(root) };
+        let mails: Vec<Mail> = raw.iter().filter_map(|bytes| parse_message(bytes)).collect();
+        let threads = group_threads(mails);
+        tracing::info!(
+            "Mailbox {}: {} messages in {} threads",
+            self.path,
+            raw.len(),
+            threads.len()
+        );
+
+        threads
+            .into_iter()
+            .filter_map(|(root_id, mails)| {
+                let subject = mails
+                    .iter()
+                    .find(|m| m.id == root_id)
+                    .or_else(|| mails.first())
+                    .map(|m| REPLY_PREFIX_RE.replace(&m.subject, "").trim().to_string())
+                    .filter(|s| !s.is_empty())
+                    .unwrap_or_else(|| "(no subject)".into());
+                let mut addresses: Vec<String> = Vec::new();
+                for addr in mails.iter().filter_map(|m| m.from_addr.clone()) {
+                    if !addresses.contains(&addr) {
+                        addresses.push(addr);
+                    }
+                }
+                let mut recipients: Vec<String> = Vec::new();
+                for recipient in mails.iter().flat_map(|m| m.recipients.iter()) {
+                    if !recipients.contains(recipient) {
+                        recipients.push(recipient.clone());
+                    }
+                }
+                let mut extra = Map::new();
+                extra.insert("subject".into(), json!(subject));
+                extra.insert("thread_id".into(), json!(root_id));
+                extra.insert(
+                    "message_ids".into(),
+                    json!(mails.iter().map(|m| m.id.as_str()).collect::<Vec<_>>()),
+                );
+                extra.insert("participant_addresses".into(), json!(addresses));
+                extra.insert("recipients".into(), json!(recipients));
+                Conversation {
+                    path: format!("email:{}/{}", self.path, root_id),
+                    title: subject,
+                    channel: None,
+                    messages: mails
+                        .into_iter()
+                        .map(|m| ConversationMessage {
+                            author: m.from,
+                            author_id: m.from_addr,
+                            sent_at: m.sent_at,
+                            text: m.body,
+                        })
+                        .collect(),
+                    extra,
+                }
+                .into_document("email", &self.namespace)
+            })
+            .collect()
+    }
+}
+
+/// Raw messages of a Maildir tree, plus any mbox files found in it.
+fn read_dir_messages(root: &Path) -> Vec<Vec<u8>> {
+    let mut messages = Vec::new();
+    for entry in WalkDir::new(root)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter(|e| e.file_type().is_file())
+    {
+        let path = entry.path();
+        let in_maildir = path
+            .parent()
+            .and_then(|p| p.file_name())
+            .is_some_and(|name| name == "cur" || name == "new");
+        if in_maildir {
+            if let Ok(bytes) = std::fs::read(path) {
+                messages.push(bytes);
+            }
+        } else if path.extension().is_some_and(|ext| ext == "mbox") {
+            messages.extend(read_mbox_file(path));
+        }
+    }
+    messages
+}
+
+fn read_mbox_file(path: &Path) -> Vec<Vec<u8>> {
+    match std::fs::read(path) {
+        Ok(bytes) => split_mbox(&String::from_utf8_lossy(&bytes)),
+        Err(e) => {
+            tracing::warn!("Skipping unreadable mbox {}: {}", path.display(), e);
+            Vec::new()
+        }
+    }
+}
+
+/// Split an mbox on its `From ` separator lines, undoing `>From ` escaping.
+pub fn split_mbox(text: &str) -> Vec<Vec<u8>> {
+    let mut messages = Vec::new();
+    let mut current: Option<String> = None;
+    let mut previous_blank = true;
+    for line in text.split_inclusive('\n') {
+        if line.starts_with("From ") && previous_blank {
+            messages.extend(current.take());
+            current = Some(String::new());
+        } else if let Some(message) = current.as_mut() {
+            message.push_str(line);
+        }
+        previous_blank = line.trim_end_matches(['\r', '\n']).is_empty();
+    }
+    messages.extend(current);
+    messages
+        .into_iter()
+        .map(|m| ESCAPED_FROM_RE.replace_all(&m, "$1").into_owned().into_bytes())
+        .collect()
+}
+
+fn parse_message(bytes: &[u8]) -> Option<Mail> {
+    let parsed = mailparse::parse_mail(bytes).ok()?;
+    let header = |name: &str| parsed.headers.get_first_value(name).map(|v| v.trim().to_string());
+    let ids = |value: Option<String>| -> Vec<String> {
+        value
+            .map(|v| MESSAGE_ID_RE.captures_iter(&v).map(|c| c[1].to_string()).collect())
+            .unwrap_or_default()
+    };
+
+    let body = strip_quoted(&message_text(&parsed).unwrap_or_default());
+    let id = ids(header("Message-ID")).into_iter().next().unwrap_or_else(|| {
+        // Without an id the message can only root its own thread; hash it for a stable key.
+        let mut hasher = Sha256::new();
+        hasher.update(bytes);
+        format!("{:x}", hasher.finalize())
+    });
+    let (from, from_addr) = header("From")
+        .and_then(|f| mailparse::addrparse(&f).ok())
+        .and_then(|list| {
+            list.iter().find_map(|addr| match addr {
+                MailAddr::Single(info) => Some(info.clone()),
+                MailAddr::Group(group) => group.addrs.first().cloned(),
+            })
+        })
+        .map(|info| {
+            let addr = info.addr.to_lowercase();
+            (info.display_name.filter(|n| !n.trim().is_empty()).unwrap_or(addr.clone()), Some(addr))
+        })
+        .unwrap_or_else(|| ("unknown".into(), None));
+    let recipients = ["To", "Cc"]
+        .iter()
+        .filter_map(|name| header(name))
+        .filter_map(|value| mailparse::addrparse(&value).ok())
+        .flat_map(|list| {
+            list.iter()
+                .flat_map(|addr| match addr {
+                    MailAddr::Single(info) => vec![info.addr.to_lowercase()],
+                    MailAddr::Group(group) => {
+                        group.addrs.iter().map(|i| i.addr.to_lowercase()).collect()
+                    }
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect();
+
+    Some(Mail {
+        id,
+        in_reply_to: ids(header("In-Reply-To")).into_iter().next(),
+        references: ids(header("References")),
+        subject: header("Subject").unwrap_or_default(),
+        from,
+        from_addr,
+        recipients,
+        sent_at: header("Date")
+            .and_then(|d| mailparse::dateparse(&d).ok())
+            .and_then(|ts| DateTime::from_timestamp(ts, 0)),
+        body,
+    })
+}
+
+/// The message's readable text: the first inline `text/plain` part, else `text/html`
+/// converted to Markdown. Attachments are skipped.
+fn message_text(mail: &ParsedMail) -> Option<String> {
+    find_part(mail, "text/plain").and_then(|p| p.get_body().ok()).or_else(|| {
+        let html = find_part(mail, "text/html")?.get_body().ok()?;
+        Some(html_to_markdown(&html).markdown)
+    })
+}
+
+fn find_part<'a>(mail: &'a ParsedMail<'a>, mimetype: &str) -> Option<&'a ParsedMail<'a>> {
+    if matches!(mail.get_content_disposition().disposition, DispositionType::Attachment) {
+        return None;
+    }
+    if mail.subparts.is_empty() {
+        return mail.ctype.mimetype.eq_ignore_ascii_case(mimetype).then_some(mail);
+    }
+    mail.subparts.iter().find_map(|part| find_part(part, mimetype))
+}
+
+/// Group messages by thread root. `References` lists the root first; replies carrying only
+/// `In-Reply-To` follow their parents up the archive.
+fn group_threads(mails: Vec<Mail>) -> BTreeMap<String, Vec<Mail>> {
+    let by_id: HashMap<&str, &Mail> = mails.iter().map(|m| (m.id.as_str(), m)).collect();
+    let roots: Vec<String> = mails.iter().map(|m| thread_root(m, &by_id)).collect();
+    let mut threads: BTreeMap<String, Vec<Mail>> = BTreeMap::new();
+    for (root, mail) in roots.into_iter().zip(mails) {
+        threads.entry(root).or_default().push(mail);
+    }
+    threads
+}
+
+fn thread_root(mail: &Mail, by_id: &HashMap<&str, &Mail>) -> String {
+    let mut current = mail;
+    let mut seen = HashSet::new();
+    loop {
+        if let Some(first) = current.references.first() {
+            return first.clone();
+        }
+        let Some(parent) = current.in_reply_to.as_deref() else { return current.id.clone() };
+        match by_id.get(parent) {
+            Some(next) if seen.insert(parent) => current = next,
+            _ => return parent.to_string(),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl super::registry::Provider for MailboxProvider {
+    fn name(&self) -> String {
+        "mailbox".into()
+    }
+
+    fn priority(&self) -> u8 {
+        4
+    }
+
+    async fn load_documents(&self) -> Vec<Document> {
+        MailboxProvider::load_documents(self)
+    }
+
+    fn prune_scope(&self) -> Option<super::registry::PruneScope> {
+        Some(super::registry::PruneScope {
+            namespace: self.namespace.clone(),
+            prefix: format!("email:{}/", self.path),
+        })
+    }
+}
@@ -1,6 +1,23 @@
+pub mod archive;
+pub mod asyncapi;
+pub mod chat;
+pub mod confluence;
+pub(crate) mod contract;
+pub(crate) mod conversation;
 pub mod docx;
+pub(crate) mod file_filter;
 pub mod filesystem;
+pub mod git;
 pub mod github;
+pub mod github_threads;
+pub mod graphql;
+pub mod mailbox;
+pub mod notebook;
+pub mod notion;
+pub mod obsidian;
 pub mod openapi;
 pub mod pdf;
+pub mod protobuf;
 pub mod registry;
+pub mod tabular;
+pub mod wiki;
@@ -0,0 +1,221 @@
+use mnemo_core::models::document::Document;
+use mnemo_core::utils::text_normalizer::TextNormalizer;
+use serde_json::{Map, Value, json};
+use sha2::{Digest, Sha256};
+
+/// Separator between cells in extracted notebook text; `ChunkBuilder` numbers cells by it.
+///
+/// Each cell starts with a line holding its type (`markdown`, `code` or `raw`).
+pub const CELL_BREAK: char = '\u{0b}';
+
+/// Output text kept per code cell when no limit is configured.
+pub const DEFAULT_MAX_OUTPUT_CHARS: usize = 2_000;
+
+/// Size cap for a notebook file before outputs are stripped; embedded images make raw
+/// notebooks far larger than their text, which is held to the usual file cap instead.
+pub const MAX_NOTEBOOK_BYTES: u64 = 100 * 1024 * 1024;
+
+/// Output MIME types rendered as text, in order of preference. Images and other binary
+/// payloads are skipped.
+const TEXT_MIME_TYPES: &[&str] = &["text/markdown", "text/plain"];
+
+/// Provider for Jupyter notebooks (`.ipynb`), emitting markdown and code cells with their
+/// text outputs.
+pub struct NotebookProvider {
+    pub paths: Vec<String>,
+    pub namespace: String,
+    /// Per-cell output limit; `None` keeps outputs whole.
+    pub max_output_chars: Option<usize>,
+}
+
+impl NotebookProvider {
+    pub fn new(paths: Vec<String>, namespace: String) -> Self {
+        Self { paths, namespace, max_output_chars: Some(DEFAULT_MAX_OUTPUT_CHARS) }
+    }
+
+    pub fn with_max_output_chars(mut self, max_output_chars: Option<usize>) -> Self {
+        self.max_output_chars = max_output_chars;
+        self
+    }
+
+    pub fn load_documents(&self) -> Vec<Document> {
+        let mut docs = Vec::new();
+        for path in &self.paths {
+            let Ok(bytes) = std::fs::read(path) else {
+                continue;
+            };
+            let Some((text, metadata)) = extract_notebook(&bytes, self.max_output_chars) else {
+                tracing::warn!("Skipping unreadable notebook: {}", path);
+                continue;
+            };
+            let mut hasher = Sha256::new();
+            hasher.update(text.as_bytes());
+            docs.push(Document {
+                path: path.clone(),
+                content: text,
+                fingerprint: format!("{:x}", hasher.finalize()),
+                namespace: self.namespace.clone(),
+                modified_at: None,
+                file_size: Some(bytes.len() as i64),
+                file_type: Some("ipynb".into()),
+                language: Some("notebook".into()),
+                metadata: Some(Value::Object(metadata)),
+            });
+        }
+        docs
+    }
+}
+
+/// Render notebook cells joined by [`CELL_BREAK`], or `None` if the JSON is not a notebook.
+///
+/// Markdown and raw cells are normalized; code keeps its indentation and is fenced with the
+/// kernel language. Text outputs follow their code cell, cut to `max_output_chars`.
+pub fn extract_notebook_text(bytes: &[u8], max_output_chars: Option<usize>) -> Option<String> {
+    let notebook: Value = serde_json::from_slice(bytes).ok()?;
+    render_notebook(&notebook, max_output_chars)
+}
+
+/// [`extract_notebook_text`] and [`notebook_metadata`] from a single parse.
+pub fn extract_notebook(
+    bytes: &[u8],
+    max_output_chars: Option<usize>,
+) -> Option<(String, Map<String, Value>)> {
+    let notebook: Value = serde_json::from_slice(bytes).ok()?;
+    let text = render_notebook(&notebook, max_output_chars)?;
+    Some((text, metadata_of(&notebook)))
+}
+
+fn render_notebook(notebook: &Value, max_output_chars: Option<usize>) -> Option<String> {
+    let cells = notebook.get("cells")?.as_array()?;
+    let language = kernel_language(notebook).unwrap_or_default();
+    let rendered: Vec<String> = cells
+        .iter()
+        .map(|cell| {
+            let cell_type = cell.get("cell_type").and_then(|v| v.as_str()).unwrap_or("raw");
+            let source = multiline(cell.get("source"));
+            let body = match cell_type {
+                "code" => render_code(&source, &language, cell.get("outputs"), max_output_chars),
+                _ => TextNormalizer::normalize(&source),
+            };
+            format!("{cell_type}\n{}", body.replace(CELL_BREAK, " "))
+        })
+        .collect();
+    if rendered.is_empty() {
+        return None;
+    }
+    Some(rendered.join(&CELL_BREAK.to_string()))
+}
+
+/// Split text from [`extract_notebook_text`] into `(cell index, cell type, body)`.
+pub fn notebook_cells(text: &str) -> impl Iterator<Item = (usize, &str, &str)> {
+    text.split(CELL_BREAK).enumerate().map(|(idx, cell)| {
+        let (cell_type, body) = cell.split_once('\n').unwrap_or((cell, ""));
+        (idx, cell_type.trim(), body)
+    })
+}
+
+/// Kernel, language and cell counts.
+pub fn notebook_metadata(bytes: &[u8]) -> Map<String, Value> {
+    match serde_json::from_slice::<Value>(bytes) {
+        Ok(notebook) => metadata_of(&notebook),
+        Err(_) => Map::new(),
+    }
+}
+
+fn metadata_of(notebook: &Value) -> Map<String, Value> {
+    let mut meta = Map::new();
+    if let Some(language) = kernel_language(notebook) {
+        meta.insert("notebook_language".into(), json!(language));
+    }
+    if let Some(kernel) = notebook.pointer("/metadata/kernelspec/display_name") {
+        meta.insert("notebook_kernel".into(), kernel.clone());
+    }
+    let cells = notebook.get("cells").and_then(|c| c.as_array());
+    let count = |kind: &str| {
+        cells.map_or(0, |cells| {
+            cells
+                .iter()
+                .filter(|c| c.get("cell_type").and_then(|v| v.as_str()) == Some(kind))
+                .count()
+        })
+    };
+    meta.insert("cell_count".into(), json!(cells.map_or(0, |c| c.len())));
+    meta.insert("code_cells".into(), json!(count("code")));
+    meta.insert("markdown_cells".into(), json!(count("markdown")));
+    meta
+}
+
+fn kernel_language(notebook: &Value) -> Option<String> {
+    notebook
+        .pointer("/metadata/kernelspec/language")
+        .or_else(|| notebook.pointer("/metadata/language_info/name"))
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_ascii_lowercase())
+}
+
+/// nbformat stores multiline strings either whole or as a list of lines.
+fn multiline(value: Option<&Value>) -> String {
+    match value {
+        Some(Value::String(s)) => s.clone(),
+        Some(Value::Array(lines)) => lines.iter().filter_map(|l| l.as_str()).collect(),
+        _ => String::new(),
+    }
+}
+
+fn render_code(
+    source: &str,
+    language: &str,
+    outputs: Option<&Value>,
+    max_output_chars: Option<usize>,
+) -> String {
+    let outputs = outputs.and_then(|o| o.as_array()).map(|o| o.as_slice()).unwrap_or_default();
+    let text: Vec<String> = outputs.iter().filter_map(output_text).collect();
+    let mut text = text.join("\n").trim().to_string();
+    if source.trim().is_empty() && text.is_empty() {
+        return String::new();
+    }
+    let code: Vec<&str> = source.lines().map(|l| l.trim_end()).collect();
+    let mut out = format!("```{language}\n{}\n```", code.join("\n").trim_matches('\n'));
+    if text.is_empty() {
+        return out;
+    }
+    if let Some((cut, _)) = max_output_chars.and_then(|max| text.char_indices().nth(max)) {
+        text.truncate(cut);
+        text.push_str("\n… [output truncated]");
+    }
+    out.push_str(&format!("\n\nOutput:\n```\n{text}\n```"));
+    out
+}
+
+/// Text of one output; `None` for outputs with only binary data such as images.
+fn output_text(output: &Value) -> Option<String> {
+    match output.get("output_type").and_then(|v| v.as_str())? {
+        "stream" => Some(multiline(output.get("text"))),
+        "execute_result" | "display_data" => {
+            let data = output.get("data")?;
+            TEXT_MIME_TYPES.iter().find_map(|mime| data.get(*mime)).map(|v| multiline(Some(v)))
+        }
+        // Tracebacks are ANSI-colored frame dumps; the exception line is what matters.
+        "error" => {
+            let name = output.get("ename").and_then(|v| v.as_str()).unwrap_or("Error");
+            let value = output.get("evalue").and_then(|v| v.as_str()).unwrap_or_default();
+            Some(format!("{name}: {value}"))
+        }
+        _ => None,
+    }
+}
+
+#[async_trait::async_trait]
+impl super::registry::Provider for NotebookProvider {
+    fn name(&self) -> String {
+        "notebook".into()
+    }
+
+    fn priority(&self) -> u8 {
+        3
+    }
+
+    async fn load_documents(&self) -> Vec<Document> {
+        self.load_documents()
+    }
+}
@@ -0,0 +1,162 @@
+use std::collections::HashSet;
+use std::path::Path;
+
+use mnemo_core::models::document::Document;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde_json::{Map, json};
+
+use super::wiki::{ExportFiles, WikiAttachment, WikiPage, file_stem, into_documents, resolve_link};
+
+/// Notion appends a 32-hex-digit block id to every exported file and folder name.
+static NOTION_ID_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\s+([0-9a-f]{32})$").unwrap());
+static MD_LINK_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"!?\[[^\]]*\]\(([^)\s]+)(?:\s+"[^"]*")?\)"#).unwrap());
+
+/// Provider for a Notion "Markdown & CSV" export, as the downloaded `.zip` or unpacked.
+///
+/// Sub-pages live in a folder named like their parent page; databases are a CSV file plus a
+/// folder holding one Markdown page per row.
+pub struct NotionProvider {
+    pub export_path: String,
+    pub namespace: String,
+}
+
+impl NotionProvider {
+    pub fn new(export_path: String, namespace: String) -> Self {
+        Self { export_path, namespace }
+    }
+
+    pub fn load_documents(&self) -> Vec<Document> {
+        let files = match ExportFiles::open(Path::new(&self.export_path)) {
+            Ok(files) => files,
+            Err(e) => {
+                tracing::error!("Notion export unreadable ({}): {}", self.export_path, e);
+                return Vec::new();
+            }
+        };
+        let pages = notion_pages(&files);
+        tracing::info!("Notion export {}: {} pages", self.export_path, pages.len());
+        into_documents("notion", &self.export_path, &self.namespace, &files, pages)
+    }
+}
+
+/// Page title from a file stem, without the block id and the `_all` database suffix.
+fn notion_title(stem: &str) -> String {
+    NOTION_ID_RE.replace(stem.trim_end_matches("_all"), "").trim().to_string()
+}
+
+fn notion_id(stem: &str) -> Option<String> {
+    NOTION_ID_RE.captures(stem.trim_end_matches("_all")).map(|c| c[1].to_string())
+}
+
+fn notion_pages(files: &ExportFiles) -> Vec<WikiPage> {
+    let paths = files.paths();
+    let listed: HashSet<&str> = paths.iter().copied().collect();
+    // Newer exports write `<db>_all.csv` with every row next to the view-filtered `<db>.csv`.
+    let page_keys: Vec<&str> = paths
+        .iter()
+        .copied()
+        .filter(|p| {
+            p.ends_with(".md")
+                || p.ends_with("_all.csv")
+                || (p.ends_with(".csv")
+                    && !listed.contains(format!("{}_all.csv", &p[..p.len() - 4]).as_str()))
+        })
+        .collect();
+    let is_page: HashSet<&str> = page_keys.iter().copied().collect();
+
+    let mut pages = Vec::new();
+    for key in page_keys {
+        let Some(text) = files.text(key) else { continue };
+        let stem = file_stem(key);
+        let mut links = Vec::new();
+        let mut attachments = Vec::new();
+        let (title, markdown) = if key.ends_with(".csv") {
+            let title = notion_title(stem);
+            let table = csv_table(&text);
+            (title, table)
+        } else {
+            for caps in MD_LINK_RE.captures_iter(&text) {
+                let Some(target) = resolve_link(key, &caps[1]) else { continue };
+                if is_page.contains(target.as_str()) {
+                    links.push(target);
+                } else if files.contains(&target) {
+                    attachments.push(WikiAttachment { key: target.clone(), file: target });
+                }
+            }
+            let title = text
+                .lines()
+                .next()
+                .and_then(|l| l.strip_prefix("# "))
+                .map(|t| t.trim().to_string())
+                .unwrap_or_else(|| notion_title(stem));
+            (title, text)
+        };
+
+        let mut extra = Map::new();
+        if let Some(id) = notion_id(stem) {
+            extra.insert("notion_id".into(), json!(id));
+        }
+        if key.ends_with(".csv") {
+            extra.insert("notion_database".into(), json!(true));
+        }
+        let mut folders: Vec<String> = key.split('/').map(notion_title).collect();
+        folders.pop();
+        pages.push(WikiPage {
+            key: key.to_string(),
+            title,
+            markdown,
+            parent: parent_page(key, &is_page),
+            crumb_prefix: folders,
+            links,
+            attachments,
+            modified_at: files.modified(key),
+            extra,
+        });
+    }
+    pages
+}
+
+/// The page or database whose folder contains `key`.
+fn parent_page(key: &str, is_page: &HashSet<&str>) -> Option<String> {
+    let (dir, _) = key.rsplit_once('/')?;
+    [format!("{dir}.md"), format!("{dir}_all.csv"), format!("{dir}.csv")]
+        .into_iter()
+        .find(|candidate| is_page.contains(candidate.as_str()))
+}
+
+/// Render a database export as a Markdown table.
+fn csv_table(text: &str) -> String {
+    let mut reader =
+        csv::ReaderBuilder::new().flexible(true).has_headers(false).from_reader(text.as_bytes());
+    let cell = |value: &str| value.replace('|', "\\|").replace(['\r', '\n'], " ");
+    let mut out = String::new();
+    for (i, record) in reader.records().filter_map(Result::ok).enumerate() {
+        let cells: Vec<String> = record.iter().map(cell).collect();
+        out.push_str(&format!("| {} |\n", cells.join(" | ")));
+        if i == 0 {
+            out.push_str(&format!("|{}\n", " --- |".repeat(cells.len())));
+        }
+    }
+    out
+}
+
+#[async_trait::async_trait]
+impl super::registry::Provider for NotionProvider {
+    fn name(&self) -> String {
+        "notion".into()
+    }
+
+    fn priority(&self) -> u8 {
+        4
+    }
+
+    async fn load_documents(&self) -> Vec<Document> {
+        NotionProvider::load_documents(self)
+    }
+
+    fn prune_scope(&self) -> Option<super::registry::PruneScope> {
+        Some(super::wiki::export_scope("notion", &self.export_path, &self.namespace))
+    }
+}
@@ -0,0 +1,260 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use mnemo_core::models::document::Document;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde_json::{Map, Value, json};
+
+use super::wiki::{ExportFiles, WikiAttachment, WikiPage, file_stem, into_documents, resolve_link};
+
+/// `[[Target]]`, `[[Target#Heading|Alias]]` and `![[embed.png]]`.
+static WIKILINK_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(!?)\[\[([^\[\]|#^]*)(?:[#^][^\[\]|]*)?(?:\|([^\[\]]*))?\]\]").unwrap()
+});
+static MD_LINK_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"!?\[[^\]]*\]\(([^)\s]+)(?:\s+"[^"]*")?\)"#).unwrap());
+
+/// Provider for an Obsidian vault directory (or a zip of one).
+///
+/// Wikilinks resolve the way Obsidian does: by vault path when the target has a folder, else
+/// by note name or alias, preferring the linking note's folder and then the shortest path.
+/// Folder notes (`Folder/Folder.md` or `Folder.md` beside the folder) act as parent pages.
+pub struct ObsidianProvider {
+    pub vault_path: String,
+    pub namespace: String,
+}
+
+impl ObsidianProvider {
+    pub fn new(vault_path: String, namespace: String) -> Self {
+        Self { vault_path, namespace }
+    }
+
+    pub fn load_documents(&self) -> Vec<Document> {
+        let files = match ExportFiles::open(Path::new(&self.vault_path)) {
+            Ok(files) => files,
+            Err(e) => {
+                tracing::error!("Obsidian vault unreadable ({}): {}", self.vault_path, e);
+                return Vec::new();
+            }
+        };
+        let pages = vault_pages(&files);
+        tracing::info!("Obsidian vault {}: {} notes", self.vault_path, pages.len());
+        into_documents("obsidian", &self.vault_path, &self.namespace, &files, pages)
+    }
+}
+
+struct Note {
+    key: String,
+    frontmatter: Option<Value>,
+    body: String,
+}
+
+/// Vault file lookup for link resolution.
+struct VaultIndex<'a> {
+    /// Lowercased path, and path without `.md`, to the file.
+    by_path: HashMap<String, &'a str>,
+    /// Lowercased note name or alias (notes) or file name (other files) to candidates.
+    by_name: HashMap<String, Vec<&'a str>>,
+}
+
+impl VaultIndex<'_> {
+    fn resolve(&self, from: &str, target: &str) -> Option<String> {
+        let target = target.trim();
+        if target.is_empty() {
+            return None;
+        }
+        let lower = target.trim_start_matches('/').to_lowercase();
+        if lower.contains('/') {
+            return self.by_path.get(&lower).map(|k| k.to_string());
+        }
+        let candidates = self
+            .by_name
+            .get(&lower)
+            .or_else(|| self.by_name.get(lower.strip_suffix(".md").unwrap_or(&lower)))?;
+        let folder = from.rsplit_once('/').map(|(dir, _)| dir).unwrap_or_default();
+        candidates
+            .iter()
+            .min_by_key(|k| {
+                let same_folder =
+                    k.rsplit_once('/').map(|(dir, _)| dir).unwrap_or_default() == folder;
+                (!same_folder, k.len())
+            })
+            .map(|k| k.to_string())
+    }
+}
+
+fn is_hidden(key: &str) -> bool {
+    key.split('/').any(|segment| segment.starts_with('.'))
+}
+
+fn is_note(key: &str) -> bool {
+    key.to_ascii_lowercase().ends_with(".md")
+}
+
+/// Split YAML frontmatter from a note body.
+fn split_frontmatter(text: &str) -> (Option<Value>, String) {
+    let Some(rest) = text.strip_prefix("---\n").or_else(|| text.strip_prefix("---\r\n")) else {
+        return (None, text.to_string());
+    };
+    let Some(end) = rest.find("\n---") else { return (None, text.to_string()) };
+    let yaml = &rest[..end];
+    let body = rest[end + 4..].split_once('\n').map(|(_, body)| body).unwrap_or_default();
+    let frontmatter = serde_yaml::from_str::<serde_yaml::Value>(yaml)
+        .ok()
+        .and_then(|v| serde_json::to_value(v).ok())
+        .filter(|v| v.is_object());
+    (frontmatter, body.to_string())
+}
+
+/// A frontmatter field that may be a single string or a list of strings.
+fn string_list(frontmatter: Option<&Value>, key: &str) -> Vec<String> {
+    match frontmatter.and_then(|f| f.get(key)) {
+        Some(Value::String(s)) => {
+            s.split(',').map(|s| s.trim().to_string()).filter(|s| !s.is_empty()).collect()
+        }
+        Some(Value::Array(items)) => {
+            items.iter().filter_map(|i| i.as_str()).map(|s| s.trim().to_string()).collect()
+        }
+        _ => Vec::new(),
+    }
+}
+
+fn vault_pages(files: &ExportFiles) -> Vec<WikiPage> {
+    let paths: Vec<&str> = files.paths().into_iter().filter(|p| !is_hidden(p)).collect();
+    let notes: Vec<Note> = paths
+        .iter()
+        .filter(|p| is_note(p))
+        .filter_map(|key| {
+            let (frontmatter, body) = split_frontmatter(&files.text(key)?);
+            Some(Note { key: key.to_string(), frontmatter, body })
+        })
+        .collect();
+
+    let mut index = VaultIndex { by_path: HashMap::new(), by_name: HashMap::new() };
+    for key in paths.iter().copied() {
+        let lower = key.to_lowercase();
+        if is_note(key) {
+            index.by_path.insert(lower[..lower.len() - 3].to_string(), key);
+            index.by_name.entry(file_stem(&lower).to_string()).or_default().push(key);
+        } else {
+            let name = lower.rsplit('/').next().unwrap_or(&lower).to_string();
+            index.by_name.entry(name).or_default().push(key);
+        }
+        index.by_path.insert(lower, key);
+    }
+    for note in &notes {
+        for alias in string_list(note.frontmatter.as_ref(), "aliases") {
+            index.by_name.entry(alias.to_lowercase()).or_default().push(note.key.as_str());
+        }
+    }
+
+    notes
+        .iter()
+        .map(|note| {
+            let mut links = Vec::new();
+            let mut attachments = Vec::new();
+            let mut classify = |target: String| {
+                if is_note(&target) {
+                    links.push(target);
+                } else {
+                    attachments.push(WikiAttachment { key: target.clone(), file: target });
+                }
+            };
+            // Readable text: `[[Target|Alias]]` becomes `Alias`, `[[Target]]` becomes `Target`.
+            let body = WIKILINK_RE.replace_all(&note.body, |caps: &regex::Captures| {
+                let target = caps[2].trim();
+                if let Some(resolved) = index.resolve(&note.key, target) {
+                    classify(resolved);
+                }
+                match caps.get(3) {
+                    Some(alias) => alias.as_str().trim().to_string(),
+                    None if target.is_empty() => caps[0].trim_matches(['!', '[', ']']).to_string(),
+                    None => target.to_string(),
+                }
+            });
+            for caps in MD_LINK_RE.captures_iter(&body) {
+                let Some(target) = resolve_link(&note.key, &caps[1]) else { continue };
+                // Relative to the note first, then from the vault root.
+                let resolved = [target, caps[1].trim_start_matches('/').to_string()]
+                    .into_iter()
+                    .find_map(|t| index.by_path.get(&t.to_lowercase()).map(|k| k.to_string()));
+                if let Some(resolved) = resolved {
+                    classify(resolved);
+                }
+            }
+
+            let frontmatter = note.frontmatter.as_ref();
+            let title = frontmatter
+                .and_then(|f| f.get("title"))
+                .and_then(|t| t.as_str())
+                .map(|t| t.to_string())
+                .unwrap_or_else(|| file_stem(&note.key).to_string());
+            let mut extra = Map::new();
+            if let Some(frontmatter) = frontmatter {
+                extra.insert("frontmatter".into(), frontmatter.clone());
+            }
+            let tags = string_list(frontmatter, "tags");
+            if !tags.is_empty() {
+                extra.insert("tags".into(), json!(tags));
+            }
+            let aliases = string_list(frontmatter, "aliases");
+            if !aliases.is_empty() {
+                extra.insert("aliases".into(), json!(aliases));
+            }
+            let (parent, folders) = folder_parent(&note.key, &index);
+            WikiPage {
+                key: note.key.clone(),
+                title,
+                markdown: body.into_owned(),
+                parent,
+                crumb_prefix: folders,
+                links,
+                attachments,
+                modified_at: files.modified(&note.key),
+                extra,
+            }
+        })
+        .collect()
+}
+
+/// Folder note acting as parent of `key`, and the folder names above the note.
+///
+/// A folder note's own parent is looked up one folder higher.
+fn folder_parent(key: &str, index: &VaultIndex) -> (Option<String>, Vec<String>) {
+    let mut folders: Vec<&str> = key.split('/').collect();
+    let name = folders.pop().map(file_stem).unwrap_or_default();
+    if folders.last() == Some(&name) {
+        folders.pop();
+    }
+    let crumbs = folders.iter().map(|f| f.to_string()).collect();
+    let Some(folder) = folders.last() else { return (None, crumbs) };
+    let dir = folders.join("/");
+    let parent_dir = folders[..folders.len() - 1].join("/");
+    let candidates = [format!("{dir}/{folder}.md"), format!("{parent_dir}/{folder}.md")];
+    let parent = candidates
+        .iter()
+        .map(|c| c.trim_start_matches('/').to_lowercase())
+        .find_map(|c| index.by_path.get(&c).map(|k| k.to_string()))
+        .filter(|p| p != key);
+    (parent, crumbs)
+}
+
+#[async_trait::async_trait]
+impl super::registry::Provider for ObsidianProvider {
+    fn name(&self) -> String {
+        "obsidian".into()
+    }
+
+    fn priority(&self) -> u8 {
+        4
+    }
+
+    async fn load_documents(&self) -> Vec<Document> {
+        ObsidianProvider::load_documents(self)
+    }
+
+    fn prune_scope(&self) -> Option<super::registry::PruneScope> {
+        Some(super::wiki::export_scope("obsidian", &self.vault_path, &self.namespace))
+    }
+}
@@ -1,9 +1,27 @@
+use std::collections::BTreeSet;
+
 use async_trait::async_trait;
 use mnemo_core::models::document::Document;
-use mnemo_core::utils::text_normalizer::TextNormalizer;
-use sha2::{Digest, Sha256};
+use serde_json::{Map, Value, json};
+
+use super::contract::{contract_document, fetch_source, parse_structured};
+
+/// Path item keys that are operations; the others (`parameters`, `summary`, `servers`, ...)
+/// apply to every operation under the path.
+const HTTP_METHODS: &[&str] =
+    &["get", "put", "post", "delete", "options", "head", "patch", "trace"];
+
+/// Nesting depth at which property listings stop expanding object fields.
+const MAX_SCHEMA_DEPTH: usize = 3;
 
-/// Provider for ingesting OpenAPI specs from local files or URLs.
+/// Guard against `$ref` chains that loop back on themselves.
+const MAX_REF_HOPS: usize = 16;
+
+/// Provider for ingesting OpenAPI 3.x and Swagger 2.0 specs from local files or URLs.
+///
+/// Emits one document per operation (parameters, request body and responses with their
+/// schemas inlined) and one per named schema, with local `$ref`s resolved. Document paths
+/// start with the spec's source, so specs sharing route or schema names stay apart.
 pub struct OpenApiProvider {
     pub source: String,
 }
@@ -17,102 +35,449 @@ impl OpenApiProvider {
         "openapi".to_string()
     }
 
-    async fn load_raw(&self) -> Option<String> {
-        if self.source.starts_with("http://") || self.source.starts_with("https://") {
-            let client = reqwest::Client::new();
-            client.get(&self.source).send().await.ok()?.text().await.ok()
-        } else {
-            std::fs::read_to_string(&self.source).ok()
+    pub fn parse_spec(&self, raw: &str) -> Vec<Document> {
+        let value = parse_structured(raw);
+        let spec = Spec::new(&value);
+        let namespace = self.namespace();
+        let mut docs = Vec::new();
+
+        if let Some(paths) = value.get("paths").and_then(|p| p.as_object()) {
+            for (route, item) in paths {
+                let item = spec.resolve(item);
+                for method in HTTP_METHODS {
+                    let Some(operation) = item.get(*method) else { continue };
+                    let (content, mut meta, used) = spec.operation(route, method, item, operation);
+                    meta.insert("schemas".into(), json!(self.schema_paths(&used)));
+                    docs.push(contract_document(
+                        self.doc_path(&format!("{} {}", method.to_uppercase(), route)),
+                        &content,
+                        "openapi",
+                        meta,
+                        &namespace,
+                    ));
+                }
+            }
         }
+
+        for (name, schema) in spec.schemas().into_iter().flatten() {
+            let (content, mut meta, mut used) = spec.schema_document(name, schema);
+            used.remove(name.as_str());
+            meta.insert("openapi_version".into(), json!(spec.version()));
+            meta.insert("api_title".into(), json!(value.pointer("/info/title")));
+            meta.insert("schemas".into(), json!(self.schema_paths(&used)));
+            docs.push(contract_document(
+                self.schema_path(name),
+                &content,
+                "openapi",
+                meta,
+                &namespace,
+            ));
+        }
+
+        docs
     }
 
-    fn parse_spec(&self, raw: &str) -> Vec<Document> {
-        // Try JSON first, then YAML.
-        let value: serde_json::Value = serde_json::from_str(raw).unwrap_or_else(|_| {
-            serde_yaml::from_str(raw)
-                .map(|v: serde_yaml::Value| {
-                    serde_json::to_value(v).unwrap_or(serde_json::Value::Null)
-                })
-                .unwrap_or(serde_json::Value::Null)
-        });
+    /// `<source>#<item>`, the document path of an operation or schema in this spec.
+    fn doc_path(&self, item: &str) -> String {
+        format!("{}#{}", self.source, item)
+    }
 
-        let mut docs = Vec::new();
-        let Some(paths) = value.get("paths") else { return docs };
-        let Some(paths_map) = paths.as_object() else { return docs };
+    /// Document path of a named schema.
+    fn schema_path(&self, name: &str) -> String {
+        self.doc_path(&format!("schema {name}"))
+    }
 
-        let namespace = self.namespace();
+    fn schema_paths(&self, names: &BTreeSet<String>) -> Vec<String> {
+        names.iter().map(|n| self.schema_path(n)).collect()
+    }
+}
 
-        for (route, entry) in paths_map {
-            if let Some(methods) = entry.as_object() {
-                for (method, meta) in methods {
-                    let method_upper = method.to_uppercase();
-                    let doc_path = format!("{} {}", method_upper, route);
-                    let description = meta
-                        .get("description")
-                        .and_then(|v| v.as_str())
-                        .or_else(|| meta.get("summary").and_then(|v| v.as_str()))
-                        .unwrap_or("")
-                        .to_string();
-
-                    let mut body = String::new();
-                    if !description.is_empty() {
-                        body.push_str(&format!("Description: {}\n", description));
-                    }
+/// A parsed spec with its version-specific layout.
+pub(crate) struct Spec<'a> {
+    pub(crate) root: &'a Value,
+    /// Swagger 2.0 keeps schemas under `definitions` and body schemas on parameters.
+    swagger: bool,
+    /// `$ref` prefix of named schemas.
+    schema_prefix: &'static str,
+}
 
-                    if let Some(params) = meta.get("parameters").and_then(|v| v.as_array()) {
-                        body.push_str("Parameters:\n");
-                        for p in params {
-                            let name = p.get("name").and_then(|v| v.as_str()).unwrap_or("");
-                            let desc = p.get("description").and_then(|v| v.as_str()).unwrap_or("");
-                            body.push_str(&format!("- {}: {}\n", name, desc));
-                        }
-                    }
+impl<'a> Spec<'a> {
+    fn new(root: &'a Value) -> Self {
+        let swagger =
+            root.get("swagger").and_then(|v| v.as_str()).is_some_and(|v| v.starts_with('2'));
+        let schema_prefix = if swagger { "#/definitions/" } else { "#/components/schemas/" };
+        Self { root, swagger, schema_prefix }
+    }
 
-                    if let Some(resps) = meta.get("responses").and_then(|v| v.as_object()) {
-                        body.push_str("Responses:\n");
-                        for (code, r) in resps {
-                            let desc = r.get("description").and_then(|v| v.as_str()).unwrap_or("");
-                            body.push_str(&format!("{}: {}\n", code, desc));
-                        }
-                    }
+    /// A JSON Schema based spec other than OpenAPI, such as AsyncAPI.
+    pub(crate) fn with_schema_prefix(root: &'a Value, schema_prefix: &'static str) -> Self {
+        Self { root, swagger: false, schema_prefix }
+    }
 
-                    // capture schemas
-                    if let Some(components) = value
-                        .get("components")
-                        .and_then(|c| c.get("schemas"))
-                        .and_then(|s| s.as_object())
-                    {
-                        body.push_str("Schemas:\n");
-                        for (name, schema) in components {
-                            let summary =
-                                schema.get("description").and_then(|v| v.as_str()).unwrap_or("");
-                            body.push_str(&format!("{}: {}\n", name, summary));
-                        }
-                    }
+    fn version(&self) -> Option<&'a str> {
+        self.root.get(if self.swagger { "swagger" } else { "openapi" }).and_then(|v| v.as_str())
+    }
+
+    /// Named schemas, from the section `$ref`s into it point at.
+    pub(crate) fn schemas(&self) -> Option<&'a Map<String, Value>> {
+        let pointer = self.schema_prefix.trim_start_matches('#').trim_end_matches('/');
+        self.root.pointer(pointer).and_then(|s| s.as_object())
+    }
+
+    /// Name of the schema a `$ref` points at, for references into the schema section.
+    fn schema_ref<'v>(&self, value: &'v Value) -> Option<&'v str> {
+        value.get("$ref")?.as_str()?.strip_prefix(self.schema_prefix)
+    }
+
+    /// Follow local `$ref`s; external references are left unresolved.
+    pub(crate) fn resolve(&self, mut value: &'a Value) -> &'a Value {
+        for _ in 0..MAX_REF_HOPS {
+            let target = value
+                .get("$ref")
+                .and_then(|r| r.as_str())
+                .and_then(|r| r.strip_prefix('#'))
+                .and_then(|pointer| self.root.pointer(pointer));
+            match target {
+                Some(target) => value = target,
+                None => break,
+            }
+        }
+        value
+    }
 
-                    let normalized = TextNormalizer::normalize(&body);
-                    let body_len = normalized.len() as i64;
-                    let mut hasher = Sha256::new();
-                    hasher.update(normalized.as_bytes());
-                    let fingerprint = format!("{:x}", hasher.finalize());
-
-                    docs.push(Document {
-                        path: doc_path,
-                        content: normalized,
-                        fingerprint,
-                        namespace: namespace.clone(),
-                        modified_at: None,
-                        file_size: Some(body_len),
-                        file_type: Some("openapi".to_string()),
-                        language: Some("openapi".to_string()),
-                        metadata: None,
-                    });
+    /// Markdown for one operation, its metadata, and the schemas it references.
+    fn operation(
+        &self,
+        route: &str,
+        method: &str,
+        item: &'a Value,
+        operation: &'a Value,
+    ) -> (String, Map<String, Value>, BTreeSet<String>) {
+        let mut writer = SchemaWriter::new(self);
+        let text = |key: &str| operation.get(key).and_then(|v| v.as_str()).map(|s| s.trim());
+        let mut out = format!("# {} {}\n\n", method.to_uppercase(), route);
+        for key in ["summary", "description"] {
+            if let Some(value) = text(key).filter(|v| !v.is_empty()) {
+                out.push_str(&format!("{value}\n\n"));
+            }
+        }
+        if let Some(id) = text("operationId") {
+            out.push_str(&format!("Operation ID: `{id}`\n"));
+        }
+        let tags: Vec<&str> = operation
+            .get("tags")
+            .and_then(|t| t.as_array())
+            .map(|t| t.iter().filter_map(|v| v.as_str()).collect())
+            .unwrap_or_default();
+        if !tags.is_empty() {
+            out.push_str(&format!("Tags: {}\n", tags.join(", ")));
+        }
+        let deprecated = operation.get("deprecated").and_then(|v| v.as_bool()).unwrap_or(false);
+        if deprecated {
+            out.push_str("Deprecated\n");
+        }
+
+        // Operation parameters override path-level ones with the same name and location.
+        let mut params: Vec<&'a Value> = Vec::new();
+        for source in [item.get("parameters"), operation.get("parameters")] {
+            for param in source.and_then(|p| p.as_array()).into_iter().flatten() {
+                let param = self.resolve(param);
+                params.retain(|existing| {
+                    existing.get("name") != param.get("name")
+                        || existing.get("in") != param.get("in")
+                });
+                params.push(param);
+            }
+        }
+        let listed: Vec<&'a Value> =
+            params.iter().copied().filter(|p| param_location(p) != "body").collect();
+        if !listed.is_empty() {
+            out.push_str("\n## Parameters\n\n");
+            for param in listed {
+                let name = param.get("name").and_then(|v| v.as_str()).unwrap_or_default();
+                // OpenAPI 3 puts the type in `schema` (or `content`); Swagger 2.0 on the parameter.
+                let schema = param
+                    .get("schema")
+                    .or_else(|| media_schema(param.get("content"), self).map(|(_, s)| s))
+                    .unwrap_or(param);
+                let mut line =
+                    format!("- `{name}` ({}, {}", param_location(param), writer.type_label(schema));
+                if param.get("required").and_then(|v| v.as_bool()).unwrap_or(false) {
+                    line.push_str(", required");
                 }
+                line.push(')');
+                push_description(&mut line, param.get("description"));
+                out.push_str(&line);
+                out.push('\n');
             }
         }
 
-        docs
+        let body = if self.swagger {
+            params.iter().find(|p| param_location(p) == "body").and_then(|p| {
+                let media = self.media_types(operation, "consumes");
+                Some((media, p.get("schema")?, *p))
+            })
+        } else {
+            operation.get("requestBody").map(|b| self.resolve(b)).and_then(|body| {
+                let (media, schema) = media_schema(body.get("content"), self)?;
+                Some((media, schema, body))
+            })
+        };
+        if let Some((media, schema, body)) = body {
+            out.push_str(&format!("\n## Request body{}\n\n", media_suffix(&media)));
+            if let Some(description) = body.get("description").and_then(|d| d.as_str()) {
+                out.push_str(&format!("{}\n\n", description.trim()));
+            }
+            writer.body(schema, &mut out);
+        }
+
+        if let Some(responses) = operation.get("responses").and_then(|r| r.as_object()) {
+            out.push_str("\n## Responses\n");
+            for (code, response) in responses {
+                let response = self.resolve(response);
+                let description =
+                    response.get("description").and_then(|d| d.as_str()).unwrap_or_default();
+                let (media, schema) = if self.swagger {
+                    (self.media_types(operation, "produces"), response.get("schema"))
+                } else {
+                    match media_schema(response.get("content"), self) {
+                        Some((media, schema)) => (media, Some(schema)),
+                        None => (Vec::new(), None),
+                    }
+                };
+                out.push_str(&format!(
+                    "\n### {code}: {}{}\n\n",
+                    description.trim(),
+                    media_suffix(&media)
+                ));
+                if let Some(schema) = schema {
+                    writer.body(schema, &mut out);
+                }
+            }
+        }
+
+        let mut meta = Map::new();
+        meta.insert("openapi_version".into(), json!(self.version()));
+        meta.insert("api_title".into(), json!(self.root.pointer("/info/title")));
+        meta.insert("method".into(), json!(method.to_uppercase()));
+        meta.insert("route".into(), json!(route));
+        meta.insert("operation_id".into(), json!(text("operationId")));
+        meta.insert("tags".into(), json!(tags));
+        meta.insert("deprecated".into(), json!(deprecated));
+        (out, meta, writer.used)
     }
+
+    /// Markdown for a named schema, its metadata, and the schemas it references.
+    pub(crate) fn schema_document(
+        &self,
+        name: &'a str,
+        schema: &'a Value,
+    ) -> (String, Map<String, Value>, BTreeSet<String>) {
+        let mut writer = SchemaWriter::new(self);
+        let resolved = self.resolve(schema);
+        let mut out = format!("# {name}\n\n");
+        if let Some(description) = resolved.get("description").and_then(|d| d.as_str()) {
+            out.push_str(&format!("{}\n\n", description.trim()));
+        }
+        out.push_str(&format!("Type: {}\n", writer.type_label(resolved)));
+        writer.properties(schema, "", 0, &mut vec![name]);
+        for line in writer.lines.drain(..) {
+            out.push_str(&line);
+            out.push('\n');
+        }
+        let mut meta = Map::new();
+        meta.insert("schema_name".into(), json!(name));
+        (out, meta, writer.used)
+    }
+
+    /// Swagger 2.0 media types from the operation, falling back to the spec-wide list.
+    fn media_types(&self, operation: &'a Value, key: &str) -> Vec<&'a str> {
+        operation
+            .get(key)
+            .or_else(|| self.root.get(key))
+            .and_then(|m| m.as_array())
+            .map(|m| m.iter().filter_map(|v| v.as_str()).collect())
+            .unwrap_or_default()
+    }
+}
+
+/// OpenAPI 3 `content` map: every media type, and the schema of the JSON one (else the first).
+fn media_schema<'a>(
+    content: Option<&'a Value>,
+    spec: &Spec<'a>,
+) -> Option<(Vec<&'a str>, &'a Value)> {
+    let content = content?.as_object()?;
+    let media: Vec<&'a str> = content.keys().map(|k| k.as_str()).collect();
+    let preferred = content
+        .iter()
+        .find(|(k, _)| k.contains("json"))
+        .or_else(|| content.iter().next())
+        .map(|(_, v)| spec.resolve(v))?;
+    Some((media, preferred.get("schema")?))
+}
+
+fn media_suffix(media: &[&str]) -> String {
+    if media.is_empty() { String::new() } else { format!(" ({})", media.join(", ")) }
+}
+
+fn push_description(line: &mut String, description: Option<&Value>) {
+    let description = description
+        .and_then(|d| d.as_str())
+        .map(|d| d.split_whitespace().collect::<Vec<_>>().join(" "));
+    if let Some(description) = description.filter(|d| !d.is_empty()) {
+        line.push_str(": ");
+        line.push_str(&description);
+    }
+}
+
+/// Renders schemas as flat property lists (`- \`owner.name\` (string, required): ...`), which
+/// survive whitespace normalization, and records every named schema reached.
+pub(crate) struct SchemaWriter<'s, 'a> {
+    spec: &'s Spec<'a>,
+    lines: Vec<String>,
+    pub(crate) used: BTreeSet<String>,
+}
+
+impl<'s, 'a> SchemaWriter<'s, 'a> {
+    pub(crate) fn new(spec: &'s Spec<'a>) -> Self {
+        Self { spec, lines: Vec::new(), used: BTreeSet::new() }
+    }
+
+    /// Request or response body: its type, then its fields.
+    pub(crate) fn body(&mut self, schema: &'a Value, out: &mut String) {
+        out.push_str(&format!("Schema: `{}`\n", self.type_label(schema)));
+        self.properties(schema, "", 0, &mut Vec::new());
+        for line in self.lines.drain(..) {
+            out.push_str(&line);
+            out.push('\n');
+        }
+    }
+
+    /// Short type description: a schema name, `array of X`, `string<date-time>`, enums.
+    fn type_label(&mut self, schema: &'a Value) -> String {
+        if let Some(name) = self.spec.schema_ref(schema) {
+            self.used.insert(name.to_string());
+            return name.to_string();
+        }
+        let schema = self.spec.resolve(schema);
+        for (key, separator) in [("oneOf", " | "), ("anyOf", " | "), ("allOf", " & ")] {
+            if let Some(options) = schema.get(key).and_then(|o| o.as_array()) {
+                let labels: Vec<String> = options.iter().map(|o| self.type_label(o)).collect();
+                return labels.join(separator);
+            }
+        }
+        let kind = match schema.get("type") {
+            Some(Value::String(kind)) => kind.clone(),
+            Some(Value::Array(kinds)) => {
+                kinds.iter().filter_map(|k| k.as_str()).collect::<Vec<_>>().join(" | ")
+            }
+            _ if schema.get("properties").is_some() => "object".into(),
+            _ => "any".into(),
+        };
+        let mut label = match (kind.as_str(), schema.get("items")) {
+            ("array", Some(items)) => format!("array of {}", self.type_label(items)),
+            _ => kind,
+        };
+        if let Some(format) = schema.get("format").and_then(|f| f.as_str()) {
+            label = format!("{label}<{format}>");
+        }
+        if let Some(values) = schema.get("enum").and_then(|e| e.as_array()) {
+            let values: Vec<String> = values
+                .iter()
+                .map(|v| v.as_str().map(|s| s.to_string()).unwrap_or_else(|| v.to_string()))
+                .collect();
+            label.push_str(&format!(", one of {}", values.join(" | ")));
+        }
+        if schema.get("nullable").and_then(|n| n.as_bool()).unwrap_or(false) {
+            label.push_str(", nullable");
+        }
+        label
+    }
+
+    /// One line per property, nested objects as dotted paths. `seen` holds the named schemas
+    /// being expanded, so recursive schemas stop at their first repetition.
+    fn properties(
+        &mut self,
+        schema: &'a Value,
+        prefix: &str,
+        depth: usize,
+        seen: &mut Vec<&'a str>,
+    ) {
+        let entered = self.spec.schema_ref(schema);
+        if let Some(name) = entered {
+            if seen.contains(&name) {
+                return;
+            }
+            seen.push(name);
+        }
+        let resolved = self.spec.resolve(schema);
+        if let Some(items) = resolved.get("items").filter(|_| is_array(resolved)) {
+            let prefix = format!("{}[].", prefix.trim_end_matches('.'));
+            self.properties(items, &prefix, depth, seen);
+        }
+        let mut props: Vec<(&'a str, &'a Value)> = Vec::new();
+        let mut required: Vec<&'a str> = Vec::new();
+        self.collect_properties(resolved, &mut props, &mut required, 0);
+        for (name, prop) in props {
+            let mut line = format!("- `{prefix}{name}` ({}", self.type_label(prop));
+            if required.contains(&name) {
+                line.push_str(", required");
+            }
+            line.push(')');
+            let description =
+                prop.get("description").or_else(|| self.spec.resolve(prop).get("description"));
+            push_description(&mut line, description);
+            self.lines.push(line);
+            if depth + 1 < MAX_SCHEMA_DEPTH {
+                self.properties(prop, &format!("{prefix}{name}."), depth + 1, seen);
+            }
+        }
+        if entered.is_some() {
+            seen.pop();
+        }
+    }
+
+    /// Properties and required names, merged across `allOf` parts.
+    fn collect_properties(
+        &mut self,
+        schema: &'a Value,
+        props: &mut Vec<(&'a str, &'a Value)>,
+        required: &mut Vec<&'a str>,
+        hops: usize,
+    ) {
+        if hops > MAX_REF_HOPS {
+            return;
+        }
+        for part in schema.get("allOf").and_then(|a| a.as_array()).into_iter().flatten() {
+            if let Some(name) = self.spec.schema_ref(part) {
+                self.used.insert(name.to_string());
+            }
+            self.collect_properties(self.spec.resolve(part), props, required, hops + 1);
+        }
+        for (name, prop) in
+            schema.get("properties").and_then(|p| p.as_object()).into_iter().flatten()
+        {
+            if !props.iter().any(|(existing, _)| existing == name) {
+                props.push((name.as_str(), prop));
+            }
+        }
+        required.extend(
+            schema
+                .get("required")
+                .and_then(|r| r.as_array())
+                .into_iter()
+                .flatten()
+                .filter_map(|r| r.as_str()),
+        );
+    }
+}
+
+fn param_location(param: &Value) -> &str {
+    param.get("in").and_then(|v| v.as_str()).unwrap_or_default()
+}
+
+fn is_array(schema: &Value) -> bool {
+    schema.get("type").and_then(|t| t.as_str()) == Some("array")
 }
 
 #[async_trait]
@@ -126,6 +491,14 @@ impl super::registry::Provider for OpenApiProvider {
     }
 
     async fn load_documents(&self) -> Vec<Document> {
-        if let Some(raw) = self.load_raw().await { self.parse_spec(&raw) } else { Vec::new() }
+        if let Some(raw) = fetch_source(&self.source).await {
+            self.parse_spec(&raw)
+        } else {
+            Vec::new()
+        }
+    }
+
+    fn prune_scope(&self) -> Option<super::registry::PruneScope> {
+        Some(super::registry::PruneScope { namespace: self.namespace(), prefix: self.doc_path("") })
     }
 }
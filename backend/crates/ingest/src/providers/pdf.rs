@@ -18,9 +18,8 @@ impl PdfProvider {
         for path in &self.paths {
             match std::fs::read(path) {
                 Ok(bytes) => {
-                    if let Ok(text) = pdf_extract::extract_text_from_mem(&bytes) {
-                        let normalized = TextNormalizer::normalize(&text);
-                        let pages = count_pdf_pages(&bytes);
+                    if let Some(normalized) = extract_pdf_text(path, &bytes) {
+                        let pages = normalized.split(PAGE_BREAK).count();
                         let title = extract_pdf_title(&bytes);
                         let mut hasher = Sha256::new();
                         hasher.update(normalized.as_bytes());
@@ -47,6 +46,39 @@ impl PdfProvider {
     }
 }
 
+/// Separator between pages in extracted PDF text; `ChunkBuilder` numbers pages by it.
+pub const PAGE_BREAK: char = '\u{0c}';
+
+/// Extract normalized text page by page, joined with [`PAGE_BREAK`].
+///
+/// The PDF parser can panic on malformed input, so the panic is contained here and the file is
+/// skipped instead of taking down the whole ingestion job.
+pub fn extract_pdf_text(path: &str, bytes: &[u8]) -> Option<String> {
+    let pages =
+        match std::panic::catch_unwind(|| pdf_extract::extract_text_from_mem_by_pages(bytes)) {
+            Ok(Ok(pages)) => pages,
+            Ok(Err(e)) => {
+                tracing::warn!("PDF extraction failed for {}: {}", path, e);
+                return None;
+            }
+            Err(_) => {
+                tracing::warn!("PDF parser panicked on {}; skipping file", path);
+                return None;
+            }
+        };
+    // Normalize per page so trimming never merges or drops page boundaries.
+    let text = pages
+        .iter()
+        .map(|page| TextNormalizer::normalize(page))
+        .collect::<Vec<_>>()
+        .join(&PAGE_BREAK.to_string());
+    if text.chars().all(|c| c.is_whitespace()) {
+        tracing::warn!("PDF has no extractable text: {}", path);
+        return None;
+    }
+    Some(text)
+}
+
 pub fn count_pdf_pages(bytes: &[u8]) -> usize {
     let hay = String::from_utf8_lossy(bytes).to_lowercase();
     hay.matches("/type /page").count()
@@ -0,0 +1,596 @@
+use std::collections::{BTreeSet, HashMap};
+
+use async_trait::async_trait;
+use mnemo_core::models::document::Document;
+use serde_json::{Map, json};
+
+use super::contract::{contract_document, fetch_sources};
+
+/// Provider for Protocol Buffers definitions: a `.proto` file, URL, or a directory of them.
+///
+/// Every rpc, message and enum becomes a document. Field and rpc types are resolved with
+/// protobuf scoping rules against all files of the source, so imports across files link up.
+pub struct ProtobufProvider {
+    pub source: String,
+}
+
+/// A token with the comments written around it.
+struct Token {
+    text: String,
+    line: usize,
+    /// Comment lines directly above the token.
+    leading: Vec<String>,
+    /// Comment on the same line after the token.
+    trailing: Option<String>,
+}
+
+struct ProtoField {
+    name: String,
+    label: Option<String>,
+    /// Type as written, e.g. `map<string, Item>`.
+    type_label: String,
+    /// Message or enum names the field refers to, unresolved.
+    type_refs: Vec<String>,
+    number: String,
+    oneof: Option<String>,
+    comment: Option<String>,
+}
+
+struct ProtoMessage {
+    full_name: String,
+    comment: Option<String>,
+    fields: Vec<ProtoField>,
+}
+
+struct ProtoEnum {
+    full_name: String,
+    comment: Option<String>,
+    values: Vec<(String, String, Option<String>)>,
+}
+
+struct ProtoRpc {
+    service: String,
+    service_comment: Option<String>,
+    name: String,
+    request: String,
+    request_stream: bool,
+    response: String,
+    response_stream: bool,
+    comment: Option<String>,
+}
+
+/// Definitions of one `.proto` file.
+#[derive(Default)]
+struct ProtoFile {
+    package: String,
+    messages: Vec<ProtoMessage>,
+    enums: Vec<ProtoEnum>,
+    rpcs: Vec<ProtoRpc>,
+}
+
+impl ProtobufProvider {
+    pub fn new(source: String) -> Self {
+        Self { source }
+    }
+
+    fn namespace(&self) -> String {
+        "protobuf".to_string()
+    }
+
+    /// Documents for `.proto` files given as `(file, text)` pairs.
+    pub fn parse_files(&self, files: &[(String, String)]) -> Vec<Document> {
+        let parsed: Vec<(&str, ProtoFile)> =
+            files.iter().map(|(path, text)| (path.as_str(), parse_proto(text))).collect();
+        // Fully qualified name -> document path, across all files.
+        let mut known: HashMap<String, String> = HashMap::new();
+        for (_, file) in &parsed {
+            for message in &file.messages {
+                known.insert(message.full_name.clone(), message_path(&message.full_name));
+            }
+            for proto_enum in &file.enums {
+                known.insert(proto_enum.full_name.clone(), enum_path(&proto_enum.full_name));
+            }
+        }
+        let messages: HashMap<&str, &ProtoMessage> = parsed
+            .iter()
+            .flat_map(|(_, f)| f.messages.iter().map(|m| (m.full_name.as_str(), m)))
+            .collect();
+        let namespace = self.namespace();
+        let mut docs = Vec::new();
+
+        for (path, file) in &parsed {
+            let base_meta = |kind: &str| {
+                let mut meta = Map::new();
+                meta.insert("proto_file".into(), json!(path));
+                meta.insert("proto_package".into(), json!(file.package));
+                meta.insert("proto_kind".into(), json!(kind));
+                meta
+            };
+
+            for rpc in &file.rpcs {
+                let request = resolve_type(&rpc.request, &rpc.service, &known);
+                let response = resolve_type(&rpc.response, &rpc.service, &known);
+                let short_service = rpc.service.rsplit('.').next().unwrap_or(&rpc.service);
+                let stream = |flag: bool| if flag { "stream " } else { "" };
+                let mut out = format!("# {short_service}.{}\n\n", rpc.name);
+                if let Some(comment) = &rpc.comment {
+                    out.push_str(&format!("{comment}\n\n"));
+                }
+                out.push_str(&format!("Service: `{}`\n", rpc.service));
+                if let Some(comment) = &rpc.service_comment {
+                    out.push_str(&format!("{comment}\n"));
+                }
+                out.push_str(&format!(
+                    "Signature: `rpc {}({}{}) returns ({}{})`\n",
+                    rpc.name,
+                    stream(rpc.request_stream),
+                    rpc.request,
+                    stream(rpc.response_stream),
+                    rpc.response
+                ));
+                for (heading, written, resolved) in
+                    [("Request", &rpc.request, &request), ("Response", &rpc.response, &response)]
+                {
+                    let name = resolved.as_ref().map(|(name, _)| name.as_str()).unwrap_or(written);
+                    out.push_str(&format!("\n## {heading} `{name}`\n\n"));
+                    if let Some(message) = messages.get(name) {
+                        if let Some(comment) = &message.comment {
+                            out.push_str(&format!("{comment}\n\n"));
+                        }
+                        for field in &message.fields {
+                            out.push_str(&field_line(field));
+                            out.push('\n');
+                        }
+                    }
+                }
+
+                let schemas: BTreeSet<&String> =
+                    [&request, &response].into_iter().flatten().map(|(_, path)| path).collect();
+                let mut meta = base_meta("rpc");
+                meta.insert("proto_service".into(), json!(rpc.service));
+                meta.insert("proto_method".into(), json!(rpc.name));
+                meta.insert("client_streaming".into(), json!(rpc.request_stream));
+                meta.insert("server_streaming".into(), json!(rpc.response_stream));
+                meta.insert("request_type".into(), json!(request.as_ref().map(|(n, _)| n)));
+                meta.insert("response_type".into(), json!(response.as_ref().map(|(n, _)| n)));
+                meta.insert("schemas".into(), json!(schemas));
+                docs.push(contract_document(
+                    format!("proto rpc {}/{}", rpc.service, rpc.name),
+                    &out,
+                    "protobuf",
+                    meta,
+                    &namespace,
+                ));
+            }
+
+            for message in &file.messages {
+                let mut out = format!("# {}\n\n", message.full_name);
+                if let Some(comment) = &message.comment {
+                    out.push_str(&format!("{comment}\n\n"));
+                }
+                if !message.fields.is_empty() {
+                    out.push_str("## Fields\n\n");
+                    for field in &message.fields {
+                        out.push_str(&field_line(field));
+                        out.push('\n');
+                    }
+                }
+                let schemas: BTreeSet<String> = message
+                    .fields
+                    .iter()
+                    .flat_map(|f| f.type_refs.iter())
+                    .filter_map(|t| resolve_type(t, &message.full_name, &known))
+                    .filter(|(name, _)| *name != message.full_name)
+                    .map(|(_, path)| path)
+                    .collect();
+                let mut meta = base_meta("message");
+                meta.insert("type_name".into(), json!(message.full_name));
+                meta.insert("schemas".into(), json!(schemas));
+                docs.push(contract_document(
+                    message_path(&message.full_name),
+                    &out,
+                    "protobuf",
+                    meta,
+                    &namespace,
+                ));
+            }
+
+            for proto_enum in &file.enums {
+                let mut out = format!("# {}\n\n", proto_enum.full_name);
+                if let Some(comment) = &proto_enum.comment {
+                    out.push_str(&format!("{comment}\n\n"));
+                }
+                out.push_str("## Values\n\n");
+                for (name, number, comment) in &proto_enum.values {
+                    out.push_str(&format!("- `{name}` = {number}"));
+                    if let Some(comment) = comment {
+                        out.push_str(&format!(": {comment}"));
+                    }
+                    out.push('\n');
+                }
+                let mut meta = base_meta("enum");
+                meta.insert("type_name".into(), json!(proto_enum.full_name));
+                docs.push(contract_document(
+                    enum_path(&proto_enum.full_name),
+                    &out,
+                    "protobuf",
+                    meta,
+                    &namespace,
+                ));
+            }
+        }
+
+        docs
+    }
+}
+
+fn message_path(full_name: &str) -> String {
+    format!("proto message {full_name}")
+}
+
+fn enum_path(full_name: &str) -> String {
+    format!("proto enum {full_name}")
+}
+
+fn field_line(field: &ProtoField) -> String {
+    let label = field.label.as_deref().map(|l| format!("{l} ")).unwrap_or_default();
+    let mut line = format!("- `{}` ({label}{}, {}", field.name, field.type_label, field.number);
+    if let Some(oneof) = &field.oneof {
+        line.push_str(&format!(", oneof {oneof}"));
+    }
+    line.push(')');
+    if let Some(comment) = &field.comment {
+        line.push_str(&format!(": {comment}"));
+    }
+    line
+}
+
+/// Resolve a type name as written inside `scope` (a package or message): the innermost scope
+/// defining it wins, and a leading `.` means fully qualified. Returns the full name and its
+/// document path, or `None` for scalars and types outside the source.
+fn resolve_type(
+    name: &str,
+    scope: &str,
+    known: &HashMap<String, String>,
+) -> Option<(String, String)> {
+    if let Some(qualified) = name.strip_prefix('.') {
+        return known.get(qualified).map(|path| (qualified.to_string(), path.clone()));
+    }
+    let parts: Vec<&str> = scope.split('.').filter(|p| !p.is_empty()).collect();
+    (0..=parts.len()).rev().find_map(|depth| {
+        let candidate = match depth {
+            0 => name.to_string(),
+            _ => format!("{}.{name}", parts[..depth].join(".")),
+        };
+        known.get(&candidate).map(|path| (candidate, path.clone()))
+    })
+}
+
+/// Split `.proto` source into tokens, attaching `//` and `/* */` comments to the token they
+/// document.
+fn tokenize(text: &str) -> Vec<Token> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut tokens: Vec<Token> = Vec::new();
+    let mut pending: Vec<String> = Vec::new();
+    let mut line = 1;
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c == '\n' {
+            line += 1;
+            // A blank line detaches comments from the next definition.
+            if chars.get(i + 1) == Some(&'\n') {
+                pending.clear();
+            }
+            i += 1;
+            continue;
+        }
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+        if c == '/' && matches!(chars.get(i + 1), Some('/') | Some('*')) {
+            let block = chars[i + 1] == '*';
+            let start = i + 2;
+            let end = if block {
+                (start..chars.len().saturating_sub(1))
+                    .find(|&j| chars[j] == '*' && chars[j + 1] == '/')
+                    .unwrap_or(chars.len())
+            } else {
+                (start..chars.len()).find(|&j| chars[j] == '\n').unwrap_or(chars.len())
+            };
+            let body: String = chars[start..end.min(chars.len())].iter().collect();
+            let comment = body
+                .lines()
+                .map(|l| l.trim().trim_start_matches('*').trim())
+                .filter(|l| !l.is_empty())
+                .collect::<Vec<_>>()
+                .join(" ");
+            let comment_line = line;
+            line += body.matches('\n').count();
+            i = if block { end + 2 } else { end };
+            if comment.is_empty() {
+                continue;
+            }
+            match tokens.last_mut() {
+                Some(last) if last.line == comment_line && pending.is_empty() => {
+                    last.trailing = Some(comment);
+                }
+                _ => pending.push(comment),
+            }
+            continue;
+        }
+        let start = i;
+        if c == '"' || c == '\'' {
+            i += 1;
+            while i < chars.len() && chars[i] != c {
+                i += if chars[i] == '\\' { 2 } else { 1 };
+            }
+            i += 1;
+        } else if c.is_alphanumeric() || c == '_' || c == '.' || c == '-' || c == '+' {
+            while i < chars.len()
+                && (chars[i].is_alphanumeric() || matches!(chars[i], '_' | '.' | '-' | '+'))
+            {
+                i += 1;
+            }
+        } else {
+            i += 1;
+        }
+        let text: String = chars[start..i.min(chars.len())].iter().collect();
+        tokens.push(Token { text, line, leading: std::mem::take(&mut pending), trailing: None });
+    }
+    tokens
+}
+
+/// Recursive-descent reader over tokens; unknown statements are skipped.
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+    file: ProtoFile,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&str> {
+        self.tokens.get(self.pos).map(|t| t.text.as_str())
+    }
+
+    fn next(&mut self) -> Option<String> {
+        let token = self.tokens.get(self.pos)?;
+        self.pos += 1;
+        Some(token.text.clone())
+    }
+
+    fn eat(&mut self, text: &str) -> bool {
+        if self.peek() == Some(text) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Leading comment of the token at `pos`, else the trailing comment of the statement ending
+    /// at or after `pos`.
+    fn comment_at(&self, pos: usize) -> Option<String> {
+        let token = self.tokens.get(pos)?;
+        if !token.leading.is_empty() {
+            return Some(token.leading.join(" "));
+        }
+        self.tokens[pos..]
+            .iter()
+            .take_while(|t| t.line == token.line)
+            .find_map(|t| t.trailing.clone())
+    }
+
+    /// Skip a statement up to its `;`, or a `{ ... }` block.
+    fn skip_statement(&mut self) {
+        let mut depth = 0usize;
+        while let Some(token) = self.next() {
+            match token.as_str() {
+                "{" => depth += 1,
+                "}" if depth <= 1 => return,
+                "}" => depth -= 1,
+                ";" if depth == 0 => return,
+                _ => {}
+            }
+        }
+    }
+
+    fn parse_file(mut self) -> ProtoFile {
+        while let Some(token) = self.peek() {
+            match token {
+                "package" => {
+                    self.pos += 1;
+                    self.file.package = self.next().unwrap_or_default();
+                    self.eat(";");
+                }
+                "message" => {
+                    let scope = self.file.package.clone();
+                    self.parse_message(&scope);
+                }
+                "enum" => {
+                    let scope = self.file.package.clone();
+                    self.parse_enum(&scope);
+                }
+                "service" => self.parse_service(),
+                ";" => self.pos += 1,
+                _ => self.skip_statement(),
+            }
+        }
+        self.file
+    }
+
+    fn parse_message(&mut self, scope: &str) {
+        let comment = self.comment_at(self.pos);
+        self.pos += 1;
+        let full_name = qualify(scope, &self.next().unwrap_or_default());
+        let mut fields = Vec::new();
+        if !self.eat("{") {
+            return;
+        }
+        let mut oneof: Option<String> = None;
+        while let Some(token) = self.peek() {
+            match token {
+                "}" => {
+                    self.pos += 1;
+                    if oneof.take().is_none() {
+                        break;
+                    }
+                }
+                "message" => self.parse_message(&full_name),
+                "enum" => self.parse_enum(&full_name),
+                "oneof" => {
+                    self.pos += 1;
+                    oneof = self.next();
+                    self.eat("{");
+                }
+                "option" | "reserved" | "extensions" | "extend" | "group" => self.skip_statement(),
+                ";" => self.pos += 1,
+                _ => match self.parse_field(oneof.clone()) {
+                    Some(field) => fields.push(field),
+                    None => self.skip_statement(),
+                },
+            }
+        }
+        self.file.messages.push(ProtoMessage { full_name, comment, fields });
+    }
+
+    /// `[label] type name = number [options];` or `map<K, V> name = number;`
+    fn parse_field(&mut self, oneof: Option<String>) -> Option<ProtoField> {
+        let start = self.pos;
+        let comment = self.comment_at(start);
+        let mut label = None;
+        if matches!(self.peek(), Some("repeated" | "optional" | "required")) {
+            label = self.next();
+        }
+        let (type_label, type_refs) = if self.eat("map") {
+            self.eat("<");
+            let key = self.next()?;
+            self.eat(",");
+            let value = self.next()?;
+            self.eat(">");
+            (format!("map<{key}, {value}>"), vec![key, value])
+        } else {
+            let ty = self.next()?;
+            (ty.clone(), vec![ty])
+        };
+        let name = self.next()?;
+        if !self.eat("=") {
+            self.pos = start;
+            return None;
+        }
+        let number = self.next()?;
+        if self.peek() == Some("[") {
+            while let Some(token) = self.next() {
+                if token == "]" {
+                    break;
+                }
+            }
+        }
+        self.eat(";");
+        Some(ProtoField { name, label, type_label, type_refs, number, oneof, comment })
+    }
+
+    fn parse_enum(&mut self, scope: &str) {
+        let comment = self.comment_at(self.pos);
+        self.pos += 1;
+        let full_name = qualify(scope, &self.next().unwrap_or_default());
+        let mut values = Vec::new();
+        if !self.eat("{") {
+            return;
+        }
+        while let Some(token) = self.peek() {
+            match token {
+                "}" => {
+                    self.pos += 1;
+                    break;
+                }
+                "option" | "reserved" => self.skip_statement(),
+                ";" => self.pos += 1,
+                _ => {
+                    let value_comment = self.comment_at(self.pos);
+                    let name = self.next().unwrap_or_default();
+                    if !self.eat("=") {
+                        continue;
+                    }
+                    let number = self.next().unwrap_or_default();
+                    values.push((name, number, value_comment));
+                    self.skip_statement();
+                }
+            }
+        }
+        self.file.enums.push(ProtoEnum { full_name, comment, values });
+    }
+
+    fn parse_service(&mut self) {
+        let service_comment = self.comment_at(self.pos);
+        self.pos += 1;
+        let name = self.next().unwrap_or_default();
+        let service = qualify(&self.file.package, &name);
+        if !self.eat("{") {
+            return;
+        }
+        while let Some(token) = self.peek() {
+            match token {
+                "}" => {
+                    self.pos += 1;
+                    break;
+                }
+                "rpc" => {
+                    let comment = self.comment_at(self.pos);
+                    self.pos += 1;
+                    let name = self.next().unwrap_or_default();
+                    self.eat("(");
+                    let request_stream = self.eat("stream");
+                    let request = self.next().unwrap_or_default();
+                    self.eat(")");
+                    self.eat("returns");
+                    self.eat("(");
+                    let response_stream = self.eat("stream");
+                    let response = self.next().unwrap_or_default();
+                    self.eat(")");
+                    // Either `;` or an options block.
+                    if !self.eat(";") && self.peek() == Some("{") {
+                        self.skip_statement();
+                    }
+                    self.file.rpcs.push(ProtoRpc {
+                        service: service.clone(),
+                        service_comment: service_comment.clone(),
+                        name,
+                        request,
+                        request_stream,
+                        response,
+                        response_stream,
+                        comment,
+                    });
+                }
+                ";" => self.pos += 1,
+                _ => self.skip_statement(),
+            }
+        }
+    }
+}
+
+fn qualify(scope: &str, name: &str) -> String {
+    if scope.is_empty() { name.to_string() } else { format!("{scope}.{name}") }
+}
+
+fn parse_proto(text: &str) -> ProtoFile {
+    Parser { tokens: tokenize(text), pos: 0, file: ProtoFile::default() }.parse_file()
+}
+
+#[async_trait]
+impl super::registry::Provider for ProtobufProvider {
+    fn name(&self) -> String {
+        "protobuf".into()
+    }
+
+    fn priority(&self) -> u8 {
+        5
+    }
+
+    async fn load_documents(&self) -> Vec<Document> {
+        let files = fetch_sources(&self.source, &["proto"]).await;
+        self.parse_files(&files)
+    }
+}
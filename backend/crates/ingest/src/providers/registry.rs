@@ -1,18 +1,56 @@
 use async_trait::async_trait;
 use mnemo_core::config::providers::ProvidersConfig;
 use mnemo_core::models::document::Document;
+use mnemo_storage::metadata::postgres::PostgresMetadataStore;
 
+use super::asyncapi::AsyncApiProvider;
+use super::chat::{ChatExportProvider, ChatFormat};
+use super::confluence::ConfluenceProvider;
 use super::docx::DocxProvider;
 use super::filesystem::FilesystemProvider;
+use super::git::GitRepoProvider;
 use super::github::GitHubProvider;
+use super::graphql::GraphQlProvider;
+use super::mailbox::MailboxProvider;
+use super::notebook::NotebookProvider;
+use super::notion::NotionProvider;
+use super::obsidian::ObsidianProvider;
 use super::openapi::OpenApiProvider;
 use super::pdf::PdfProvider;
+use super::protobuf::ProtobufProvider;
+use super::tabular::TabularProvider;
+
+/// Namespace and document path prefix of everything a provider indexes.
+pub struct PruneScope {
+    pub namespace: String,
+    pub prefix: String,
+}
 
 #[async_trait]
 pub trait Provider: Send + Sync {
     fn name(&self) -> String;
     fn priority(&self) -> u8;
     async fn load_documents(&self) -> Vec<Document>;
+
+    /// Paths of previously indexed documents the last load found deleted at the source.
+    /// Pruned in the namespace of [`Provider::prune_scope`].
+    fn deleted_paths(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// Where this provider's documents live. After a load that reached the source, indexed
+    /// documents in the scope that it neither returned nor retained are pruned.
+    fn prune_scope(&self) -> Option<PruneScope> {
+        None
+    }
+
+    /// Paths the last load found at the source without returning them, e.g. unchanged files.
+    fn retained_paths(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// Record sync state (commit, cursor) once the documents of the last load are indexed.
+    async fn commit_sync(&self) {}
 }
 
 pub struct ProviderRegistry {
@@ -31,8 +69,73 @@ impl ProviderRegistry {
         self.sort_by_priority();
     }
 
-    pub fn register_github(&mut self, repo: String) {
-        self.providers.push(Box::new(GitHubProvider::new(repo)));
+    /// Register a GitHub repository. With a store, runs only download blobs whose SHA changed
+    /// since they were indexed.
+    pub fn register_github(&mut self, repo: String, store: Option<PostgresMetadataStore>) {
+        let mut provider = GitHubProvider::new(repo);
+        if let Some(store) = store {
+            provider = provider.with_store(store);
+        }
+        self.providers.push(Box::new(provider));
+        self.sort_by_priority();
+    }
+
+    /// Register a local git clone. With a store, runs only load files changed since the last
+    /// indexed commit of `reference`.
+    pub fn register_git_repo(
+        &mut self,
+        path: String,
+        reference: String,
+        namespace: String,
+        store: Option<PostgresMetadataStore>,
+    ) {
+        let mut provider = GitRepoProvider::new(path, reference, namespace);
+        if let Some(store) = store {
+            provider = provider.with_store(store);
+        }
+        self.providers.push(Box::new(provider));
+        self.sort_by_priority();
+    }
+
+    pub fn register_notebook_paths(&mut self, paths: Vec<String>) {
+        self.providers.push(Box::new(NotebookProvider::new(paths, "local".into())));
+        self.sort_by_priority();
+    }
+
+    /// Register CSV/TSV, JSONL or Parquet files, optionally with a schema summary per table.
+    pub fn register_tabular_paths(&mut self, paths: Vec<String>, schema_summary: bool) {
+        let provider =
+            TabularProvider::new(paths, "local".into()).with_schema_summary(schema_summary);
+        self.providers.push(Box::new(provider));
+        self.sort_by_priority();
+    }
+
+    /// Register a Confluence space export (HTML or XML; directory or `.zip`).
+    pub fn register_confluence_export(&mut self, path: String, namespace: String) {
+        self.providers.push(Box::new(ConfluenceProvider::new(path, namespace)));
+        self.sort_by_priority();
+    }
+
+    /// Register a Notion Markdown & CSV export (directory or `.zip`).
+    pub fn register_notion_export(&mut self, path: String, namespace: String) {
+        self.providers.push(Box::new(NotionProvider::new(path, namespace)));
+        self.sort_by_priority();
+    }
+
+    pub fn register_obsidian_vault(&mut self, path: String, namespace: String) {
+        self.providers.push(Box::new(ObsidianProvider::new(path, namespace)));
+        self.sort_by_priority();
+    }
+
+    /// Register an mbox file or Maildir directory.
+    pub fn register_mailbox(&mut self, path: String) {
+        self.providers.push(Box::new(MailboxProvider::new(path, "local".into())));
+        self.sort_by_priority();
+    }
+
+    /// Register a Slack or Mattermost export (directory, `.zip`, or Mattermost `.jsonl`).
+    pub fn register_chat_export(&mut self, path: String, format: ChatFormat) {
+        self.providers.push(Box::new(ChatExportProvider::new(path, format, "local".into())));
         self.sort_by_priority();
     }
 
@@ -41,6 +144,21 @@ impl ProviderRegistry {
         self.sort_by_priority();
     }
 
+    pub fn register_asyncapi(&mut self, source: String) {
+        self.providers.push(Box::new(AsyncApiProvider::new(source)));
+        self.sort_by_priority();
+    }
+
+    pub fn register_graphql_schema(&mut self, source: String) {
+        self.providers.push(Box::new(GraphQlProvider::new(source)));
+        self.sort_by_priority();
+    }
+
+    pub fn register_protobuf(&mut self, source: String) {
+        self.providers.push(Box::new(ProtobufProvider::new(source)));
+        self.sort_by_priority();
+    }
+
     pub fn register_pdf_paths(&mut self, paths: Vec<String>) {
         self.providers.push(Box::new(PdfProvider::new(paths, "local".into())));
         self.sort_by_priority();
@@ -51,10 +169,12 @@ impl ProviderRegistry {
         self.sort_by_priority();
     }
 
-    /// Build providers from configuration flags.
+    /// Build providers from configuration flags. With a store, incremental providers keep
+    /// their sync state in it.
     pub fn load_active_providers(
         &mut self,
         config: &ProvidersConfig,
+        store: Option<PostgresMetadataStore>,
     ) -> Vec<Box<dyn Provider + Send + Sync>> {
         let mut active: Vec<Box<dyn Provider + Send + Sync>> = Vec::new();
         if config.filesystem.enabled {
@@ -62,7 +182,28 @@ impl ProviderRegistry {
             active.push(Box::new(FilesystemProvider::new(vec![root])));
         }
         if config.github.enabled {
-            active.push(Box::new(GitHubProvider::new("".into())));
+            let options = &config.github.options;
+            let repo = options.get("repo").cloned().unwrap_or_default();
+            let threads = options.get("threads").is_some_and(|v| v == "true");
+            let mut provider = GitHubProvider::new(repo).with_threads(threads);
+            if let Some(store) = &store {
+                provider = provider.with_store(store.clone());
+            }
+            active.push(Box::new(provider));
+        }
+        if config.git.enabled {
+            let option = |key: &str, default: &str| {
+                config.git.options.get(key).cloned().unwrap_or_else(|| default.to_string())
+            };
+            let mut provider = GitRepoProvider::new(
+                option("path", "."),
+                option("ref", "HEAD"),
+                option("namespace", "local"),
+            );
+            if let Some(store) = &store {
+                provider = provider.with_store(store.clone());
+            }
+            active.push(Box::new(provider));
         }
         if config.github.enabled {
             active.push(Box::new(OpenApiProvider::new("openapi.yaml".into())));
@@ -0,0 +1,440 @@
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate};
+use mnemo_core::models::document::Document;
+use mnemo_core::utils::text_normalizer::TextNormalizer;
+use parquet::basic::{ConvertedType, Type as PhysicalType};
+use parquet::file::reader::{FileReader, SerializedFileReader};
+use parquet::record::Field;
+use serde_json::{Map, Value, json};
+use sha2::{Digest, Sha256};
+
+/// Separator between the table header (name, column types, header row) and the data rows in
+/// extracted table text; `ChunkBuilder` repeats everything before it in each row-group chunk.
+pub const ROWS_BREAK: char = '\u{1d}';
+
+/// Rows kept per table when no limit is configured; the rest are only counted.
+pub const DEFAULT_MAX_ROWS: usize = 100_000;
+
+/// Extensions read as tables.
+pub const TABLE_EXTENSIONS: &[&str] = &["csv", "tsv", "jsonl", "ndjson", "parquet"];
+
+/// Sample values listed per column in schema summaries.
+const SAMPLE_VALUES: usize = 3;
+
+/// Provider for tabular files: CSV/TSV, JSON Lines and Parquet.
+///
+/// Each table becomes one document rendered as a Markdown table with its column types; chunks
+/// are groups of rows that repeat the header. With `with_schema_summary(true)` a `#schema`
+/// document per table describes its columns, null counts and sample values.
+pub struct TabularProvider {
+    pub paths: Vec<String>,
+    pub namespace: String,
+    /// Row limit per table; `None` keeps every row.
+    pub max_rows: Option<usize>,
+    pub schema_summary: bool,
+}
+
+/// A column with its inferred (CSV, JSONL) or declared (Parquet) type.
+#[derive(Clone, Debug)]
+pub struct TableColumn {
+    pub name: String,
+    pub data_type: String,
+    /// Empty or null cells among the kept rows.
+    pub nulls: usize,
+    /// First distinct non-empty values.
+    pub samples: Vec<String>,
+}
+
+/// A parsed table with cells already rendered as text.
+#[derive(Clone, Debug)]
+pub struct Table {
+    pub format: String,
+    pub columns: Vec<TableColumn>,
+    pub rows: Vec<Vec<String>>,
+    /// Rows in the source, including any beyond the row limit.
+    pub total_rows: usize,
+}
+
+impl TabularProvider {
+    pub fn new(paths: Vec<String>, namespace: String) -> Self {
+        Self { paths, namespace, max_rows: Some(DEFAULT_MAX_ROWS), schema_summary: false }
+    }
+
+    pub fn with_max_rows(mut self, max_rows: Option<usize>) -> Self {
+        self.max_rows = max_rows;
+        self
+    }
+
+    pub fn with_schema_summary(mut self, schema_summary: bool) -> Self {
+        self.schema_summary = schema_summary;
+        self
+    }
+
+    pub fn load_documents(&self) -> Vec<Document> {
+        let mut docs = Vec::new();
+        for path in &self.paths {
+            let Ok(bytes) = std::fs::read(path) else {
+                continue;
+            };
+            let Some(table) = extract_table(path, &bytes, self.max_rows) else {
+                tracing::warn!("Skipping unreadable table: {}", path);
+                continue;
+            };
+            let text = table.render(&table_name(path));
+            docs.push(table_document(
+                path.clone(),
+                text,
+                "table",
+                Some(bytes.len() as i64),
+                table.metadata(),
+                &self.namespace,
+            ));
+            if self.schema_summary {
+                let mut meta = table.metadata();
+                meta.insert("table_path".into(), json!(path));
+                docs.push(table_document(
+                    format!("{path}#schema"),
+                    TextNormalizer::normalize(&table.schema_summary(&table_name(path))),
+                    "markdown",
+                    None,
+                    meta,
+                    &self.namespace,
+                ));
+            }
+        }
+        docs
+    }
+}
+
+fn table_document(
+    path: String,
+    content: String,
+    language: &str,
+    file_size: Option<i64>,
+    meta: Map<String, Value>,
+    namespace: &str,
+) -> Document {
+    let mut hasher = Sha256::new();
+    hasher.update(content.as_bytes());
+    let file_type = path
+        .split('#')
+        .next()
+        .and_then(|p| std::path::Path::new(p).extension())
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_ascii_lowercase());
+    Document {
+        path,
+        content,
+        fingerprint: format!("{:x}", hasher.finalize()),
+        namespace: namespace.to_string(),
+        modified_at: None,
+        file_size,
+        file_type,
+        language: Some(language.into()),
+        metadata: Some(Value::Object(meta)),
+    }
+}
+
+/// File name of a table path, used as its title.
+pub(crate) fn table_name(path: &str) -> String {
+    std::path::Path::new(path)
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.to_string())
+}
+
+impl Table {
+    /// Header block, [`ROWS_BREAK`], then one Markdown table row per line.
+    pub fn render(&self, name: &str) -> String {
+        let columns: Vec<String> =
+            self.columns.iter().map(|c| format!("{} ({})", c.name, c.data_type)).collect();
+        let names: Vec<String> = self.columns.iter().map(|c| escape_cell(&c.name)).collect();
+        let mut out =
+            format!("Table: {name}\nFormat: {}\nColumns: {}\n", self.format, columns.join(", "));
+        out.push_str(&format!("| {} |\n", names.join(" | ")));
+        out.push_str(&format!("|{}\n", " --- |".repeat(self.columns.len())));
+        out.push(ROWS_BREAK);
+        let rows: Vec<String> = self
+            .rows
+            .iter()
+            .map(|row| {
+                let cells: Vec<String> = row.iter().map(|c| escape_cell(c)).collect();
+                format!("| {} |", cells.join(" | "))
+            })
+            .collect();
+        out.push_str(&rows.join("\n"));
+        out
+    }
+
+    /// Markdown description of the columns, for the optional schema summary document.
+    pub fn schema_summary(&self, name: &str) -> String {
+        let mut out = format!("# Schema: {name}\n\nFormat: {}\n", self.format);
+        out.push_str(&format!(
+            "Rows: {}\nColumns: {}\n\n## Columns\n\n",
+            self.total_rows,
+            self.columns.len()
+        ));
+        for column in &self.columns {
+            out.push_str(&format!("- `{}` ({}", column.name, column.data_type));
+            if column.nulls > 0 {
+                out.push_str(&format!(", {} empty", column.nulls));
+            }
+            out.push(')');
+            if !column.samples.is_empty() {
+                out.push_str(&format!(": e.g. {}", column.samples.join(", ")));
+            }
+            out.push('\n');
+        }
+        out
+    }
+
+    /// Schema and size metadata for the table's documents.
+    pub fn metadata(&self) -> Map<String, Value> {
+        let columns: Vec<Value> = self
+            .columns
+            .iter()
+            .map(|c| json!({"name": c.name, "type": c.data_type, "nulls": c.nulls}))
+            .collect();
+        let mut meta = Map::new();
+        meta.insert("table_format".into(), json!(self.format));
+        meta.insert("table_columns".into(), json!(columns));
+        meta.insert("column_count".into(), json!(self.columns.len()));
+        meta.insert("row_count".into(), json!(self.total_rows));
+        if self.rows.len() < self.total_rows {
+            meta.insert("rows_indexed".into(), json!(self.rows.len()));
+        }
+        meta
+    }
+}
+
+/// Split text from [`Table::render`] into the header block and the data rows.
+pub fn table_parts(text: &str) -> Option<(&str, Vec<&str>)> {
+    let (header, rows) = text.split_once(ROWS_BREAK)?;
+    Some((header.trim_end(), rows.lines().filter(|l| !l.trim().is_empty()).collect()))
+}
+
+/// Parse a table by extension, keeping at most `max_rows` rows; `None` if it is unreadable.
+pub fn extract_table(path: &str, bytes: &[u8], max_rows: Option<usize>) -> Option<Table> {
+    let ext = std::path::Path::new(path)
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_ascii_lowercase())
+        .unwrap_or_default();
+    let max_rows = max_rows.unwrap_or(usize::MAX);
+    match ext.as_str() {
+        "csv" => delimited_table(bytes, b',', "csv", max_rows),
+        "tsv" => delimited_table(bytes, b'\t', "tsv", max_rows),
+        "jsonl" | "ndjson" => jsonl_table(bytes, max_rows),
+        "parquet" => parquet_table(bytes, max_rows),
+        _ => None,
+    }
+}
+
+fn delimited_table(bytes: &[u8], delimiter: u8, format: &str, max_rows: usize) -> Option<Table> {
+    let mut reader =
+        csv::ReaderBuilder::new().delimiter(delimiter).flexible(true).from_reader(bytes);
+    let names: Vec<String> = reader.headers().ok()?.iter().map(|h| h.trim().to_string()).collect();
+    if names.iter().all(|n| n.is_empty()) {
+        return None;
+    }
+    let mut rows = Vec::new();
+    let mut total_rows = 0;
+    for record in reader.records() {
+        let Ok(record) = record else { continue };
+        total_rows += 1;
+        if rows.len() < max_rows {
+            let mut row: Vec<String> = record.iter().map(clean_cell).collect();
+            row.resize(names.len(), String::new());
+            rows.push(row);
+        }
+    }
+    let kinds = |idx: usize| rows.iter().map(move |r: &Vec<String>| text_kind(&r[idx]));
+    let columns = names
+        .iter()
+        .enumerate()
+        .map(|(idx, name)| column(name, idx, merge_kinds(kinds(idx)), &rows))
+        .collect();
+    Some(Table { format: format.into(), columns, rows, total_rows })
+}
+
+fn jsonl_table(bytes: &[u8], max_rows: usize) -> Option<Table> {
+    let text = std::str::from_utf8(bytes).ok()?;
+    let mut names: Vec<String> = Vec::new();
+    let mut objects: Vec<Map<String, Value>> = Vec::new();
+    let mut total_rows = 0;
+    for line in text.lines().filter(|l| !l.trim().is_empty()) {
+        let Ok(Value::Object(object)) = serde_json::from_str::<Value>(line) else { continue };
+        total_rows += 1;
+        if objects.len() < max_rows {
+            // Columns are the union of keys, in first-seen order.
+            for key in object.keys() {
+                if !names.contains(key) {
+                    names.push(key.clone());
+                }
+            }
+            objects.push(object);
+        }
+    }
+    if objects.is_empty() {
+        return None;
+    }
+    let rows: Vec<Vec<String>> = objects
+        .iter()
+        .map(|object| names.iter().map(|n| json_cell(object.get(n))).collect())
+        .collect();
+    let columns = names
+        .iter()
+        .enumerate()
+        .map(|(idx, name)| {
+            let kind = merge_kinds(objects.iter().map(|o| json_kind(o.get(name))));
+            column(name, idx, kind, &rows)
+        })
+        .collect();
+    Some(Table { format: "jsonl".into(), columns, rows, total_rows })
+}
+
+fn parquet_table(bytes: &[u8], max_rows: usize) -> Option<Table> {
+    let reader = SerializedFileReader::new(bytes::Bytes::copy_from_slice(bytes)).ok()?;
+    let file_meta = reader.metadata().file_metadata();
+    let total_rows = file_meta.num_rows().max(0) as usize;
+    let fields = file_meta.schema().get_fields();
+    let names: Vec<String> = fields.iter().map(|f| f.name().to_string()).collect();
+    let kinds: Vec<&str> = fields.iter().map(|f| parquet_kind(f)).collect();
+    let mut rows = Vec::new();
+    for row in reader.get_row_iter(None).ok()?.take(max_rows) {
+        let Ok(row) = row else { break };
+        rows.push(row.get_column_iter().map(|(_, field)| parquet_cell(field)).collect());
+    }
+    let columns = names
+        .iter()
+        .enumerate()
+        .map(|(idx, name)| column(name, idx, Some(kinds[idx]), &rows))
+        .collect();
+    Some(Table { format: "parquet".into(), columns, rows, total_rows })
+}
+
+fn column(name: &str, idx: usize, kind: Option<&str>, rows: &[Vec<String>]) -> TableColumn {
+    let mut samples: Vec<String> = Vec::new();
+    let mut nulls = 0;
+    for cell in rows.iter().map(|r| r.get(idx).map(String::as_str).unwrap_or_default()) {
+        if cell.is_empty() {
+            nulls += 1;
+        } else if samples.len() < SAMPLE_VALUES && !samples.iter().any(|s| s == cell) {
+            samples.push(cell.chars().take(40).collect());
+        }
+    }
+    let name = if name.is_empty() { format!("column_{}", idx + 1) } else { name.to_string() };
+    TableColumn { name, data_type: kind.unwrap_or("string").to_string(), nulls, samples }
+}
+
+/// Common type of non-null cells: numbers widen to `float`, dates to `datetime`, any other
+/// mix is `string`. `None` when every cell is empty.
+fn merge_kinds<'k>(kinds: impl Iterator<Item = Option<&'k str>>) -> Option<&'k str> {
+    kinds.flatten().reduce(|a, b| match (a, b) {
+        _ if a == b => a,
+        ("integer", "float") | ("float", "integer") => "float",
+        ("date", "datetime") | ("datetime", "date") => "datetime",
+        _ => "string",
+    })
+}
+
+fn text_kind(cell: &str) -> Option<&'static str> {
+    if cell.is_empty() {
+        return None;
+    }
+    Some(if cell.parse::<i64>().is_ok() {
+        "integer"
+    } else if cell.parse::<f64>().is_ok_and(|f| f.is_finite()) {
+        "float"
+    } else if matches!(cell.to_ascii_lowercase().as_str(), "true" | "false") {
+        "boolean"
+    } else {
+        date_kind(cell).unwrap_or("string")
+    })
+}
+
+fn date_kind(cell: &str) -> Option<&'static str> {
+    if NaiveDate::parse_from_str(cell, "%Y-%m-%d").is_ok() {
+        Some("date")
+    } else if DateTime::parse_from_rfc3339(cell).is_ok() {
+        Some("datetime")
+    } else {
+        None
+    }
+}
+
+fn json_kind(value: Option<&Value>) -> Option<&'static str> {
+    match value? {
+        Value::Null => None,
+        Value::Bool(_) => Some("boolean"),
+        Value::Number(n) if n.is_i64() || n.is_u64() => Some("integer"),
+        Value::Number(_) => Some("float"),
+        Value::String(s) => Some(date_kind(s).unwrap_or("string")),
+        Value::Array(_) => Some("array"),
+        Value::Object(_) => Some("object"),
+    }
+}
+
+fn json_cell(value: Option<&Value>) -> String {
+    match value {
+        None | Some(Value::Null) => String::new(),
+        Some(Value::String(s)) => clean_cell(s),
+        Some(other) => other.to_string(),
+    }
+}
+
+fn parquet_kind(field: &parquet::schema::types::Type) -> &'static str {
+    let converted = field.get_basic_info().converted_type();
+    if !field.is_primitive() {
+        return match converted {
+            ConvertedType::LIST => "list",
+            ConvertedType::MAP | ConvertedType::MAP_KEY_VALUE => "map",
+            _ => "struct",
+        };
+    }
+    match converted {
+        ConvertedType::UTF8 | ConvertedType::ENUM | ConvertedType::JSON => "string",
+        ConvertedType::DATE => "date",
+        ConvertedType::TIMESTAMP_MILLIS | ConvertedType::TIMESTAMP_MICROS => "datetime",
+        ConvertedType::DECIMAL => "decimal",
+        _ => match field.get_physical_type() {
+            PhysicalType::BOOLEAN => "boolean",
+            PhysicalType::INT32 | PhysicalType::INT64 => "integer",
+            PhysicalType::INT96 => "datetime",
+            PhysicalType::FLOAT | PhysicalType::DOUBLE => "float",
+            PhysicalType::BYTE_ARRAY | PhysicalType::FIXED_LEN_BYTE_ARRAY => "binary",
+        },
+    }
+}
+
+fn parquet_cell(field: &Field) -> String {
+    match field {
+        Field::Null => String::new(),
+        Field::Str(s) => clean_cell(s),
+        other => other.to_string(),
+    }
+}
+
+/// Cell text on one line with collapsed whitespace.
+fn clean_cell(cell: &str) -> String {
+    cell.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn escape_cell(cell: &str) -> String {
+    cell.replace('|', "\\|")
+}
+
+#[async_trait]
+impl super::registry::Provider for TabularProvider {
+    fn name(&self) -> String {
+        "tabular".into()
+    }
+
+    fn priority(&self) -> u8 {
+        3
+    }
+
+    async fn load_documents(&self) -> Vec<Document> {
+        self.load_documents()
+    }
+}
@@ -0,0 +1,651 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::io::{self, Read};
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use mnemo_core::models::document::Document;
+use mnemo_core::utils::text_normalizer::TextNormalizer;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde_json::{Map, Value, json};
+use sha2::{Digest, Sha256};
+use walkdir::WalkDir;
+
+use super::filesystem::{detect_language, extract_content, is_allowed, normalize_content};
+use super::registry::PruneScope;
+
+/// Files above this size are listed, so links to them still resolve, but never read.
+const MAX_EXPORT_FILE_BYTES: u64 = 20 * 1024 * 1024;
+/// Bytes read per export; files past the budget are listed but not read.
+const MAX_EXPORT_TOTAL_BYTES: u64 = 500 * 1024 * 1024;
+
+static TAG_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r#"(?s)<!--.*?-->|<!\[CDATA\[(.*?)\]\]>|<(/?)([A-Za-z][A-Za-z0-9:-]*)((?:[^>"']|"[^"]*"|'[^']*')*)>"#,
+    )
+    .unwrap()
+});
+static ATTR_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"([A-Za-z_:][-A-Za-z0-9_:.]*)\s*=\s*(?:"([^"]*)"|'([^']*)')"#).unwrap()
+});
+static ENTITY_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"&(#[0-9]+|#[xX][0-9a-fA-F]+|[A-Za-z]+);").unwrap());
+
+/// Files of an export directory or zip archive, keyed by `/`-separated relative path.
+pub(crate) struct ExportFiles {
+    contents: BTreeMap<String, Vec<u8>>,
+    modified: HashMap<String, DateTime<Utc>>,
+    /// Every file in the export, including ones too large to read.
+    listed: HashSet<String>,
+    total_bytes: u64,
+    /// Files skipped because the export exceeded [`MAX_EXPORT_TOTAL_BYTES`].
+    over_budget: usize,
+}
+
+impl ExportFiles {
+    /// Read a directory (an unpacked export or an Obsidian vault) or a `.zip` archive.
+    pub(crate) fn open(path: &Path) -> io::Result<Self> {
+        let files = if path.is_dir() { Self::from_dir(path) } else { Self::from_zip(path) }?;
+        if files.over_budget > 0 {
+            tracing::warn!(
+                "Export {} exceeds {} bytes; {} files were not read",
+                path.display(),
+                MAX_EXPORT_TOTAL_BYTES,
+                files.over_budget
+            );
+        }
+        Ok(files)
+    }
+
+    fn empty() -> Self {
+        Self {
+            contents: BTreeMap::new(),
+            modified: HashMap::new(),
+            listed: HashSet::new(),
+            total_bytes: 0,
+            over_budget: 0,
+        }
+    }
+
+    /// Read a listed file of `declared` size within the per-file and per-export limits. A
+    /// reader yielding more than it declared is dropped.
+    fn read_file(&mut self, key: String, declared: u64, reader: impl Read) {
+        if declared > MAX_EXPORT_FILE_BYTES {
+            tracing::debug!("Export file too large to read: {}", key);
+            return;
+        }
+        if declared > MAX_EXPORT_TOTAL_BYTES.saturating_sub(self.total_bytes) {
+            self.over_budget += 1;
+            return;
+        }
+        let mut bytes = Vec::with_capacity(declared as usize);
+        if reader.take(declared + 1).read_to_end(&mut bytes).is_err()
+            || bytes.len() as u64 > declared
+        {
+            return;
+        }
+        self.total_bytes += bytes.len() as u64;
+        self.contents.insert(key, bytes);
+    }
+
+    fn from_dir(root: &Path) -> io::Result<Self> {
+        if !root.exists() {
+            return Err(io::Error::new(io::ErrorKind::NotFound, root.display().to_string()));
+        }
+        let mut files = Self::empty();
+        for entry in WalkDir::new(root)
+            .into_iter()
+            .filter_map(Result::ok)
+            .filter(|e| e.file_type().is_file())
+        {
+            let Ok(rel) = entry.path().strip_prefix(root) else { continue };
+            let key = rel
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy().into_owned())
+                .collect::<Vec<_>>()
+                .join("/");
+            let Ok(metadata) = entry.metadata() else { continue };
+            files.listed.insert(key.clone());
+            if let Ok(modified) = metadata.modified() {
+                files.modified.insert(key.clone(), DateTime::<Utc>::from(modified));
+            }
+            if let Ok(file) = std::fs::File::open(entry.path()) {
+                files.read_file(key, metadata.len(), file);
+            }
+        }
+        Ok(files)
+    }
+
+    fn from_zip(path: &Path) -> io::Result<Self> {
+        let mut archive = zip::ZipArchive::new(std::fs::File::open(path)?)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        let mut files = Self::empty();
+        for i in 0..archive.len() {
+            let Ok(mut entry) = archive.by_index(i) else { continue };
+            if entry.is_dir() {
+                continue;
+            }
+            // `enclosed_name` rejects absolute paths and `..` components.
+            let Some(key) = entry.enclosed_name().map(|p| {
+                p.components()
+                    .map(|c| c.as_os_str().to_string_lossy().into_owned())
+                    .collect::<Vec<_>>()
+                    .join("/")
+            }) else {
+                continue;
+            };
+            files.listed.insert(key.clone());
+            let declared = entry.size();
+            files.read_file(key, declared, &mut entry);
+        }
+        Ok(files)
+    }
+
+    /// All listed paths in sorted order.
+    pub(crate) fn paths(&self) -> Vec<&str> {
+        let mut paths: Vec<&str> = self.listed.iter().map(|p| p.as_str()).collect();
+        paths.sort_unstable();
+        paths
+    }
+
+    pub(crate) fn contains(&self, key: &str) -> bool {
+        self.listed.contains(key)
+    }
+
+    pub(crate) fn bytes(&self, key: &str) -> Option<&[u8]> {
+        self.contents.get(key).map(|b| b.as_slice())
+    }
+
+    /// UTF-8 text of a file, without a byte-order mark.
+    pub(crate) fn text(&self, key: &str) -> Option<String> {
+        let bytes = self.bytes(key)?;
+        let bytes = bytes.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(bytes);
+        Some(String::from_utf8_lossy(bytes).into_owned())
+    }
+
+    pub(crate) fn modified(&self, key: &str) -> Option<DateTime<Utc>> {
+        self.modified.get(key).copied()
+    }
+}
+
+/// A wiki page normalized across export formats.
+pub(crate) struct WikiPage {
+    /// Export-relative key, unique within the export.
+    pub key: String,
+    pub title: String,
+    pub markdown: String,
+    /// Key of the parent page.
+    pub parent: Option<String>,
+    /// Breadcrumb entries above the top-most parent page, such as the space name or vault folders.
+    pub crumb_prefix: Vec<String>,
+    /// Keys of linked pages.
+    pub links: Vec<String>,
+    pub attachments: Vec<WikiAttachment>,
+    pub modified_at: Option<DateTime<Utc>>,
+    /// Format-specific metadata (space, frontmatter, Notion id).
+    pub extra: Map<String, Value>,
+}
+
+/// File attached to or embedded in a page.
+pub(crate) struct WikiAttachment {
+    /// Key used for the document path; its extension selects the extractor.
+    pub key: String,
+    /// Path of the bytes in the export.
+    pub file: String,
+}
+
+/// Breadcrumb titles from the root down to `page`.
+fn breadcrumb(page: &WikiPage, by_key: &HashMap<&str, &WikiPage>) -> Vec<String> {
+    let mut chain = vec![page.title.clone()];
+    let mut seen = HashSet::from([page.key.as_str()]);
+    let mut top = page;
+    while let Some(parent) = top.parent.as_deref().and_then(|k| by_key.get(k).copied()) {
+        if !seen.insert(parent.key.as_str()) {
+            break;
+        }
+        chain.push(parent.title.clone());
+        top = parent;
+    }
+    chain.extend(top.crumb_prefix.iter().rev().cloned());
+    chain.reverse();
+    chain
+}
+
+/// Turn pages into Markdown documents, plus one document per readable attachment.
+///
+/// Scope of the documents `into_documents` builds for an export.
+pub(crate) fn export_scope(source: &str, root: &str, namespace: &str) -> PruneScope {
+    PruneScope {
+        namespace: namespace.to_string(),
+        prefix: format!("{source}:{}/", root.trim_end_matches('/')),
+    }
+}
+
+/// Parent, link and attachment targets become document paths under `wiki_parent`, `wiki_links`
+/// and `attachments`, which `GraphBuilderStep` turns into edges.
+pub(crate) fn into_documents(
+    source: &str,
+    root: &str,
+    namespace: &str,
+    files: &ExportFiles,
+    pages: Vec<WikiPage>,
+) -> Vec<Document> {
+    let root = root.trim_end_matches('/');
+    let doc_path = |key: &str| format!("{source}:{root}/{key}");
+    let by_key: HashMap<&str, &WikiPage> = pages.iter().map(|p| (p.key.as_str(), p)).collect();
+    // Attachment key -> (export file, owning page paths).
+    let mut attachments: BTreeMap<String, (String, Vec<String>)> = BTreeMap::new();
+    let mut docs = Vec::new();
+
+    for page in &pages {
+        let path = doc_path(&page.key);
+        let crumbs = breadcrumb(page, &by_key);
+        let mut content = page.markdown.trim().to_string();
+        let has_title = content
+            .lines()
+            .next()
+            .is_some_and(|l| l.starts_with("# ") && l[2..].trim() == page.title);
+        if !has_title {
+            content = format!("# {}\n\n{}", page.title, content);
+        }
+        let content = TextNormalizer::normalize(&content);
+
+        let mut seen = HashSet::new();
+        let links: Vec<String> = page
+            .links
+            .iter()
+            .filter(|k| **k != page.key && by_key.contains_key(k.as_str()) && seen.insert(*k))
+            .map(|k| doc_path(k))
+            .collect();
+        let parent = page.parent.as_deref().filter(|k| by_key.contains_key(k)).map(&doc_path);
+        let mut attached = Vec::new();
+        for attachment in page.attachments.iter().filter(|a| files.contains(&a.file)) {
+            let entry = attachments
+                .entry(attachment.key.clone())
+                .or_insert_with(|| (attachment.file.clone(), Vec::new()));
+            if !entry.1.contains(&path) {
+                entry.1.push(path.clone());
+                attached.push(doc_path(&attachment.key));
+            }
+        }
+
+        let mut meta = page.extra.clone();
+        meta.insert("wiki_source".into(), json!(source));
+        meta.insert("title".into(), json!(page.title));
+        meta.insert("breadcrumb".into(), json!(crumbs.join(" > ")));
+        meta.insert("breadcrumb_titles".into(), json!(crumbs));
+        meta.insert("wiki_parent".into(), json!(parent));
+        meta.insert("wiki_links".into(), json!(links));
+        meta.insert("attachments".into(), json!(attached));
+
+        let mut hasher = Sha256::new();
+        hasher.update(content.as_bytes());
+        docs.push(Document {
+            path,
+            fingerprint: format!("{:x}", hasher.finalize()),
+            file_size: Some(content.len() as i64),
+            content,
+            namespace: namespace.to_string(),
+            modified_at: page.modified_at,
+            file_type: Some(source.to_string()),
+            language: Some("markdown".into()),
+            metadata: Some(Value::Object(meta)),
+        });
+    }
+
+    for (key, (file, owners)) in attachments {
+        let key_path = Path::new(&key);
+        let Some(bytes) = files.bytes(&file) else { continue };
+        // Images and other binaries stay link targets only.
+        if !is_allowed(key_path, bytes.len() as u64) {
+            continue;
+        }
+        let Some(content) = extract_content(key_path, bytes.to_vec()) else { continue };
+        let content = normalize_content(key_path, content);
+        let file_type =
+            key_path.extension().and_then(|e| e.to_str()).map(|s| s.to_ascii_lowercase());
+        let language = detect_language(&key, &content, file_type.as_deref());
+        let title = key.rsplit('/').next().unwrap_or(&key).to_string();
+        let mut hasher = Sha256::new();
+        hasher.update(content.as_bytes());
+        docs.push(Document {
+            path: doc_path(&key),
+            fingerprint: format!("{:x}", hasher.finalize()),
+            file_size: Some(bytes.len() as i64),
+            content,
+            namespace: namespace.to_string(),
+            modified_at: files.modified(&file),
+            file_type,
+            language,
+            metadata: Some(json!({
+                "wiki_source": source,
+                "title": title,
+                "attached_to": owners,
+            })),
+        });
+    }
+    docs
+}
+
+/// Resolve a link relative to the export file `from`.
+///
+/// Returns `None` for external, `mailto:` and anchor-only links. The result may not exist.
+pub(crate) fn resolve_link(from: &str, target: &str) -> Option<String> {
+    let target = target.split(['#', '?']).next()?.trim();
+    if target.is_empty() || target.contains("://") || target.starts_with("mailto:") {
+        return None;
+    }
+    let decoded = percent_decode(target);
+    let mut parts: Vec<&str> = Vec::new();
+    if !decoded.starts_with('/') {
+        parts.extend(from.split('/'));
+        parts.pop();
+    }
+    for segment in decoded.split('/') {
+        match segment {
+            "" | "." => {}
+            ".." => {
+                parts.pop();
+            }
+            s => parts.push(s),
+        }
+    }
+    Some(parts.join("/"))
+}
+
+fn percent_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let hex = bytes.get(i + 1..i + 3).and_then(|h| std::str::from_utf8(h).ok());
+            if let Some(byte) = hex.and_then(|h| u8::from_str_radix(h, 16).ok()) {
+                out.push(byte);
+                i += 3;
+                continue;
+            }
+        }
+        out.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+/// File name without extension.
+pub(crate) fn file_stem(key: &str) -> &str {
+    let name = key.rsplit('/').next().unwrap_or(key);
+    name.rsplit_once('.').map(|(stem, _)| stem).filter(|s| !s.is_empty()).unwrap_or(name)
+}
+
+/// Markdown and references extracted from an HTML or Confluence storage-format fragment.
+#[derive(Default)]
+pub(crate) struct HtmlText {
+    pub markdown: String,
+    /// `href` and `src` values, unresolved.
+    pub hrefs: Vec<String>,
+    /// Page titles referenced by `<ri:page ri:content-title>`.
+    pub page_titles: Vec<String>,
+    /// File names referenced by `<ri:attachment ri:filename>`.
+    pub attachment_names: Vec<String>,
+}
+
+#[derive(Default)]
+struct HtmlWriter {
+    text: HtmlText,
+    /// `None` for bullets, `Some(n)` for numbered lists.
+    lists: Vec<Option<usize>>,
+    pre: bool,
+    /// Depth inside elements whose text is dropped (scripts, macro parameters).
+    skip: usize,
+    header_row: bool,
+    row_cells: usize,
+    header_done: bool,
+    /// Output length and target title at the start of an `<ac:link>`.
+    link: Option<(usize, Option<String>)>,
+}
+
+impl HtmlWriter {
+    fn out(&mut self) -> &mut String {
+        &mut self.text.markdown
+    }
+
+    fn line(&mut self) {
+        let out = self.out();
+        while out.ends_with(' ') {
+            out.pop();
+        }
+        if !out.is_empty() && !out.ends_with('\n') {
+            out.push('\n');
+        }
+    }
+
+    fn block(&mut self) {
+        self.line();
+        let out = self.out();
+        if !out.is_empty() && !out.ends_with("\n\n") {
+            out.push('\n');
+        }
+    }
+
+    fn push_text(&mut self, raw: &str) {
+        if self.skip > 0 {
+            return;
+        }
+        let decoded = decode_entities(raw);
+        if self.pre {
+            self.out().push_str(&decoded);
+            return;
+        }
+        let collapsed = decoded.split_whitespace().collect::<Vec<_>>().join(" ");
+        let leading = decoded.starts_with(char::is_whitespace);
+        let trailing = decoded.ends_with(char::is_whitespace) && !collapsed.is_empty();
+        let out = self.out();
+        let at_line_start = out.is_empty() || out.ends_with(['\n', ' ']);
+        if leading && !at_line_start {
+            out.push(' ');
+        }
+        out.push_str(&collapsed);
+        if trailing {
+            out.push(' ');
+        }
+    }
+
+    fn tag(&mut self, name: &str, closing: bool, attrs: &str) {
+        let self_closing = attrs.trim_end().ends_with('/');
+        match name {
+            "script" | "style" | "head" | "title" | "ac:parameter" => {
+                if closing {
+                    self.skip = self.skip.saturating_sub(1);
+                } else if !self_closing {
+                    self.skip += 1;
+                }
+                return;
+            }
+            _ if self.skip > 0 => return,
+            _ => {}
+        }
+        match (name, closing) {
+            ("h1" | "h2" | "h3" | "h4" | "h5" | "h6", false) => {
+                self.block();
+                let level = name[1..].parse::<usize>().unwrap_or(1);
+                self.out().push_str(&format!("{} ", "#".repeat(level)));
+            }
+            ("h1" | "h2" | "h3" | "h4" | "h5" | "h6", true) => self.block(),
+            ("table", false) => {
+                // Each table gets its own header separator.
+                self.header_done = false;
+                self.block()
+            }
+            ("p" | "div" | "section" | "article" | "blockquote" | "table" | "dl", _) => {
+                self.block()
+            }
+            ("br", _) | ("dt" | "dd", _) => self.line(),
+            ("hr", false) => {
+                self.block();
+                self.out().push_str("---");
+                self.block();
+            }
+            ("ul" | "ol", false) => {
+                self.line();
+                self.lists.push((name == "ol").then_some(0));
+            }
+            ("ul" | "ol", true) => {
+                self.lists.pop();
+                if self.lists.is_empty() { self.block() } else { self.line() }
+            }
+            ("li", false) => {
+                self.line();
+                let indent = "  ".repeat(self.lists.len().saturating_sub(1));
+                let marker = match self.lists.last_mut() {
+                    Some(Some(n)) => {
+                        *n += 1;
+                        format!("{n}. ")
+                    }
+                    _ => "- ".to_string(),
+                };
+                self.out().push_str(&format!("{indent}{marker}"));
+            }
+            ("li", true) => self.line(),
+            ("tr", false) => {
+                self.line();
+                self.out().push('|');
+                self.header_row = false;
+                self.row_cells = 0;
+            }
+            ("tr", true) => {
+                self.line();
+                if self.header_row && !self.header_done {
+                    let separator = format!("|{}\n", " --- |".repeat(self.row_cells));
+                    self.out().push_str(&separator);
+                    self.header_done = true;
+                }
+            }
+            ("th" | "td", false) => {
+                self.header_row |= name == "th";
+                self.row_cells += 1;
+                self.out().push(' ');
+            }
+            ("th" | "td", true) => self.out().push_str(" |"),
+            ("pre", false) => {
+                self.block();
+                self.out().push_str("```\n");
+                self.pre = true;
+            }
+            ("pre", true) => {
+                self.pre = false;
+                self.line();
+                self.out().push_str("```");
+                self.block();
+            }
+            ("code", _) if !self.pre => self.out().push('`'),
+            ("strong" | "b", _) => self.out().push_str("**"),
+            ("em" | "i", _) => self.out().push('_'),
+            ("a", false) => {
+                if let Some(href) = attr_value(attrs, "href") {
+                    self.text.hrefs.push(href);
+                }
+            }
+            ("img", false) => {
+                if let Some(src) = attr_value(attrs, "src") {
+                    self.text.hrefs.push(src);
+                }
+                if let Some(alt) = attr_value(attrs, "alt") {
+                    self.push_text(&alt);
+                }
+            }
+            ("ac:link", false) if !self_closing => {
+                self.link = Some((self.text.markdown.len(), None));
+            }
+            ("ac:link", true) => {
+                if let Some((start, Some(title))) = self.link.take() {
+                    // Links without a body render as the target title.
+                    if self.text.markdown.len() == start {
+                        self.push_text(&title);
+                    }
+                }
+            }
+            ("ri:page", false) => {
+                if let Some(title) = attr_value(attrs, "ri:content-title") {
+                    self.text.page_titles.push(title.clone());
+                    if let Some((_, target)) = self.link.as_mut() {
+                        *target = Some(title);
+                    }
+                }
+            }
+            ("ri:attachment", false) => {
+                if let Some(name) = attr_value(attrs, "ri:filename") {
+                    self.text.attachment_names.push(name);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Convert HTML or Confluence storage format to Markdown, collecting link targets.
+///
+/// This is a tolerant tag scanner rather than a full parser: unclosed tags are fine, and
+/// unknown elements keep their text.
+pub(crate) fn html_to_markdown(html: &str) -> HtmlText {
+    let mut writer = HtmlWriter::default();
+    let mut last = 0;
+    for caps in TAG_RE.captures_iter(html) {
+        let m = caps.get(0).unwrap();
+        writer.push_text(&html[last..m.start()]);
+        last = m.end();
+        if let Some(cdata) = caps.get(1) {
+            let pre = writer.pre;
+            writer.pre = true;
+            writer.push_text(cdata.as_str());
+            writer.pre = pre;
+            continue;
+        }
+        let Some(name) = caps.get(3) else { continue };
+        let closing = caps.get(2).is_some_and(|c| !c.as_str().is_empty());
+        let attrs = caps.get(4).map(|a| a.as_str()).unwrap_or_default();
+        writer.tag(&name.as_str().to_ascii_lowercase(), closing, attrs);
+    }
+    writer.push_text(&html[last..]);
+    let markdown =
+        writer.text.markdown.lines().map(|l| l.trim_end()).collect::<Vec<_>>().join("\n");
+    HtmlText { markdown: markdown.trim().to_string(), ..writer.text }
+}
+
+/// Value of an attribute in a raw attribute string, entity-decoded.
+pub(crate) fn attr_value(attrs: &str, name: &str) -> Option<String> {
+    ATTR_RE
+        .captures_iter(attrs)
+        .find(|c| c[1].eq_ignore_ascii_case(name))
+        .and_then(|c| c.get(2).or_else(|| c.get(3)))
+        .map(|v| decode_entities(v.as_str()))
+}
+
+pub(crate) fn decode_entities(text: &str) -> String {
+    if !text.contains('&') {
+        return text.to_string();
+    }
+    ENTITY_RE
+        .replace_all(text, |caps: &regex::Captures| {
+            let entity = &caps[1];
+            let decoded = match entity {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some(' '),
+                "ndash" => Some('–'),
+                "mdash" => Some('—'),
+                "hellip" => Some('…'),
+                _ => entity
+                    .strip_prefix("#x")
+                    .or_else(|| entity.strip_prefix("#X"))
+                    .map(|hex| u32::from_str_radix(hex, 16).ok())
+                    .unwrap_or_else(|| entity.strip_prefix('#').and_then(|d| d.parse().ok()))
+                    .and_then(char::from_u32),
+            };
+            decoded.map(String::from).unwrap_or_else(|| caps[0].to_string())
+        })
+        .into_owned()
+}
@@ -0,0 +1,213 @@
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use mnemo_core::error::{MnemoError, MnemoResult};
+use mnemo_core::ws::WS_HUB;
+use mnemo_storage::metadata::postgres::PostgresMetadataStore;
+use notify::event::{ModifyKind, RenameMode};
+use notify::{Event, EventKind, RecursiveMode, Watcher};
+use serde_json::json;
+use tokio::sync::mpsc::UnboundedReceiver;
+use tokio::time::Instant;
+
+use crate::fs_ingest_runner::{filesystem_pipeline, metadata_store};
+use crate::metrics::store_last_metrics;
+use crate::pipeline::data::PipelineData;
+use crate::pipeline::executor::PipelineExecutor;
+use crate::pipeline::prune_step::{prune_documents, removed_documents};
+use crate::providers::filesystem::{self, FilesystemProvider};
+
+const DEFAULT_DEBOUNCE_MS: u64 = 500;
+const DEFAULT_MAX_LATENCY_MS: u64 = 5_000;
+
+/// Settings for watch mode.
+#[derive(Clone, Debug)]
+pub struct WatchConfig {
+    pub roots: Vec<String>,
+    /// Quiet period after the last event before a batch is indexed.
+    pub debounce: Duration,
+    /// Longest a batch waits after its first event, so a steady stream of writes still flushes.
+    pub max_latency: Duration,
+}
+
+impl WatchConfig {
+    /// `INGESTION_ROOT` (default `/app/data`), `MNEMO_WATCH_DEBOUNCE_MS` (default 500) and
+    /// `MNEMO_WATCH_MAX_LATENCY_MS` (default 5000).
+    pub fn from_env() -> Self {
+        let root = std::env::var("INGESTION_ROOT").unwrap_or_else(|_| "/app/data".to_string());
+        let millis = |key: &str, default: u64| {
+            std::env::var(key).ok().and_then(|v| v.parse().ok()).unwrap_or(default)
+        };
+        Self {
+            roots: vec![root],
+            debounce: Duration::from_millis(millis("MNEMO_WATCH_DEBOUNCE_MS", DEFAULT_DEBOUNCE_MS)),
+            max_latency: Duration::from_millis(millis(
+                "MNEMO_WATCH_MAX_LATENCY_MS",
+                DEFAULT_MAX_LATENCY_MS,
+            )),
+        }
+    }
+}
+
+/// Whether the API service should run the watcher in the background (`MNEMO_WATCH=1`).
+pub fn watch_enabled() -> bool {
+    std::env::var("MNEMO_WATCH").is_ok_and(|v| matches!(v.as_str(), "1" | "true"))
+}
+
+/// Paths touched by a batch of filesystem events; the last event for a path wins.
+#[derive(Debug, Default)]
+pub struct ChangeSet {
+    pub changed: BTreeSet<PathBuf>,
+    pub removed: BTreeSet<PathBuf>,
+}
+
+impl ChangeSet {
+    /// Fold one event in. A rename removes the old path and changes the new one; access
+    /// events are ignored.
+    pub fn record(&mut self, event: &Event) {
+        match &event.kind {
+            EventKind::Modify(ModifyKind::Name(RenameMode::Both)) if event.paths.len() == 2 => {
+                self.remove(&event.paths[0]);
+                self.change(&event.paths[1]);
+            }
+            EventKind::Modify(ModifyKind::Name(RenameMode::From)) | EventKind::Remove(_) => {
+                event.paths.iter().for_each(|p| self.remove(p));
+            }
+            EventKind::Modify(ModifyKind::Name(_)) => {
+                for path in &event.paths {
+                    if path.exists() { self.change(path) } else { self.remove(path) }
+                }
+            }
+            EventKind::Create(_) | EventKind::Modify(_) | EventKind::Any => {
+                event.paths.iter().for_each(|p| self.change(p));
+            }
+            EventKind::Access(_) | EventKind::Other => {}
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.changed.is_empty() && self.removed.is_empty()
+    }
+
+    /// Split into paths to rescan and paths to forget, by what is on disk now.
+    pub fn resolve(self) -> (Vec<PathBuf>, Vec<PathBuf>) {
+        let (changed, gone): (Vec<PathBuf>, Vec<PathBuf>) =
+            self.changed.into_iter().partition(|p| p.exists());
+        let mut removed: Vec<PathBuf> = self.removed.into_iter().chain(gone).collect();
+        removed.sort();
+        (changed, removed)
+    }
+
+    fn change(&mut self, path: &Path) {
+        self.removed.remove(path);
+        self.changed.insert(path.to_path_buf());
+    }
+
+    fn remove(&mut self, path: &Path) {
+        self.changed.remove(path);
+        self.removed.insert(path.to_path_buf());
+    }
+}
+
+/// Outcome of indexing one debounced batch.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct WatchBatch {
+    pub indexed: usize,
+    /// Documents deleted from every store.
+    pub removed: usize,
+}
+
+/// Watch the roots and index each debounced batch of changes until the watcher stops.
+///
+/// Changed files are rescanned under the filesystem provider's rules and run through the
+/// filesystem pipeline, where unchanged content is still dropped by fingerprint. Documents
+/// under removed, renamed-away or newly ignored paths are deleted from every store.
+pub async fn watch_filesystem(config: WatchConfig) -> MnemoResult<()> {
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel::<Event>();
+    let mut watcher = notify::recommended_watcher(move |res: notify::Result<Event>| match res {
+        Ok(event) => {
+            let _ = tx.send(event);
+        }
+        Err(err) => tracing::warn!("Filesystem watch error: {}", err),
+    })
+    .map_err(|e| MnemoError::Message(format!("failed to start watcher: {e}")))?;
+    for root in &config.roots {
+        watcher
+            .watch(Path::new(root), RecursiveMode::Recursive)
+            .map_err(|e| MnemoError::Message(format!("failed to watch {root}: {e}")))?;
+        tracing::info!("Watching {} (debounce {:?})", root, config.debounce);
+    }
+
+    let meta_store = metadata_store()?;
+    let provider = FilesystemProvider::new(config.roots.clone());
+    while let Some(changes) = next_batch(&mut rx, &config).await {
+        if changes.is_empty() {
+            continue;
+        }
+        match index_changes(&provider, &meta_store, changes).await {
+            Ok(batch) => tracing::info!(
+                "Watch batch: {} documents indexed, {} removed",
+                batch.indexed,
+                batch.removed
+            ),
+            Err(err) => tracing::warn!("Watch batch failed: {}", err),
+        }
+    }
+    Ok(())
+}
+
+/// Wait for an event, then collect events until `debounce` passes without one or
+/// `max_latency` has passed since the first. `None` once the channel is closed and drained.
+pub async fn next_batch(
+    rx: &mut UnboundedReceiver<Event>,
+    config: &WatchConfig,
+) -> Option<ChangeSet> {
+    let first = rx.recv().await?;
+    let deadline = Instant::now() + config.max_latency;
+    let mut changes = ChangeSet::default();
+    changes.record(&first);
+    loop {
+        let quiet = (Instant::now() + config.debounce).min(deadline);
+        match tokio::time::timeout_at(quiet, rx.recv()).await {
+            Ok(Some(event)) => changes.record(&event),
+            Ok(None) | Err(_) => break,
+        }
+    }
+    Some(changes)
+}
+
+async fn index_changes(
+    provider: &FilesystemProvider,
+    meta_store: &PostgresMetadataStore,
+    changes: ChangeSet,
+) -> MnemoResult<WatchBatch> {
+    let (changed, removed) = changes.resolve();
+    let (docs, skipped) = provider.scan_paths(&changed);
+    let scopes: Vec<String> =
+        changed.iter().chain(&removed).map(|p| p.to_string_lossy().to_string()).collect();
+    let gone = removed_documents(meta_store, &scopes, &docs).await?;
+    let batch = WatchBatch { indexed: docs.len(), removed: gone.len() };
+
+    let mut data = PipelineData::new();
+    prune_documents(meta_store, filesystem::NAMESPACE, &gone, &None, &mut data.metrics).await?;
+    if docs.is_empty() {
+        if !gone.is_empty() {
+            store_last_metrics(data.metrics);
+        }
+        return Ok(batch);
+    }
+    WS_HUB.broadcast(
+        json!({"event":"log","message":format!("Watch: reindexing {} documents", docs.len())})
+            .to_string(),
+    );
+    data.documents = docs;
+    data.metrics.documents_processed = data.documents.len();
+    for file in skipped {
+        data.metrics.record_skip(file);
+    }
+    let executor = PipelineExecutor::new(filesystem_pipeline(meta_store));
+    let output = executor.execute_with_data(data).await?;
+    store_last_metrics(output.metrics);
+    Ok(batch)
+}
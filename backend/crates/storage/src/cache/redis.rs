@@ -1,4 +1,4 @@
-/// Placeholder Redis cache store adapter.
+/// Redis adapter for binary cache values.
 pub struct RedisCacheStore {
     pub url: String,
 }
@@ -7,4 +7,46 @@ impl RedisCacheStore {
     pub fn new(url: String) -> Self {
         Self { url }
     }
+
+    async fn connection(&self) -> Option<redis::aio::Connection> {
+        let client = redis::Client::open(self.url.as_str()).ok()?;
+        client.get_async_connection().await.ok()
+    }
+
+    /// Values for `keys` in order; `None` for misses, or for every key when Redis is down.
+    pub async fn get_many(&self, keys: &[String]) -> Vec<Option<Vec<u8>>> {
+        if keys.is_empty() {
+            return Vec::new();
+        }
+        let Some(mut conn) = self.connection().await else {
+            tracing::warn!("Redis cache unavailable at {}", self.url);
+            return vec![None; keys.len()];
+        };
+        let mut pipe = redis::pipe();
+        for key in keys {
+            pipe.get(key);
+        }
+        pipe.query_async(&mut conn).await.unwrap_or_else(|err| {
+            tracing::warn!("Redis cache read failed: {}", err);
+            vec![None; keys.len()]
+        })
+    }
+
+    /// Store values with a TTL in seconds; failures are logged and otherwise ignored.
+    pub async fn set_many(&self, entries: &[(String, Vec<u8>)], ttl_secs: u64) {
+        if entries.is_empty() {
+            return;
+        }
+        let Some(mut conn) = self.connection().await else {
+            tracing::warn!("Redis cache unavailable at {}", self.url);
+            return;
+        };
+        let mut pipe = redis::pipe();
+        for (key, value) in entries {
+            pipe.set_ex(key, value.as_slice(), ttl_secs).ignore();
+        }
+        if let Err(err) = pipe.query_async::<_, ()>(&mut conn).await {
+            tracing::warn!("Redis cache write failed: {}", err);
+        }
+    }
 }
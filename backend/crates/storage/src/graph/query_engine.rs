@@ -1,5 +1,6 @@
 use super::graph_engine::GraphEngine;
 use crate::surreal_store::SurrealStore;
+use mnemo_core::access::AccessScope;
 use mnemo_core::error::MnemoResult;
 use serde::Serialize;
 use serde_json::Value;
@@ -33,11 +34,25 @@ impl GraphQueryEngine {
 
     /// Expand graph using SurrealDB edges with simple BFS, returning nodes and edges.
     pub async fn expand_with_edges(
+        &self,
+        surreal_url: &str,
+        start: &str,
+        depth: usize,
+    ) -> MnemoResult<GraphExpansion> {
+        self.expand_with_edges_scoped(surreal_url, start, depth, &AccessScope::unrestricted()).await
+    }
+
+    /// Like `expand_with_edges`, but only follows edges whose endpoints are both visible to
+    /// the scope (permitted namespace and, with a tag grant, an allowed tag).
+    pub async fn expand_with_edges_scoped(
         &self,
         _surreal_url: &str,
         start: &str,
         depth: usize,
+        scope: &AccessScope,
     ) -> MnemoResult<GraphExpansion> {
+        let scoped =
+            scope.surreal_edge_condition().map(|c| format!(" AND {}", c)).unwrap_or_default();
         let store = match SurrealStore::get().await {
             Ok(c) => c,
             Err(err) => {
@@ -71,8 +86,8 @@ impl GraphQueryEngine {
                 continue;
             }
             let sql = format!(
-                "SELECT in, out FROM contains WHERE in = '{}' OR out = '{}' LIMIT 100;",
-                node, node
+                "SELECT in, out FROM contains WHERE (in = '{}' OR out = '{}'){} LIMIT 100;",
+                node, node, scoped
             );
             let rows = match store.select_all(&sql).await {
                 Ok(r) => r,
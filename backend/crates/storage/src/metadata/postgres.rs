@@ -1,8 +1,13 @@
+use std::collections::HashMap;
+
 use super::postgres_pool::PG_POOL;
 use mnemo_core::error::{MnemoError, MnemoResult};
 use mnemo_core::models::{chunk::Chunk, document::Document};
 use once_cell::sync::OnceCell;
-use sqlx::PgPool;
+use sqlx::{PgPool, Postgres, QueryBuilder};
+
+/// Rows per embedding cache INSERT, keeping its three binds per row under Postgres' limit.
+const EMBEDDING_CACHE_WRITE_ROWS: usize = 1_000;
 
 /// PostgreSQL-backed metadata store for fingerprints, files, chunks, and jobs.
 #[derive(Clone)]
@@ -117,6 +122,53 @@ impl PostgresMetadataStore {
                 created_at TIMESTAMPTZ DEFAULT now()
             );"#,
             r#"ALTER TABLE files ADD COLUMN IF NOT EXISTS metadata JSONB;"#,
+            r#"CREATE TABLE IF NOT EXISTS git_sync_state(
+                repo_path TEXT NOT NULL,
+                ref_name TEXT NOT NULL,
+                commit_sha TEXT NOT NULL,
+                updated_at TIMESTAMPTZ DEFAULT now(),
+                PRIMARY KEY(repo_path, ref_name)
+            );"#,
+            r#"CREATE TABLE IF NOT EXISTS provider_sync_state(
+                provider TEXT NOT NULL,
+                scope TEXT NOT NULL,
+                sync_cursor TEXT NOT NULL,
+                updated_at TIMESTAMPTZ DEFAULT now(),
+                PRIMARY KEY(provider, scope)
+            );"#,
+            r#"CREATE TABLE IF NOT EXISTS embedding_cache(
+                model TEXT NOT NULL,
+                text_hash TEXT NOT NULL,
+                embedding REAL[] NOT NULL,
+                created_at TIMESTAMPTZ DEFAULT now(),
+                PRIMARY KEY(model, text_hash)
+            );"#,
+            r#"CREATE TABLE IF NOT EXISTS rag_queries(
+                id UUID PRIMARY KEY,
+                namespace TEXT NOT NULL,
+                query TEXT NOT NULL,
+                candidates JSONB NOT NULL DEFAULT '[]'::jsonb,
+                created_at TIMESTAMPTZ DEFAULT now()
+            );"#,
+            r#"CREATE INDEX IF NOT EXISTS idx_rag_queries_namespace ON rag_queries(namespace);"#,
+            r#"CREATE TABLE IF NOT EXISTS rag_feedback(
+                id SERIAL PRIMARY KEY,
+                query_id UUID NOT NULL REFERENCES rag_queries(id) ON DELETE CASCADE,
+                rating TEXT NOT NULL CHECK (rating IN ('up', 'down')),
+                selected_citations TEXT[] NOT NULL DEFAULT '{}',
+                comment TEXT,
+                created_at TIMESTAMPTZ DEFAULT now()
+            );"#,
+            r#"CREATE INDEX IF NOT EXISTS idx_rag_feedback_query ON rag_feedback(query_id);"#,
+            r#"CREATE TABLE IF NOT EXISTS ranking_profiles(
+                namespace TEXT NOT NULL,
+                version INTEGER NOT NULL,
+                weights JSONB NOT NULL,
+                samples INTEGER NOT NULL DEFAULT 0,
+                active BOOLEAN NOT NULL DEFAULT false,
+                created_at TIMESTAMPTZ DEFAULT now(),
+                PRIMARY KEY(namespace, version)
+            );"#,
         ];
 
         for stmt in ddl {
@@ -157,6 +209,93 @@ impl PostgresMetadataStore {
         Ok(())
     }
 
+    /// Forget a path's fingerprint so the next scan processes it again.
+    pub async fn clear_fingerprint(&self, path: &str) -> MnemoResult<()> {
+        self.ensure_schema().await?;
+        tracing::debug!("Metadata: clear fingerprint for {}", path);
+        sqlx::query("DELETE FROM fingerprints WHERE path = $1")
+            .bind(path)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| MnemoError::Message(format!("clear_fingerprint failed: {e}")))?;
+        Ok(())
+    }
+
+    /// Fingerprinted paths of `namespace` at `path` or derived from it: `#` fragments such as
+    /// segments, `!/` archive members and, for a directory, everything below it. Paths without
+    /// a `files` row, such as whole archives, count as any namespace.
+    pub async fn fingerprint_paths_under(
+        &self,
+        namespace: &str,
+        path: &str,
+    ) -> MnemoResult<Vec<String>> {
+        self.ensure_schema().await?;
+        let path = if path.len() > 1 { path.trim_end_matches('/') } else { path };
+        let rows: Vec<(String,)> = sqlx::query_as(
+            "SELECT fp.path FROM fingerprints fp LEFT JOIN files f ON f.path = fp.path
+             WHERE (f.namespace IS NULL OR f.namespace = $2)
+             AND (fp.path = $1 OR starts_with(fp.path, $1 || '#')
+             OR starts_with(fp.path, $1 || '!/') OR starts_with(fp.path, $1 || '/'))",
+        )
+        .bind(path)
+        .bind(namespace)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| MnemoError::Message(format!("fingerprint listing failed: {e}")))?;
+        Ok(rows.into_iter().map(|(path,)| path).collect())
+    }
+
+    /// Fingerprinted paths of `namespace` starting with `prefix`.
+    pub async fn fingerprint_paths_with_prefix(
+        &self,
+        namespace: &str,
+        prefix: &str,
+    ) -> MnemoResult<Vec<String>> {
+        self.ensure_schema().await?;
+        let rows: Vec<(String,)> = sqlx::query_as(
+            "SELECT fp.path FROM fingerprints fp JOIN files f ON f.path = fp.path
+             WHERE f.namespace = $2 AND starts_with(fp.path, $1)",
+        )
+        .bind(prefix)
+        .bind(namespace)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| MnemoError::Message(format!("fingerprint listing failed: {e}")))?;
+        Ok(rows.into_iter().map(|(path,)| path).collect())
+    }
+
+    /// Remove a document's fingerprint and `files` row; its `chunks` and `file_chunks` rows
+    /// cascade.
+    pub async fn delete_document(&self, path: &str) -> MnemoResult<()> {
+        self.ensure_schema().await?;
+        tracing::debug!("Metadata: delete document {}", path);
+        for stmt in
+            ["DELETE FROM fingerprints WHERE path = $1", "DELETE FROM files WHERE path = $1"]
+        {
+            sqlx::query(stmt)
+                .bind(path)
+                .execute(&self.pool)
+                .await
+                .map_err(|e| MnemoError::Message(format!("delete_document failed: {e}")))?;
+        }
+        Ok(())
+    }
+
+    /// Remove a document's `chunks` rows with index `from` and above.
+    pub async fn delete_chunks_from(&self, path: &str, from: usize) -> MnemoResult<u64> {
+        self.ensure_schema().await?;
+        let result = sqlx::query(
+            "DELETE FROM chunks WHERE idx >= $2
+             AND file_id IN (SELECT id FROM files WHERE path = $1)",
+        )
+        .bind(path)
+        .bind(from as i32)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| MnemoError::Message(format!("delete_chunks_from failed: {e}")))?;
+        Ok(result.rows_affected())
+    }
+
     pub async fn insert_document_metadata(&self, doc: &Document) -> MnemoResult<()> {
         self.ensure_schema().await?;
         self.set_fingerprint(&doc.path, &doc.fingerprint).await?;
@@ -284,6 +423,51 @@ impl PostgresMetadataStore {
         Ok(chunk_id)
     }
 
+    /// Cached embeddings under `model` for the given text hashes, keyed by hash.
+    pub async fn get_cached_embeddings(
+        &self,
+        model: &str,
+        hashes: &[String],
+    ) -> MnemoResult<HashMap<String, Vec<f32>>> {
+        self.ensure_schema().await?;
+        let rows: Vec<(String, Vec<f32>)> = sqlx::query_as(
+            "SELECT text_hash, embedding FROM embedding_cache
+             WHERE model = $1 AND text_hash = ANY($2)",
+        )
+        .bind(model)
+        .bind(hashes)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| MnemoError::Message(format!("embedding cache lookup failed: {e}")))?;
+        Ok(rows.into_iter().collect())
+    }
+
+    /// Cache embeddings under `model` with one INSERT per `EMBEDDING_CACHE_WRITE_ROWS` rows.
+    /// Hashes already cached are left as they are: the same text under the same model has the
+    /// same vector.
+    pub async fn put_cached_embeddings(
+        &self,
+        model: &str,
+        rows: &[(String, Vec<f32>)],
+    ) -> MnemoResult<()> {
+        self.ensure_schema().await?;
+        for batch in rows.chunks(EMBEDDING_CACHE_WRITE_ROWS) {
+            let mut insert = QueryBuilder::<Postgres>::new(
+                "INSERT INTO embedding_cache(model, text_hash, embedding) ",
+            );
+            insert.push_values(batch, |mut row, (hash, embedding)| {
+                row.push_bind(model).push_bind(hash).push_bind(embedding);
+            });
+            insert.push(" ON CONFLICT(model, text_hash) DO NOTHING");
+            insert
+                .build()
+                .execute(&self.pool)
+                .await
+                .map_err(|e| MnemoError::Message(format!("embedding cache write failed: {e}")))?;
+        }
+        Ok(())
+    }
+
     /// Record a file-level dependency. Returns Ok(true) if inserted, Ok(false) if it already existed.
     pub async fn record_dependency(&self, path: &str, dep: &str) -> MnemoResult<bool> {
         self.ensure_schema().await?;
@@ -299,6 +483,84 @@ impl PostgresMetadataStore {
         Ok(rows.rows_affected() > 0)
     }
 
+    /// Last commit indexed for a repository ref, used as the base for incremental git syncs.
+    pub async fn get_git_commit(
+        &self,
+        repo_path: &str,
+        ref_name: &str,
+    ) -> MnemoResult<Option<String>> {
+        self.ensure_schema().await?;
+        let row: Option<(String,)> = sqlx::query_as(
+            "SELECT commit_sha FROM git_sync_state WHERE repo_path = $1 AND ref_name = $2",
+        )
+        .bind(repo_path)
+        .bind(ref_name)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| MnemoError::Message(format!("git commit lookup failed: {e}")))?;
+        Ok(row.map(|(sha,)| sha))
+    }
+
+    pub async fn set_git_commit(
+        &self,
+        repo_path: &str,
+        ref_name: &str,
+        sha: &str,
+    ) -> MnemoResult<()> {
+        self.ensure_schema().await?;
+        sqlx::query(
+            "INSERT INTO git_sync_state(repo_path, ref_name, commit_sha) VALUES ($1, $2, $3)
+             ON CONFLICT(repo_path, ref_name)
+             DO UPDATE SET commit_sha = EXCLUDED.commit_sha, updated_at = now()",
+        )
+        .bind(repo_path)
+        .bind(ref_name)
+        .bind(sha)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| MnemoError::Message(format!("set_git_commit failed: {e}")))?;
+        Ok(())
+    }
+
+    /// Opaque resume cursor (e.g. an `updated_at` timestamp) for a provider and scope.
+    pub async fn get_sync_cursor(
+        &self,
+        provider: &str,
+        scope: &str,
+    ) -> MnemoResult<Option<String>> {
+        self.ensure_schema().await?;
+        let row: Option<(String,)> = sqlx::query_as(
+            "SELECT sync_cursor FROM provider_sync_state WHERE provider = $1 AND scope = $2",
+        )
+        .bind(provider)
+        .bind(scope)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| MnemoError::Message(format!("sync cursor lookup failed: {e}")))?;
+        Ok(row.map(|(cursor,)| cursor))
+    }
+
+    pub async fn set_sync_cursor(
+        &self,
+        provider: &str,
+        scope: &str,
+        cursor: &str,
+    ) -> MnemoResult<()> {
+        self.ensure_schema().await?;
+        sqlx::query(
+            "INSERT INTO provider_sync_state(provider, scope, sync_cursor) VALUES ($1, $2, $3)
+             ON CONFLICT(provider, scope)
+             DO UPDATE SET sync_cursor = EXCLUDED.sync_cursor, updated_at = now()",
+        )
+        .bind(provider)
+        .bind(scope)
+        .bind(cursor)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| MnemoError::Message(format!("set_sync_cursor failed: {e}")))?;
+        Ok(())
+    }
+
     /// Load ontology rules (tag + patterns).
     pub async fn load_ontology_rules(&self) -> MnemoResult<Vec<(String, Vec<String>)>> {
         self.ensure_schema().await?;
@@ -1,4 +1,5 @@
 use super::vector_engine::VectorEngine;
+use mnemo_core::access::AccessScope;
 
 /// Wrapper providing a unified vector search interface.
 pub struct VectorSearchEngine {
@@ -16,7 +17,8 @@ impl VectorSearchEngine {
         top_k: usize,
         namespace: &str,
         tag: Option<&str>,
+        scope: &AccessScope,
     ) -> mnemo_core::error::MnemoResult<Vec<String>> {
-        self.engine.search(query, top_k, namespace, tag).await
+        self.engine.search(query, top_k, namespace, tag, scope).await
     }
 }
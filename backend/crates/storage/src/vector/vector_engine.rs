@@ -1,4 +1,5 @@
 use super::qdrant::QdrantVectorStore;
+use mnemo_core::access::AccessScope;
 use mnemo_core::error::MnemoResult;
 use serde_json::Map;
 use serde_json::Value;
@@ -20,8 +21,9 @@ impl VectorEngine {
         top_k: usize,
         namespace: &str,
         tag: Option<&str>,
+        scope: &AccessScope,
     ) -> MnemoResult<Vec<String>> {
-        if top_k == 0 {
+        if top_k == 0 || !scope.allows_namespace(namespace) {
             return Ok(Vec::new());
         }
 
@@ -29,10 +31,8 @@ impl VectorEngine {
         let url = format!("{}/collections/mnemo_chunks/points/search", self.store.url);
 
         // Sparse query is not provided here (legacy call); dense-only fallback.
-        let mut must = vec![json!({
-            "key": "namespace",
-            "match": { "value": namespace }
-        })];
+        // Scope conditions are mandatory; the optional tag only narrows further.
+        let mut must = scope.qdrant_must(namespace);
         if let Some(t) = tag {
             must.push(json!({
                 "key": "tags",
@@ -91,27 +91,34 @@ impl VectorEngine {
         namespace: &str,
         tags: &[String],
         chunk_index: usize,
+        metadata: &serde_json::Map<String, serde_json::Value>,
     ) -> MnemoResult<()> {
         let client = reqwest::Client::new();
         let url = format!("{}/collections/mnemo_chunks/points?wait=true", self.store.url);
         // Avoid overlarge payloads to Qdrant by truncating text.
         let text_preview: String = chunk_text.chars().take(2000).collect();
 
+        let mut point_payload = json!({
+            "text": text_preview,
+            "path": document_path,
+            "namespace": namespace,
+            "chunk_index": chunk_index as i32,
+            "tags": tags,
+        });
+        // Chunk metadata never overrides the core fields used for filtering.
+        if let Some(obj) = point_payload.as_object_mut() {
+            for (key, value) in metadata {
+                obj.entry(key.clone()).or_insert_with(|| value.clone());
+            }
+        }
+
         // Use PointsBatch format to avoid “missing field `ids`” parsing errors.
         let payload = json!({
             "ids": [chunk_id],
             "vectors": [
                 { "dense": vector }
             ],
-            "payloads": [
-                {
-                    "text": text_preview,
-                    "path": document_path,
-                    "namespace": namespace,
-                    "chunk_index": chunk_index as i32,
-                    "tags": tags,
-                }
-            ]
+            "payloads": [point_payload]
         });
 
         let res = client.post(url).json(&payload).send().await;
@@ -129,6 +136,39 @@ impl VectorEngine {
         }
     }
 
+    /// Delete the points of a document in `namespace` with `chunk_index` at or above
+    /// `from_index`; 0 deletes all. The same path in another namespace is left alone.
+    pub async fn delete_chunks(
+        &self,
+        namespace: &str,
+        document_path: &str,
+        from_index: usize,
+    ) -> MnemoResult<()> {
+        let client = reqwest::Client::new();
+        let url = format!("{}/collections/mnemo_chunks/points/delete?wait=true", self.store.url);
+        let payload = json!({
+            "filter": {
+                "must": [
+                    { "key": "namespace", "match": { "value": namespace } },
+                    { "key": "path", "match": { "value": document_path } },
+                    { "key": "chunk_index", "range": { "gte": from_index } }
+                ]
+            }
+        });
+
+        let res = client.post(url).json(&payload).send().await.map_err(|e| {
+            mnemo_core::error::MnemoError::Message(format!("Qdrant delete failed: {e}"))
+        })?;
+        if res.status().is_success() {
+            Ok(())
+        } else {
+            Err(mnemo_core::error::MnemoError::Message(format!(
+                "Qdrant delete failed with status {}",
+                res.status()
+            )))
+        }
+    }
+
     /// Update only metadata fields for an existing point without touching vectors.
     /// Allows refreshing tags / namespace / file_path when ontology or file metadata changes.
     pub async fn update_metadata(
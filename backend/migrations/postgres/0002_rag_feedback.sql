@@ -0,0 +1,33 @@
+-- Answer feedback and learned ranking profiles
+
+-- Scored candidates of every answered query, referenced by feedback
+CREATE TABLE IF NOT EXISTS rag_queries (
+    id UUID PRIMARY KEY,
+    namespace TEXT NOT NULL,
+    query TEXT NOT NULL,
+    candidates JSONB NOT NULL DEFAULT '[]'::jsonb,
+    created_at TIMESTAMPTZ DEFAULT now()
+);
+CREATE INDEX IF NOT EXISTS idx_rag_queries_namespace ON rag_queries(namespace);
+
+-- Thumbs up/down with the citations the user selected
+CREATE TABLE IF NOT EXISTS rag_feedback (
+    id SERIAL PRIMARY KEY,
+    query_id UUID NOT NULL REFERENCES rag_queries(id) ON DELETE CASCADE,
+    rating TEXT NOT NULL CHECK (rating IN ('up', 'down')),
+    selected_citations TEXT[] NOT NULL DEFAULT '{}',
+    comment TEXT,
+    created_at TIMESTAMPTZ DEFAULT now()
+);
+CREATE INDEX IF NOT EXISTS idx_rag_feedback_query ON rag_feedback(query_id);
+
+-- Versioned ranking weights per namespace; at most one active version
+CREATE TABLE IF NOT EXISTS ranking_profiles (
+    namespace TEXT NOT NULL,
+    version INTEGER NOT NULL,
+    weights JSONB NOT NULL,
+    samples INTEGER NOT NULL DEFAULT 0,
+    active BOOLEAN NOT NULL DEFAULT false,
+    created_at TIMESTAMPTZ DEFAULT now(),
+    PRIMARY KEY (namespace, version)
+);
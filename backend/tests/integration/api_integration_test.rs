@@ -1,7 +1,43 @@
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
 use mnemo_api::build_router;
+use tower::ServiceExt;
 
 #[tokio::test]
 async fn router_initializes() {
     let _router = build_router();
     assert!(true);
 }
+
+#[tokio::test]
+async fn graph_snapshot_requires_a_known_caller() {
+    let path = std::env::temp_dir().join(format!("mnemo_access_{}.yaml", std::process::id()));
+    std::fs::write(
+        &path,
+        "principals:\n  team-a:\n    api_keys: [\"key-a\"]\n    namespaces: [\"team-a\"]\n",
+    )
+    .unwrap();
+    // Only this test touches these; Surreal points at a closed port so the handler degrades
+    // to an empty snapshot instead of waiting on a database.
+    unsafe {
+        std::env::set_var("MNEMO_ACCESS_POLICY", &path);
+        std::env::set_var("SURREALDB_URL", "ws://127.0.0.1:1");
+    }
+    let router = build_router();
+
+    let request = |key: Option<&str>| {
+        let mut builder = Request::get("/v1/graph/snapshot");
+        if let Some(key) = key {
+            builder = builder.header("x-api-key", key);
+        }
+        builder.body(Body::empty()).unwrap()
+    };
+    let ok = router.clone().oneshot(request(Some("key-a"))).await.unwrap();
+    let anonymous = router.clone().oneshot(request(None)).await.unwrap();
+    let unknown = router.oneshot(request(Some("nope"))).await.unwrap();
+    let _ = std::fs::remove_file(path);
+
+    assert_eq!(ok.status(), StatusCode::OK);
+    assert_eq!(anonymous.status(), StatusCode::UNAUTHORIZED);
+    assert_eq!(unknown.status(), StatusCode::UNAUTHORIZED);
+}
@@ -0,0 +1,83 @@
+use mnemo_core::access::{AccessPolicy, AccessScope};
+
+fn policy() -> AccessPolicy {
+    let path = std::env::temp_dir().join(format!("mnemo_access_{}.yaml", std::process::id()));
+    std::fs::write(
+        &path,
+        r#"
+principals:
+  team-a:
+    api_keys: ["key-a"]
+    namespaces: ["team-a", "shared"]
+  bot:
+    namespaces: ["*"]
+    tags: ["public"]
+"#,
+    )
+    .unwrap();
+    let policy = AccessPolicy::load_from(path.to_str().unwrap()).expect("policy");
+    let _ = std::fs::remove_file(path);
+    policy
+}
+
+#[test]
+fn api_key_maps_to_allowed_namespaces() {
+    let policy = policy();
+    assert_eq!(policy.principal_for_api_key("key-a"), Some("team-a"));
+    assert_eq!(policy.principal_for_api_key("nope"), None);
+
+    let scope = policy.scope_for("team-a").unwrap();
+    assert!(scope.allows_namespace("shared"));
+    assert!(!scope.allows_namespace("team-b"));
+    assert!(policy.scope_for("stranger").is_none());
+}
+
+#[test]
+fn tag_grants_become_mandatory_qdrant_filters() {
+    let scope = policy().scope_for("bot").unwrap();
+    assert!(scope.allows_namespace("anything"));
+    let must = scope.qdrant_must("docs");
+    assert_eq!(must.len(), 2);
+    assert_eq!(must[1]["match"]["any"][0], "public");
+    assert!(!scope.allows_tags(&["internal".to_string()]));
+}
+
+#[test]
+fn unrestricted_scope_has_no_graph_condition() {
+    assert!(AccessScope::unrestricted().surreal_namespace_condition("in.namespace").is_none());
+    let scope = policy().scope_for("team-a").unwrap();
+    assert_eq!(
+        scope.surreal_namespace_condition("in.namespace").unwrap(),
+        "in.namespace INSIDE ['team-a', 'shared']"
+    );
+}
+
+#[test]
+fn tag_grants_restrict_graph_records_and_edges() {
+    let scope = policy().scope_for("bot").unwrap();
+    assert_eq!(scope.surreal_record_condition("").unwrap(), "tags CONTAINSANY ['public']");
+    assert_eq!(
+        scope.surreal_edge_condition().unwrap(),
+        "in.tags CONTAINSANY ['public'] AND out.tags CONTAINSANY ['public']"
+    );
+
+    let both = AccessScope {
+        principal: None,
+        namespaces: Some(vec!["docs".into()]),
+        tags: Some(vec!["public".into()]),
+    };
+    assert_eq!(
+        both.surreal_record_condition("out.").unwrap(),
+        "out.namespace INSIDE ['docs'] AND out.tags CONTAINSANY ['public']"
+    );
+    assert!(AccessScope::unrestricted().surreal_edge_condition().is_none());
+}
+
+#[test]
+fn rag_events_reach_only_the_requesting_principal() {
+    let team_a = policy().scope_for("team-a").unwrap();
+    assert!(team_a.may_receive("team-a", Some("team-a")));
+    assert!(!team_a.may_receive("team-a", Some("bot")));
+    assert!(!team_a.may_receive("secret", Some("team-a")));
+    assert!(AccessScope::unrestricted().may_receive("default", None));
+}
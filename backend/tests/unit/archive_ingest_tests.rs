@@ -0,0 +1,93 @@
+use std::io::Write;
+
+use mnemo_ingest::providers::archive::{ArchiveKind, ArchiveLimits, expand_archive};
+use mnemo_ingest::providers::filesystem::FilesystemProvider;
+
+mod support;
+use support::fixture_dir;
+
+fn zip_bytes(entries: &[(&str, &str)]) -> Vec<u8> {
+    let mut writer = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+    for (name, text) in entries {
+        writer.start_file(*name, zip::write::FileOptions::default()).unwrap();
+        writer.write_all(text.as_bytes()).unwrap();
+    }
+    writer.finish().unwrap().into_inner()
+}
+
+fn tar_gz_bytes(entries: &[(&str, &str)]) -> Vec<u8> {
+    let encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    let mut builder = tar::Builder::new(encoder);
+    for (name, text) in entries {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(text.len() as u64);
+        header.set_mode(0o644);
+        builder.append_data(&mut header, name, text.as_bytes()).unwrap();
+    }
+    builder.into_inner().unwrap().finish().unwrap()
+}
+
+#[test]
+fn zip_members_are_scanned_with_bang_paths_and_unsafe_paths_skipped() {
+    let dir = fixture_dir("archive_zip", &[]);
+    let bundle = zip_bytes(&[
+        ("docs/guide.md", "# Guide\n\nInstall the tool."),
+        ("../evil.md", "# Escaped"),
+        ("/etc/abs.md", "# Absolute"),
+        ("inner.zip", "not expanded"),
+        ("logo.png", "binary"),
+    ]);
+    std::fs::write(dir.join("bundle.zip"), bundle).unwrap();
+
+    let docs = FilesystemProvider::new(vec![dir.to_string_lossy().to_string()]).scan();
+    let _ = std::fs::remove_dir_all(&dir);
+
+    assert_eq!(docs.len(), 1, "{:?}", docs.iter().map(|d| &d.path).collect::<Vec<_>>());
+    let guide = &docs[0];
+    assert!(guide.path.ends_with("bundle.zip!/docs/guide.md"));
+    assert_eq!(guide.language.as_deref(), Some("markdown"));
+    assert!(guide.content.contains("Install the tool."));
+    let meta = guide.metadata.as_ref().unwrap();
+    assert!(meta["archive"].as_str().unwrap().ends_with("bundle.zip"));
+    assert_eq!(meta["archive_member"], "docs/guide.md");
+    assert_eq!(meta["archive_fingerprint"].as_str().unwrap().len(), 64);
+}
+
+#[test]
+fn tar_gz_members_share_the_archive_fingerprint() {
+    let dir = fixture_dir("archive_tgz", &[]);
+    std::fs::write(
+        dir.join("drop.tar.gz"),
+        tar_gz_bytes(&[("notes/a.md", "# A\n\nFirst."), ("notes/b.txt", "Second.")]),
+    )
+    .unwrap();
+
+    let mut docs = FilesystemProvider::new(vec![dir.to_string_lossy().to_string()]).scan();
+    let _ = std::fs::remove_dir_all(&dir);
+    docs.sort_by(|a, b| a.path.cmp(&b.path));
+
+    assert_eq!(docs.len(), 2);
+    assert!(docs[0].path.ends_with("drop.tar.gz!/notes/a.md"));
+    assert!(docs[1].path.ends_with("drop.tar.gz!/notes/b.txt"));
+    let fingerprint = |i: usize| docs[i].metadata.as_ref().unwrap()["archive_fingerprint"].clone();
+    assert_eq!(fingerprint(0), fingerprint(1));
+    assert_ne!(docs[0].fingerprint, docs[1].fingerprint);
+}
+
+#[test]
+fn limits_cap_entries_and_member_size() {
+    let bundle = zip_bytes(&[("a.md", "small"), ("b.md", &"x".repeat(2_000)), ("c.md", "small")]);
+
+    let limits = ArchiveLimits { max_entry_bytes: 1_000, ..ArchiveLimits::default() };
+    let members = expand_archive(ArchiveKind::Zip, &bundle, &limits);
+    let names: Vec<&str> = members.iter().map(|m| m.path.as_str()).collect();
+    assert_eq!(names, ["a.md", "c.md"]);
+
+    let limits = ArchiveLimits { max_entries: 1, ..ArchiveLimits::default() };
+    assert_eq!(expand_archive(ArchiveKind::Zip, &bundle, &limits).len(), 1);
+
+    let limits = ArchiveLimits { max_total_bytes: 8, ..ArchiveLimits::default() };
+    assert_eq!(expand_archive(ArchiveKind::Zip, &bundle, &limits).len(), 1);
+
+    assert!(expand_archive(ArchiveKind::TarGz, b"not an archive", &limits).is_empty());
+}
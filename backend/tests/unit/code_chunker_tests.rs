@@ -0,0 +1,81 @@
+use mnemo_ingest::pipeline::chunk_builder::{ChunkBuilder, FileType};
+use mnemo_ingest::pipeline::code_chunker::{CodeLanguage, chunk_code};
+
+const RUST_SRC: &str = r#"use std::fmt;
+
+/// A server.
+pub(crate) struct Server {
+    port: u16,
+}
+
+impl Server {
+    /// Start listening.
+    pub(crate) fn start(&self) -> bool {
+        true
+    }
+
+    fn stop(&self) {}
+}
+
+mod inner {
+    pub fn helper() {}
+}
+"#;
+
+#[test]
+fn rust_items_keep_doc_comments_and_scope() {
+    let items = chunk_code(RUST_SRC, CodeLanguage::Rust).expect("parses");
+    let start = items.iter().find(|i| i.name == "start").expect("method chunk");
+    assert!(start.text.starts_with("/// Start listening."));
+    assert_eq!(start.kind, "function_item");
+    assert_eq!(start.scope_path(CodeLanguage::Rust), "Server");
+
+    let server = items.iter().find(|i| i.kind == "struct_item").expect("struct chunk");
+    assert!(server.text.starts_with("/// A server."));
+    assert!(server.scope.is_empty());
+
+    let helper = items.iter().find(|i| i.name == "helper").expect("nested fn");
+    assert_eq!(helper.scope, vec!["inner".to_string()]);
+    assert!(items.iter().any(|i| i.text.contains("use std::fmt;")));
+}
+
+#[test]
+fn python_and_typescript_methods_are_separate_items() {
+    let py = "class Repo:\n    \"\"\"Docs.\"\"\"\n\n    @property\n    def name(self):\n        return 1\n\n    def save(self):\n        pass\n";
+    let items = chunk_code(py, CodeLanguage::Python).expect("parses");
+    let name = items.iter().find(|i| i.name == "name").expect("decorated method");
+    assert!(name.text.starts_with("@property"));
+    assert_eq!(name.scope, vec!["Repo".to_string()]);
+    assert!(items.iter().any(|i| i.name == "save"));
+
+    let ts = "export class Api {\n  /** Fetch one. */\n  get(id: string) { return id; }\n}\nexport const load = () => 1;\n";
+    let items = chunk_code(ts, CodeLanguage::TypeScript).expect("parses");
+    let get = items.iter().find(|i| i.name == "get").expect("method");
+    assert!(get.text.starts_with("/** Fetch one. */"));
+    assert_eq!(get.scope_path(CodeLanguage::TypeScript), "Api");
+    assert!(items.iter().any(|i| i.name == "load"));
+}
+
+#[test]
+fn unknown_language_falls_back_to_regex_chunks() {
+    let ruby = "def a\n  1\nend\n\ndef b\n  2\nend\n";
+    let chunks = ChunkBuilder::build_document(ruby, FileType::Code, "lib/x.rb", None);
+    assert!(!chunks.is_empty());
+    assert!(chunks.iter().all(|c| c.metadata.is_empty()));
+
+    let chunks = ChunkBuilder::build_document(RUST_SRC, FileType::Code, "src/server.rs", None);
+    let start = chunks.iter().find(|c| c.text.contains("fn start")).expect("chunk");
+    assert_eq!(start.metadata["code_symbol"], "start");
+    assert_eq!(start.metadata["code_scope"], "Server");
+    assert_eq!(start.metadata["code_language"], "rust");
+}
+
+#[test]
+fn tsx_files_parse_with_the_tsx_grammar() {
+    let tsx = "export function Button({ label }: Props) {\n  return <button className=\"btn\">{label}</button>;\n}\n\nexport const Icon = () => <svg />;\n";
+    let chunks =
+        ChunkBuilder::build_document(tsx, FileType::Code, "ui/Button.tsx", Some("typescript"));
+    let button = chunks.iter().find(|c| c.text.contains("<button")).expect("chunk");
+    assert_eq!(button.metadata["code_symbol"], "Button");
+    assert_eq!(button.metadata["code_language"], "tsx");
+}
@@ -0,0 +1,291 @@
+use mnemo_core::models::document::Document;
+use mnemo_ingest::providers::asyncapi::AsyncApiProvider;
+use mnemo_ingest::providers::graphql::GraphQlProvider;
+use mnemo_ingest::providers::protobuf::ProtobufProvider;
+use serde_json::json;
+
+fn find<'a>(docs: &'a [Document], path: &str) -> &'a Document {
+    docs.iter().find(|d| d.path == path).unwrap_or_else(|| panic!("missing {path}"))
+}
+
+fn meta<'a>(doc: &'a Document, key: &str) -> &'a serde_json::Value {
+    &doc.metadata.as_ref().unwrap()[key]
+}
+
+const ORDERS_V2: &str = r##"
+asyncapi: 2.6.0
+info: {title: Orders, version: "1"}
+channels:
+  orders/created:
+    description: New orders
+    subscribe:
+      operationId: onOrderCreated
+      summary: Order was created
+      message: {$ref: "#/components/messages/OrderCreated"}
+  orders/cancel:
+    publish:
+      message:
+        oneOf:
+          - $ref: "#/components/messages/CancelOrder"
+          - name: CancelAll
+            payload: {type: object, properties: {reason: {type: string}}}
+components:
+  messages:
+    OrderCreated:
+      contentType: application/json
+      payload: {$ref: "#/components/schemas/Order"}
+    CancelOrder:
+      payload:
+        type: object
+        properties:
+          orderId: {type: string}
+  schemas:
+    Order:
+      type: object
+      properties:
+        id: {type: string}
+        total: {type: number}
+"##;
+
+#[test]
+fn asyncapi2_channels_become_operations_with_messages() {
+    let docs = AsyncApiProvider::new("orders.yaml".into()).parse_spec(ORDERS_V2);
+    assert_eq!(docs.len(), 5);
+
+    let created = find(&docs, "asyncapi SUBSCRIBE orders/created");
+    assert!(created.content.contains("Order was created"));
+    assert!(created.content.contains("Channel: `orders/created`"));
+    assert!(created.content.contains("New orders"));
+    assert!(created.content.contains("## Message `OrderCreated`"));
+    assert!(created.content.contains("Content type: application/json"));
+    assert!(created.content.contains("- `total` (number)"));
+    assert_eq!(meta(created, "asyncapi_version"), "2.6.0");
+    assert_eq!(meta(created, "operation_id"), "onOrderCreated");
+    assert_eq!(meta(created, "messages"), &json!(["asyncapi message OrderCreated"]));
+    assert_eq!(meta(created, "schemas"), &json!(["asyncapi schema Order"]));
+
+    let cancel = find(&docs, "asyncapi PUBLISH orders/cancel");
+    assert!(cancel.content.contains("## Message `CancelOrder`"));
+    assert!(cancel.content.contains("## Message `CancelAll`"));
+    assert!(cancel.content.contains("- `reason` (string)"));
+    assert!(
+        meta(cancel, "messages")
+            .as_array()
+            .unwrap()
+            .contains(&json!("asyncapi message CancelOrder"))
+    );
+
+    let message = find(&docs, "asyncapi message OrderCreated");
+    assert_eq!(meta(message, "schemas"), &json!(["asyncapi schema Order"]));
+    assert!(find(&docs, "asyncapi schema Order").content.contains("- `id` (string)"));
+}
+
+#[test]
+fn asyncapi3_operations_resolve_channel_address() {
+    let spec = json!({
+        "asyncapi": "3.0.0",
+        "info": {"title": "Chat", "version": "1"},
+        "channels": {
+            "roomMessages": {
+                "address": "rooms/{roomId}/messages",
+                "messages": {"Posted": {"$ref": "#/components/messages/Posted"}}
+            }
+        },
+        "operations": {
+            "sendMessage": {
+                "action": "send",
+                "channel": {"$ref": "#/channels/roomMessages"},
+                "messages": [{"$ref": "#/channels/roomMessages/messages/Posted"}]
+            }
+        },
+        "components": {
+            "messages": {
+                "Posted": {"payload": {"type": "object", "properties": {"text": {"type": "string"}}}}
+            }
+        }
+    });
+    let docs = AsyncApiProvider::new("chat.json".into()).parse_spec(&spec.to_string());
+    assert_eq!(docs.len(), 2);
+
+    let send = find(&docs, "asyncapi SEND rooms/{roomId}/messages");
+    assert!(send.content.contains("## Message `Posted`"));
+    assert!(send.content.contains("- `text` (string)"));
+    assert_eq!(meta(send, "action"), "send");
+    assert_eq!(meta(send, "operation_id"), "sendMessage");
+    assert_eq!(meta(send, "messages"), &json!(["asyncapi message Posted"]));
+}
+
+const SCHEMA_SDL: &str = r#"
+"""A user account."""
+type User implements Node {
+  id: ID!
+  name: String @deprecated(reason: "use displayName")
+  posts(first: Int = 10): [Post!]!
+}
+
+interface Node {
+  id: ID!
+}
+
+type Post {
+  id: ID!
+  author: User
+}
+
+type Query {
+  "Look up a user."
+  user(id: ID!): User
+}
+
+enum Role {
+  ADMIN
+  "Regular member"
+  MEMBER
+}
+"#;
+
+const EXTENSION_SDL: &str = r#"
+extend type Query {
+  posts: [Post!]!
+}
+
+extend type User {
+  role: Role
+}
+"#;
+
+#[test]
+fn graphql_root_fields_and_types_merge_extensions() {
+    let files = vec![
+        ("ext.graphql".to_string(), EXTENSION_SDL.to_string()),
+        ("schema.graphql".to_string(), SCHEMA_SDL.to_string()),
+    ];
+    let docs = GraphQlProvider::new("schema".into()).parse_schema(&files);
+    assert_eq!(docs.len(), 6);
+
+    let user_query = find(&docs, "graphql Query.user");
+    assert!(user_query.content.contains("Look up a user."));
+    assert!(user_query.content.contains("Operation: query"));
+    assert!(user_query.content.contains("Signature: `user(id: ID!): User`"));
+    assert!(user_query.content.contains("- `id` (ID!)"));
+    assert!(user_query.content.contains("`User`: A user account."));
+    assert_eq!(meta(user_query, "return_type"), "User");
+    assert_eq!(meta(user_query, "schemas"), &json!(["graphql type User"]));
+
+    // Declared in the extension file only.
+    let posts = find(&docs, "graphql Query.posts");
+    assert_eq!(meta(posts, "schemas"), &json!(["graphql type Post"]));
+
+    let user = find(&docs, "graphql type User");
+    assert!(user.content.contains("Implements: Node"));
+    assert!(user.content.contains("- `posts(first: Int)` ([Post!]!)"));
+    assert!(user.content.contains("- `name` (String), deprecated"));
+    assert!(user.content.contains("- `role` (Role)"));
+    assert_eq!(meta(user, "graphql_kind"), "object");
+    assert_eq!(
+        meta(user, "schemas"),
+        &json!(["graphql type Node", "graphql type Post", "graphql type Role"])
+    );
+
+    let role = find(&docs, "graphql type Role");
+    assert!(role.content.contains("- `MEMBER`: Regular member"));
+}
+
+const CATALOG_PROTO: &str = r#"
+syntax = "proto3";
+package shop.v1;
+
+import "shop/v1/common.proto";
+
+// Catalog of items.
+service Catalog {
+  // Fetch one item.
+  rpc GetItem(GetItemRequest) returns (Item);
+  rpc WatchItems(GetItemRequest) returns (stream Item) {
+    option deprecated = true;
+  }
+}
+
+message GetItemRequest {
+  string id = 1; // Item id
+}
+
+// A catalog item.
+message Item {
+  string id = 1;
+  repeated Tag tags = 2;
+  map<string, Price> prices = 3;
+  oneof source {
+    string url = 4;
+    bytes blob = 5;
+  }
+  Status status = 6;
+
+  enum Status {
+    STATUS_UNKNOWN = 0;
+    // Visible to buyers.
+    STATUS_ACTIVE = 1;
+  }
+}
+"#;
+
+const COMMON_PROTO: &str = r#"
+syntax = "proto3";
+package shop.v1;
+
+message Tag {
+  string name = 1;
+}
+
+message Price {
+  int64 cents = 1;
+  string currency = 2 [json_name = "cur"];
+}
+"#;
+
+#[test]
+fn protobuf_rpcs_messages_and_enums_resolve_across_files() {
+    let files = vec![
+        ("catalog.proto".to_string(), CATALOG_PROTO.to_string()),
+        ("common.proto".to_string(), COMMON_PROTO.to_string()),
+    ];
+    let docs = ProtobufProvider::new("protos".into()).parse_files(&files);
+    assert_eq!(docs.len(), 7);
+
+    let get = find(&docs, "proto rpc shop.v1.Catalog/GetItem");
+    assert!(get.content.contains("Fetch one item."));
+    assert!(get.content.contains("Catalog of items."));
+    assert!(get.content.contains("Signature: `rpc GetItem(GetItemRequest) returns (Item)`"));
+    assert!(get.content.contains("## Request `shop.v1.GetItemRequest`"));
+    assert!(get.content.contains("- `id` (string, 1): Item id"));
+    assert_eq!(meta(get, "request_type"), "shop.v1.GetItemRequest");
+    assert_eq!(
+        meta(get, "schemas"),
+        &json!(["proto message shop.v1.GetItemRequest", "proto message shop.v1.Item"])
+    );
+
+    let watch = find(&docs, "proto rpc shop.v1.Catalog/WatchItems");
+    assert_eq!(meta(watch, "client_streaming"), false);
+    assert_eq!(meta(watch, "server_streaming"), true);
+
+    let item = find(&docs, "proto message shop.v1.Item");
+    assert!(item.content.contains("A catalog item."));
+    assert!(item.content.contains("- `tags` (repeated Tag, 2)"));
+    assert!(item.content.contains("- `prices` (map<string, Price>, 3)"));
+    assert!(item.content.contains("- `url` (string, 4, oneof source)"));
+    // Nested enum resolves before the package scope.
+    assert_eq!(
+        meta(item, "schemas"),
+        &json!([
+            "proto enum shop.v1.Item.Status",
+            "proto message shop.v1.Price",
+            "proto message shop.v1.Tag"
+        ])
+    );
+
+    let status = find(&docs, "proto enum shop.v1.Item.Status");
+    assert!(status.content.contains("- `STATUS_ACTIVE` = 1: Visible to buyers."));
+    assert!(
+        find(&docs, "proto message shop.v1.Price").content.contains("- `currency` (string, 2)")
+    );
+}
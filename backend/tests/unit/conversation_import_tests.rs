@@ -0,0 +1,205 @@
+use mnemo_core::models::document::Document;
+use mnemo_ingest::providers::chat::{ChatExportProvider, ChatFormat};
+use mnemo_ingest::providers::mailbox::MailboxProvider;
+use serde_json::json;
+
+mod support;
+use support::fixture_dir;
+
+fn meta<'a>(doc: &'a Document, key: &str) -> &'a serde_json::Value {
+    &doc.metadata.as_ref().unwrap()[key]
+}
+
+const MBOX: &str = "From alice@example.com Wed May  1 10:00:00 2024
+Message-ID: <1@example.com>
+From: Alice <alice@example.com>
+To: team@example.com
+Subject: Deploy window
+Date: Wed, 01 May 2024 10:00:00 +0000
+
+Can we deploy on Friday?
+
+From bob@example.com Wed May  1 11:00:00 2024
+Message-ID: <2@example.com>
+In-Reply-To: <1@example.com>
+References: <1@example.com>
+From: Bob <bob@example.com>
+To: team@example.com
+Subject: Re: Deploy window
+Date: Wed, 01 May 2024 11:00:00 +0000
+
+Friday works.
+>From the runbook it takes an hour.
+
+On Wed, May 1, 2024 at 10:00 AM Alice <alice@example.com> wrote:
+> Can we deploy on Friday?
+
+From carol@example.com Wed May  1 12:00:00 2024
+Message-ID: <3@example.com>
+In-Reply-To: <2@example.com>
+From: carol@example.com
+Subject: Re: Deploy window
+Date: Wed, 01 May 2024 12:00:00 +0000
+
+Agreed.
+-----Original Message-----
+Friday works.
+
+From dave@example.com Thu May  2 09:00:00 2024
+Message-ID: <4@example.com>
+From: Dave <dave@example.com>
+Subject: Lunch
+Date: Thu, 02 May 2024 09:00:00 +0000
+
+Pizza?
+";
+
+#[test]
+fn mbox_threads_follow_reply_headers_and_drop_quotes() {
+    let root = fixture_dir("chat_mbox", &[("archive.mbox", MBOX)]);
+    let mbox = root.join("archive.mbox").display().to_string();
+    let docs = MailboxProvider::new(mbox, "local".into()).load_documents();
+    assert_eq!(docs.len(), 2);
+
+    let thread = docs.iter().find(|d| d.path.ends_with("/1@example.com")).unwrap();
+    assert!(thread.content.starts_with("# Deploy window"));
+    assert!(thread.content.contains("From the runbook it takes an hour."));
+    assert!(!thread.content.contains("> Can we deploy"));
+    assert!(!thread.content.contains("wrote:"));
+    assert_eq!(thread.content.matches("Friday works.").count(), 1);
+    assert_eq!(meta(thread, "participants"), &json!(["Alice", "Bob", "carol@example.com"]));
+    assert_eq!(
+        meta(thread, "participant_ids"),
+        &json!(["alice@example.com", "bob@example.com", "carol@example.com"])
+    );
+    assert_eq!(meta(thread, "message_count"), 3);
+    assert_eq!(meta(thread, "started_at"), "2024-05-01T10:00:00+00:00");
+    assert_eq!(meta(thread, "last_message_at"), "2024-05-01T12:00:00+00:00");
+    assert_eq!(meta(thread, "recipients"), &json!(["team@example.com"]));
+}
+
+#[test]
+fn mail_participants_are_keyed_by_address() {
+    let mbox = "From alice@example.com Wed May  1 10:00:00 2024
+Message-ID: <10@example.com>
+From: Alice <alice@example.com>
+Subject: Release
+Date: Wed, 01 May 2024 10:00:00 +0000
+
+Ship it?
+
+From alice@example.com Wed May  1 11:00:00 2024
+Message-ID: <11@example.com>
+In-Reply-To: <10@example.com>
+From: \"Alice Smith (phone)\" <Alice@Example.com>
+Subject: Re: Release
+Date: Wed, 01 May 2024 11:00:00 +0000
+
+Shipping now.
+";
+    let root = fixture_dir("chat_mbox_ids", &[("archive.mbox", mbox)]);
+    let path = root.join("archive.mbox").display().to_string();
+    let docs = MailboxProvider::new(path, "local".into()).load_documents();
+
+    assert_eq!(docs.len(), 1);
+    assert_eq!(meta(&docs[0], "participants"), &json!(["Alice", "Alice Smith (phone)"]));
+    assert_eq!(meta(&docs[0], "participant_ids"), &json!(["alice@example.com"]));
+}
+
+#[test]
+fn maildir_reads_cur_and_new() {
+    let message = |id: &str, parent: &str, body: &str| {
+        format!(
+            "Message-ID: <{id}>\nIn-Reply-To: <{parent}>\nFrom: Eve <eve@example.com>\n\
+             Subject: Re: Budget\nDate: Fri, 03 May 2024 08:00:00 +0000\n\
+             Content-Type: text/html; charset=utf-8\n\n{body}"
+        )
+    };
+    let root = fixture_dir(
+        "chat_maildir",
+        &[
+            ("INBOX/cur/1:2,S", message("b@x", "a@x", "<p>First <b>reply</b></p>").as_str()),
+            ("INBOX/new/2", message("c@x", "b@x", "<p>Second reply</p>").as_str()),
+            ("INBOX/tmp/3", message("d@x", "a@x", "<p>Still being delivered</p>").as_str()),
+        ],
+    );
+    let docs = MailboxProvider::new(root.display().to_string(), "local".into()).load_documents();
+    assert_eq!(docs.len(), 1);
+    // The root message is missing from the archive; replies still share its thread.
+    assert!(docs[0].path.ends_with("/a@x"));
+    assert_eq!(docs[0].content.lines().next(), Some("# Budget"));
+    assert!(docs[0].content.contains("Second reply"));
+    assert!(!docs[0].content.contains("Still being delivered"));
+    assert_eq!(meta(&docs[0], "message_count"), 2);
+}
+
+#[test]
+fn slack_export_groups_threads_and_channel_days() {
+    let day = json!([
+        {"type": "message", "user": "U1", "text": "Is <#C1|general> the right place?", "ts": "1714557600.000100", "thread_ts": "1714557600.000100", "reply_count": 1},
+        {"type": "message", "user": "U2", "text": "&gt; Is it?\nYes <@U1>", "ts": "1714557660.000200", "thread_ts": "1714557600.000100"},
+        {"type": "message", "subtype": "channel_join", "user": "U2", "text": "<@U2> has joined", "ts": "1714557700.000000"},
+        {"type": "message", "user": "U2", "text": "Morning all", "ts": "1714557800.000000"},
+    ]);
+    let users = json!([
+        {"id": "U1", "name": "ana", "profile": {"display_name": "Ana", "real_name": "Ana Lopez"}},
+        {"id": "U2", "name": "ben", "profile": {"display_name": "", "real_name": "Ben Ode"}},
+    ]);
+    let root = fixture_dir(
+        "chat_slack",
+        &[
+            ("users.json", users.to_string().as_str()),
+            ("channels.json", r#"[{"id": "C1", "name": "general"}]"#),
+            ("general/2024-05-01.json", day.to_string().as_str()),
+        ],
+    );
+    let docs =
+        ChatExportProvider::new(root.display().to_string(), ChatFormat::Slack, "local".into())
+            .load_documents();
+    assert_eq!(docs.len(), 2);
+
+    let thread = docs.iter().find(|d| d.path.ends_with("general/1714557600.000100")).unwrap();
+    assert!(thread.content.starts_with("# Is #general the right place?"));
+    assert!(thread.content.contains("Yes @Ana"));
+    assert!(!thread.content.contains("Is it?"));
+    assert_eq!(meta(thread, "channel"), "#general");
+    assert_eq!(meta(thread, "participants"), &json!(["Ana", "Ben Ode"]));
+    assert_eq!(meta(thread, "conversation_source"), "slack");
+
+    let day_doc = docs.iter().find(|d| d.path.ends_with("general/2024-05-01")).unwrap();
+    assert!(day_doc.content.starts_with("# #general on 2024-05-01"));
+    assert!(!day_doc.content.contains("has joined"));
+    assert_eq!(meta(day_doc, "message_count"), 1);
+}
+
+#[test]
+fn mattermost_jsonl_threads_replies_under_root_post() {
+    let lines = [
+        json!({"type": "version", "version": 1}),
+        json!({"type": "post", "post": {
+            "team": "eng", "channel": "ops", "user": "sam", "message": "Disk is full on db-1",
+            "create_at": 1714557600000i64,
+            "replies": [{"user": "kim", "message": "> Disk is full on db-1\nRotated the logs.", "create_at": 1714557660000i64}]
+        }}),
+        json!({"type": "direct_post", "direct_post": {
+            "channel_members": ["kim", "sam"], "user": "kim", "message": "thanks", "create_at": 1714557700000i64
+        }}),
+    ];
+    let text = lines.iter().map(|l| l.to_string()).collect::<Vec<_>>().join("\n");
+    let root = fixture_dir("chat_mattermost", &[("export.jsonl", text.as_str())]);
+    let path = root.join("export.jsonl").display().to_string();
+    let docs =
+        ChatExportProvider::new(path, ChatFormat::Mattermost, "local".into()).load_documents();
+    assert_eq!(docs.len(), 2);
+
+    let thread = docs.iter().find(|d| d.path.ends_with("eng/ops/1714557600000")).unwrap();
+    assert!(thread.content.starts_with("# Disk is full on db-1"));
+    assert!(thread.content.contains("Rotated the logs."));
+    assert_eq!(thread.content.matches("Disk is full").count(), 2);
+    assert_eq!(meta(thread, "channel"), "~ops");
+    assert_eq!(meta(thread, "team"), "eng");
+    assert_eq!(meta(thread, "participants"), &json!(["sam", "kim"]));
+
+    let direct = docs.iter().find(|d| d.path.contains("direct/kim,sam/")).unwrap();
+    assert_eq!(meta(direct, "channel"), "@kim,sam");
+}
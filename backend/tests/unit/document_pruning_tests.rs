@@ -0,0 +1,117 @@
+use std::sync::{Arc, Mutex};
+
+use axum::Json;
+use axum::Router;
+use axum::routing::post;
+use mnemo_core::models::document::Document;
+use mnemo_ingest::pipeline::prune_step::{missing_documents, unlisted_documents};
+use mnemo_ingest::providers::registry::PruneScope;
+use mnemo_storage::vector::qdrant::QdrantVectorStore;
+use mnemo_storage::vector::vector_engine::VectorEngine;
+use serde_json::{Value, json};
+
+fn doc(path: &str, metadata: Option<serde_json::Value>) -> Document {
+    Document {
+        path: path.into(),
+        content: String::new(),
+        fingerprint: String::new(),
+        namespace: "local".into(),
+        modified_at: None,
+        file_size: None,
+        file_type: None,
+        language: None,
+        metadata,
+    }
+}
+
+fn known(paths: &[&str]) -> Vec<String> {
+    paths.iter().map(|p| p.to_string()).collect()
+}
+
+#[test]
+fn deleted_files_and_dropped_segments_are_missing() {
+    let docs = [doc("/data/a.md", None), doc("/data/big.txt#segment_0", None)];
+    let missing = missing_documents(
+        known(&[
+            "/data/a.md",
+            "/data/gone.md",
+            "/data/big.txt#segment_0",
+            "/data/big.txt#segment_1",
+            "/data/gone.md",
+        ]),
+        &docs,
+    );
+    assert_eq!(missing, ["/data/big.txt#segment_1", "/data/gone.md"]);
+}
+
+#[test]
+fn archives_of_scanned_members_stay_and_removed_members_go() {
+    let member = doc(
+        "/data/bundle.zip!/docs/a.md",
+        Some(json!({"archive": "/data/bundle.zip", "archive_fingerprint": "abc"})),
+    );
+    let missing = missing_documents(
+        known(&["/data/bundle.zip", "/data/bundle.zip!/docs/a.md", "/data/bundle.zip!/docs/b.md"]),
+        &[member],
+    );
+    assert_eq!(missing, ["/data/bundle.zip!/docs/b.md"]);
+
+    // With no member left, the archive fingerprint itself goes too.
+    let missing = missing_documents(known(&["/data/old.tar", "/data/old.tar!/x.md"]), &[]);
+    assert_eq!(missing, ["/data/old.tar", "/data/old.tar!/x.md"]);
+}
+
+#[test]
+fn documents_from_other_providers_under_the_root_are_kept() {
+    let missing = missing_documents(known(&["/data/sales.csv#schema"]), &[]);
+    assert!(missing.is_empty());
+}
+
+#[test]
+fn provider_scope_prunes_only_its_own_unlisted_documents() {
+    let scope = PruneScope { namespace: "acme/docs".into(), prefix: "github:acme/docs/".into() };
+    let docs = [doc("github:acme/docs/README.md", None)];
+    let retained = known(&["github:acme/docs/src/lib.rs"]);
+    let missing = unlisted_documents(
+        known(&[
+            "github:acme/docs/README.md",
+            "github:acme/docs/src/lib.rs",
+            "github:acme/docs/old.md",
+            "github:acme/docs#issues/7",
+            "github:acme/docs-site/index.md",
+        ]),
+        &scope,
+        &docs,
+        &retained,
+    );
+    assert_eq!(missing, ["github:acme/docs/old.md"]);
+}
+
+#[tokio::test]
+async fn qdrant_deletes_are_confined_to_the_namespace() {
+    let seen: Arc<Mutex<Vec<Value>>> = Arc::default();
+    let recorded = seen.clone();
+    let app = Router::new().route(
+        "/collections/mnemo_chunks/points/delete",
+        post(move |Json(body): Json<Value>| async move {
+            recorded.lock().unwrap().push(body);
+            Json(json!({"status": "ok"}))
+        }),
+    );
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+    let engine = VectorEngine::new(QdrantVectorStore { url: format!("http://{addr}") });
+    engine.delete_chunks("team-a", "/data/a.md", 2).await.unwrap();
+
+    let must = seen.lock().unwrap()[0]["filter"]["must"].clone();
+    assert_eq!(
+        must,
+        json!([
+            { "key": "namespace", "match": { "value": "team-a" } },
+            { "key": "path", "match": { "value": "/data/a.md" } },
+            { "key": "chunk_index", "range": { "gte": 2 } }
+        ])
+    );
+}
@@ -0,0 +1,57 @@
+use std::io::Write;
+
+use mnemo_ingest::providers::docx::{docx_properties, extract_docx_text};
+
+const DOCUMENT: &str = r#"<?xml version="1.0"?>
+<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"><w:body>
+<w:p><w:pPr><w:pStyle w:val="Heading1"/></w:pPr><w:r><w:t>Setup</w:t></w:r></w:p>
+<w:p><w:r><w:t xml:space="preserve">Install the </w:t></w:r><w:r><w:t>agent &amp; CLI.</w:t></w:r></w:p>
+<w:p><w:pPr><w:numPr><w:ilvl w:val="0"/><w:numId w:val="1"/></w:numPr></w:pPr><w:r><w:t>Download</w:t></w:r></w:p>
+<w:p><w:pPr><w:numPr><w:ilvl w:val="0"/><w:numId w:val="1"/></w:numPr></w:pPr><w:r><w:t>Run</w:t></w:r></w:p>
+<w:tbl>
+<w:tr><w:tc><w:p><w:r><w:t>Flag</w:t></w:r></w:p></w:tc><w:tc><w:p><w:r><w:t>Meaning</w:t></w:r></w:p></w:tc></w:tr>
+<w:tr><w:tc><w:p><w:r><w:t>-v</w:t></w:r></w:p></w:tc><w:tc><w:p><w:r><w:t>verbose</w:t></w:r></w:p></w:tc></w:tr>
+</w:tbl>
+</w:body></w:document>"#;
+
+const NUMBERING: &str = r#"<?xml version="1.0"?>
+<w:numbering xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main">
+<w:abstractNum w:abstractNumId="7"><w:lvl w:ilvl="0"><w:numFmt w:val="decimal"/></w:lvl></w:abstractNum>
+<w:num w:numId="1"><w:abstractNumId w:val="7"/></w:num>
+</w:numbering>"#;
+
+const CORE: &str = r#"<?xml version="1.0"?>
+<cp:coreProperties xmlns:cp="http://schemas.openxmlformats.org/package/2006/metadata/core-properties" xmlns:dc="http://purl.org/dc/elements/1.1/" xmlns:dcterms="http://purl.org/dc/terms/">
+<dc:title>Runbook</dc:title><dc:creator>Ops</dc:creator><cp:keywords>setup, cli</cp:keywords>
+<dcterms:modified>2024-05-01T10:00:00Z</dcterms:modified>
+</cp:coreProperties>"#;
+
+fn docx(parts: &[(&str, &str)]) -> Vec<u8> {
+    let mut zip = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+    let options =
+        zip::write::FileOptions::default().compression_method(zip::CompressionMethod::Stored);
+    for (name, body) in parts {
+        zip.start_file(*name, options).unwrap();
+        zip.write_all(body.as_bytes()).unwrap();
+    }
+    zip.finish().unwrap().into_inner()
+}
+
+#[test]
+fn docx_renders_headings_lists_and_tables_as_markdown() {
+    let bytes = docx(&[("word/document.xml", DOCUMENT), ("word/numbering.xml", NUMBERING)]);
+    let text = extract_docx_text(&bytes).expect("document body");
+    assert!(text.starts_with("# Setup\n\nInstall the agent & CLI."));
+    assert!(text.contains("1. Download\n1. Run"));
+    assert!(text.contains("| Flag | Meaning |\n| --- | --- |\n| -v | verbose |"));
+}
+
+#[test]
+fn docx_properties_include_core_fields() {
+    let bytes = docx(&[("word/document.xml", DOCUMENT), ("docProps/core.xml", CORE)]);
+    let props = docx_properties(&bytes);
+    assert_eq!(props["title"], "Runbook");
+    assert_eq!(props["author"], "Ops");
+    assert_eq!(props["keywords"], "setup, cli");
+    assert_eq!(props["modified"], "2024-05-01T10:00:00Z");
+}
@@ -0,0 +1,232 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use axum::Json;
+use axum::Router;
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::routing::post;
+use mnemo_core::models::chunk::Chunk;
+use mnemo_core::models::document::Document;
+use mnemo_inference::engines::tensorzero_embed::parse_retry_after;
+use mnemo_inference::{RetryPolicy, TensorZeroEmbedder};
+use mnemo_ingest::pipeline::embedding_step::{embed_chunks, forgotten_paths};
+use mnemo_storage::cache::embedding_cache::EmbeddingCache;
+use serde_json::{Value, json};
+
+/// Mock `/v1/embeddings`: the first `throttled` requests get a 429, later ones embed each
+/// input as `[len, position]`, listed in reverse order with their `index`.
+async fn mock_embeddings(requests: Arc<AtomicUsize>, throttled: usize) -> String {
+    let app = Router::new().route(
+        "/v1/embeddings",
+        post(move |Json(body): Json<Value>| async move {
+            let n = requests.fetch_add(1, Ordering::SeqCst);
+            if n < throttled {
+                return (StatusCode::TOO_MANY_REQUESTS, [("Retry-After", "0")], "slow down")
+                    .into_response();
+            }
+            let Some(inputs) = body["input"].as_array() else {
+                return (StatusCode::BAD_REQUEST, "input must be an array").into_response();
+            };
+            let data: Vec<Value> = inputs
+                .iter()
+                .enumerate()
+                .rev()
+                .map(|(i, text)| {
+                    let len = text.as_str().unwrap_or_default().len();
+                    json!({"index": i, "embedding": [len as f32, i as f32]})
+                })
+                .collect();
+            Json(json!({"data": data})).into_response()
+        }),
+    );
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    format!("http://{addr}")
+}
+
+/// Mock `/v1/embeddings` that records every input it is sent and embeds it as `[len]`;
+/// a request with an input starting with `bad` is rejected whole with a 400.
+async fn mock_recording(seen: Arc<Mutex<Vec<String>>>) -> String {
+    let app = Router::new().route(
+        "/v1/embeddings",
+        post(move |Json(body): Json<Value>| async move {
+            let inputs: Vec<String> = body["input"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .map(|v| v.as_str().unwrap_or_default().to_string())
+                .collect();
+            seen.lock().unwrap().extend(inputs.iter().cloned());
+            if inputs.iter().any(|text| text.starts_with("bad")) {
+                return (StatusCode::BAD_REQUEST, "unprocessable input").into_response();
+            }
+            let data: Vec<Value> = inputs
+                .iter()
+                .enumerate()
+                .map(|(i, text)| json!({"index": i, "embedding": [text.len() as f32]}))
+                .collect();
+            Json(json!({"data": data})).into_response()
+        }),
+    );
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    format!("http://{addr}")
+}
+
+fn chunk(path: &str, index: usize, text: &str) -> Chunk {
+    Chunk {
+        document_path: path.into(),
+        text: text.into(),
+        tags: Vec::new(),
+        embedding: None,
+        chunk_index: index,
+        vector_id: None,
+        namespace: "local".into(),
+        sparse_indices: Vec::new(),
+        sparse_values: Vec::new(),
+        metadata: Default::default(),
+    }
+}
+
+fn document(path: &str, archive: Option<&str>) -> Document {
+    Document {
+        path: path.into(),
+        content: String::new(),
+        fingerprint: String::new(),
+        namespace: "local".into(),
+        modified_at: None,
+        file_size: None,
+        file_type: None,
+        language: None,
+        metadata: archive.map(|a| json!({"archive": a})),
+    }
+}
+
+fn fast_retry(max_retries: u32) -> RetryPolicy {
+    RetryPolicy { max_retries, base_delay: Duration::from_millis(1), ..RetryPolicy::default() }
+}
+
+#[tokio::test]
+async fn batch_is_one_request_with_vectors_in_input_order() {
+    let requests = Arc::new(AtomicUsize::new(0));
+    let url = mock_embeddings(requests.clone(), 0).await;
+    let embedder = TensorZeroEmbedder::new(url, vec!["embed-small".into()]);
+
+    let texts = vec!["a".to_string(), "bbb".to_string(), "cc".to_string()];
+    let (model, vecs) = embedder.embed_batch(&texts).await.unwrap();
+
+    assert_eq!(model, "embed-small");
+    assert_eq!(vecs, [vec![1.0, 0.0], vec![3.0, 1.0], vec![2.0, 2.0]]);
+    assert_eq!(requests.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn throttled_requests_are_retried() {
+    let requests = Arc::new(AtomicUsize::new(0));
+    let url = mock_embeddings(requests.clone(), 2).await;
+    let embedder =
+        TensorZeroEmbedder::new(url, vec!["embed-small".into()]).with_retry(fast_retry(3));
+
+    let vec = embedder.embed("hello").await.unwrap();
+
+    assert_eq!(vec, [5.0, 0.0]);
+    assert_eq!(requests.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn retries_stop_at_the_limit() {
+    let requests = Arc::new(AtomicUsize::new(0));
+    let url = mock_embeddings(requests.clone(), usize::MAX).await;
+    let embedder =
+        TensorZeroEmbedder::new(url, vec!["embed-small".into()]).with_retry(fast_retry(2));
+
+    let err = embedder.embed("hello").await.unwrap_err();
+
+    assert!(err.to_string().contains("429"), "{err}");
+    assert_eq!(requests.load(Ordering::SeqCst), 3);
+}
+
+#[test]
+fn backoff_doubles_and_retry_after_wins() {
+    let policy = RetryPolicy {
+        max_retries: 5,
+        base_delay: Duration::from_millis(100),
+        max_delay: Duration::from_secs(1),
+    };
+    assert_eq!(policy.delay(0, None), Duration::from_millis(100));
+    assert_eq!(policy.delay(2, None), Duration::from_millis(400));
+    assert_eq!(policy.delay(6, None), Duration::from_secs(1));
+    assert_eq!(policy.delay(0, Some(Duration::from_millis(750))), Duration::from_millis(750));
+    assert_eq!(policy.delay(0, Some(Duration::from_secs(120))), Duration::from_secs(1));
+}
+
+#[test]
+fn retry_after_takes_seconds_only() {
+    assert_eq!(parse_retry_after("2"), Some(Duration::from_secs(2)));
+    assert_eq!(parse_retry_after(" 0.5 "), Some(Duration::from_millis(500)));
+    assert_eq!(parse_retry_after("Wed, 21 Oct 2015 07:28:00 GMT"), None);
+    assert_eq!(parse_retry_after("-1"), None);
+}
+
+#[tokio::test]
+async fn cached_chunks_are_not_sent_to_the_embedder() {
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let url = mock_recording(seen.clone()).await;
+    let embedder = Arc::new(TensorZeroEmbedder::new(url, vec!["embed-small".into()]));
+    let cache = EmbeddingCache::memory();
+    cache.set_many("embed-small", &[("unchanged", &[9.0][..])]).await;
+    let chunks = [chunk("/data/a.md", 0, "unchanged"), chunk("/data/a.md", 1, "edited")];
+
+    let first = embed_chunks(embedder.clone(), &cache, &[], &chunks).await;
+
+    assert_eq!(first.cache_hits, 1);
+    assert_eq!(first.requests, 1);
+    assert!(first.failed.is_empty());
+    assert_eq!(first.embeddings, [vec![9.0], vec![6.0]]);
+    assert_eq!(*seen.lock().unwrap(), ["edited"]);
+
+    // The fresh vector was cached, so a second run sends nothing.
+    let second = embed_chunks(embedder, &cache, &[], &chunks).await;
+    assert_eq!(second.cache_hits, 2);
+    assert_eq!(second.requests, 0);
+    assert_eq!(seen.lock().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn failed_batch_is_retried_per_chunk_and_only_the_bad_chunk_is_dropped() {
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let url = mock_recording(seen.clone()).await;
+    let embedder = Arc::new(TensorZeroEmbedder::new(url, vec!["embed-small".into()]));
+    let cache = EmbeddingCache::memory();
+    let documents =
+        [document("/data/a.md", None), document("/data/b.zip!/bad.md", Some("/data/b.zip"))];
+    let chunks = [
+        chunk("/data/a.md", 0, "good"),
+        chunk("/data/b.zip!/bad.md", 0, "bad input"),
+        chunk("/data/a.md", 1, "also good"),
+    ];
+
+    let out = embed_chunks(embedder, &cache, &documents, &chunks).await;
+
+    // One batch request, then one request per chunk.
+    assert_eq!(
+        *seen.lock().unwrap(),
+        ["good", "bad input", "also good", "good", "bad input", "also good"]
+    );
+    assert_eq!(out.requests, 4);
+    assert_eq!(out.failed.len(), 1);
+    assert_eq!(out.failed[0].0, 1);
+    assert!(out.failed[0].1.contains("400"), "{}", out.failed[0].1);
+    assert_eq!(out.embeddings, [vec![4.0], Vec::new(), vec![9.0]]);
+    // The chunks that did embed are cached; the failed one is not.
+    let cached = cache.get_many("embed-small", &["good", "bad input", "also good"]).await;
+    assert_eq!(cached, [Some(vec![4.0]), None, Some(vec![9.0])]);
+
+    // The failed chunk's document and its archive lose their fingerprints; `a.md` keeps its.
+    let forgotten = forgotten_paths(&documents, &chunks, &out.failed);
+    assert_eq!(forgotten.into_iter().collect::<Vec<_>>(), ["/data/b.zip", "/data/b.zip!/bad.md"]);
+}
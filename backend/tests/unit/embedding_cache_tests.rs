@@ -0,0 +1,93 @@
+use mnemo_ingest::metrics::IngestionMetrics;
+use mnemo_storage::cache::embedding_cache::{EmbeddingCache, EmbeddingCacheBackend};
+use mnemo_storage::cache::redis::RedisCacheStore;
+use mnemo_storage::metadata::postgres::PostgresMetadataStore;
+
+/// Stores a vector, reads it back next to a miss, under a per-run model name.
+async fn assert_round_trip(cache: &EmbeddingCache) {
+    let model = format!("embed-test-{}", std::process::id());
+    let vector = [0.25, -1.5, 3.0e-7, f32::MAX];
+    cache.set_many(&model, &[("stored", &vector[..])]).await;
+    let found = cache.get_many(&model, &["stored", "never stored"]).await;
+    assert_eq!(found, [Some(vector.to_vec()), None]);
+    // Vectors are keyed by model as well as text.
+    assert_eq!(cache.get_many("other-model", &["stored"]).await, [None]);
+}
+
+#[test]
+fn text_hash_is_hex_sha256_of_the_text() {
+    assert_eq!(
+        EmbeddingCache::text_hash("hello"),
+        "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824"
+    );
+    assert_ne!(EmbeddingCache::text_hash("hello"), EmbeddingCache::text_hash("hello "));
+}
+
+#[tokio::test]
+async fn disabled_cache_misses_every_text() {
+    let cache = EmbeddingCache::new(EmbeddingCacheBackend::Disabled);
+    cache.set_many("embed-small", &[("a", &[0.5, 1.0][..])]).await;
+    let found = cache.get_many("embed-small", &["a", "b"]).await;
+    assert_eq!(found, [None, None]);
+}
+
+#[test]
+fn cache_is_off_unless_configured() {
+    // Only this test touches MNEMO_EMBED_CACHE.
+    unsafe {
+        std::env::remove_var("MNEMO_EMBED_CACHE");
+    }
+    assert!(matches!(EmbeddingCache::from_env().backend, EmbeddingCacheBackend::Disabled));
+    unsafe {
+        std::env::set_var("MNEMO_EMBED_CACHE", "memory");
+    }
+    assert!(matches!(EmbeddingCache::from_env().backend, EmbeddingCacheBackend::Memory(_)));
+    unsafe {
+        std::env::remove_var("MNEMO_EMBED_CACHE");
+    }
+}
+
+#[test]
+fn redis_values_round_trip_as_little_endian_f32() {
+    let vector = [1.0, -0.5, f32::MIN_POSITIVE, 1.0e9];
+    let bytes = EmbeddingCache::encode(&vector);
+    assert_eq!(bytes.len(), 16);
+    assert_eq!(&bytes[..4], &1.0f32.to_le_bytes());
+    assert_eq!(EmbeddingCache::decode(&bytes).unwrap(), vector);
+    assert_eq!(EmbeddingCache::decode(&bytes[..15]), None);
+    assert_eq!(EmbeddingCache::decode(&[]), None);
+}
+
+#[tokio::test]
+async fn memory_cache_round_trips() {
+    assert_round_trip(&EmbeddingCache::memory()).await;
+}
+
+#[tokio::test]
+async fn redis_cache_round_trips() {
+    let Ok(url) = std::env::var("MNEMO_REDIS_URL") else {
+        eprintln!("Skipping Redis embedding cache test; set MNEMO_REDIS_URL.");
+        return;
+    };
+    let backend = EmbeddingCacheBackend::Redis { store: RedisCacheStore::new(url), ttl_secs: 60 };
+    assert_round_trip(&EmbeddingCache::new(backend)).await;
+}
+
+#[tokio::test]
+async fn postgres_cache_round_trips() {
+    let Ok(url) = std::env::var("MNEMO_METADATA_PG") else {
+        eprintln!("Skipping Postgres embedding cache test; set MNEMO_METADATA_PG.");
+        return;
+    };
+    let store = PostgresMetadataStore::new(url).unwrap();
+    assert_round_trip(&EmbeddingCache::new(EmbeddingCacheBackend::Postgres(store))).await;
+}
+
+#[test]
+fn hit_rate_counts_cached_chunks() {
+    let mut metrics = IngestionMetrics::new();
+    assert_eq!(metrics.embedding_cache_hit_rate(), 0.0);
+    metrics.embedding_cache_hits = 3;
+    metrics.embedding_cache_misses = 1;
+    assert_eq!(metrics.embedding_cache_hit_rate(), 0.75);
+}
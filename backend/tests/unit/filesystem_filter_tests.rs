@@ -0,0 +1,139 @@
+use std::collections::HashMap;
+
+use mnemo_core::config::filesystem::{FilesystemConfig, FilesystemRootConfig};
+use mnemo_ingest::metrics::{IngestionMetrics, MAX_SKIPPED_FILES, SkipReason, SkippedFile};
+use mnemo_ingest::providers::filesystem::FilesystemProvider;
+
+mod support;
+use support::fixture_dir;
+
+/// Relative document paths and `(relative path, reason)` skips, both sorted.
+fn scan(
+    dir: &std::path::Path,
+    config: FilesystemConfig,
+) -> (Vec<String>, Vec<(String, SkipReason)>) {
+    let root = dir.to_string_lossy().to_string();
+    let (docs, skipped) =
+        FilesystemProvider::new(vec![root.clone()]).with_config(config).scan_with_report();
+    let _ = std::fs::remove_dir_all(dir);
+    let relative = |p: &str| p.trim_start_matches(&root).trim_start_matches('/').to_string();
+    let mut paths: Vec<String> = docs.iter().map(|d| relative(&d.path)).collect();
+    paths.sort();
+    let mut skips: Vec<(String, SkipReason)> =
+        skipped.iter().map(|s| (relative(&s.path), s.reason)).collect();
+    skips.sort();
+    (paths, skips)
+}
+
+#[test]
+fn gitignore_and_mnemoignore_rules_nest() {
+    let dir = fixture_dir(
+        "fsfilter_ignore",
+        &[
+            (".gitignore", "build/\n*.log.md\ndrafts/*.md\n"),
+            ("drafts/.gitignore", "!keep.md\n"),
+            ("drafts/keep.md", "# Keep"),
+            ("drafts/scratch.md", "# Scratch"),
+            ("build/out.md", "# Out"),
+            ("server.log.md", "# Log"),
+            (".mnemoignore", "private.md\n"),
+            ("private.md", "# Private"),
+            ("readme.md", "# Readme"),
+        ],
+    );
+
+    let (paths, skips) = scan(&dir, FilesystemConfig::default());
+    assert_eq!(paths, ["drafts/keep.md", "readme.md"]);
+    let ignored: Vec<&str> = skips
+        .iter()
+        .filter(|(_, reason)| *reason == SkipReason::Ignored)
+        .map(|(path, _)| path.as_str())
+        .collect();
+    // The ignored directory is reported once, not per file.
+    assert_eq!(ignored, ["build", "drafts/scratch.md", "private.md", "server.log.md"]);
+}
+
+#[test]
+fn root_globs_extensions_and_size_limits_apply() {
+    let dir = fixture_dir(
+        "fsfilter_globs",
+        &[
+            ("docs/guide.md", "# Guide"),
+            ("docs/big.md", &"x".repeat(2_000)),
+            ("docs/notes.txt", "notes"),
+            ("docs/vendor/lib.md", "# Vendored"),
+            ("src/main.rs", "fn main() {}"),
+        ],
+    );
+    let root = dir.to_string_lossy().to_string();
+    let config = FilesystemConfig {
+        defaults: FilesystemRootConfig {
+            exclude: vec!["**/vendor".into()],
+            ..FilesystemRootConfig::default()
+        },
+        roots: HashMap::from([(
+            root,
+            FilesystemRootConfig {
+                include: vec!["docs/**".into()],
+                extensions: Some(vec![".md".into()]),
+                max_file_bytes: Some(1_000),
+                ..FilesystemRootConfig::default()
+            },
+        )]),
+    };
+
+    let (paths, skips) = scan(&dir, config);
+    assert_eq!(paths, ["docs/guide.md"]);
+    assert_eq!(
+        skips,
+        [
+            ("docs/big.md".to_string(), SkipReason::TooLarge),
+            ("docs/notes.txt".to_string(), SkipReason::Extension),
+            ("docs/vendor".to_string(), SkipReason::Excluded),
+            ("src/main.rs".to_string(), SkipReason::NotIncluded),
+        ]
+    );
+}
+
+#[test]
+fn for_root_layers_root_rules_over_defaults() {
+    let config: FilesystemConfig = serde_yaml::from_str(
+        "defaults: { exclude: ['**/node_modules'], max_file_bytes: 100 }\n\
+         roots:\n  /data: { exclude: ['tmp/**'], extensions: [md] }\n",
+    )
+    .unwrap();
+
+    let rules = config.for_root("/data/");
+    assert_eq!(rules.exclude, ["**/node_modules", "tmp/**"]);
+    assert_eq!(rules.extensions, Some(vec!["md".to_string()]));
+    assert_eq!(rules.max_file_bytes, Some(100));
+    assert_eq!(config.for_root("/other").exclude, ["**/node_modules"]);
+}
+
+#[test]
+fn metrics_count_every_skip_but_cap_the_list() {
+    let mut metrics = IngestionMetrics::new();
+    for i in 0..MAX_SKIPPED_FILES + 5 {
+        let reason = if i % 2 == 0 { SkipReason::Extension } else { SkipReason::Ignored };
+        metrics.record_skip(SkippedFile { path: format!("f{i}"), reason });
+    }
+    assert_eq!(metrics.skipped_files.len(), MAX_SKIPPED_FILES);
+    assert_eq!(metrics.skip_counts.values().sum::<usize>(), MAX_SKIPPED_FILES + 5);
+    assert_eq!(metrics.skip_counts[&SkipReason::Extension], MAX_SKIPPED_FILES / 2 + 3);
+}
+
+#[test]
+fn large_files_split_on_character_boundaries() {
+    // One ASCII byte first puts every 50KB segment edge inside a two-byte character.
+    let text = format!("a{}", "é".repeat(125_000));
+    let dir = fixture_dir("fsfilter_segments", &[("big.txt", text.as_str())]);
+    let root = dir.to_string_lossy().to_string();
+
+    let (docs, _) = FilesystemProvider::new(vec![root]).scan_with_report();
+    let _ = std::fs::remove_dir_all(&dir);
+
+    assert!(docs.len() > 1);
+    assert!(docs.iter().all(|d| d.path.contains("big.txt#segment_")));
+    let joined: String = docs.iter().map(|d| d.content.as_str()).collect();
+    assert_eq!(joined, text);
+}
@@ -0,0 +1,109 @@
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use mnemo_core::config::filesystem::FilesystemConfig;
+use mnemo_ingest::providers::filesystem::FilesystemProvider;
+use mnemo_ingest::watcher::{ChangeSet, WatchConfig, next_batch};
+use notify::event::{CreateKind, DataChange, ModifyKind, RemoveKind, RenameMode};
+use notify::{Event, EventKind};
+
+mod support;
+use support::fixture_dir;
+
+fn event(kind: EventKind, paths: &[&PathBuf]) -> Event {
+    paths.iter().fold(Event::new(kind), |event, path| event.add_path(path.to_path_buf()))
+}
+
+#[test]
+fn renames_and_removals_fold_into_one_change_set() {
+    let dir = fixture_dir("watch_changes", &[("new.md", "# New"), ("edited.md", "# Edited")]);
+    let (old, new, edited, gone) =
+        (dir.join("old.md"), dir.join("new.md"), dir.join("edited.md"), dir.join("gone.md"));
+
+    let mut changes = ChangeSet::default();
+    changes.record(&event(EventKind::Create(CreateKind::File), &[&gone]));
+    changes.record(&event(EventKind::Modify(ModifyKind::Data(DataChange::Any)), &[&edited]));
+    changes.record(&event(EventKind::Modify(ModifyKind::Name(RenameMode::Both)), &[&old, &new]));
+    changes.record(&event(EventKind::Remove(RemoveKind::File), &[&gone]));
+    changes.record(&event(EventKind::Access(notify::event::AccessKind::Any), &[&old]));
+
+    let (changed, removed) = changes.resolve();
+    let _ = std::fs::remove_dir_all(&dir);
+    assert_eq!(changed, [edited, new]);
+    assert_eq!(removed, [gone, old]);
+}
+
+#[test]
+fn changed_paths_that_vanished_before_the_batch_are_removals() {
+    let dir = fixture_dir("watch_vanished", &[]);
+    let tmp = dir.join("draft.md");
+
+    let mut changes = ChangeSet::default();
+    changes.record(&event(EventKind::Create(CreateKind::File), &[&tmp]));
+    let (changed, removed) = changes.resolve();
+    let _ = std::fs::remove_dir_all(&dir);
+    assert!(changed.is_empty());
+    assert_eq!(removed, [tmp]);
+}
+
+#[test]
+fn scan_paths_applies_root_rules_to_changed_paths() {
+    let dir = fixture_dir(
+        "watch_scan",
+        &[
+            (".gitignore", "build/\n"),
+            ("build/out/report.md", "# Report"),
+            ("docs/guide.md", "# Guide"),
+            ("docs/api/ref.md", "# Ref"),
+            ("logo.png", "binary"),
+        ],
+    );
+    let root = dir.to_string_lossy().to_string();
+    let provider =
+        FilesystemProvider::new(vec![root.clone()]).with_config(FilesystemConfig::default());
+
+    let (docs, skipped) = provider.scan_paths(&[
+        dir.join("build/out/report.md"),
+        dir.join("docs"),
+        dir.join("logo.png"),
+        dir.join("missing.md"),
+        PathBuf::from("/elsewhere/notes.md"),
+    ]);
+    let _ = std::fs::remove_dir_all(&dir);
+
+    let mut paths: Vec<&str> = docs.iter().map(|d| d.path.trim_start_matches(&root)).collect();
+    paths.sort();
+    assert_eq!(paths, ["/docs/api/ref.md", "/docs/guide.md"]);
+    assert_eq!(skipped.len(), 1);
+    assert!(skipped[0].path.ends_with("logo.png"));
+}
+
+#[tokio::test]
+async fn steady_writes_still_flush_after_the_max_latency() {
+    let config = WatchConfig {
+        roots: Vec::new(),
+        debounce: Duration::from_millis(200),
+        max_latency: Duration::from_millis(400),
+    };
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+    let writer = tokio::spawn(async move {
+        // One write every 50ms, well inside the debounce window, for three seconds.
+        for i in 0..60 {
+            let path = PathBuf::from(format!("/tmp/busy/{i}.md"));
+            let kind = EventKind::Modify(ModifyKind::Data(DataChange::Content));
+            if tx.send(event(kind, &[&path])).is_err() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+    });
+
+    let started = Instant::now();
+    let batch = next_batch(&mut rx, &config).await.unwrap();
+    let waited = started.elapsed();
+    writer.abort();
+
+    assert!(waited < Duration::from_millis(1500), "batch waited {waited:?}");
+    assert!(batch.changed.len() > 1);
+    assert!(batch.changed.len() < 60);
+}
@@ -0,0 +1,98 @@
+use std::path::Path;
+
+use git2::{Repository, Signature};
+use mnemo_ingest::providers::git::GitRepoProvider;
+
+fn commit_files(repo: &Repository, files: &[(&str, &str)], message: &str) {
+    let root = repo.workdir().unwrap();
+    let mut index = repo.index().unwrap();
+    for (path, body) in files {
+        let full = root.join(path);
+        std::fs::create_dir_all(full.parent().unwrap()).unwrap();
+        std::fs::write(&full, body).unwrap();
+        index.add_path(Path::new(path)).unwrap();
+    }
+    index.write().unwrap();
+    let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+    let sig = Signature::now("Dana Writer", "dana@example.com").unwrap();
+    let parent = repo.head().ok().and_then(|h| h.peel_to_commit().ok());
+    let parents: Vec<_> = parent.iter().collect();
+    repo.commit(Some("HEAD"), &sig, &sig, message, &tree, &parents).unwrap();
+}
+
+#[tokio::test]
+async fn git_provider_reads_ref_with_commit_metadata_and_gitignore() {
+    let dir = std::env::temp_dir().join(format!("mnemo_git_repo_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    let repo = Repository::init(&dir).unwrap();
+    commit_files(
+        &repo,
+        &[
+            ("README.md", "# Project\n\nHello."),
+            (".gitignore", "build/\n"),
+            ("build/generated.md", "generated output"),
+        ],
+        "initial",
+    );
+    commit_files(&repo, &[("src/lib.rs", "pub fn answer() -> u32 { 42 }\n")], "add lib");
+
+    let provider =
+        GitRepoProvider::new(dir.to_string_lossy().to_string(), "HEAD".into(), "local".into());
+    let docs = provider.load_documents().await;
+    let _ = std::fs::remove_dir_all(&dir);
+
+    assert!(docs.iter().all(|d| !d.path.contains("build/")));
+    let lib = docs.iter().find(|d| d.path.ends_with("src/lib.rs")).expect("lib.rs");
+    let meta = lib.metadata.as_ref().unwrap();
+    assert_eq!(meta["last_commit_author"], "Dana Writer");
+    assert_eq!(meta["git_path"], "src/lib.rs");
+    assert_eq!(lib.language.as_deref(), Some("rust"));
+
+    let readme = docs.iter().find(|d| d.path.ends_with("README.md")).expect("README");
+    let readme_meta = readme.metadata.as_ref().unwrap();
+    assert_ne!(readme_meta["last_commit_sha"], meta["last_commit_sha"]);
+}
+
+#[tokio::test]
+async fn second_sync_loads_only_changed_files_and_reports_deletions() {
+    let dir = std::env::temp_dir().join(format!("mnemo_git_sync_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    let repo = Repository::init(&dir).unwrap();
+    commit_files(
+        &repo,
+        &[("a.md", "# A\n\nFirst."), ("b.md", "# B\n\nSecond."), ("c.md", "# C\n\nThird.")],
+        "initial",
+    );
+    // Uncommitted ignore rules in the working tree do not apply to the ref.
+    std::fs::write(dir.join(".gitignore"), "b.md\n").unwrap();
+
+    let root = dir.to_string_lossy().to_string();
+    let provider = GitRepoProvider::new(root.clone(), "HEAD".into(), "local".into());
+    let first = provider.load_documents().await;
+    assert_eq!(first.len(), 3);
+    provider.commit_sync().await;
+
+    commit_files(&repo, &[("a.md", "# A\n\nFirst, edited.")], "edit a");
+    let mut index = repo.index().unwrap();
+    index.remove_path(Path::new("c.md")).unwrap();
+    index.write().unwrap();
+    let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+    let sig = Signature::now("Dana Writer", "dana@example.com").unwrap();
+    let parent = repo.head().unwrap().peel_to_commit().unwrap();
+    repo.commit(Some("HEAD"), &sig, &sig, "remove c", &tree, &[&parent]).unwrap();
+
+    let second = provider.load_documents().await;
+    let paths: Vec<&str> = second.iter().map(|d| d.path.as_str()).collect();
+    assert_eq!(paths, vec![format!("{root}/a.md")]);
+    assert_eq!(provider.deleted_paths(), vec![format!("{root}/c.md")]);
+    assert_eq!(provider.retained_paths(), vec![format!("{root}/b.md")]);
+
+    // Without a successful run the synced commit does not move.
+    assert_eq!(provider.load_documents().await.len(), 1);
+    provider.commit_sync().await;
+    let third = provider.load_documents().await;
+    let _ = std::fs::remove_dir_all(&dir);
+    assert!(third.is_empty());
+    assert!(provider.deleted_paths().is_empty());
+    assert_eq!(provider.retained_paths().len(), 2);
+}
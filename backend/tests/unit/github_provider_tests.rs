@@ -0,0 +1,88 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+use axum::Json;
+use axum::Router;
+use axum::extract::Path;
+use axum::routing::get;
+use mnemo_ingest::providers::github::GitHubProvider;
+use mnemo_ingest::providers::registry::Provider;
+use serde_json::json;
+
+fn initial_tree() -> serde_json::Value {
+    json!({
+        "sha": "tree123",
+        "truncated": false,
+        "tree": [
+            { "path": "README.md", "type": "blob", "sha": "aaa111", "size": 24 },
+            { "path": "logo.png", "type": "blob", "sha": "ccc333", "size": 100 },
+            { "path": "huge.md", "type": "blob", "sha": "ddd444", "size": 10_000_000 },
+            { "path": "src", "type": "tree", "sha": "eee555" },
+            { "path": "src/lib.rs", "type": "blob", "sha": "bbb222", "size": 30 }
+        ]
+    })
+}
+
+async fn mock_github(
+    tree: Arc<Mutex<serde_json::Value>>,
+    blob_requests: Arc<AtomicUsize>,
+) -> String {
+    let app = Router::new()
+        .route(
+            "/repos/acme/docs/git/trees/HEAD",
+            get(move || async move { Json(tree.lock().unwrap().clone()) }),
+        )
+        .route(
+            "/repos/acme/docs/git/blobs/:sha",
+            get(move |Path(sha): Path<String>| async move {
+                blob_requests.fetch_add(1, Ordering::SeqCst);
+                match sha.as_str() {
+                    "aaa111" => "# Docs\n\nWelcome aboard.".to_string(),
+                    _ => "pub fn answer() -> u32 { 42 }".to_string(),
+                }
+            }),
+        );
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    format!("http://{addr}")
+}
+
+#[tokio::test]
+async fn github_provider_lists_tree_and_filters_blobs() {
+    let blob_requests = Arc::new(AtomicUsize::new(0));
+    let base_url = mock_github(Arc::new(Mutex::new(initial_tree())), blob_requests.clone()).await;
+
+    let provider = GitHubProvider::new("acme/docs".into()).with_base_url(base_url);
+    let docs = provider.load_documents().await;
+
+    assert_eq!(docs.len(), 2);
+    assert_eq!(blob_requests.load(Ordering::SeqCst), 2);
+    let readme = docs.iter().find(|d| d.path == "github:acme/docs/README.md").expect("README");
+    assert_eq!(readme.fingerprint, "aaa111");
+    assert!(readme.content.contains("Welcome aboard."));
+    assert_eq!(readme.metadata.as_ref().unwrap()["tree_sha"], "tree123");
+    let lib = docs.iter().find(|d| d.path.ends_with("src/lib.rs")).expect("lib.rs");
+    assert_eq!(lib.language.as_deref(), Some("rust"));
+}
+
+#[tokio::test]
+async fn second_sync_skips_unchanged_blobs() {
+    let tree = Arc::new(Mutex::new(initial_tree()));
+    let blob_requests = Arc::new(AtomicUsize::new(0));
+    let base_url = mock_github(tree.clone(), blob_requests.clone()).await;
+    let provider = GitHubProvider::new("acme/docs".into()).with_base_url(base_url);
+
+    assert_eq!(provider.load_documents().await.len(), 2);
+    provider.commit_sync().await;
+    tree.lock().unwrap()["tree"][4]["sha"] = json!("fff666");
+    let docs = provider.load_documents().await;
+
+    assert_eq!(blob_requests.load(Ordering::SeqCst), 3);
+    assert_eq!(docs.len(), 1);
+    assert_eq!(docs[0].path, "github:acme/docs/src/lib.rs");
+    assert_eq!(docs[0].fingerprint, "fff666");
+    let mut retained = provider.retained_paths();
+    retained.sort();
+    assert_eq!(retained, ["github:acme/docs/README.md", "github:acme/docs/src/lib.rs"]);
+}
@@ -0,0 +1,140 @@
+use axum::Json;
+use axum::Router;
+use axum::routing::{get, post};
+use mnemo_ingest::providers::github::GitHubProvider;
+use mnemo_ingest::providers::registry::Provider;
+use serde_json::json;
+
+async fn mock_github() -> String {
+    let issues = json!([
+        {
+            "number": 7, "title": "Crash on empty config", "body": "Steps to reproduce.",
+            "user": { "login": "alice" }, "labels": [{ "name": "bug" }], "state": "open",
+            "html_url": "https://github.com/acme/docs/issues/7", "comments": 1,
+            "created_at": "2024-05-01T10:00:00Z", "updated_at": "2024-05-02T10:00:00Z"
+        },
+        {
+            "number": 8, "title": "Handle empty config", "body": "Fixes #7.",
+            "user": { "login": "bob" }, "labels": [], "state": "closed",
+            "pull_request": { "merged_at": "2024-05-03T09:00:00Z" },
+            "html_url": "https://github.com/acme/docs/pull/8", "comments": 0,
+            "created_at": "2024-05-02T12:00:00Z", "updated_at": "2024-05-03T09:00:00Z"
+        }
+    ]);
+    let issue_comments = json!([
+        { "user": { "login": "carol" }, "body": "Seeing this too.", "created_at": "2024-05-01T11:00:00Z" }
+    ]);
+    let files = json!([{ "filename": "src/config.rs" }]);
+    let review_comments = json!([
+        {
+            "user": { "login": "alice" }, "body": "Add a test here.", "path": "src/config.rs",
+            "line": 12, "created_at": "2024-05-02T13:00:00Z"
+        }
+    ]);
+    let discussions = json!({
+        "data": { "repository": { "discussions": {
+            "pageInfo": { "hasNextPage": false, "endCursor": null },
+            "nodes": [{
+                "number": 3, "title": "Roadmap", "body": "What next?", "closed": false,
+                "url": "https://github.com/acme/docs/discussions/3",
+                "createdAt": "2024-04-01T10:00:00Z", "updatedAt": "2024-04-02T10:00:00Z",
+                "author": { "login": "dave" }, "category": { "name": "Ideas" },
+                "labels": { "nodes": [] },
+                "comments": { "nodes": [{ "body": "Plugins.", "createdAt": "2024-04-02T10:00:00Z", "author": { "login": "erin" } }] }
+            }]
+        }}}
+    });
+    let app = Router::new()
+        .route(
+            "/repos/acme/docs/git/trees/HEAD",
+            get(|| async { Json(json!({ "sha": "t", "tree": [] })) }),
+        )
+        .route("/repos/acme/docs/issues", get(move || async move { Json(issues) }))
+        .route(
+            "/repos/acme/docs/issues/7/comments",
+            get(move || async move { Json(issue_comments) }),
+        )
+        .route("/repos/acme/docs/pulls/8/files", get(move || async move { Json(files) }))
+        .route(
+            "/repos/acme/docs/pulls/8/comments",
+            get(move || async move { Json(review_comments) }),
+        )
+        .route("/graphql", post(move || async move { Json(discussions) }));
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    format!("http://{addr}")
+}
+
+#[tokio::test]
+async fn github_threads_become_documents() {
+    let base_url = mock_github().await;
+    let provider =
+        GitHubProvider::new("acme/docs".into()).with_base_url(base_url).with_threads(true);
+    let docs = provider.load_documents().await;
+    assert_eq!(docs.len(), 3);
+
+    let issue = docs.iter().find(|d| d.path == "github:acme/docs#issues/7").expect("issue");
+    let meta = issue.metadata.as_ref().unwrap();
+    assert_eq!(meta["kind"], "issue");
+    assert_eq!(meta["author"], "alice");
+    assert_eq!(meta["labels"], json!(["bug"]));
+    assert!(issue.content.contains("Seeing this too."));
+    assert_eq!(issue.language.as_deref(), Some("markdown"));
+
+    let pr = docs.iter().find(|d| d.path == "github:acme/docs#pull/8").expect("pull request");
+    let meta = pr.metadata.as_ref().unwrap();
+    assert_eq!(meta["state"], "merged");
+    assert_eq!(meta["linked_files"], json!(["github:acme/docs/src/config.rs"]));
+    assert!(pr.content.contains("alice on src/config.rs:12"));
+
+    let discussion =
+        docs.iter().find(|d| d.path == "github:acme/docs#discussions/3").expect("discussion");
+    assert!(discussion.content.contains("Plugins."));
+    assert_eq!(provider.pending_thread_cursor().as_deref(), Some("2024-05-03T09:00:00Z"));
+}
+
+#[tokio::test]
+async fn discussions_past_the_page_cap_leave_the_cursor_unset() {
+    let nodes: Vec<_> = (1..=50)
+        .map(|number| {
+            json!({
+                "number": number, "title": "Topic", "body": "Body", "closed": false,
+                "url": format!("https://github.com/acme/docs/discussions/{number}"),
+                "createdAt": "2024-04-01T10:00:00Z", "updatedAt": "2024-04-02T10:00:00Z",
+                "author": { "login": "dave" }, "labels": { "nodes": [] },
+                "comments": { "nodes": [] }
+            })
+        })
+        .collect();
+    let page = json!({
+        "data": { "repository": { "discussions": {
+            "pageInfo": { "hasNextPage": true, "endCursor": "next" },
+            "nodes": nodes
+        }}}
+    });
+    let app = Router::new()
+        .route(
+            "/repos/acme/docs/git/trees/HEAD",
+            get(|| async { Json(json!({ "sha": "t", "tree": [] })) }),
+        )
+        .route("/repos/acme/docs/issues", get(|| async { Json(json!([])) }))
+        .route("/graphql", post(move || async move { Json(page) }));
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+    let provider = GitHubProvider::new("acme/docs".into())
+        .with_base_url(format!("http://{addr}"))
+        .with_threads(true);
+    let docs = provider.load_documents().await;
+    assert!(!docs.is_empty());
+    assert_eq!(provider.pending_thread_cursor(), None);
+}
+
+#[tokio::test]
+async fn github_threads_are_off_by_default() {
+    let base_url = mock_github().await;
+    let provider = GitHubProvider::new("acme/docs".into()).with_base_url(base_url);
+    assert!(provider.load_documents().await.is_empty());
+}
@@ -0,0 +1,22 @@
+use mnemo_ingest::pipeline::chunk_builder::{ChunkBuilder, FileType};
+
+const DOC: &str = "# Install\n\nIntro text.\n\n## Docker\n\nRun the image.\n\n```sh\n# not a heading\ndocker run mnemo\n```\n\n### Troubleshooting\n\n| Error | Fix |\n| --- | --- |\n| port | change it |\n\n## Binary\n\n- download\n- unpack\n";
+
+#[test]
+fn headings_inside_code_fences_do_not_split_sections() {
+    let chunks = ChunkBuilder::build_document(DOC, FileType::Markdown, "docs/install.md", None);
+    let docker = chunks.iter().find(|c| c.text.contains("docker run")).expect("docker chunk");
+    assert!(docker.text.contains("# not a heading"));
+    assert!(docker.text.contains("Run the image."));
+    assert_eq!(docker.metadata["heading_path"], "Install > Docker");
+}
+
+#[test]
+fn breadcrumb_is_prepended_and_resets_on_sibling_headings() {
+    let chunks = ChunkBuilder::build_document(DOC, FileType::Markdown, "docs/install.md", None);
+    let table = chunks.iter().find(|c| c.text.contains("| port |")).expect("table chunk");
+    assert!(table.text.starts_with("Install > Docker > Troubleshooting\n\n"));
+
+    let binary = chunks.iter().find(|c| c.text.contains("- unpack")).expect("list chunk");
+    assert_eq!(binary.metadata["heading_path"], "Install > Binary");
+}
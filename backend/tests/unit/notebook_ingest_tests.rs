@@ -0,0 +1,114 @@
+use mnemo_core::config::filesystem::{FilesystemConfig, FilesystemRootConfig};
+use mnemo_ingest::metrics::SkipReason;
+use mnemo_ingest::pipeline::chunk_builder::{ChunkBuilder, FileType};
+use mnemo_ingest::providers::filesystem::FilesystemProvider;
+use mnemo_ingest::providers::notebook::{extract_notebook_text, notebook_metadata};
+use serde_json::json;
+
+fn notebook() -> Vec<u8> {
+    json!({
+        "metadata": {
+            "kernelspec": { "display_name": "Python 3", "language": "python", "name": "python3" }
+        },
+        "nbformat": 4,
+        "nbformat_minor": 5,
+        "cells": [
+            { "cell_type": "markdown", "metadata": {}, "source": ["# Load data\n", "\n", "Read the CSV."] },
+            {
+                "cell_type": "code", "metadata": {}, "execution_count": 1,
+                "source": ["def load(path):\n", "    return open(path).read()\n"],
+                "outputs": [
+                    { "output_type": "stream", "name": "stdout", "text": ["x".repeat(50)] },
+                    {
+                        "output_type": "display_data", "metadata": {},
+                        "data": { "image/png": "iVBORw0KGgoAAAANSUhEUg==" }
+                    }
+                ]
+            },
+            {
+                "cell_type": "code", "metadata": {}, "execution_count": 2, "source": "1 / 0",
+                "outputs": [{
+                    "output_type": "error", "ename": "ZeroDivisionError",
+                    "evalue": "division by zero", "traceback": ["\u{1b}[0;31m..."]
+                }]
+            }
+        ]
+    })
+    .to_string()
+    .into_bytes()
+}
+
+#[test]
+fn notebook_cells_keep_index_type_and_code_layout() {
+    let text = extract_notebook_text(&notebook(), Some(20)).expect("notebook text");
+    assert!(!text.contains("iVBORw0KGgo"), "image outputs are skipped");
+
+    let chunks =
+        ChunkBuilder::build_document(&text, FileType::Notebook, "a.ipynb", Some("notebook"));
+    assert_eq!(chunks.len(), 3);
+    assert_eq!(chunks[0].metadata["cell_index"], 0);
+    assert_eq!(chunks[0].metadata["cell_type"], "markdown");
+    assert!(chunks[0].text.starts_with("# Load data"));
+
+    assert_eq!(chunks[1].metadata["cell_type"], "code");
+    assert!(
+        chunks[1].text.contains("```python\ndef load(path):\n    return open(path).read()\n```")
+    );
+    assert!(chunks[1].text.contains(&format!("{}\n… [output truncated]", "x".repeat(20))));
+
+    assert_eq!(chunks[2].metadata["cell_index"], 2);
+    assert!(chunks[2].text.contains("ZeroDivisionError: d\n… [output truncated]"));
+}
+
+#[test]
+fn notebook_outputs_can_be_kept_whole() {
+    let text = extract_notebook_text(&notebook(), None).unwrap();
+    assert!(text.contains(&"x".repeat(50)));
+    assert!(!text.contains("truncated"));
+
+    let meta = notebook_metadata(&notebook());
+    assert_eq!(meta["notebook_language"], "python");
+    assert_eq!(meta["code_cells"], 2);
+}
+
+#[test]
+fn invalid_notebook_is_rejected() {
+    assert!(extract_notebook_text(b"{\"not\": \"a notebook\"}", None).is_none());
+    assert!(extract_notebook_text(b"not json", None).is_none());
+}
+
+#[test]
+fn notebook_size_cap_applies_after_outputs_are_stripped() {
+    let image = "A".repeat(4_000);
+    let with_image = json!({
+        "metadata": { "kernelspec": { "language": "python" } },
+        "cells": [{
+            "cell_type": "code", "source": "plot()",
+            "outputs": [{ "output_type": "display_data", "data": { "image/png": image } }]
+        }]
+    });
+    let long_text = json!({
+        "cells": [{ "cell_type": "markdown", "source": "word ".repeat(400) }]
+    });
+    let dir = std::env::temp_dir().join(format!("mnemo_notebook_cap_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("plot.ipynb"), with_image.to_string()).unwrap();
+    std::fs::write(dir.join("essay.ipynb"), long_text.to_string()).unwrap();
+    let config = FilesystemConfig {
+        defaults: FilesystemRootConfig { max_file_bytes: Some(1_000), ..Default::default() },
+        ..Default::default()
+    };
+
+    let root = dir.to_string_lossy().to_string();
+    let (docs, skipped) =
+        FilesystemProvider::new(vec![root]).with_config(config).scan_with_report();
+    let _ = std::fs::remove_dir_all(&dir);
+
+    assert_eq!(docs.len(), 1);
+    assert!(docs[0].path.ends_with("plot.ipynb"));
+    assert_eq!(docs[0].metadata.as_ref().unwrap()["notebook_language"], "python");
+    assert_eq!(skipped.len(), 1);
+    assert!(skipped[0].path.ends_with("essay.ipynb"));
+    assert_eq!(skipped[0].reason, SkipReason::TooLarge);
+}
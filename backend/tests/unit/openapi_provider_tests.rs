@@ -0,0 +1,178 @@
+use mnemo_core::models::document::Document;
+use mnemo_ingest::providers::openapi::OpenApiProvider;
+use serde_json::json;
+
+fn find<'a>(docs: &'a [Document], path: &str) -> &'a Document {
+    docs.iter().find(|d| d.path == path).unwrap_or_else(|| panic!("missing {path}"))
+}
+
+fn meta<'a>(doc: &'a Document, key: &str) -> &'a serde_json::Value {
+    &doc.metadata.as_ref().unwrap()[key]
+}
+
+const PETSTORE: &str = r##"
+openapi: 3.0.3
+info: {title: Petstore, version: "1.0"}
+paths:
+  /pets/{petId}:
+    parameters:
+      - $ref: "#/components/parameters/PetId"
+    get:
+      operationId: getPet
+      summary: Fetch one pet
+      tags: [pets]
+      parameters:
+        - name: fields
+          in: query
+          schema: {type: array, items: {type: string}}
+      responses:
+        "200":
+          description: The pet
+          content:
+            application/json:
+              schema: {$ref: "#/components/schemas/Pet"}
+        "404":
+          $ref: "#/components/responses/NotFound"
+    put:
+      requestBody:
+        required: true
+        content:
+          application/json:
+            schema: {$ref: "#/components/schemas/NewPet"}
+      responses:
+        "204": {description: Updated}
+components:
+  parameters:
+    PetId: {name: petId, in: path, required: true, description: Pet id, schema: {type: integer, format: int64}}
+  responses:
+    NotFound:
+      description: No such pet
+      content:
+        application/json:
+          schema: {$ref: "#/components/schemas/Error"}
+  schemas:
+    NewPet:
+      type: object
+      required: [name]
+      properties:
+        name: {type: string, description: Display name}
+        owner: {$ref: "#/components/schemas/Owner"}
+    Pet:
+      allOf:
+        - $ref: "#/components/schemas/NewPet"
+        - type: object
+          required: [id]
+          properties:
+            id: {type: integer, format: int64}
+            status: {type: string, enum: [available, sold]}
+    Owner:
+      type: object
+      properties:
+        name: {type: string}
+        pets:
+          type: array
+          items: {$ref: "#/components/schemas/Pet"}
+    Error:
+      type: object
+      properties:
+        message: {type: string}
+"##;
+
+#[test]
+fn openapi3_operations_resolve_refs_and_link_schemas() {
+    let docs = OpenApiProvider::new("petstore.yaml".into()).parse_spec(PETSTORE);
+    assert_eq!(docs.len(), 6);
+
+    let get = find(&docs, "petstore.yaml#GET /pets/{petId}");
+    assert!(get.content.contains("Fetch one pet"));
+    assert!(get.content.contains("- `petId` (path, integer<int64>, required): Pet id"));
+    assert!(get.content.contains("- `fields` (query, array of string)"));
+    assert!(get.content.contains("### 200: The pet (application/json)"));
+    assert!(get.content.contains("Schema: `Pet`"));
+    // allOf members are merged; nested references expand as dotted paths.
+    assert!(get.content.contains("- `name` (string, required): Display name"));
+    assert!(get.content.contains("- `id` (integer<int64>, required)"));
+    assert!(get.content.contains("- `status` (string, one of available | sold)"));
+    assert!(get.content.contains("- `owner.pets` (array of Pet)"));
+    assert!(get.content.contains("### 404: No such pet"));
+    assert_eq!(meta(get, "operation_id"), "getPet");
+    assert_eq!(
+        meta(get, "schemas"),
+        &json!([
+            "petstore.yaml#schema Error",
+            "petstore.yaml#schema NewPet",
+            "petstore.yaml#schema Owner",
+            "petstore.yaml#schema Pet"
+        ])
+    );
+
+    let put = find(&docs, "petstore.yaml#PUT /pets/{petId}");
+    assert!(put.content.contains("## Request body (application/json)"));
+    assert!(put.content.contains("Schema: `NewPet`"));
+    assert!(put.content.contains("- `petId` (path"));
+
+    // The recursive Owner -> Pet -> NewPet -> Owner chain stops at the repeat.
+    let owner = find(&docs, "petstore.yaml#schema Owner");
+    assert!(owner.content.contains("- `pets[].id` (integer<int64>, required)"));
+    assert!(!owner.content.contains("pets[].owner.pets"));
+    assert_eq!(
+        meta(owner, "schemas"),
+        &json!(["petstore.yaml#schema NewPet", "petstore.yaml#schema Pet"])
+    );
+    assert_eq!(meta(owner, "schema_name"), "Owner");
+}
+
+#[test]
+fn swagger2_body_parameters_and_definitions() {
+    let spec = json!({
+        "swagger": "2.0",
+        "info": {"title": "Legacy", "version": "1"},
+        "consumes": ["application/json"],
+        "paths": {
+            "/users": {
+                "post": {
+                    "parameters": [
+                        {"name": "body", "in": "body", "required": true, "schema": {"$ref": "#/definitions/User"}},
+                        {"name": "dryRun", "in": "query", "type": "boolean"}
+                    ],
+                    "responses": {
+                        "201": {"description": "Created", "schema": {"$ref": "#/definitions/User"}}
+                    }
+                }
+            }
+        },
+        "definitions": {
+            "User": {"type": "object", "properties": {"email": {"type": "string", "format": "email"}}}
+        }
+    });
+    let docs = OpenApiProvider::new("legacy.json".into()).parse_spec(&spec.to_string());
+    assert_eq!(docs.len(), 2);
+
+    let post = find(&docs, "legacy.json#POST /users");
+    assert!(post.content.contains("- `dryRun` (query, boolean)"));
+    assert!(!post.content.contains("`body`"));
+    assert!(post.content.contains("## Request body (application/json)"));
+    assert!(post.content.contains("- `email` (string<email>)"));
+    assert_eq!(meta(post, "openapi_version"), "2.0");
+    assert_eq!(meta(post, "schemas"), &json!(["legacy.json#schema User"]));
+
+    let user = find(&docs, "legacy.json#schema User");
+    assert!(user.content.contains("Type: object"));
+}
+
+#[test]
+fn specs_sharing_names_get_distinct_paths() {
+    let spec = json!({
+        "openapi": "3.0.0",
+        "paths": {"/health": {"get": {"responses": {"200": {"description": "OK"}}}}},
+        "components": {"schemas": {"Status": {"type": "string"}}}
+    })
+    .to_string();
+    let billing = OpenApiProvider::new("billing.yaml".into()).parse_spec(&spec);
+    let users = OpenApiProvider::new("users.yaml".into()).parse_spec(&spec);
+
+    find(&billing, "billing.yaml#GET /health");
+    find(&billing, "billing.yaml#schema Status");
+    find(&users, "users.yaml#GET /health");
+    find(&users, "users.yaml#schema Status");
+}
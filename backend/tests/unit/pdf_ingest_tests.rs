@@ -0,0 +1,27 @@
+use mnemo_ingest::pipeline::chunk_builder::{ChunkBuilder, FileType};
+use mnemo_ingest::providers::filesystem::FilesystemProvider;
+
+#[test]
+fn pdf_chunks_carry_their_page_number() {
+    let text = "Cover page.\u{0c}\u{0c}Third page intro.\n\nThird page details.";
+    let chunks = ChunkBuilder::build_document(text, FileType::Pdf, "manual.pdf", Some("pdf"));
+    let cover = chunks.iter().find(|c| c.text.contains("Cover")).expect("cover chunk");
+    assert_eq!(cover.metadata["page"], 1);
+    let details = chunks.iter().find(|c| c.text.contains("details")).expect("page 3 chunk");
+    assert_eq!(details.metadata["page"], 3);
+}
+
+#[test]
+fn malformed_pdf_is_skipped_without_failing_the_scan() {
+    let root = std::env::temp_dir().join(format!("mnemo_pdf_scan_{}", std::process::id()));
+    std::fs::create_dir_all(&root).unwrap();
+    std::fs::write(root.join("broken.pdf"), b"%PDF-1.4\n1 0 obj << /Type /Page >>\ngarbage")
+        .unwrap();
+    std::fs::write(root.join("notes.md"), "# Notes\n\nStill indexed.").unwrap();
+
+    let docs = FilesystemProvider::new(vec![root.to_string_lossy().to_string()]).scan();
+    let _ = std::fs::remove_dir_all(&root);
+
+    assert_eq!(docs.len(), 1);
+    assert!(docs[0].path.ends_with("notes.md"));
+}
@@ -0,0 +1,22 @@
+use mnemo_core::models::rag_context::RetrievalStatus;
+use mnemo_core::rag::orchestrator::confidence_score;
+
+#[test]
+fn confidence_is_zero_without_candidates() {
+    assert_eq!(confidence_score(&[]), 0.0);
+}
+
+#[test]
+fn confidence_rewards_a_clear_winner() {
+    let clear = confidence_score(&[0.8, 0.1, 0.1]);
+    let flat = confidence_score(&[0.8, 0.8, 0.8]);
+    assert!(clear > flat);
+    assert!((0.0..=1.0).contains(&clear));
+    assert!((0.0..=1.0).contains(&flat));
+}
+
+#[test]
+fn retrieval_status_serializes_snake_case() {
+    let json = serde_json::to_string(&RetrievalStatus::DegradedBackend).unwrap();
+    assert_eq!(json, "\"degraded_backend\"");
+}
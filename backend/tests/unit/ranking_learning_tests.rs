@@ -0,0 +1,34 @@
+use mnemo_core::ranking::learning::{FeedbackSample, LearningConfig, fit_profile};
+use mnemo_core::ranking::profile::RankingProfile;
+
+fn sample(signals: [f32; 4], label: bool) -> FeedbackSample {
+    FeedbackSample { signals, label }
+}
+
+#[test]
+fn fit_profile_favours_predictive_signal() {
+    // Relevance tracks the keyword signal; the vector signal is noise.
+    let mut samples = Vec::new();
+    for i in 0..40 {
+        let noise = (i % 5) as f32 / 5.0;
+        samples.push(sample([noise, 0.9, 0.1, 0.1], true));
+        samples.push(sample([1.0 - noise, 0.1, 0.1, 0.1], false));
+    }
+    let profile = fit_profile(&samples, &LearningConfig::default()).expect("profile");
+    let sum = profile.vector + profile.keyword + profile.graph + profile.knowledge;
+    assert!((sum - 1.0).abs() < 1e-4);
+    assert!(profile.keyword > profile.vector);
+}
+
+#[test]
+fn fit_profile_requires_both_labels() {
+    let samples = vec![sample([0.5, 0.5, 0.5, 0.5], true); 50];
+    assert!(fit_profile(&samples, &LearningConfig::default()).is_none());
+    assert!(fit_profile(&samples[..3], &LearningConfig::default()).is_none());
+}
+
+#[test]
+fn normalized_falls_back_to_baseline() {
+    let zero = RankingProfile { vector: -1.0, keyword: 0.0, graph: 0.0, knowledge: 0.0 };
+    assert_eq!(zero.normalized(), RankingProfile::baseline());
+}
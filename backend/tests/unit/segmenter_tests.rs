@@ -0,0 +1,42 @@
+use std::sync::Arc;
+
+use mnemo_core::config::chunking::ChunkSizeConfig;
+use mnemo_ingest::pipeline::segmenter::{
+    ApproxTokenizer, Segmenter, Tokenizer, WhitespaceTokenizer,
+};
+
+fn strip(s: &str) -> String {
+    s.chars().filter(|c| !c.is_whitespace()).collect()
+}
+
+#[test]
+fn multibyte_text_is_split_without_panicking_or_losing_text() {
+    let text = "Grüße aus Köln — naïve café. ".repeat(40) + &"日本語のテキスト。".repeat(60);
+    let sizes = ChunkSizeConfig { max_tokens: 20, overlap_tokens: 0, min_tokens: 5 };
+    let chunks = Segmenter::new(Arc::new(ApproxTokenizer), sizes).segment(&text);
+    assert!(chunks.len() > 1);
+    assert!(chunks.iter().all(|c| ApproxTokenizer.count(c) <= 20));
+    assert_eq!(strip(&chunks.concat()), strip(&text));
+}
+
+#[test]
+fn consecutive_chunks_share_overlap() {
+    let text = (0..60).map(|i| format!("Sentence number {i} ends here.")).collect::<Vec<_>>();
+    let text = text.join(" ");
+    let sizes = ChunkSizeConfig { max_tokens: 30, overlap_tokens: 10, min_tokens: 5 };
+    let chunks = Segmenter::new(Arc::new(WhitespaceTokenizer), sizes).segment(&text);
+    assert!(chunks.len() > 2);
+    for pair in chunks.windows(2) {
+        let last_sentence = pair[0].rsplit(". ").next().unwrap();
+        assert!(pair[1].contains(last_sentence.trim_end_matches('.')));
+    }
+}
+
+#[test]
+fn short_trailing_text_is_kept() {
+    let text = format!("{}\nthe end", "word ".repeat(50));
+    let sizes = ChunkSizeConfig { max_tokens: 25, overlap_tokens: 0, min_tokens: 10 };
+    let chunks = Segmenter::new(Arc::new(WhitespaceTokenizer), sizes).segment(&text);
+    assert!(chunks.last().unwrap().ends_with("the end"));
+    assert_eq!(strip(&chunks.concat()), strip(&text));
+}
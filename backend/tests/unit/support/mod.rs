@@ -0,0 +1,15 @@
+use std::fs;
+use std::path::PathBuf;
+
+/// Fresh temp directory `mnemo_<name>_<pid>` holding `files` as `(relative path, contents)`.
+pub fn fixture_dir(name: &str, files: &[(&str, &str)]) -> PathBuf {
+    let root = std::env::temp_dir().join(format!("mnemo_{}_{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&root);
+    fs::create_dir_all(&root).unwrap();
+    for (path, content) in files {
+        let full = root.join(path);
+        fs::create_dir_all(full.parent().unwrap()).unwrap();
+        fs::write(full, content).unwrap();
+    }
+    root
+}
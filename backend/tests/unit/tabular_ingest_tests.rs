@@ -0,0 +1,101 @@
+use mnemo_ingest::pipeline::chunk_builder::{ChunkBuilder, FileType};
+use mnemo_ingest::providers::tabular::{TabularProvider, extract_table};
+use serde_json::json;
+
+fn sales_csv(rows: usize) -> String {
+    let mut csv = String::from("id,region,amount,shipped,note\n");
+    for i in 1..=rows {
+        let amount = if i % 2 == 0 { format!("{i}.5") } else { i.to_string() };
+        let note = if i == 3 { String::new() } else { format!("order {i} | priority") };
+        csv.push_str(&format!("{i},north,{amount},2024-01-{:02},\"{note}\"\n", i % 28 + 1));
+    }
+    csv
+}
+
+#[test]
+fn csv_types_are_inferred_and_rows_chunked_with_header() {
+    let table = extract_table("sales.csv", sales_csv(200).as_bytes(), None).expect("table");
+    let types: Vec<(&str, &str)> =
+        table.columns.iter().map(|c| (c.name.as_str(), c.data_type.as_str())).collect();
+    assert_eq!(
+        types,
+        [
+            ("id", "integer"),
+            ("region", "string"),
+            ("amount", "float"),
+            ("shipped", "date"),
+            ("note", "string")
+        ]
+    );
+    assert_eq!(table.columns[4].nulls, 1);
+    assert_eq!(table.metadata()["row_count"], 200);
+
+    let text = table.render("sales.csv");
+    let chunks = ChunkBuilder::build_document(&text, FileType::Table, "sales.csv", Some("table"));
+    assert!(chunks.len() > 1, "200 rows exceed one chunk");
+    for chunk in &chunks {
+        assert!(chunk.text.starts_with(
+            "Table: sales.csv\nFormat: csv\nColumns: id (integer), region (string), \
+             amount (float), shipped (date), note (string)\n| id | region | amount |"
+        ));
+    }
+    // Row groups are contiguous and cover every row.
+    assert_eq!(chunks[0].metadata["row_start"], 1);
+    for pair in chunks.windows(2) {
+        let end = pair[0].metadata["row_end"].as_u64().unwrap();
+        assert_eq!(pair[1].metadata["row_start"].as_u64().unwrap(), end + 1);
+    }
+    assert_eq!(chunks.last().unwrap().metadata["row_end"], 200);
+    assert!(chunks[0].text.contains("| 1 | north | 1 | 2024-01-02 | order 1 \\| priority |"));
+}
+
+#[test]
+fn jsonl_columns_union_keys_and_widen_types() {
+    let lines = [
+        json!({"user": "ada", "score": 3, "tags": ["a"]}),
+        json!({"user": "bob", "score": 4.5, "active": true}),
+        json!({"user": "cy", "score": null, "joined": "2024-03-01T10:00:00Z"}),
+    ];
+    let text: Vec<String> = lines.iter().map(|l| l.to_string()).collect();
+    let table = extract_table("events.jsonl", text.join("\n").as_bytes(), Some(2)).unwrap();
+    let types: Vec<(&str, &str)> =
+        table.columns.iter().map(|c| (c.name.as_str(), c.data_type.as_str())).collect();
+    assert_eq!(
+        types,
+        [("user", "string"), ("score", "float"), ("tags", "array"), ("active", "boolean")]
+    );
+    assert_eq!(table.rows.len(), 2);
+    assert_eq!(table.rows[0], ["ada", "3", "[\"a\"]", ""]);
+    let meta = table.metadata();
+    assert_eq!(meta["row_count"], 3);
+    assert_eq!(meta["rows_indexed"], 2);
+    assert_eq!(meta["table_columns"][1], json!({"name": "score", "type": "float", "nulls": 0}));
+}
+
+#[test]
+fn provider_emits_table_and_schema_summary_documents() {
+    let dir =
+        std::env::temp_dir().join(format!("mnemo_tabular_{}_{}", "summary", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("sales.csv");
+    std::fs::write(&path, sales_csv(5)).unwrap();
+    let path = path.to_string_lossy().to_string();
+
+    let docs = TabularProvider::new(vec![path.clone()], "local".into())
+        .with_schema_summary(true)
+        .load_documents();
+    assert_eq!(docs.len(), 2);
+    assert_eq!(docs[0].path, path);
+    assert_eq!(docs[0].language.as_deref(), Some("table"));
+    assert_eq!(docs[0].metadata.as_ref().unwrap()["column_count"], 5);
+
+    let summary = &docs[1];
+    assert_eq!(summary.path, format!("{path}#schema"));
+    assert_eq!(summary.language.as_deref(), Some("markdown"));
+    assert!(summary.content.contains("Rows: 5"));
+    assert!(summary.content.contains("- `id` (integer): e.g. 1, 2, 3"));
+    assert!(summary.content.contains("- `note` (string, 1 empty)"));
+    assert_eq!(summary.metadata.as_ref().unwrap()["table_path"], json!(path));
+
+    std::fs::remove_dir_all(&dir).ok();
+}
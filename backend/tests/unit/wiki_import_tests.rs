@@ -0,0 +1,204 @@
+use mnemo_core::models::document::Document;
+use mnemo_ingest::providers::confluence::ConfluenceProvider;
+use mnemo_ingest::providers::notion::NotionProvider;
+use mnemo_ingest::providers::obsidian::ObsidianProvider;
+
+mod support;
+use support::fixture_dir;
+
+fn find<'a>(docs: &'a [Document], suffix: &str) -> &'a Document {
+    docs.iter().find(|d| d.path.ends_with(suffix)).unwrap_or_else(|| panic!("missing {suffix}"))
+}
+
+fn meta<'a>(doc: &'a Document, key: &str) -> &'a serde_json::Value {
+    &doc.metadata.as_ref().unwrap()[key]
+}
+
+#[test]
+fn obsidian_vault_resolves_wikilinks_and_folder_notes() {
+    let root = fixture_dir(
+        "wiki_obsidian",
+        &[
+            ("Projects/Projects.md", "Index of projects."),
+            (
+                "Projects/Apollo.md",
+                "---\ntags: [space, launch]\naliases: [Moonshot]\n---\nSee [[Budget|the budget]] and ![[plan.txt]].",
+            ),
+            ("Budget.md", "Back to [[moonshot]]."),
+            ("Projects/plan.txt", "Launch in May."),
+            (".obsidian/workspace.md", "ignored"),
+        ],
+    );
+    let docs = ObsidianProvider::new(root.display().to_string(), "local".into()).load_documents();
+    assert!(!docs.iter().any(|d| d.path.contains(".obsidian")));
+
+    let apollo = find(&docs, "Projects/Apollo.md");
+    assert!(apollo.content.starts_with("# Apollo"));
+    assert!(apollo.content.contains("See the budget and plan.txt."));
+    assert_eq!(meta(apollo, "breadcrumb"), "Projects > Apollo");
+    assert!(meta(apollo, "wiki_parent").as_str().unwrap().ends_with("Projects/Projects.md"));
+    assert!(meta(apollo, "wiki_links")[0].as_str().unwrap().ends_with("/Budget.md"));
+    assert_eq!(meta(apollo, "tags"), &serde_json::json!(["space", "launch"]));
+
+    // Alias resolution, case-insensitive.
+    let budget = find(&docs, "/Budget.md");
+    assert!(meta(budget, "wiki_links")[0].as_str().unwrap().ends_with("Projects/Apollo.md"));
+
+    let plan = find(&docs, "Projects/plan.txt");
+    assert!(plan.content.contains("Launch in May."));
+    assert_eq!(meta(plan, "attached_to")[0], apollo.path.as_str());
+}
+
+#[test]
+fn notion_export_keeps_hierarchy_and_databases() {
+    let id = |n: u8| format!("{:032x}", n);
+    let team = format!("Team {}", id(1));
+    let tasks = format!("Tasks {}", id(2));
+    let root = fixture_dir(
+        "wiki_notion",
+        &[
+            (
+                format!("{team}.md").as_str(),
+                format!("# Team\n\nOur [tasks](Team%20{}/Tasks%20{}.csv).", id(1), id(2)).as_str(),
+            ),
+            (format!("{team}/{tasks}.csv").as_str(), "\u{feff}Name,Status\nShip it,Done\n"),
+            (format!("{team}/{tasks}/Ship it {}.md", id(3)).as_str(), "# Ship it\n\nStatus: Done"),
+        ],
+    );
+    let docs = NotionProvider::new(root.display().to_string(), "local".into()).load_documents();
+    assert_eq!(docs.len(), 3);
+
+    let team_doc = find(&docs, format!("{team}.md").as_str());
+    assert_eq!(meta(team_doc, "notion_id"), id(1).as_str());
+    assert!(meta(team_doc, "wiki_links")[0].as_str().unwrap().ends_with(".csv"));
+
+    let db = find(&docs, ".csv");
+    assert!(db.content.contains("| Name | Status |"));
+    assert!(db.content.contains("| Ship it | Done |"));
+    assert_eq!(meta(db, "breadcrumb"), "Team > Tasks");
+
+    let row = find(&docs, format!("Ship it {}.md", id(3)).as_str());
+    assert!(meta(row, "wiki_parent").as_str().unwrap().ends_with(".csv"));
+    assert_eq!(meta(row, "breadcrumb"), "Team > Tasks > Ship it");
+}
+
+#[test]
+fn confluence_html_export_uses_breadcrumbs_and_attachments() {
+    let page = |title: &str, crumbs: &str, body: &str| {
+        format!(
+            "<html><head><title>Eng : {title}</title></head><body>\
+             <div id=\"breadcrumb-section\"><ol id=\"breadcrumbs\">{crumbs}</ol></div>\
+             <div id=\"main-content\" class=\"wiki-content group\">{body}</div>\
+             <div class=\"pageSection group\"><a href=\"attachments/2/10.txt\">notes.txt</a></div>\
+             </body></html>"
+        )
+    };
+    let root = fixture_dir(
+        "wiki_confluence_html",
+        &[
+            ("index.html", "<html><title>Eng</title></html>"),
+            (
+                "Home_1.html",
+                page("Home", "<li><a href=\"index.html\">Eng</a></li>", "<p>Welcome</p>").as_str(),
+            ),
+            (
+                "Setup_2.html",
+                page(
+                    "Setup",
+                    "<li><a href=\"index.html\">Eng</a></li><li><a href=\"Home_1.html\">Home</a></li>",
+                    "<h2>Install</h2><ul><li>Run &amp; wait</li></ul><p>See <a href=\"Home_1.html\">Home</a></p>",
+                ).as_str(),
+            ),
+            ("attachments/2/10.txt", "Attachment text."),
+        ],
+    );
+    let docs = ConfluenceProvider::new(root.display().to_string(), "local".into()).load_documents();
+
+    let setup = find(&docs, "Setup_2.html");
+    assert!(setup.content.contains("## Install"));
+    assert!(setup.content.contains("- Run & wait"));
+    assert_eq!(meta(setup, "breadcrumb"), "Eng > Home > Setup");
+    assert_eq!(meta(setup, "confluence_space"), "Eng");
+    assert!(meta(setup, "wiki_parent").as_str().unwrap().ends_with("Home_1.html"));
+    // The parent link in the body is ancestry, not a separate reference.
+    assert_eq!(meta(setup, "wiki_links"), &serde_json::json!([]));
+    assert!(meta(setup, "attachments")[0].as_str().unwrap().ends_with("attachments/2/10.txt"));
+    assert!(find(&docs, "attachments/2/10.txt").content.contains("Attachment text."));
+}
+
+#[test]
+fn confluence_tables_each_get_a_header_separator() {
+    let body = "<table><tr><th>Key</th><th>Value</th></tr><tr><td>a</td><td>1</td></tr></table>\
+                <table><tr><th>Name</th></tr><tr><td>b</td></tr></table>";
+    let root = fixture_dir(
+        "wiki_confluence_tables",
+        &[(
+            "Tables_1.html",
+            format!("<html><body><div id=\"main-content\">{body}</div></body></html>").as_str(),
+        )],
+    );
+    let docs = ConfluenceProvider::new(root.display().to_string(), "local".into()).load_documents();
+
+    let content = &find(&docs, "Tables_1.html").content;
+    assert!(content.contains("| Key | Value |\n| --- | --- |\n| a | 1 |"));
+    assert!(content.contains("| Name |\n| --- |\n| b |"));
+}
+
+#[test]
+fn confluence_xml_export_reads_current_pages() {
+    let entities = r#"<?xml version="1.0" encoding="UTF-8"?>
+<hibernate-generic datetime="2024-05-01 10:00:00">
+<object class="Space" package="com.atlassian.confluence.spaces">
+<id name="id">5</id>
+<property name="name"><![CDATA[Engineering]]></property>
+</object>
+<object class="Page" package="com.atlassian.confluence.pages">
+<id name="id">100</id>
+<property name="title"><![CDATA[Home]]></property>
+<property name="space" class="Space" package="com.atlassian.confluence.spaces"><id name="id">5</id></property>
+<property name="contentStatus"><![CDATA[current]]></property>
+</object>
+<object class="Page" package="com.atlassian.confluence.pages">
+<id name="id">101</id>
+<property name="title"><![CDATA[Runbook]]></property>
+<property name="parent" class="Page" package="com.atlassian.confluence.pages"><id name="id">100</id></property>
+<property name="space" class="Space" package="com.atlassian.confluence.spaces"><id name="id">5</id></property>
+<property name="contentStatus"><![CDATA[current]]></property>
+<property name="lastModificationDate">2024-05-02 09:30:00.000</property>
+</object>
+<object class="Page" package="com.atlassian.confluence.pages">
+<id name="id">99</id>
+<property name="title"><![CDATA[Runbook]]></property>
+<property name="originalVersion" class="Page" package="com.atlassian.confluence.pages"><id name="id">101</id></property>
+</object>
+<object class="BodyContent" package="com.atlassian.confluence.core">
+<id name="id">200</id>
+<property name="body"><![CDATA[<p>Restart with <ac:link><ri:page ri:content-title="Home" /></ac:link>.</p>]]></property>
+<property name="content" class="Page" package="com.atlassian.confluence.pages"><id name="id">101</id></property>
+</object>
+<object class="Attachment" package="com.atlassian.confluence.pages">
+<id name="id">300</id>
+<property name="title"><![CDATA[steps.md]]></property>
+<property name="containerContent" class="Page" package="com.atlassian.confluence.pages"><id name="id">101</id></property>
+</object>
+</hibernate-generic>"#;
+    let root = fixture_dir(
+        "wiki_confluence_xml",
+        &[
+            ("entities.xml", entities),
+            ("attachments/101/300/1", "old steps"),
+            ("attachments/101/300/2", "Step one."),
+        ],
+    );
+    let docs = ConfluenceProvider::new(root.display().to_string(), "local".into()).load_documents();
+    assert_eq!(docs.len(), 3);
+
+    let runbook = find(&docs, "pages/101");
+    assert!(runbook.content.contains("Restart with Home."));
+    assert_eq!(meta(runbook, "breadcrumb"), "Engineering > Home > Runbook");
+    assert!(meta(runbook, "wiki_links")[0].as_str().unwrap().ends_with("pages/100"));
+    assert!(runbook.modified_at.is_some());
+
+    let steps = find(&docs, "attachments/101/300/steps.md");
+    assert_eq!(steps.content, "Step one.");
+}